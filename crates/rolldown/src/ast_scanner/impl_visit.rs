@@ -3,18 +3,33 @@ use std::sync::Arc;
 use oxc::{
   ast::{ast::IdentifierReference, Visit},
   codegen::{self, Codegen, CodegenOptions, Gen},
+  span::Span,
+  syntax::scope::ScopeFlags,
 };
 use rolldown_common::ImportKind;
 use rolldown_error::BuildError;
+use rolldown_oxc_utils::ExpressionExt;
 
 use super::{side_effect_detector::SideEffectDetector, AstScanner};
 
 impl<'ast> AstScanner<'ast> {
   fn visit_top_level_stmt(&mut self, stmt: &oxc::ast::ast::Statement<'ast>) {
-    self.current_stmt_info.side_effect =
-      SideEffectDetector::new(self.scope).detect_side_effect_of_stmt(stmt);
+    self.current_stmt_info.side_effect = SideEffectDetector::new(
+      self.scope,
+      self.source,
+      self.annotations,
+      self.property_read_side_effects,
+      self.try_catch_deoptimization,
+    )
+    .detect_side_effect_of_stmt(stmt);
     self.visit_statement(stmt);
   }
+
+  fn record_await(&mut self, span: Span) {
+    if self.function_depth == 0 && self.result.top_level_await.is_none() {
+      self.result.top_level_await = Some(span);
+    }
+  }
 }
 
 impl<'ast> Visit<'ast> for AstScanner<'ast> {
@@ -31,10 +46,34 @@ impl<'ast> Visit<'ast> for AstScanner<'ast> {
         self.current_stmt_info.debug_label = Some(codegen.into_source_text());
       }
       self.visit_top_level_stmt(stmt);
-      self.result.stmt_infos.add_stmt_info(std::mem::take(&mut self.current_stmt_info));
+      let stmt_info_id =
+        self.result.stmt_infos.add_stmt_info(std::mem::take(&mut self.current_stmt_info));
+      self
+        .result
+        .dynamic_import_expressions
+        .extend(self.current_dynamic_import_expressions.drain(..).map(|expr| (stmt_info_id, expr)));
+    }
+  }
+
+  fn enter_scope(&mut self, flags: ScopeFlags) {
+    let is_function = flags.intersects(ScopeFlags::Function | ScopeFlags::ClassStaticBlock);
+    self.function_scopes.push(is_function);
+    if is_function {
+      self.function_depth += 1;
+    }
+  }
+
+  fn leave_scope(&mut self) {
+    if self.function_scopes.pop() == Some(true) {
+      self.function_depth -= 1;
     }
   }
 
+  fn visit_await_expression(&mut self, expr: &oxc::ast::ast::AwaitExpression<'ast>) {
+    self.record_await(expr.span);
+    self.visit_expression(&expr.argument);
+  }
+
   fn visit_binding_identifier(&mut self, ident: &oxc::ast::ast::BindingIdentifier) {
     let symbol_id = ident.symbol_id.get().unwrap();
     if self.is_top_level(symbol_id) {
@@ -71,16 +110,67 @@ impl<'ast> Visit<'ast> for AstScanner<'ast> {
   }
 
   fn visit_statement(&mut self, stmt: &oxc::ast::ast::Statement<'ast>) {
-    if let oxc::ast::ast::Statement::ModuleDeclaration(decl) = stmt {
-      self.scan_module_decl(decl.0);
+    match stmt {
+      oxc::ast::ast::Statement::ModuleDeclaration(decl) => self.scan_module_decl(decl.0),
+      oxc::ast::ast::Statement::ForOfStatement(for_of) if for_of.r#await => {
+        self.record_await(for_of.span);
+      }
+      _ => {}
     }
     self.visit_statement_match(stmt);
   }
 
+  fn visit_expression(&mut self, expr: &oxc::ast::ast::Expression<'ast>) {
+    if let Some(reference_id) = expr.as_import_meta_file_url() {
+      if !self.result.file_url_references.iter().any(|id| id == reference_id) {
+        self.result.file_url_references.push(reference_id.to_string());
+      }
+    }
+    self.visit_expression_match(expr);
+  }
+
   fn visit_import_expression(&mut self, expr: &oxc::ast::ast::ImportExpression<'ast>) {
     if let oxc::ast::ast::Expression::StringLiteral(request) = &expr.source {
       let id = self.add_import_record(&request.value, ImportKind::DynamicImport);
+      self.result.import_records[id].attributes =
+        Self::dynamic_import_attributes(expr.arguments.first());
       self.result.imports.insert(expr.span, id);
+    } else {
+      self.add_dynamic_import_expression(expr);
+    }
+    self.visit_expression(&expr.source);
+    for arg in &expr.arguments {
+      self.visit_expression(arg);
+    }
+  }
+
+  fn visit_new_expression(&mut self, expr: &oxc::ast::ast::NewExpression<'ast>) {
+    if let (
+      oxc::ast::ast::Expression::Identifier(callee),
+      [oxc::ast::ast::Argument::Expression(path), oxc::ast::ast::Argument::Expression(base)],
+    ) = (&expr.callee, expr.arguments.as_slice())
+    {
+      if callee.name == "URL" && self.is_unresolved_reference(callee) && base.is_import_meta_url() {
+        match path {
+          oxc::ast::ast::Expression::StringLiteral(path) if path.value.starts_with('.') => {
+            self.result.asset_urls.push((expr.span, path.value.to_string()));
+          }
+          // Absolute URLs don't refer to files of the project
+          oxc::ast::ast::Expression::StringLiteral(_) => {}
+          _ => self.result.warnings.push(
+            BuildError::dynamic_asset_url(
+              self.file_path.to_string(),
+              Arc::clone(self.source),
+              expr.span,
+            )
+            .with_severity_warning(),
+          ),
+        }
+      }
+    }
+    self.visit_expression(&expr.callee);
+    for arg in &expr.arguments {
+      self.visit_argument(arg);
     }
   }
 
@@ -5,18 +5,19 @@ use index_vec::IndexVec;
 use oxc::{
   ast::{
     ast::{
-      ExportAllDeclaration, ExportDefaultDeclaration, ExportNamedDeclaration, IdentifierReference,
-      ImportDeclaration, ModuleDeclaration, Program,
+      ExportAllDeclaration, ExportDefaultDeclaration, ExportNamedDeclaration, Expression,
+      IdentifierReference, ImportDeclaration, ImportExpression, ModuleDeclaration,
+      ObjectPropertyKind, Program, WithClause,
     },
     Visit,
   },
   semantic::SymbolId,
-  span::{Atom, Span},
+  span::{Atom, GetSpan, Span},
 };
 use rolldown_common::{
-  representative_name, AstScope, ExportsKind, FilePath, ImportKind, ImportRecordId, LocalExport,
-  ModuleType, NamedImport, NormalModuleId, RawImportRecord, Specifier, StmtInfo, StmtInfos,
-  SymbolRef,
+  representative_name, AstScope, ExportsKind, FilePath, ImportAttributes, ImportKind,
+  ImportRecordId, LocalExport, ModuleType, NamedImport, NormalModuleId, PropertyReadSideEffects,
+  RawImportRecord, Specifier, StmtInfo, StmtInfoId, StmtInfos, SymbolRef,
 };
 use rolldown_error::BuildError;
 use rolldown_oxc_utils::{BindingIdentifierExt, BindingPatternExt};
@@ -26,6 +27,17 @@ use std::sync::Arc;
 
 use super::types::ast_symbols::AstSymbols;
 
+/// `import(expr)` whose source isn't a string literal, which only becomes an import record if plugins resolve it
+/// with `resolve_dynamic_import` hooks.
+#[derive(Debug)]
+pub struct DynamicImportExpression {
+  /// The span of the `import(...)` expression.
+  pub span: Span,
+  /// The code of the source expression.
+  pub code: String,
+  pub record: RawImportRecord,
+}
+
 #[derive(Debug, Default)]
 pub struct ScanResult {
   pub repr_name: String,
@@ -37,9 +49,19 @@ pub struct ScanResult {
   pub default_export_ref: Option<SymbolRef>,
   pub imports: FxHashMap<Span, ImportRecordId>,
   pub exports_kind: ExportsKind,
+  /// Relative paths in `new URL('./file', import.meta.url)` along with the spans of the `new` expressions, in
+  /// the order they appear.
+  pub asset_urls: Vec<(Span, String)>,
+  /// Reference ids of emitted files in `import.meta.ROLLUP_FILE_URL_<referenceId>`, without duplicates.
+  pub file_url_references: Vec<String>,
+  /// The span of the first `await` outside functions, including `for await`.
+  pub top_level_await: Option<Span>,
+  /// Dynamic imports of expressions along with the top-level statements containing them.
+  pub dynamic_import_expressions: Vec<(StmtInfoId, DynamicImportExpression)>,
   pub warnings: Vec<BuildError>,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct AstScanner<'a> {
   idx: NormalModuleId,
   source: &'a Arc<str>,
@@ -48,12 +70,20 @@ pub struct AstScanner<'a> {
   scope: &'a AstScope,
   symbol_table: &'a mut AstSymbols,
   current_stmt_info: StmtInfo,
+  /// Dynamic imports of expressions in the current top-level statement.
+  current_dynamic_import_expressions: Vec<DynamicImportExpression>,
   result: ScanResult,
   esm_export_keyword: Option<Span>,
   esm_import_keyword: Option<Span>,
   pub namespace_ref: SymbolRef,
   used_exports_ref: bool,
   used_module_ref: bool,
+  annotations: bool,
+  property_read_side_effects: PropertyReadSideEffects,
+  try_catch_deoptimization: bool,
+  /// Whether each entered scope is a function scope, to tell `await` at the top level from `await` in functions.
+  function_scopes: Vec<bool>,
+  function_depth: u32,
 }
 
 impl<'ast> AstScanner<'ast> {
@@ -86,6 +116,7 @@ impl<'ast> AstScanner<'ast> {
       scope,
       symbol_table,
       current_stmt_info: StmtInfo::default(),
+      current_dynamic_import_expressions: vec![],
       result,
       esm_export_keyword: None,
       esm_import_keyword: None,
@@ -95,9 +126,38 @@ impl<'ast> AstScanner<'ast> {
       used_module_ref: false,
       source,
       file_path,
+      annotations: true,
+      property_read_side_effects: PropertyReadSideEffects::default(),
+      try_catch_deoptimization: true,
+      function_scopes: vec![],
+      function_depth: 0,
     }
   }
 
+  /// Set whether `/*#__PURE__*/` annotations are respected while detecting side effects, enabled by default.
+  #[must_use]
+  pub fn with_annotations(mut self, annotations: bool) -> Self {
+    self.annotations = annotations;
+    self
+  }
+
+  /// Set whether reading properties is considered having side effects, `true` by default.
+  #[must_use]
+  pub fn with_property_read_side_effects(
+    mut self,
+    property_read_side_effects: PropertyReadSideEffects,
+  ) -> Self {
+    self.property_read_side_effects = property_read_side_effects;
+    self
+  }
+
+  /// Set whether `try` statements are always considered having side effects, `true` by default.
+  #[must_use]
+  pub fn with_try_catch_deoptimization(mut self, try_catch_deoptimization: bool) -> Self {
+    self.try_catch_deoptimization = try_catch_deoptimization;
+    self
+  }
+
   pub fn scan(mut self, program: &Program<'ast>) -> ScanResult {
     self.visit_program(program);
     let mut exports_kind = ExportsKind::None;
@@ -112,7 +172,12 @@ impl<'ast> AstScanner<'ast> {
         ModuleType::CJS | ModuleType::CjsPackageJson => {
           exports_kind = ExportsKind::CommonJs;
         }
-        ModuleType::EsmMjs | ModuleType::EsmPackageJson => {
+        ModuleType::EsmMjs
+        | ModuleType::EsmPackageJson
+        | ModuleType::Json
+        | ModuleType::Css
+        | ModuleType::Text
+        | ModuleType::Base64 => {
           exports_kind = ExportsKind::Esm;
         }
         ModuleType::Unknown => {
@@ -143,34 +208,72 @@ impl<'ast> AstScanner<'ast> {
     self.scope.get_root_binding(name).expect("must have")
   }
 
-  fn add_import_record(&mut self, module_request: &Atom, kind: ImportKind) -> ImportRecordId {
+  fn new_import_record(&mut self, module_request: Rstr, kind: ImportKind) -> RawImportRecord {
     // If 'foo' in `import ... from 'foo'` is finally a commonjs module, we will convert the import statement
     // to `var import_foo = __toESM(require_foo())`, so we create a symbol for `import_foo` here. Notice that we
     // just create the symbol here, if the symbol is finally used would be determined in linking stage.
     let namespace_ref: SymbolRef =
       (self.idx, self.symbol_table.create_symbol("".into(), self.scope.root_scope_id())).into();
-    let rec = RawImportRecord::new(module_request.to_rstr(), kind, namespace_ref);
+    RawImportRecord::new(module_request, kind, namespace_ref)
+  }
 
+  fn add_import_record(&mut self, module_request: &Atom, kind: ImportKind) -> ImportRecordId {
+    let rec = self.new_import_record(module_request.to_rstr(), kind);
     let id = self.result.import_records.push(rec);
     self.current_stmt_info.import_records.push(id);
     id
   }
 
-  fn add_named_import(&mut self, local: SymbolId, imported: &Atom, record_id: ImportRecordId) {
+  /// Record `import(expr)` whose source isn't a string literal, so plugins could resolve it later.
+  fn add_dynamic_import_expression(&mut self, expr: &ImportExpression) {
+    let span = expr.source.span();
+    let code = self.source[span.start as usize..span.end as usize].to_string();
+    let mut record = self.new_import_record(code.as_str().into(), ImportKind::DynamicImport);
+    record.attributes = Self::dynamic_import_attributes(expr.arguments.first());
+    self.current_dynamic_import_expressions.push(DynamicImportExpression {
+      span: expr.span,
+      code,
+      record,
+    });
+  }
+
+  /// Record attributes in the `with` clause of an import or re-export declaration.
+  fn set_import_attributes(&mut self, record_id: ImportRecordId, with_clause: Option<&WithClause>) {
+    let Some(with_clause) = with_clause else { return };
+    self.result.import_records[record_id].attributes = with_clause
+      .with_entries
+      .iter()
+      .map(|attr| (attr.key.as_atom().to_string(), attr.value.value.to_string()))
+      .collect();
+  }
+
+  fn add_named_import(
+    &mut self,
+    local: SymbolId,
+    imported: &Atom,
+    record_id: ImportRecordId,
+    span: Span,
+  ) {
     self.result.named_imports.insert(
       local,
       NamedImport {
         imported: imported.to_rstr().into(),
         imported_as: (self.idx, local).into(),
         record_id,
+        span,
       },
     );
   }
 
-  fn add_star_import(&mut self, local: SymbolId, record_id: ImportRecordId) {
+  fn add_star_import(&mut self, local: SymbolId, record_id: ImportRecordId, span: Span) {
     self.result.named_imports.insert(
       local,
-      NamedImport { imported: Specifier::Star, imported_as: (self.idx, local).into(), record_id },
+      NamedImport {
+        imported: Specifier::Star,
+        imported_as: (self.idx, local).into(),
+        record_id,
+        span,
+      },
     );
   }
 
@@ -188,7 +291,13 @@ impl<'ast> AstScanner<'ast> {
       .insert("default".into(), LocalExport { referenced: (self.idx, local).into() });
   }
 
-  fn add_re_export(&mut self, export_name: &Atom, imported: &Atom, record_id: ImportRecordId) {
+  fn add_re_export(
+    &mut self,
+    export_name: &Atom,
+    imported: &Atom,
+    record_id: ImportRecordId,
+    span: Span,
+  ) {
     let generated_imported_as_ref = (
       self.idx,
       self.symbol_table.create_symbol(
@@ -208,6 +317,7 @@ impl<'ast> AstScanner<'ast> {
       imported: imported.to_rstr().into(),
       imported_as: generated_imported_as_ref,
       record_id,
+      span,
     };
     if name_import.imported.is_default() {
       self.result.import_records[record_id].contains_import_default = true;
@@ -219,15 +329,19 @@ impl<'ast> AstScanner<'ast> {
       .insert(export_name.to_rstr(), LocalExport { referenced: generated_imported_as_ref });
   }
 
-  fn add_star_re_export(&mut self, export_name: &Atom, record_id: ImportRecordId) {
+  fn add_star_re_export(&mut self, export_name: &Atom, record_id: ImportRecordId, span: Span) {
     let generated_imported_as_ref = (
       self.idx,
       self.symbol_table.create_symbol(export_name.to_compact_string(), self.scope.root_scope_id()),
     )
       .into();
     self.current_stmt_info.declared_symbols.push(generated_imported_as_ref);
-    let name_import =
-      NamedImport { imported: Specifier::Star, imported_as: generated_imported_as_ref, record_id };
+    let name_import = NamedImport {
+      imported: Specifier::Star,
+      imported_as: generated_imported_as_ref,
+      record_id,
+      span,
+    };
     self.result.named_imports.insert(generated_imported_as_ref.symbol, name_import);
     self.result.import_records[record_id].contains_import_star = true;
     self
@@ -238,9 +352,10 @@ impl<'ast> AstScanner<'ast> {
 
   fn scan_export_all_decl(&mut self, decl: &ExportAllDeclaration) {
     let id = self.add_import_record(&decl.source.value, ImportKind::Import);
+    self.set_import_attributes(id, decl.with_clause.as_ref());
     if let Some(exported) = &decl.exported {
       // export * as ns from '...'
-      self.add_star_re_export(exported.name(), id);
+      self.add_star_re_export(exported.name(), id, decl.span);
     } else {
       // export * from '...'
       self.result.star_exports.push(id);
@@ -252,7 +367,7 @@ impl<'ast> AstScanner<'ast> {
     if let Some(source) = &decl.source {
       let record_id = self.add_import_record(&source.value, ImportKind::Import);
       decl.specifiers.iter().for_each(|spec| {
-        self.add_re_export(spec.exported.name(), spec.local.name(), record_id);
+        self.add_re_export(spec.exported.name(), spec.local.name(), record_id, spec.span);
         self.result.imports.insert(decl.span, record_id);
       });
     } else {
@@ -314,6 +429,7 @@ impl<'ast> AstScanner<'ast> {
 
   fn scan_import_decl(&mut self, decl: &ImportDeclaration) {
     let id = self.add_import_record(&decl.source.value, ImportKind::Import);
+    self.set_import_attributes(id, decl.with_clause.as_ref());
     self.result.imports.insert(decl.span, id);
     let Some(specifiers) = &decl.specifiers else { return };
     specifiers.iter().for_each(|spec| match spec {
@@ -321,19 +437,24 @@ impl<'ast> AstScanner<'ast> {
         let sym = spec.local.expect_symbol_id();
         let imported = spec.imported.name();
         if imported == &"default" {
-          self.add_named_import(sym, imported, id);
+          self.add_named_import(sym, imported, id, spec.span);
           self.result.import_records[id].contains_import_default = true;
         } else {
-          self.add_named_import(sym, imported, id);
+          self.add_named_import(sym, imported, id, spec.span);
         }
-        self.add_named_import(sym, spec.imported.name(), id);
+        self.add_named_import(sym, spec.imported.name(), id, spec.span);
       }
       oxc::ast::ast::ImportDeclarationSpecifier::ImportDefaultSpecifier(spec) => {
-        self.add_named_import(spec.local.expect_symbol_id(), &Atom::Arena("default"), id);
+        self.add_named_import(
+          spec.local.expect_symbol_id(),
+          &Atom::Arena("default"),
+          id,
+          spec.span,
+        );
         self.result.import_records[id].contains_import_default = true;
       }
       oxc::ast::ast::ImportDeclarationSpecifier::ImportNamespaceSpecifier(spec) => {
-        self.add_star_import(spec.local.expect_symbol_id(), id);
+        self.add_star_import(spec.local.expect_symbol_id(), id, spec.span);
         self.result.import_records[id].contains_import_star = true;
       }
     });
@@ -361,6 +482,38 @@ impl<'ast> AstScanner<'ast> {
     }
   }
 
+  /// Attributes in the options of `import('./foo', { with: { type: 'json' } })`. Options that aren't object
+  /// literals are ignored.
+  fn dynamic_import_attributes(options: Option<&Expression>) -> ImportAttributes {
+    let Some(Expression::ObjectExpression(options)) = options else {
+      return ImportAttributes::default();
+    };
+    let with = options.properties.iter().find_map(|prop| match prop {
+      ObjectPropertyKind::ObjectProperty(prop)
+        if prop.key.static_name().is_some_and(|name| name == "with" || name == "assert") =>
+      {
+        Some(&prop.value)
+      }
+      _ => None,
+    });
+    let Some(Expression::ObjectExpression(with)) = with else {
+      return ImportAttributes::default();
+    };
+    with
+      .properties
+      .iter()
+      .filter_map(|prop| match prop {
+        ObjectPropertyKind::ObjectProperty(prop) => match &prop.value {
+          Expression::StringLiteral(value) => {
+            Some((prop.key.static_name()?.to_string(), value.value.to_string()))
+          }
+          _ => None,
+        },
+        ObjectPropertyKind::SpreadProperty(_) => None,
+      })
+      .collect()
+  }
+
   pub fn add_referenced_symbol(&mut self, id: SymbolId) {
     self.current_stmt_info.referenced_symbols.push((self.idx, id).into());
   }
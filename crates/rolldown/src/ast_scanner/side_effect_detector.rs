@@ -1,6 +1,6 @@
 use once_cell::sync::Lazy;
 use oxc::ast::ast::{IdentifierReference, MemberExpression};
-use rolldown_common::AstScope;
+use rolldown_common::{AstScope, PropertyReadSideEffects};
 use rustc_hash::FxHashSet;
 
 // Probably we should generate this using macros.
@@ -27,11 +27,64 @@ static SIDE_EFFECT_FREE_MEMBER_EXPR_3: Lazy<FxHashSet<(&'static str, &'static st
 /// Detect if a statement "may" have side effect.
 pub struct SideEffectDetector<'a> {
   pub scope: &'a AstScope,
+  pub source: &'a str,
+  /// Whether to respect `/*#__PURE__*/` annotations.
+  pub annotations: bool,
+  pub property_read_side_effects: PropertyReadSideEffects,
+  /// Whether `try` statements are always considered having side effects.
+  pub try_catch_deoptimization: bool,
 }
 
 impl<'a> SideEffectDetector<'a> {
-  pub fn new(scope: &'a AstScope) -> Self {
-    Self { scope }
+  pub fn new(
+    scope: &'a AstScope,
+    source: &'a str,
+    annotations: bool,
+    property_read_side_effects: PropertyReadSideEffects,
+    try_catch_deoptimization: bool,
+  ) -> Self {
+    Self { scope, source, annotations, property_read_side_effects, try_catch_deoptimization }
+  }
+
+  fn detect_side_effect_of_block(&self, stmts: &[oxc::ast::ast::Statement]) -> bool {
+    stmts.iter().any(|stmt| self.detect_side_effect_of_stmt(stmt))
+  }
+
+  /// Check if the expression starting at `start` is preceded by `/*#__PURE__*/` or `/*@__PURE__*/`. Whitespace,
+  /// opening parentheses and other comments are allowed in between, such as `/*#__PURE__*/ (foo())`.
+  fn is_annotated_pure(&self, start: u32) -> bool {
+    if !self.annotations {
+      return false;
+    }
+    // Code generated while loading, like lowered TypeScript enums, has spans of its own.
+    let Some(mut before) = self.source.get(..start as usize) else {
+      return false;
+    };
+    loop {
+      before = before.trim_end();
+      if let Some(rest) = before.strip_suffix('(') {
+        before = rest;
+      } else if let Some(rest) = before.strip_suffix("*/") {
+        let Some(comment_start) = rest.rfind("/*") else {
+          return false;
+        };
+        let content = rest[comment_start + 2..].trim();
+        if content == "#__PURE__" || content == "@__PURE__" {
+          return true;
+        }
+        before = &rest[..comment_start];
+      } else {
+        return false;
+      }
+    }
+  }
+
+  fn detect_side_effect_of_arguments(&self, args: &[oxc::ast::ast::Argument]) -> bool {
+    args.iter().any(|arg| match arg {
+      oxc::ast::ast::Argument::Expression(expr) => self.detect_side_effect_of_expr(expr),
+      // Spreading an iterable may run arbitrary code.
+      oxc::ast::ast::Argument::SpreadElement(_) => true,
+    })
   }
 
   fn is_unresolved_reference(&self, ident_ref: &IdentifierReference) -> bool {
@@ -65,7 +118,32 @@ impl<'a> SideEffectDetector<'a> {
     })
   }
 
-  fn detect_side_effect_of_member_expr(expr: &oxc::ast::ast::MemberExpression) -> bool {
+  fn detect_side_effect_of_member_expr(&self, expr: &oxc::ast::ast::MemberExpression) -> bool {
+    match self.property_read_side_effects {
+      PropertyReadSideEffects::Always => true,
+      PropertyReadSideEffects::Boolean(true) => {
+        Self::detect_side_effect_of_builtin_member_expr(expr)
+      }
+      // Only evaluating the object and the computed key could have side effects.
+      PropertyReadSideEffects::Boolean(false) => {
+        Self::detect_side_effect_of_builtin_member_expr(expr)
+          && match expr {
+            MemberExpression::StaticMemberExpression(member_expr) => {
+              self.detect_side_effect_of_expr(&member_expr.object)
+            }
+            MemberExpression::ComputedMemberExpression(member_expr) => {
+              self.detect_side_effect_of_expr(&member_expr.object)
+                || self.detect_side_effect_of_expr(&member_expr.expression)
+            }
+            MemberExpression::PrivateFieldExpression(member_expr) => {
+              self.detect_side_effect_of_expr(&member_expr.object)
+            }
+          }
+      }
+    }
+  }
+
+  fn detect_side_effect_of_builtin_member_expr(expr: &oxc::ast::ast::MemberExpression) -> bool {
     // MemberExpression is considered having side effect by default, unless it's some builtin global variables.
     let MemberExpression::StaticMemberExpression(member_expr) = expr else {
       return true;
@@ -134,7 +212,7 @@ impl<'a> SideEffectDetector<'a> {
       Expression::UnaryExpression(unary_expr) => {
         self.detect_side_effect_of_expr(&unary_expr.argument)
       }
-      Expression::MemberExpression(mem_expr) => Self::detect_side_effect_of_member_expr(mem_expr),
+      Expression::MemberExpression(mem_expr) => self.detect_side_effect_of_member_expr(mem_expr),
       Expression::ClassExpression(cls) => self.detect_side_effect_of_class(cls),
       // Accessing global variables considered as side effect.
       Expression::Identifier(ident) => self.is_unresolved_reference(ident),
@@ -151,6 +229,13 @@ impl<'a> SideEffectDetector<'a> {
       Expression::SequenceExpression(seq_expr) => {
         seq_expr.expressions.iter().any(|expr| self.detect_side_effect_of_expr(expr))
       }
+      // Arguments of a pure call are still evaluated, so they decide whether the call could be removed.
+      Expression::CallExpression(call_expr) if self.is_annotated_pure(call_expr.span.start) => {
+        self.detect_side_effect_of_arguments(&call_expr.arguments)
+      }
+      Expression::NewExpression(new_expr) if self.is_annotated_pure(new_expr.span.start) => {
+        self.detect_side_effect_of_arguments(&new_expr.arguments)
+      }
       Expression::TSAsExpression(_)
       | Expression::TSSatisfiesExpression(_)
       | Expression::TSTypeAssertion(_)
@@ -236,11 +321,22 @@ impl<'a> SideEffectDetector<'a> {
           unreachable!("ts should be transpiled")
         }
       },
-      Statement::BlockStatement(_)
-      | Statement::BreakStatement(_)
+      Statement::EmptyStatement(_) => false,
+      Statement::BlockStatement(block) => self.detect_side_effect_of_block(&block.body),
+      Statement::TryStatement(try_stmt) if !self.try_catch_deoptimization => {
+        self.detect_side_effect_of_block(&try_stmt.block.body)
+          || try_stmt
+            .handler
+            .as_ref()
+            .is_some_and(|handler| self.detect_side_effect_of_block(&handler.body.body))
+          || try_stmt
+            .finalizer
+            .as_ref()
+            .is_some_and(|finalizer| self.detect_side_effect_of_block(&finalizer.body))
+      }
+      Statement::BreakStatement(_)
       | Statement::DebuggerStatement(_)
       | Statement::DoWhileStatement(_)
-      | Statement::EmptyStatement(_)
       | Statement::ForInStatement(_)
       | Statement::ForOfStatement(_)
       | Statement::ForStatement(_)
@@ -260,12 +356,20 @@ impl<'a> SideEffectDetector<'a> {
 #[cfg(test)]
 mod test {
   use oxc::span::SourceType;
-  use rolldown_common::AstScope;
+  use rolldown_common::{AstScope, PropertyReadSideEffects};
   use rolldown_oxc_utils::OxcCompiler;
 
   use crate::ast_scanner::side_effect_detector::SideEffectDetector;
 
   fn get_statements_side_effect(code: &str) -> bool {
+    get_statements_side_effect_with(code, PropertyReadSideEffects::default(), true)
+  }
+
+  fn get_statements_side_effect_with(
+    code: &str,
+    property_read_side_effects: PropertyReadSideEffects,
+    try_catch_deoptimization: bool,
+  ) -> bool {
     let source_type = SourceType::default()
       .with_always_strict(true)
       .with_module(true)
@@ -279,11 +383,16 @@ mod test {
       AstScope::new(scope, std::mem::take(&mut symbol_table.references))
     };
 
-    let has_side_effect = program
-      .program()
-      .body
-      .iter()
-      .any(|stmt| SideEffectDetector::new(&ast_scope).detect_side_effect_of_stmt(stmt));
+    let has_side_effect = program.program().body.iter().any(|stmt| {
+      SideEffectDetector::new(
+        &ast_scope,
+        code,
+        true,
+        property_read_side_effects,
+        try_catch_deoptimization,
+      )
+      .detect_side_effect_of_stmt(stmt)
+    });
 
     has_side_effect
   }
@@ -292,6 +401,7 @@ mod test {
   fn test_side_effect() {
     assert!(!get_statements_side_effect("export { a }"));
     assert!(!get_statements_side_effect("const a = {}"));
+    assert!(!get_statements_side_effect(";"));
     assert!(!get_statements_side_effect(
       "const PatchFlags = {
         'TEXT':1,
@@ -362,4 +472,56 @@ mod test {
     assert!(get_statements_side_effect("true, bar"));
     assert!(get_statements_side_effect("foo, true"));
   }
+
+  #[test]
+  fn test_pure_annotations() {
+    assert!(!get_statements_side_effect("/*#__PURE__*/ foo()"));
+    assert!(!get_statements_side_effect("/*@__PURE__*/ new Foo()"));
+    assert!(!get_statements_side_effect("const x = /*#__PURE__*/ foo()"));
+    assert!(!get_statements_side_effect("const x = /*#__PURE__*/ (foo())"));
+    assert!(!get_statements_side_effect("const x = /* #__PURE__ */ /* other */ foo.bar(1, 'a')"));
+    assert!(!get_statements_side_effect("export default /*#__PURE__*/ foo(/*#__PURE__*/ bar())"));
+    // arguments are still evaluated
+    assert!(get_statements_side_effect("/*#__PURE__*/ foo(bar())"));
+    assert!(get_statements_side_effect("/*#__PURE__*/ foo(bar)"));
+    assert!(get_statements_side_effect("/*#__PURE__*/ foo(...[])"));
+    assert!(get_statements_side_effect("foo()"));
+    assert!(get_statements_side_effect("/* PURE */ foo()"));
+    assert!(get_statements_side_effect("const a = 1; /*#__PURE__*/ a, foo()"));
+  }
+
+  #[test]
+  fn test_property_read_side_effects() {
+    let no_read_side_effects =
+      |code| get_statements_side_effect_with(code, PropertyReadSideEffects::Boolean(false), true);
+    assert!(!no_read_side_effects("const foo = {}; foo.bar"));
+    assert!(!no_read_side_effects("const foo = {}; foo.bar.baz"));
+    assert!(!no_read_side_effects("const foo = {}; const a = foo['bar']"));
+    assert!(!no_read_side_effects("const a = Object.create"));
+    // Reading globals could still throw.
+    assert!(no_read_side_effects("foo.bar"));
+    assert!(no_read_side_effects("const foo = {}; foo[bar]"));
+
+    let always =
+      |code| get_statements_side_effect_with(code, PropertyReadSideEffects::Always, true);
+    assert!(always("const a = Object.create"));
+    assert!(always("const foo = {}; foo.bar"));
+
+    assert!(!get_statements_side_effect("const a = Object.create"));
+    assert!(get_statements_side_effect("const foo = {}; foo.bar"));
+  }
+
+  #[test]
+  fn test_try_catch_deoptimization() {
+    let no_deoptimization =
+      |code| get_statements_side_effect_with(code, PropertyReadSideEffects::default(), false);
+    assert!(!no_deoptimization("{ const a = 1 }"));
+    assert!(!no_deoptimization("try { const a = 1 } catch { const b = 2 } finally { ; }"));
+    assert!(no_deoptimization("try { foo() } catch {}"));
+    assert!(no_deoptimization("try {} catch (e) { console.log(e) }"));
+    assert!(no_deoptimization("try {} finally { foo() }"));
+
+    assert!(!get_statements_side_effect("{ const a = 1 }"));
+    assert!(get_statements_side_effect("try { const a = 1 } catch {}"));
+  }
 }
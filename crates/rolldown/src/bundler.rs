@@ -1,9 +1,9 @@
-use std::sync::Arc;
+use std::{io, sync::Arc};
 
-use rolldown_common::Output;
+use rolldown_common::{LogAction, LogLevel, Metafile, Output, RolldownCache};
 use rolldown_error::BuildError;
 use rolldown_fs::{FileSystem, OsFileSystem};
-use rolldown_plugin::{BoxPlugin, HookBuildEndArgs};
+use rolldown_plugin::{BoxPlugin, HookGenerateBundleArgs, HookWriteBundleArgs};
 use rolldown_resolver::Resolver;
 use sugar_path::AsPath;
 
@@ -17,13 +17,24 @@ use super::{
 };
 use crate::{
   error::{BatchedErrors, BatchedResult},
+  module_loader::module_cache::{ModuleCache, SharedModuleCache},
   stages::{bundle_stage::BundleStage, scan_stage::ScanStage},
+  utils::{
+    file_snapshot::{snapshot, FileSnapshot, SharedFileSnapshots},
+    metafile::generate_metafile,
+  },
+  watcher::{WatchOptions, Watcher},
   InputOptions, OutputOptions, SharedResolver,
 };
 
 pub struct RolldownOutput {
+  /// Warnings of the build and the ones emitted by plugins, except the ones dropped by `log_level` or `on_log`.
   pub warnings: Vec<BuildError>,
+  /// Info and debug logs emitted by plugins, except the ones dropped by `log_level` or `on_log`.
+  pub logs: Vec<(LogLevel, BuildError)>,
   pub assets: Vec<Output>,
+  /// Only generated if `OutputOptions::metafile` is enabled.
+  pub metafile: Option<Metafile>,
 }
 
 pub struct Bundler<T: FileSystem + Default> {
@@ -31,6 +42,12 @@ pub struct Bundler<T: FileSystem + Default> {
   plugin_driver: SharedPluginDriver,
   fs: T,
   resolver: SharedResolver<T>,
+  closed: bool,
+  /// Snapshots of the modules loaded by the latest scan, even if it failed, and of the files added by
+  /// `this.addWatchFile`, which are watched in watch mode.
+  file_snapshots: SharedFileSnapshots,
+  /// Transformed modules of previous builds, which are reused by later builds if they don't change.
+  module_cache: SharedModuleCache,
 }
 
 impl Bundler<OsFileSystem> {
@@ -50,132 +67,240 @@ impl<T: FileSystem + Default + 'static> Bundler<T> {
     fs: T,
   ) -> Self {
     rolldown_tracing::try_init_tracing();
+    let resolver: SharedResolver<T> = Resolver::with_cwd_and_fs(
+      input_options.cwd.clone(),
+      std::mem::take(&mut input_options.resolve),
+      fs.share(),
+    )
+    .into();
+    let module_cache: ModuleCache = input_options.cache.take().map(Into::into).unwrap_or_default();
+    let input_options = Arc::new(input_options);
     Self {
-      resolver: Resolver::with_cwd_and_fs(
-        input_options.cwd.clone(),
-        std::mem::take(&mut input_options.resolve),
-        fs.share(),
-      )
-      .into(),
-      plugin_driver: Arc::new(PluginDriver::new(plugins)),
-      input_options: Arc::new(input_options),
+      plugin_driver: PluginDriver::new_shared(
+        plugins,
+        Arc::clone(&input_options),
+        Arc::clone(&resolver),
+      ),
+      resolver,
+      input_options,
       fs,
+      closed: false,
+      file_snapshots: SharedFileSnapshots::default(),
+      module_cache: Arc::new(module_cache),
     }
   }
 
   pub async fn write(&mut self, output_options: OutputOptions) -> BatchedResult<RolldownOutput> {
+    self.write_output(&output_options).await
+  }
+
+  pub(crate) async fn write_output(
+    &mut self,
+    output_options: &OutputOptions,
+  ) -> BatchedResult<RolldownOutput> {
     let dir =
       self.input_options.cwd.as_path().join(&output_options.dir).to_string_lossy().to_string();
 
     let output = self.bundle_up(output_options, true).await?;
 
-    self.plugin_driver.write_bundle(&output.assets).await?;
-
-    self.fs.create_dir_all(dir.as_path()).unwrap_or_else(|_| {
-      panic!(
-        "Could not create directory for output chunks: {:?} \ncwd: {}",
-        dir.as_path(),
-        self.input_options.cwd.display()
-      )
-    });
+    self.fs.create_dir_all(dir.as_path()).map_err(|err| {
+      BuildError::from(io::Error::new(
+        err.kind(),
+        format!("Could not create directory for output chunks {dir:?}: {err}"),
+      ))
+    })?;
     for chunk in &output.assets {
       let dest = dir.as_path().join(chunk.file_name());
       if let Some(p) = dest.parent() {
         if !self.fs.exists(p) {
-          self.fs.create_dir_all(p).unwrap();
+          self.fs.create_dir_all(p).map_err(|err| {
+            BuildError::from(io::Error::new(
+              err.kind(),
+              format!("Could not create directory {p:?}: {err}"),
+            ))
+          })?;
         }
       };
-      self.fs.write(dest.as_path(), chunk.content().as_bytes()).unwrap_or_else(|_| {
-        panic!("Failed to write file in {:?}", dir.as_path().join(chunk.file_name()))
-      });
+      self.fs.write(dest.as_path(), chunk.content_as_bytes()).map_err(|err| {
+        BuildError::from(io::Error::new(err.kind(), format!("Failed to write {dest:?}: {err}")))
+      })?;
     }
 
+    self
+      .plugin_driver
+      .write_bundle(&HookWriteBundleArgs { output_options, bundle: &output.assets })
+      .await?;
+
     Ok(output)
   }
 
   pub async fn generate(&mut self, output_options: OutputOptions) -> BatchedResult<RolldownOutput> {
-    self.bundle_up(output_options, false).await
+    self.bundle_up(&output_options, false).await
   }
 
-  pub async fn scan(&mut self) -> BatchedResult<()> {
-    self.plugin_driver.build_start().await?;
+  /// Build and write the output, then rebuild whenever loaded modules or files added by `this.addWatchFile`
+  /// change, until [`Watcher::close`] is called. It must be called within a tokio runtime.
+  pub fn watch(self, output_options: OutputOptions, watch_options: WatchOptions) -> Watcher {
+    Watcher::new(self, output_options, watch_options)
+  }
 
-    let ret = self.scan_inner().await;
+  /// Transformed modules of the latest builds, which could be passed to `InputOptions::cache` of another bundler,
+  /// even in another process after being serialized.
+  pub fn cache(&self) -> RolldownCache {
+    self.module_cache.to_rolldown_cache()
+  }
+
+  /// Files whose changes should trigger a rebuild in watch mode, along with their snapshots taken in the latest
+  /// build.
+  pub(crate) fn watch_files(&self) -> Vec<(String, FileSnapshot)> {
+    self.file_snapshots.snapshots()
+  }
 
-    self.call_build_end_hook(&ret).await?;
+  pub(crate) fn fs(&self) -> &T {
+    &self.fs
+  }
 
-    ret?;
+  pub(crate) fn plugin_driver(&self) -> &SharedPluginDriver {
+    &self.plugin_driver
+  }
+
+  /// Call the `closeBundle` hook, so plugins could release the resources they hold. It should be
+  /// called once the bundler is no longer used, no matter whether `write`/`generate` succeeded.
+  pub async fn close(&mut self) -> BatchedResult<()> {
+    if self.closed {
+      return Ok(());
+    }
+    self.closed = true;
+
+    self.plugin_driver.close_bundle().await?;
 
     Ok(())
   }
 
-  async fn call_build_end_hook(
-    &mut self,
-    ret: &Result<ScanStageOutput, BatchedErrors>,
-  ) -> BatchedResult<()> {
-    if let Err(e) = ret {
-      let error = e.get().expect("should have a error");
-      self
-        .plugin_driver
-        .build_end(Some(&HookBuildEndArgs {
-          // TODO(hyf0): 1.Need a better way to expose the error
-          error: error.to_string(),
-        }))
-        .await?;
-      Ok(())
-    } else {
-      self.plugin_driver.build_end(None).await?;
+  pub async fn scan(&mut self) -> BatchedResult<()> {
+    let ret = self.scan_inner().await;
+
+    self.call_build_end_hook(ret).await?;
+
+    Ok(())
+  }
 
-      Ok(())
+  /// Call the `buildEnd` hook with the result of the build. Errors from the hook are appended to the
+  /// errors of the build if it failed.
+  async fn call_build_end_hook<R>(&mut self, ret: BatchedResult<R>) -> BatchedResult<R> {
+    match ret {
+      Ok(output) => {
+        self.plugin_driver.build_end(None).await?;
+        Ok(output)
+      }
+      Err(mut errors) => {
+        if let Err(e) = self.plugin_driver.build_end(errors.get()).await {
+          errors.push(e);
+        }
+        Err(errors)
+      }
     }
   }
 
   async fn scan_inner(&mut self) -> BatchedResult<ScanStageOutput> {
-    ScanStage::new(
+    self.file_snapshots.clear();
+    let ret = ScanStage::new(
       Arc::clone(&self.input_options),
       Arc::clone(&self.plugin_driver),
       self.fs.share(),
       Arc::clone(&self.resolver),
+      Arc::clone(&self.module_cache),
+      Arc::clone(&self.file_snapshots),
     )
     .scan()
-    .await
+    .await;
+    // Watch files added outside of `transform` hooks aren't snapshotted yet, and plugins could only add them
+    // until the scan is done.
+    for file in self.plugin_driver.watch_files().ids() {
+      if !self.file_snapshots.contains(&file) {
+        self.file_snapshots.record(&file, snapshot(&self.fs, &file));
+      }
+    }
+    ret
   }
 
   #[tracing::instrument(skip_all)]
-  async fn try_build(&mut self) -> BatchedResult<LinkStageOutput> {
-    self.plugin_driver.build_start().await?;
-
-    let scan_ret = self.scan_inner().await;
-
-    self.call_build_end_hook(&scan_ret).await?;
-
-    let build_info = scan_ret?;
+  async fn try_build(&mut self, output_options: &OutputOptions) -> BatchedResult<LinkStageOutput> {
+    let ret = self.scan_inner().await.and_then(|scan_output| {
+      LinkStage::new(scan_output, &self.input_options, output_options).link()
+    });
 
-    let link_stage = LinkStage::new(build_info, &self.input_options);
-    Ok(link_stage.link())
+    self.call_build_end_hook(ret).await
   }
 
   #[tracing::instrument(skip_all)]
   async fn bundle_up(
     &mut self,
-    output_options: OutputOptions,
+    output_options: &OutputOptions,
     is_write: bool,
   ) -> BatchedResult<RolldownOutput> {
     tracing::trace!("InputOptions {:#?}", self.input_options);
     tracing::trace!("OutputOptions: {output_options:#?}",);
-    let mut link_stage_output = self.try_build().await?;
+    let mut link_stage_output = self.try_build(output_options).await?;
 
     let mut bundle_stage = BundleStage::new(
       &mut link_stage_output,
       &self.input_options,
-      &output_options,
+      output_options,
       &self.plugin_driver,
     );
 
-    let assets = bundle_stage.bundle().await?;
+    let mut assets = bundle_stage.bundle().await?;
+
+    self
+      .plugin_driver
+      .generate_bundle(&mut HookGenerateBundleArgs {
+        output_options,
+        bundle: &mut assets,
+        is_write,
+      })
+      .await?;
+
+    let metafile = output_options.metafile.then(|| {
+      generate_metafile(&link_stage_output.module_table, link_stage_output.runtime.id(), &assets)
+    });
+
+    let (warnings, logs) =
+      self.handle_logs(std::mem::take(&mut link_stage_output.warnings)).await?;
 
-    self.plugin_driver.generate_bundle(&assets, is_write).await?;
+    Ok(RolldownOutput { warnings, logs, assets, metafile })
+  }
 
-    Ok(RolldownOutput { warnings: std::mem::take(&mut link_stage_output.warnings), assets })
+  /// Drop warnings of the build and logs of plugins more verbose than `log_level`, and let `on_log` decide what
+  /// is done with the rest. Returns the kept warnings and the kept info and debug logs.
+  async fn handle_logs(
+    &self,
+    warnings: Vec<BuildError>,
+  ) -> BatchedResult<(Vec<BuildError>, Vec<(LogLevel, BuildError)>)> {
+    let plugin_logs = self.plugin_driver.take_logs();
+    let Some(log_level) = self.input_options.log_level else {
+      return Ok((vec![], vec![]));
+    };
+    let mut kept_warnings = vec![];
+    let mut kept_logs = vec![];
+    let mut errors = BatchedErrors::default();
+    let logs = warnings.into_iter().map(|warning| (LogLevel::Warn, warning)).chain(plugin_logs);
+    for (level, log) in logs.filter(|(level, _)| *level <= log_level) {
+      let action = match &self.input_options.on_log {
+        Some(on_log) => on_log.call(level, &log).await?,
+        None => LogAction::Keep,
+      };
+      match action {
+        LogAction::Keep if level == LogLevel::Warn => kept_warnings.push(log),
+        LogAction::Keep => kept_logs.push((level, log)),
+        LogAction::Suppress => {}
+        LogAction::Error => errors.push(log.with_severity_error()),
+      }
+    }
+    if errors.is_empty() {
+      Ok((kept_warnings, kept_logs))
+    } else {
+      Err(errors)
+    }
   }
 }
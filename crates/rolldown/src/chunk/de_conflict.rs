@@ -1,15 +1,37 @@
 use std::borrow::Cow;
 
+use rolldown_common::Specifier;
 use rolldown_rstr::ToRstr;
 
 use super::Chunk;
-use crate::{stages::link_stage::LinkStageOutput, utils::renamer::Renamer};
+use crate::{
+  stages::link_stage::LinkStageOutput, utils::renamer::Renamer, OutputFormat, OutputOptions,
+};
 
 impl Chunk {
-  pub fn de_conflict(&mut self, graph: &LinkStageOutput) {
-    let mut renamer = Renamer::new(&graph.symbols, graph.module_table.normal_modules.len());
+  pub fn de_conflict(&mut self, graph: &LinkStageOutput, output_options: &OutputOptions) {
+    let mut renamer = Renamer::new(&graph.symbols, graph.module_table.normal_modules.len())
+      .with_minify(output_options.minify);
 
-    // TODO: reserve names for keywords in both non-strict and strict mode
+    match output_options.format {
+      OutputFormat::Esm => {}
+      OutputFormat::Cjs => {
+        // These names are provided by the commonjs module wrapper of Node.js
+        ["require", "exports", "module", "__filename", "__dirname"].into_iter().for_each(|name| {
+          renamer.reserve(Cow::Owned(name.into()));
+        });
+      }
+      OutputFormat::Iife | OutputFormat::Umd => {
+        // Parameter of the wrapper function to collect exports
+        renamer.reserve(Cow::Owned("exports".into()));
+      }
+      OutputFormat::SystemJs => {
+        // Parameters of the `System.register` declaration function and the setters of dependencies
+        ["_export", "_context", "module"].into_iter().for_each(|name| {
+          renamer.reserve(Cow::Owned(name.into()));
+        });
+      }
+    }
 
     self
       .modules
@@ -22,8 +44,24 @@ impl Chunk {
         renamer.reserve(Cow::Owned(name.to_rstr()));
       });
 
-    self.imports_from_other_chunks.iter().flat_map(|(_, items)| items.iter()).for_each(|item| {
-      renamer.add_top_level_symbol(item.import_ref);
+    if matches!(output_options.format, OutputFormat::Cjs) {
+      let mut namespace_refs = self.imported_chunk_namespace_refs.iter().collect::<Vec<_>>();
+      namespace_refs.sort_unstable_by_key(|(chunk_id, _)| **chunk_id);
+      namespace_refs.into_iter().for_each(|(_, namespace_ref)| {
+        renamer.add_top_level_symbol(*namespace_ref);
+      });
+    } else {
+      self.imports_from_other_chunks.iter().flat_map(|(_, items)| items.iter()).for_each(|item| {
+        renamer.add_top_level_symbol(item.import_ref);
+      });
+    }
+
+    let mut external_namespace_refs = self.external_namespace_refs.iter().collect::<Vec<_>>();
+    external_namespace_refs.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    external_namespace_refs.into_iter().for_each(|(_, namespace_ref)| {
+      renamer.add_top_level_symbol(*namespace_ref);
     });
 
     self
@@ -48,5 +86,24 @@ impl Chunk {
     renamer.rename_non_top_level_symbol(&self.modules, &graph.module_table.normal_modules);
 
     self.canonical_names = renamer.into_canonical_names();
+
+    // Bindings imported from other chunks aren't declared in the cjs format, they are read from the namespaces of
+    // the chunks on every access, so they stay live.
+    if matches!(output_options.format, OutputFormat::Cjs) {
+      self.imports_from_other_chunks.iter().for_each(|(importee_chunk_id, items)| {
+        let Some(namespace_ref) = self.imported_chunk_namespace_refs.get(importee_chunk_id) else {
+          return;
+        };
+        let namespace_name = self.canonical_names[namespace_ref].clone();
+        items.iter().for_each(|item| {
+          let Some(Specifier::Literal(export_alias)) = &item.export_alias else {
+            panic!("should not be star import from other chunks")
+          };
+          self
+            .canonical_names
+            .insert(item.import_ref, format!("{namespace_name}.{export_alias}").into());
+        });
+      });
+    }
   }
 }
@@ -1,7 +1,11 @@
 mod de_conflict;
 pub mod render_chunk;
+pub mod render_chunk_addons;
 mod render_chunk_exports;
 mod render_chunk_imports;
+mod render_chunk_wrapper;
+
+pub use render_chunk_imports::external_global_name;
 
 use index_vec::IndexVec;
 use rolldown_common::ChunkId;
@@ -9,21 +13,27 @@ use rolldown_common::ChunkId;
 pub type ChunksVec = IndexVec<ChunkId, Chunk>;
 
 use rolldown_common::{
-  ChunkKind, ExternalModuleId, NamedImport, NormalModuleId, RenderedModule, Specifier, SymbolRef,
+  ChunkFileNamesOption, ChunkKind, ExternalModuleId, LegalComments, NamedImport, NormalModuleId,
+  OutputExports, RenderedChunk, RenderedModule, Specifier, SymbolRef,
 };
 use rolldown_error::BuildError;
 use rolldown_rstr::Rstr;
-use rolldown_sourcemap::{collapse_sourcemaps, concat_sourcemaps, SourceMap};
+use rolldown_sourcemap::{concat_sourcemaps, SourceMap};
 use rolldown_utils::BitSet;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::path::Path;
+use sugar_path::SugarPath;
 
-use crate::utils::render_normal_module::render_normal_module;
+use self::render_chunk_addons::ChunkAddons;
+use crate::utils::{
+  legal_comments::collect_legal_comments, render_normal_module::render_normal_module,
+};
 use crate::{
   error::BatchedResult,
-  FileNameTemplate, InputOptions,
+  InputOptions, OutputFormat,
   {
-    chunk_graph::ChunkGraph, options::output_options::OutputOptions,
-    stages::link_stage::LinkStageOutput, types::module_render_context::ModuleRenderContext,
+    chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput,
+    types::module_render_context::ModuleRenderContext, OutputOptions,
   },
 };
 
@@ -43,14 +53,26 @@ pub struct Chunk {
   pub bits: BitSet,
   pub imports_from_other_chunks: FxHashMap<ChunkId, Vec<CrossChunkImportItem>>,
   pub imports_from_external_modules: FxHashMap<ExternalModuleId, Vec<NamedImport>>,
-  // meaningless if the chunk is an entrypoint
+  // Only used for output formats other than esm. Symbols referring to the result of `require("external")`.
+  pub external_namespace_refs: FxHashMap<ExternalModuleId, SymbolRef>,
+  // Only used for the cjs format. Symbols referring to the result of `require("./chunk.js")` of chunks this chunk
+  // imports bindings from.
+  pub imported_chunk_namespace_refs: FxHashMap<ChunkId, SymbolRef>,
+  // Entry chunks export these besides the exports of their entry modules, under the same names if they are already
+  // exported by the entry modules.
   pub exports_to_other_chunks: FxHashMap<SymbolRef, Rstr>,
+  /// File names of the CSS assets of the chunk.
+  pub imported_css: Vec<String>,
 }
 
 pub struct ChunkRenderReturn {
   pub code: String,
   pub map: Option<SourceMap>,
-  pub rendered_modules: FxHashMap<String, RenderedModule>,
+  pub rendered_chunk: RenderedChunk,
+  /// Banner and footer are added after `render_chunk` hooks, so they are returned separately.
+  pub addons: ChunkAddons,
+  /// Legal comments written to the `.LEGAL.txt` asset of the chunk with `legal_comments: external`.
+  pub legal_comments: Vec<String>,
 }
 
 impl Chunk {
@@ -66,16 +88,40 @@ impl Chunk {
   pub fn file_name_template<'a>(
     &mut self,
     output_options: &'a OutputOptions,
-  ) -> &'a FileNameTemplate {
-    if matches!(self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if is_user_defined) {
+  ) -> &'a ChunkFileNamesOption {
+    if output_options.preserve_modules
+      || matches!(self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if is_user_defined)
+    {
       &output_options.entry_file_names
     } else {
       &output_options.chunk_file_names
     }
   }
 
-  #[allow(clippy::unnecessary_wraps, clippy::cast_possible_truncation)]
-  pub fn render(
+  /// Chunks imported by this chunk, ordered by their ids to keep the output deterministic.
+  pub fn sorted_imports_from_other_chunks(&self) -> Vec<(&ChunkId, &Vec<CrossChunkImportItem>)> {
+    let mut imports_from_other_chunks = self.imports_from_other_chunks.iter().collect::<Vec<_>>();
+    imports_from_other_chunks.sort_unstable_by_key(|(chunk_id, _)| **chunk_id);
+    imports_from_other_chunks
+  }
+
+  /// The specifier used to import `importee` from this chunk, relative to the directory of this chunk.
+  pub fn import_path_for(&self, importee: &Chunk) -> String {
+    let file_name =
+      self.file_name.as_ref().expect("file name should be generated before rendering");
+    let importee_file_name =
+      importee.file_name.as_ref().expect("file name should be generated before rendering");
+    let dir = Path::new(file_name).parent().unwrap_or(Path::new(""));
+    let path = Path::new(importee_file_name).relative(dir).to_string_lossy().replace('\\', "/");
+    if path.starts_with("../") {
+      path
+    } else {
+      format!("./{path}")
+    }
+  }
+
+  #[allow(clippy::unnecessary_wraps, clippy::cast_possible_truncation, clippy::too_many_lines)]
+  pub async fn render(
     &self,
     input_options: &InputOptions,
     graph: &LinkStageOutput,
@@ -86,15 +132,27 @@ impl Chunk {
     let mut rendered_modules = FxHashMap::default();
     let mut content_and_sourcemaps = vec![];
 
-    content_and_sourcemaps
-      .push((self.render_imports_for_esm(graph, chunk_graph).to_string(), None));
+    let runtime_module_id = graph.runtime.id();
+    // Position right after the runtime module, so code relying on runtime helpers runs after they are defined.
+    let mut after_runtime_index = None;
+    let mut seen_legal_comments = FxHashSet::default();
+    // Legal comments that don't stay with their modules.
+    let mut legal_comments = vec![];
+
+    // Sources in the source map are relative to the directory of the chunk file.
+    let sourcemap_dir = output_options.sourcemap.as_ref().map(|_| {
+      let file_name =
+        self.file_name.as_ref().expect("file name should be generated before rendering");
+      let chunk_path = input_options.cwd.join(&output_options.dir).join(file_name);
+      chunk_path.parent().map(Path::to_path_buf).unwrap_or_default()
+    });
 
     self
       .modules
       .par_iter()
       .copied()
       .map(|id| &graph.module_table.normal_modules[id])
-      .filter_map(|m| {
+      .map(|m| {
         let rendered_content = render_normal_module(
           m,
           &ModuleRenderContext {
@@ -102,53 +160,186 @@ impl Chunk {
             graph,
             chunk_graph,
             input_options,
+            output_options,
           },
           &graph.ast_table[m.id],
+          sourcemap_dir.as_deref(),
         );
-        Some((
+        let legal_comments = if output_options.legal_comments == LegalComments::None {
+          vec![]
+        } else {
+          collect_legal_comments(&graph.ast_table[m.id])
+        };
+        let rendered_length = match &rendered_content {
+          Ok(Some(rendered_content)) => rendered_content.code.len(),
+          _ => 0,
+        };
+        (
+          m.id,
           m.resource_id.expect_file().to_string(),
-          RenderedModule { code: None },
+          RenderedModule { code: None, rendered_length },
           rendered_content,
-          if output_options.sourcemap.is_hidden() {
-            None
-          } else {
-            // TODO add oxc codegen sourcemap to sourcemap chain
-            Some(collapse_sourcemaps(m.sourcemap_chain.clone()))
-          },
-        ))
+          legal_comments,
+        )
       })
       .collect::<Vec<_>>()
       .into_iter()
       .try_for_each(
-        |(module_path, rendered_module, rendered_content, map)| -> Result<(), BuildError> {
-          if let Some(rendered_content) = rendered_content {
-            content_and_sourcemaps.push((
-              rendered_content.code.to_string(),
-              match map {
-                None => None,
-                Some(v) => v?,
-              },
-            ));
+        |(module_id, module_path, rendered_module, rendered_content, module_legal_comments)|
+         -> Result<(), BuildError> {
+          if let Some(rendered_content) = rendered_content? {
+            // Legal comments of modules that are tree shaken entirely are dropped along with them.
+            for comment in module_legal_comments {
+              if seen_legal_comments.insert(comment.clone()) {
+                if output_options.legal_comments == LegalComments::Inline {
+                  content_and_sourcemaps.push((comment, None));
+                } else {
+                  legal_comments.push(comment);
+                }
+              }
+            }
+            content_and_sourcemaps.push((rendered_content.code.to_string(), rendered_content.map));
+          }
+          if module_id == runtime_module_id {
+            after_runtime_index = Some(content_and_sourcemaps.len());
           }
           rendered_modules.insert(module_path, rendered_module);
           Ok(())
         },
       )?;
 
-    if let Some(exports) = self.render_exports(graph, output_options) {
-      content_and_sourcemaps.push((exports.to_string(), None));
+    let rendered_chunk =
+      self.get_rendered_chunk_info(graph, chunk_graph, output_options, rendered_modules);
+    let mut addons = ChunkAddons::render(output_options, &rendered_chunk).await?;
+    if let ChunkKind::EntryPoint { module, .. } = self.kind {
+      if let Some(hashbang) = &graph.module_table.normal_modules[module].hashbang {
+        addons.hashbang = format!("#!{hashbang}");
+      }
     }
 
-    if output_options.sourcemap.is_hidden() {
+    // Chunks loaded by other chunks, like common chunks and dynamic entries, are always in the named export mode.
+    let is_user_defined_entry =
+      matches!(self.kind, ChunkKind::EntryPoint { is_user_defined: true, .. });
+    let export_mode = match output_options.format {
+      OutputFormat::Esm | OutputFormat::SystemJs => OutputExports::Named,
+      OutputFormat::Cjs if !is_user_defined_entry => OutputExports::Named,
+      OutputFormat::Cjs | OutputFormat::Iife | OutputFormat::Umd => {
+        self.determine_export_mode(graph, output_options)?
+      }
+    };
+    let exports = self.render_exports(graph, output_options, export_mode);
+
+    match output_options.format {
+      OutputFormat::Esm => {
+        content_and_sourcemaps.insert(
+          0,
+          (self.render_imports_for_esm(graph, chunk_graph, output_options).to_string(), None),
+        );
+        if let Some(exports) = exports {
+          content_and_sourcemaps.push((exports.to_string(), None));
+        }
+        prepend_addon(&mut content_and_sourcemaps, &addons.intro);
+        append_addon(&mut content_and_sourcemaps, &addons.outro);
+      }
+      OutputFormat::Cjs => {
+        content_and_sourcemaps.insert(
+          after_runtime_index.unwrap_or(0),
+          (self.render_imports_for_cjs(graph, chunk_graph, output_options).to_string(), None),
+        );
+        if let Some(exports) = exports {
+          content_and_sourcemaps.push((exports.to_string(), None));
+        }
+        prepend_addon(&mut content_and_sourcemaps, &addons.intro);
+        append_addon(&mut content_and_sourcemaps, &addons.outro);
+        content_and_sourcemaps.insert(0, ("\"use strict\";".to_string(), None));
+      }
+      OutputFormat::Iife | OutputFormat::Umd => {
+        content_and_sourcemaps.insert(
+          after_runtime_index.unwrap_or(0),
+          (self.render_imports_for_wrapper(graph, output_options).to_string(), None),
+        );
+        let (intro, outro) = if matches!(output_options.format, OutputFormat::Iife) {
+          self.render_iife_wrapper(graph, output_options, export_mode)
+        } else {
+          self.render_umd_wrapper(graph, output_options, export_mode)
+        };
+        if let Some(exports) = exports {
+          content_and_sourcemaps.push((exports.to_string(), None));
+        }
+        prepend_addon(&mut content_and_sourcemaps, &addons.intro);
+        append_addon(&mut content_and_sourcemaps, &addons.outro);
+        content_and_sourcemaps.insert(0, (intro, None));
+        content_and_sourcemaps.push((outro, None));
+      }
+      OutputFormat::SystemJs => {
+        let (intro, outro) = self.render_system_wrapper(graph, chunk_graph, output_options);
+        if let Some(exports) = exports {
+          content_and_sourcemaps.push((exports.to_string(), None));
+        }
+        prepend_addon(&mut content_and_sourcemaps, &addons.intro);
+        append_addon(&mut content_and_sourcemaps, &addons.outro);
+        content_and_sourcemaps.insert(0, (intro, None));
+        content_and_sourcemaps.push((outro, None));
+      }
+    }
+
+    if output_options.legal_comments == LegalComments::Eof && !legal_comments.is_empty() {
+      content_and_sourcemaps.push((std::mem::take(&mut legal_comments).join("\n"), None));
+    }
+
+    // Compact output doesn't leave empty lines behind, like ones of chunks without imports.
+    if output_options.compact || output_options.minify {
+      content_and_sourcemaps.retain(|(content, _)| !content.is_empty());
+    }
+
+    if output_options.sourcemap.is_none() {
       return Ok(ChunkRenderReturn {
         code: content_and_sourcemaps.into_iter().map(|(c, _)| c).collect::<Vec<_>>().join("\n"),
         map: None,
-        rendered_modules,
+        rendered_chunk,
+        addons,
+        legal_comments,
       });
     }
 
     let (content, map) = concat_sourcemaps(&content_and_sourcemaps)?;
 
-    Ok(ChunkRenderReturn { code: content, map: Some(map), rendered_modules })
+    Ok(ChunkRenderReturn { code: content, map: Some(map), rendered_chunk, addons, legal_comments })
+  }
+}
+
+/// Add the banner and the footer, which go outside of the format wrapper, to the code after `render_chunk` hooks,
+/// along with the hashbang. Mappings of the chunk are moved down by the lines of the hashbang and the banner.
+pub fn add_banner_and_footer(
+  code: String,
+  map: Option<SourceMap>,
+  addons: &ChunkAddons,
+) -> Result<(String, Option<SourceMap>), BuildError> {
+  if addons.banner.is_empty() && addons.footer.is_empty() && addons.hashbang.is_empty() {
+    return Ok((code, map));
+  }
+  let has_map = map.is_some();
+  let mut content_and_sourcemaps = vec![(code, map)];
+  prepend_addon(&mut content_and_sourcemaps, &addons.banner);
+  prepend_addon(&mut content_and_sourcemaps, &addons.hashbang);
+  append_addon(&mut content_and_sourcemaps, &addons.footer);
+  if !has_map {
+    let code = content_and_sourcemaps.into_iter().map(|(c, _)| c).collect::<Vec<_>>().join("\n");
+    return Ok((code, None));
+  }
+  let (code, map) = concat_sourcemaps(&content_and_sourcemaps)?;
+  Ok((code, Some(map)))
+}
+
+// Empty addons shouldn't leave empty lines behind.
+fn prepend_addon(content_and_sourcemaps: &mut Vec<(String, Option<SourceMap>)>, addon: &str) {
+  if !addon.is_empty() {
+    content_and_sourcemaps.insert(0, (addon.to_string(), None));
+  }
+}
+
+fn append_addon(content_and_sourcemaps: &mut Vec<(String, Option<SourceMap>)>, addon: &str) {
+  if !addon.is_empty() {
+    content_and_sourcemaps.push((addon.to_string(), None));
   }
 }
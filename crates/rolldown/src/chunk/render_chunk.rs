@@ -1,27 +1,20 @@
-use rolldown_common::{ChunkKind, RenderedChunk, RenderedModule};
+use rolldown_common::{
+  ChunkKind, ImportKind, ModuleId, PreRenderedChunk, RenderedChunk, RenderedModule,
+};
 use rustc_hash::FxHashMap;
 
-use crate::{stages::link_stage::LinkStageOutput, OutputOptions};
+use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput, OutputOptions};
 
 use super::Chunk;
 
-#[derive(Debug, Clone)]
-pub struct PreRenderedChunk {
-  // pub name: String,
-  pub is_entry: bool,
-  pub is_dynamic_entry: bool,
-  pub facade_module_id: Option<String>,
-  pub module_ids: Vec<String>,
-  pub exports: Vec<String>,
-}
-
 impl Chunk {
   pub fn get_pre_rendered_chunk_info(
     &self,
     graph: &LinkStageOutput,
-    output_options: &OutputOptions,
+    _output_options: &OutputOptions,
   ) -> PreRenderedChunk {
     PreRenderedChunk {
+      name: self.name.clone().unwrap_or_default(),
       is_entry: matches!(&self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if *is_user_defined),
       is_dynamic_entry: matches!(&self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if !*is_user_defined),
       facade_module_id: match &self.kind {
@@ -35,13 +28,14 @@ impl Chunk {
         .iter()
         .map(|id| graph.module_table.normal_modules[*id].resource_id.expect_file().to_string())
         .collect(),
-      exports: self.get_export_names(graph, output_options),
+      exports: self.get_export_names(graph),
     }
   }
 
   pub fn get_rendered_chunk_info(
     &self,
     graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
     output_options: &OutputOptions,
     render_modules: FxHashMap<String, RenderedModule>,
   ) -> RenderedChunk {
@@ -54,6 +48,56 @@ impl Chunk {
       exports: pre_rendered_chunk.exports,
       file_name: self.file_name.clone().expect("should have file name"),
       modules: render_modules,
+      imports: self.get_imports(graph, chunk_graph),
+      dynamic_imports: self.get_dynamic_imports(graph, chunk_graph),
+      imported_css: self.imported_css.clone(),
     }
   }
+
+  /// External modules come first in execution order, followed by chunks.
+  fn get_imports(&self, graph: &LinkStageOutput, chunk_graph: &ChunkGraph) -> Vec<String> {
+    let mut external_modules = self
+      .imports_from_external_modules
+      .keys()
+      .map(|id| &graph.module_table.external_modules[*id])
+      .collect::<Vec<_>>();
+    external_modules.sort_unstable_by_key(|module| module.exec_order);
+    let mut chunk_ids = self.imports_from_other_chunks.keys().copied().collect::<Vec<_>>();
+    chunk_ids.sort_unstable();
+    external_modules
+      .into_iter()
+      .map(|module| module.resource_id.expect_file().to_string())
+      .chain(
+        chunk_ids
+          .into_iter()
+          .map(|id| chunk_graph.chunks[id].file_name.clone().expect("should have file name")),
+      )
+      .collect()
+  }
+
+  fn get_dynamic_imports(&self, graph: &LinkStageOutput, chunk_graph: &ChunkGraph) -> Vec<String> {
+    let mut dynamic_imports = vec![];
+    self
+      .modules
+      .iter()
+      .map(|id| &graph.module_table.normal_modules[*id])
+      .filter(|module| module.is_included)
+      .flat_map(|module| module.import_records.iter())
+      .filter(|rec| matches!(rec.kind, ImportKind::DynamicImport))
+      .for_each(|rec| {
+        let import = match rec.resolved_module {
+          ModuleId::Normal(id) => chunk_graph.module_to_chunk[id]
+            .and_then(|chunk_id| chunk_graph.chunks[chunk_id].file_name.clone()),
+          ModuleId::External(id) => {
+            Some(graph.module_table.external_modules[id].resource_id.expect_file().to_string())
+          }
+        };
+        if let Some(import) = import {
+          if !dynamic_imports.contains(&import) {
+            dynamic_imports.push(import);
+          }
+        }
+      });
+    dynamic_imports
+  }
 }
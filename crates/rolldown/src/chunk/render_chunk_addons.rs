@@ -0,0 +1,29 @@
+use rolldown_common::{OutputOptions, RenderedChunk};
+use rolldown_error::BuildError;
+
+/// Code added to a chunk by the `banner`, `footer`, `intro` and `outro` options.
+#[derive(Debug, Default)]
+pub struct ChunkAddons {
+  pub banner: String,
+  pub footer: String,
+  pub intro: String,
+  pub outro: String,
+  /// The `#!` line of the module of an entry chunk, which goes before the banner as the first line of the chunk.
+  pub hashbang: String,
+}
+
+impl ChunkAddons {
+  pub async fn render(
+    output_options: &OutputOptions,
+    chunk: &RenderedChunk,
+  ) -> Result<Self, BuildError> {
+    let (banner, footer, intro, outro) = futures::future::try_join4(
+      output_options.banner.call(chunk),
+      output_options.footer.call(chunk),
+      output_options.intro.call(chunk),
+      output_options.outro.call(chunk),
+    )
+    .await?;
+    Ok(Self { banner, footer, intro, outro, hashbang: String::new() })
+  }
+}
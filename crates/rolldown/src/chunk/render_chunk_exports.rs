@@ -1,44 +1,137 @@
-use rolldown_common::{ChunkKind, SymbolRef, WrapKind};
+use rolldown_common::{
+  ChunkKind, ExternalModuleId, OutputExports, OutputFormat, OutputOptions, SymbolRef, WrapKind,
+};
+use rolldown_error::BuildError;
 use rolldown_rstr::Rstr;
+use rustc_hash::FxHashMap;
 use string_wizard::MagicString;
 
-use crate::{stages::link_stage::LinkStageOutput, OutputFormat, OutputOptions};
+use crate::stages::link_stage::LinkStageOutput;
 
-use super::Chunk;
+use super::{render_chunk_imports::external_import_path, Chunk};
 
 impl Chunk {
   pub fn render_exports(
     &self,
     graph: &LinkStageOutput,
     output_options: &OutputOptions,
+    export_mode: OutputExports,
   ) -> Option<MagicString<'static>> {
-    if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &self.kind {
-      let linking_info = &graph.metas[*entry_module_id];
-      if matches!(linking_info.wrap_kind, WrapKind::Cjs) {
-        match output_options.format {
-          OutputFormat::Esm => {
-            let wrap_ref_name =
-              &self.canonical_names.get(&linking_info.wrapper_ref.unwrap()).unwrap_or_else(|| {
-                panic!(
-                  "Cannot find canonical name for wrap ref {:?} of {:?}",
-                  linking_info.wrapper_ref.unwrap(),
-                  graph.module_table.normal_modules[*entry_module_id].resource_id
-                )
-              });
-            return Some(MagicString::new(format!("export default {wrap_ref_name}();\n")));
-          }
-          OutputFormat::Cjs => {
-            unreachable!("entry CJS should not be wrapped in `OutputFormat::Cjs`")
-          }
-        }
-      }
+    if let Some(wrapper_ref) = self.entry_cjs_wrapper_ref(graph) {
+      let wrap_ref_name = &self.canonical_names.get(&wrapper_ref).unwrap_or_else(|| {
+        panic!("Cannot find canonical name for wrap ref {wrapper_ref:?} of {:?}", wrapper_ref.owner)
+      });
+      return Some(MagicString::new(match output_options.format {
+        OutputFormat::Esm => format!("export default {wrap_ref_name}();\n"),
+        OutputFormat::Cjs => format!("module.exports = {wrap_ref_name}();\n"),
+        OutputFormat::Iife | OutputFormat::Umd => format!("return {wrap_ref_name}();\n"),
+        OutputFormat::SystemJs => format!("_export(\"default\", {wrap_ref_name}());\n"),
+      }));
     }
 
     let export_items = self.get_export_items(graph);
 
-    if export_items.is_empty() {
+    // External modules are re-exported by `export * from` in the esm format, along with the imports.
+    let has_star_exports = matches!(output_options.format, OutputFormat::Cjs)
+      && !self.star_exports_from_external_modules(graph, output_options).is_empty();
+    if export_items.is_empty() && !has_star_exports {
       return None;
     }
+    match output_options.format {
+      OutputFormat::Esm => Some(self.render_exports_for_esm(graph, export_items)),
+      OutputFormat::SystemJs => self.render_exports_for_system(graph, export_items),
+      OutputFormat::Cjs | OutputFormat::Iife | OutputFormat::Umd => match export_mode {
+        OutputExports::Named => {
+          Some(self.render_exports_for_cjs(graph, output_options, export_items))
+        }
+        OutputExports::Default => {
+          let value = self.render_default_export_value(graph, export_items);
+          Some(MagicString::new(if matches!(output_options.format, OutputFormat::Cjs) {
+            format!("module.exports = {value};\n")
+          } else {
+            format!("return {value};\n")
+          }))
+        }
+        OutputExports::None | OutputExports::Auto => None,
+      },
+    }
+  }
+
+  /// The wrapper symbol of the entry module, if the entry is a commonjs module.
+  fn entry_cjs_wrapper_ref(&self, graph: &LinkStageOutput) -> Option<SymbolRef> {
+    let ChunkKind::EntryPoint { module: entry_module_id, .. } = &self.kind else {
+      return None;
+    };
+    let linking_info = &graph.metas[*entry_module_id];
+    matches!(linking_info.wrap_kind, WrapKind::Cjs).then(|| linking_info.wrapper_ref.unwrap())
+  }
+
+  /// Resolve `output.exports` against the actual exports of the chunk. Never returns `OutputExports::Auto`. Exports
+  /// are dropped in the `none` mode, which is warned about by the bundle stage.
+  pub fn determine_export_mode(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+  ) -> Result<OutputExports, BuildError> {
+    if self.entry_cjs_wrapper_ref(graph).is_some() {
+      // `module.exports` of the commonjs entry is the value
+      return Ok(OutputExports::Default);
+    }
+    let export_names = self.get_export_names(graph);
+    // Names re-exported from external modules are only known at runtime, they are exported in the named mode.
+    let has_star_exports =
+      !self.star_exports_from_external_modules(graph, output_options).is_empty();
+    let is_default_only =
+      export_names.len() == 1 && export_names[0] == "default" && !has_star_exports;
+    let invalid_export_option = |export_mode| {
+      let ChunkKind::EntryPoint { module, .. } = &self.kind else {
+        unreachable!("Only entry chunks have exports to be validated")
+      };
+      let module = &graph.module_table.normal_modules[*module];
+      BuildError::invalid_export_option(
+        export_mode,
+        module.resource_id.expect_file().as_str(),
+        export_names.clone(),
+      )
+    };
+    match output_options.exports {
+      OutputExports::Auto => Ok(if export_names.is_empty() && !has_star_exports {
+        OutputExports::None
+      } else if is_default_only {
+        OutputExports::Default
+      } else {
+        OutputExports::Named
+      }),
+      OutputExports::Default if !is_default_only => Err(invalid_export_option("default")),
+      mode => Ok(mode),
+    }
+  }
+
+  /// The expression of the default export, which is the value of the chunk in the default export mode.
+  fn render_default_export_value(
+    &self,
+    graph: &LinkStageOutput,
+    export_items: Vec<(Rstr, SymbolRef)>,
+  ) -> String {
+    let (_, export_ref) = export_items
+      .into_iter()
+      .find(|(exported_name, _)| exported_name.as_str() == "default")
+      .expect("Default export mode requires a default export");
+    let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+    let symbol = graph.symbols.get(canonical_ref);
+    if let Some(ns_alias) = &symbol.namespace_alias {
+      let canonical_ns_name = &self.canonical_names[&ns_alias.namespace_ref];
+      format!("{canonical_ns_name}.{}", ns_alias.property_name)
+    } else {
+      self.canonical_names[&canonical_ref].to_string()
+    }
+  }
+
+  fn render_exports_for_esm(
+    &self,
+    graph: &LinkStageOutput,
+    export_items: Vec<(Rstr, SymbolRef)>,
+  ) -> MagicString<'static> {
     let mut s = MagicString::new("");
     let rendered_items = export_items
       .into_iter()
@@ -59,17 +152,165 @@ impl Chunk {
       })
       .collect::<Vec<_>>();
     s.append(format!("export {{ {} }};", rendered_items.join(", "),));
-    Some(s)
+    s
+  }
+
+  fn render_exports_for_cjs(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+    export_items: Vec<(Rstr, SymbolRef)>,
+  ) -> MagicString<'static> {
+    let mut s = MagicString::new("");
+    if export_items.iter().any(|(exported_name, _)| exported_name.as_str() == "default") {
+      s.append("Object.defineProperty(exports, \"__esModule\", { value: true });\n");
+    }
+    export_items.into_iter().for_each(|(exported_name, export_ref)| {
+      let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+      let symbol = graph.symbols.get(canonical_ref);
+      if let Some(ns_alias) = &symbol.namespace_alias {
+        // Re-exports from external modules or commonjs modules are exported with getters to keep live bindings.
+        let canonical_ns_name = &self.canonical_names[&ns_alias.namespace_ref];
+        let property_name = &ns_alias.property_name;
+        s.append(render_export_getter(
+          &exported_name,
+          &format!("{canonical_ns_name}.{property_name}"),
+        ));
+      } else {
+        let canonical_name = &self.canonical_names[&canonical_ref];
+        let module = &graph.module_table.normal_modules[canonical_ref.owner];
+        if module.scope.is_reassigned(canonical_ref.symbol)
+          || self.is_imported_from_other_chunks(graph, canonical_ref)
+        {
+          // Values of reassigned bindings, and bindings of other chunks which might not be initialized yet when
+          // chunks require each other, are read on access, so they are live like in esm.
+          s.append(render_export_getter(&exported_name, canonical_name));
+        } else {
+          s.append(format!("exports.{exported_name} = {canonical_name};\n"));
+        }
+      }
+    });
+    // Properties already set above are skipped by the helper, so explicit exports take precedence.
+    let star_exports = self.star_exports_from_external_modules(graph, output_options);
+    if !star_exports.is_empty() {
+      let re_export_fn_name = graph
+        .symbols
+        .canonical_name_for(graph.runtime.resolve_symbol("__reExport"), &self.canonical_names);
+      // The module might be re-exported through a module of another chunk, so it has no binding in this chunk. It's
+      // required again here, which hits the cache of `require`.
+      star_exports.iter().for_each(|importee_id| {
+        let importee = &graph.module_table.external_modules[*importee_id];
+        let module = external_import_path(output_options, importee.resource_id.expect_file());
+        s.append(format!("{re_export_fn_name}(exports, require(\"{module}\"));\n"));
+      });
+    }
+    s
   }
 
-  fn get_export_items(&self, graph: &LinkStageOutput) -> Vec<(Rstr, SymbolRef)> {
+  /// External modules re-exported by `export * from` of the entry module, which the chunk re-exports too. Only
+  /// the esm and cjs formats support them.
+  pub fn star_exports_from_external_modules<'a>(
+    &self,
+    graph: &'a LinkStageOutput,
+    output_options: &OutputOptions,
+  ) -> &'a [ExternalModuleId] {
+    match self.kind {
+      ChunkKind::EntryPoint { module, .. }
+        if matches!(output_options.format, OutputFormat::Esm | OutputFormat::Cjs) =>
+      {
+        &graph.metas[module].star_exports_from_external_modules
+      }
+      _ => &[],
+    }
+  }
+
+  /// Exports bound by the setters of dependencies are left out, they are exported when the setters get called.
+  fn render_exports_for_system(
+    &self,
+    graph: &LinkStageOutput,
+    export_items: Vec<(Rstr, SymbolRef)>,
+  ) -> Option<MagicString<'static>> {
+    let rendered_items = export_items
+      .into_iter()
+      .filter(|(_, export_ref)| !self.is_exported_by_system_setters(graph, *export_ref))
+      .map(|(exported_name, export_ref)| {
+        let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+        let symbol = graph.symbols.get(canonical_ref);
+        if let Some(ns_alias) = &symbol.namespace_alias {
+          let canonical_ns_name = &self.canonical_names[&ns_alias.namespace_ref];
+          format!("  {exported_name}: {canonical_ns_name}.{}", ns_alias.property_name)
+        } else {
+          format!("  {exported_name}: {}", self.canonical_names[&canonical_ref])
+        }
+      })
+      .collect::<Vec<_>>();
+    if rendered_items.is_empty() {
+      return None;
+    }
+    Some(MagicString::new(format!("_export({{\n{}\n}});\n", rendered_items.join(",\n"))))
+  }
+
+  /// Whether the export is a binding imported from an external module or another chunk, which is exported
+  /// in the setter of the dependency to keep it live in the system format.
+  pub fn is_exported_by_system_setters(
+    &self,
+    graph: &LinkStageOutput,
+    export_ref: SymbolRef,
+  ) -> bool {
+    let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+    if let Some(ns_alias) = &graph.symbols.get(canonical_ref).namespace_alias {
+      return self.external_namespace_refs.values().any(|ns_ref| *ns_ref == ns_alias.namespace_ref);
+    }
+    self.is_imported_from_other_chunks(graph, canonical_ref)
+  }
+
+  fn is_imported_from_other_chunks(
+    &self,
+    graph: &LinkStageOutput,
+    canonical_ref: SymbolRef,
+  ) -> bool {
+    self
+      .imports_from_other_chunks
+      .values()
+      .flatten()
+      .any(|item| graph.symbols.par_canonical_ref_for(item.import_ref) == canonical_ref)
+  }
+
+  /// Exported names of the bindings declared in the chunk, keyed by the canonical symbols of the bindings.
+  /// Used to keep exports live in the system format, where every assignment to them needs to be reported.
+  pub fn canonical_export_names(&self, graph: &LinkStageOutput) -> FxHashMap<SymbolRef, Vec<Rstr>> {
+    let mut export_names: FxHashMap<SymbolRef, Vec<Rstr>> = FxHashMap::default();
+    self.get_export_items(graph).into_iter().for_each(|(exported_name, export_ref)| {
+      let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+      if graph.symbols.get(canonical_ref).namespace_alias.is_none() {
+        export_names.entry(canonical_ref).or_default().push(exported_name);
+      }
+    });
+    export_names
+  }
+
+  pub fn get_export_items(&self, graph: &LinkStageOutput) -> Vec<(Rstr, SymbolRef)> {
     match self.kind {
       ChunkKind::EntryPoint { module, .. } => {
         let meta = &graph.metas[module];
-        meta
-          .canonical_exports()
-          .map(|(name, export)| (name.clone(), export.symbol_ref))
-          .collect::<Vec<_>>()
+        let mut export_items = if meta.is_entry_signature_dropped {
+          vec![]
+        } else {
+          meta
+            .canonical_exports()
+            .map(|(name, export)| (name.clone(), export.symbol_ref))
+            .collect::<Vec<_>>()
+        };
+        // Bindings imported by other chunks, if the entry chunk is allowed to export more than its entry module
+        let mut extra_items = self
+          .exports_to_other_chunks
+          .iter()
+          .filter(|(_, alias)| export_items.iter().all(|(name, _)| name != *alias))
+          .map(|(export_ref, alias)| (alias.clone(), *export_ref))
+          .collect::<Vec<_>>();
+        extra_items.sort_unstable_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        export_items.extend(extra_items);
+        export_items
       }
       ChunkKind::Common => {
         let mut tmp = self
@@ -85,23 +326,9 @@ impl Chunk {
     }
   }
 
-  pub fn get_export_names(
-    &self,
-    graph: &LinkStageOutput,
-    output_options: &OutputOptions,
-  ) -> Vec<String> {
-    if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &self.kind {
-      let linking_info = &graph.metas[*entry_module_id];
-      if matches!(linking_info.wrap_kind, WrapKind::Cjs) {
-        match output_options.format {
-          OutputFormat::Esm => {
-            return vec!["default".to_string()];
-          }
-          OutputFormat::Cjs => {
-            unreachable!("entry CJS should not be wrapped in `OutputFormat::Cjs`")
-          }
-        }
-      }
+  pub fn get_export_names(&self, graph: &LinkStageOutput) -> Vec<String> {
+    if self.entry_cjs_wrapper_ref(graph).is_some() {
+      return vec!["default".to_string()];
     }
 
     self
@@ -111,3 +338,10 @@ impl Chunk {
       .collect::<Vec<_>>()
   }
 }
+
+/// `Object.defineProperty(exports, "foo", { enumerable: true, get: ... })`, which keeps the export live.
+fn render_export_getter(exported_name: &str, value: &str) -> String {
+  format!(
+    "Object.defineProperty(exports, \"{exported_name}\", {{\n  enumerable: true,\n  get: function () {{\n    return {value};\n  }}\n}});\n"
+  )
+}
@@ -1,7 +1,12 @@
-use rolldown_common::Specifier;
+use rolldown_common::{ExternalModuleId, ImportAttributes, NamedImport, Specifier};
 use string_wizard::MagicString;
 
-use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput};
+use crate::{
+  chunk_graph::ChunkGraph,
+  stages::link_stage::LinkStageOutput,
+  utils::interop::{external_interop_helper, render_external_interop},
+  OutputOptions,
+};
 
 use super::Chunk;
 
@@ -12,6 +17,7 @@ impl Chunk {
     &self,
     graph: &LinkStageOutput,
     chunk_graph: &ChunkGraph,
+    output_options: &OutputOptions,
   ) -> MagicString<'static> {
     let mut s = MagicString::new("");
     // render imports from external modules
@@ -20,9 +26,15 @@ impl Chunk {
     imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
       graph.module_table.external_modules[**module_id].exec_order
     });
+    let star_exports = self.star_exports_from_external_modules(graph, output_options);
     imports_from_external_modules.into_iter().for_each(|(importee_id, named_imports)| {
       let importee = &graph.module_table.external_modules[*importee_id];
-      let mut is_importee_imported = false;
+      let module = external_import_path(output_options, importee.resource_id.expect_file());
+      let with = render_import_attributes(&importee.attributes);
+      let mut is_importee_imported = star_exports.contains(importee_id);
+      if is_importee_imported {
+        s.append(format!("export * from \"{module}\"{with};\n"));
+      }
       let mut import_items = named_imports
         .iter()
         .filter_map(|item| {
@@ -31,10 +43,7 @@ impl Chunk {
           match &item.imported {
             Specifier::Star => {
               is_importee_imported = true;
-              s.append(format!(
-                "import * as {alias} from \"{module}\";\n",
-                module = importee.resource_id.expect_file().as_str()
-              ));
+              s.append(format!("import * as {alias} from \"{module}\"{with};\n"));
               None
             }
             Specifier::Literal(imported) => Some(if imported == alias {
@@ -47,20 +56,16 @@ impl Chunk {
         .collect::<Vec<_>>();
       import_items.sort();
       if !import_items.is_empty() {
-        s.append(format!(
-          "import {{ {} }} from \"{}\";\n",
-          import_items.join(", "),
-          importee.resource_id.expect_file().as_str()
-        ));
+        s.append(format!("import {{ {} }} from \"{module}\"{with};\n", import_items.join(", ")));
       } else if !is_importee_imported {
         // Ensure the side effect
-        s.append(format!("import \"{}\";\n", importee.resource_id.expect_file().as_str()));
+        s.append(format!("import \"{module}\"{with};\n"));
       }
     });
 
     // render imports from other chunks
 
-    self.imports_from_other_chunks.iter().for_each(|(exporter_id, items)| {
+    self.sorted_imports_from_other_chunks().into_iter().for_each(|(exporter_id, items)| {
       let importee_chunk = &chunk_graph.chunks[*exporter_id];
       let mut import_items = items
         .iter()
@@ -77,17 +82,310 @@ impl Chunk {
           }
         })
         .collect::<Vec<_>>();
-      let file_name = importee_chunk
-        .file_name
-        .as_ref()
-        .expect("At this point, file name should already be generated");
+      let import_path = self.import_path_for(importee_chunk);
       if import_items.is_empty() {
-        s.append(format!("import \"./{file_name}\";\n"));
+        s.append(format!("import \"{import_path}\";\n"));
       } else {
         import_items.sort();
-        s.append(format!("import {{ {} }} from \"./{file_name}\";\n", import_items.join(", ")));
+        s.append(format!("import {{ {} }} from \"{import_path}\";\n", import_items.join(", ")));
+      }
+    });
+    s
+  }
+}
+
+/// The `with` clause of imports from external modules, like ` with { type: "json" }`, which is empty if the module is
+/// imported without attributes.
+fn render_import_attributes(attributes: &ImportAttributes) -> String {
+  if attributes.is_empty() {
+    return String::new();
+  }
+  let attributes = attributes
+    .iter()
+    .map(|(key, value)| {
+      // Keys like `resolution-mode` aren't identifiers, so they are quoted.
+      if key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+        format!("{key}: {value:?}")
+      } else {
+        format!("{key:?}: {value:?}")
+      }
+    })
+    .collect::<Vec<_>>();
+  format!(" with {{ {} }}", attributes.join(", "))
+}
+
+impl Chunk {
+  pub fn render_imports_for_cjs(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    output_options: &OutputOptions,
+  ) -> MagicString<'static> {
+    let mut s = MagicString::new("");
+    // render imports from other chunks
+    // Chunks are required first, since runtime helpers used by the following code might come from them.
+    self.sorted_imports_from_other_chunks().into_iter().for_each(|(exporter_id, items)| {
+      let importee_chunk = &chunk_graph.chunks[*exporter_id];
+      let import_path = self.import_path_for(importee_chunk);
+      if items.is_empty() {
+        s.append(format!("require(\"{import_path}\");\n"));
+      } else {
+        // Imported bindings are already rewritten to property accesses on this binding
+        let namespace_name =
+          &self.canonical_names[&self.imported_chunk_namespace_refs[exporter_id]];
+        s.append(format!("var {namespace_name} = require(\"{import_path}\");\n"));
       }
     });
+
+    // render imports from external modules
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    imports_from_external_modules.into_iter().for_each(|(importee_id, named_imports)| {
+      let importee = &graph.module_table.external_modules[*importee_id];
+      let module = external_import_path(output_options, importee.resource_id.expect_file());
+      if named_imports.is_empty() {
+        // Ensure the side effect
+        s.append(format!("require(\"{module}\");\n"));
+      } else {
+        // Named imports are already rewritten to property accesses on this binding
+        let namespace_ref = self.external_namespace_refs[importee_id];
+        let namespace_name = graph.symbols.canonical_name_for(namespace_ref, &self.canonical_names);
+        let required = format!("require(\"{module}\")");
+        let value = self
+          .render_external_interop(graph, output_options, *importee_id, named_imports, &required)
+          .unwrap_or(required);
+        s.append(format!("var {namespace_name} = {value};\n"));
+      }
+    });
+    s
+  }
+
+  /// Wrap `value`, which is `module.exports` of an external module, with the helper of `output.interop`. Returns
+  /// `None` if the value is used as is, like when no default or namespace import of the module needs interop.
+  fn render_external_interop(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+    importee_id: ExternalModuleId,
+    named_imports: &[NamedImport],
+    value: &str,
+  ) -> Option<String> {
+    let needs_interop = named_imports
+      .iter()
+      .any(|item| matches!(&item.imported, Specifier::Star) || item.imported.is_default());
+    let importee = &graph.module_table.external_modules[importee_id];
+    let interop = output_options.interop.call(importee.resource_id.expect_file());
+    let helper = external_interop_helper(interop).filter(|_| needs_interop)?;
+    let helper_name =
+      graph.symbols.canonical_name_for(graph.runtime.resolve_symbol(helper), &self.canonical_names);
+    Some(render_external_interop(interop, helper_name, value))
+  }
+}
+
+pub struct WrapperExternalParam {
+  /// Name of the parameter of the wrapper function
+  pub name: String,
+  pub global_name: String,
+  /// The specifier the module is required with, remapped by `output.paths`.
+  pub module_id: String,
+}
+
+impl Chunk {
+  /// External modules that are passed into the wrapper function of iife/umd formats, sorted by execution order.
+  /// Side-effect-only imports are skipped, since there is nothing to bind for them.
+  fn external_modules_with_bindings<'a>(
+    &'a self,
+    graph: &LinkStageOutput,
+  ) -> Vec<(&'a ExternalModuleId, &'a Vec<NamedImport>)> {
+    let mut imports_from_external_modules = self
+      .imports_from_external_modules
+      .iter()
+      .filter(|(_, named_imports)| !named_imports.is_empty())
+      .collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    imports_from_external_modules
+  }
+
+  pub fn wrapper_external_params(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+  ) -> Vec<WrapperExternalParam> {
+    self
+      .external_modules_with_bindings(graph)
+      .into_iter()
+      .map(|(importee_id, _)| {
+        let importee = &graph.module_table.external_modules[*importee_id];
+        let module_id = importee.resource_id.expect_file();
+        let namespace_ref = self.external_namespace_refs[importee_id];
+        WrapperExternalParam {
+          name: graph.symbols.canonical_name_for(namespace_ref, &self.canonical_names).to_string(),
+          global_name: external_global_name(output_options, module_id),
+          module_id: external_import_path(output_options, module_id),
+        }
+      })
+      .collect()
+  }
+
+  /// Specifiers of external modules that are imported only for side effects, sorted by execution order.
+  pub fn side_effect_only_external_modules(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+  ) -> Vec<String> {
+    let mut external_modules = self
+      .imports_from_external_modules
+      .iter()
+      .filter(|(_, named_imports)| named_imports.is_empty())
+      .map(|(importee_id, _)| &graph.module_table.external_modules[*importee_id])
+      .collect::<Vec<_>>();
+    external_modules.sort_unstable_by_key(|module| module.exec_order);
+    external_modules
+      .into_iter()
+      .map(|module| external_import_path(output_options, module.resource_id.expect_file()))
+      .collect()
+  }
+
+  pub fn render_imports_for_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+  ) -> MagicString<'static> {
+    let mut s = MagicString::new("");
+    // Externals are passed in as parameters, we only need to take care of the interop here.
+    self.external_modules_with_bindings(graph).into_iter().for_each(
+      |(importee_id, named_imports)| {
+        let namespace_ref = self.external_namespace_refs[importee_id];
+        let namespace_name = graph.symbols.canonical_name_for(namespace_ref, &self.canonical_names);
+        if let Some(value) = self.render_external_interop(
+          graph,
+          output_options,
+          *importee_id,
+          named_imports,
+          namespace_name,
+        ) {
+          s.append(format!("{namespace_name} = {value};\n"));
+        }
+      },
+    );
     s
   }
 }
+
+/// The global variable name for an external module, guessed from the module id if it's
+/// missing from `output.globals`.
+pub fn external_global_name(output_options: &OutputOptions, module_id: &str) -> String {
+  output_options
+    .globals
+    .call(module_id)
+    .unwrap_or_else(|| rolldown_common::representative_name(module_id).into_owned())
+}
+
+/// The specifier an external module is imported with in the output, which is its id unless it's remapped by
+/// `output.paths`.
+pub fn external_import_path(output_options: &OutputOptions, module_id: &str) -> String {
+  output_options.paths.as_ref().map_or_else(|| module_id.to_string(), |paths| paths.call(module_id))
+}
+
+pub struct SystemDependency {
+  pub module_id: String,
+  /// Source of the setter function, `None` if nothing is bound from the dependency.
+  pub setter: Option<String>,
+}
+
+impl Chunk {
+  /// Dependencies of the chunk in the system format, external modules in execution order come first.
+  ///
+  /// Setters get called with the namespace object of the dependency whenever its exports change, so bindings
+  /// imported from it are assigned there. Bindings re-exported by this chunk are exported there as well.
+  pub fn system_dependencies(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    output_options: &OutputOptions,
+  ) -> Vec<SystemDependency> {
+    let export_items = self.get_export_items(graph);
+    let export_names = self.canonical_export_names(graph);
+    let render_setter = |stmts: Vec<String>| {
+      (!stmts.is_empty()).then(|| format!("function(module) {{\n  {}\n}}", stmts.join("\n  ")))
+    };
+
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    let external_dependencies =
+      imports_from_external_modules.into_iter().map(|(importee_id, named_imports)| {
+        let importee = &graph.module_table.external_modules[*importee_id];
+        let mut stmts = vec![];
+        if !named_imports.is_empty() {
+          let namespace_ref = self.external_namespace_refs[importee_id];
+          let namespace_name =
+            graph.symbols.canonical_name_for(namespace_ref, &self.canonical_names);
+          stmts.push(format!("{namespace_name} = module;"));
+          export_items.iter().for_each(|(exported_name, export_ref)| {
+            let canonical_ref = graph.symbols.par_canonical_ref_for(*export_ref);
+            match &graph.symbols.get(canonical_ref).namespace_alias {
+              Some(ns_alias) if ns_alias.namespace_ref == namespace_ref => {
+                stmts.push(format!(
+                  "_export(\"{exported_name}\", module.{});",
+                  ns_alias.property_name
+                ));
+              }
+              _ => {}
+            }
+          });
+        }
+        SystemDependency {
+          module_id: external_import_path(output_options, importee.resource_id.expect_file()),
+          setter: render_setter(stmts),
+        }
+      });
+
+    let chunk_dependencies =
+      self.sorted_imports_from_other_chunks().into_iter().map(|(exporter_id, items)| {
+        let importee_chunk = &chunk_graph.chunks[*exporter_id];
+        let mut stmts = vec![];
+        items.iter().for_each(|item| {
+          let canonical_ref = graph.symbols.par_canonical_ref_for(item.import_ref);
+          let local_binding = &self.canonical_names[&canonical_ref];
+          let Specifier::Literal(export_alias) = item.export_alias.as_ref().unwrap() else {
+            panic!("should not be star import from other chunks")
+          };
+          stmts.push(format!("{local_binding} = module.{export_alias};"));
+          export_names.get(&canonical_ref).into_iter().flatten().for_each(|exported_name| {
+            stmts.push(format!("_export(\"{exported_name}\", {local_binding});"));
+          });
+        });
+        SystemDependency {
+          module_id: self.import_path_for(importee_chunk),
+          setter: render_setter(stmts),
+        }
+      });
+
+    external_dependencies.chain(chunk_dependencies).collect()
+  }
+
+  /// Bindings assigned by the setters of dependencies in the system format.
+  pub fn system_imported_bindings(&self, graph: &LinkStageOutput) -> Vec<String> {
+    let external_bindings =
+      self.external_modules_with_bindings(graph).into_iter().map(|(importee_id, _)| {
+        let namespace_ref = self.external_namespace_refs[importee_id];
+        graph.symbols.canonical_name_for(namespace_ref, &self.canonical_names).to_string()
+      });
+    let chunk_bindings =
+      self.sorted_imports_from_other_chunks().into_iter().flat_map(|(_, items)| {
+        items.iter().map(|item| {
+          let canonical_ref = graph.symbols.par_canonical_ref_for(item.import_ref);
+          self.canonical_names[&canonical_ref].to_string()
+        })
+      });
+    external_bindings.chain(chunk_bindings).collect()
+  }
+}
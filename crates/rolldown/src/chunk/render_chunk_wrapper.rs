@@ -0,0 +1,186 @@
+use rolldown_common::{OutputExports, OutputOptions};
+
+use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput};
+
+use super::Chunk;
+
+impl Chunk {
+  /// Returns the code placed before and after the content of the chunk for the iife format.
+  pub fn render_iife_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+    export_mode: OutputExports,
+  ) -> (String, String) {
+    let has_exports = matches!(export_mode, OutputExports::Named);
+    let external_params = self.wrapper_external_params(graph, output_options);
+    // With `extend`, exports are added to the existing global variable instead of replacing it.
+    let extended_name = output_options.name.as_deref().filter(|_| output_options.extend);
+
+    let exports_arg = extended_name
+      .map_or_else(|| "{}".to_string(), |name| format!("this.{name} = this.{name} || {{}}"));
+
+    let mut params = vec![];
+    let mut args = vec![];
+    if has_exports {
+      params.push("exports");
+      args.push(exports_arg.as_str());
+    }
+    external_params.iter().for_each(|param| {
+      params.push(&param.name);
+      args.push(&param.global_name);
+    });
+
+    let has_name = output_options.name.is_some() && !matches!(export_mode, OutputExports::None);
+    let assignment = match (&output_options.name, extended_name) {
+      // The exports object is passed in as the argument, nothing needs to be assigned.
+      (_, Some(_)) if has_exports => String::new(),
+      (_, Some(name)) if has_name => format!("this.{name} = "),
+      (Some(name), _) if has_name && name.contains('.') => format!("this.{name} = "),
+      (Some(name), _) if has_name => format!("var {name} = "),
+      _ => String::new(),
+    };
+    let namespaces = output_options
+      .name
+      .as_deref()
+      .filter(|_| has_name)
+      .map(|name| namespace_assignments("this", name))
+      .unwrap_or_default()
+      .into_iter()
+      .map(|assignment| assignment + ";\n")
+      .collect::<String>();
+    let intro =
+      format!("{namespaces}{assignment}(function({}) {{\n\"use strict\";", params.join(", "));
+    let outro = if has_exports && extended_name.is_none() {
+      format!("return exports;\n}})({});", args.join(", "))
+    } else {
+      format!("}})({});", args.join(", "))
+    };
+    (intro, outro)
+  }
+
+  /// Returns the code placed before and after the content of the chunk for the umd format.
+  ///
+  /// The wrapper works with commonjs, amd and falls back to global variables. Externals are listed in
+  /// the same order in all branches. Side-effect-only externals come last, since they have no
+  /// corresponding parameters and there's no point to read them from global variables.
+  pub fn render_umd_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &OutputOptions,
+    export_mode: OutputExports,
+  ) -> (String, String) {
+    let has_exports = matches!(export_mode, OutputExports::Named);
+    let name = || {
+      output_options.name.as_deref().expect("`output.name` should be checked before rendering umd")
+    };
+
+    // `global.a = global.a || {}, ` for `a.b`
+    let namespaces = || {
+      namespace_assignments("global", name())
+        .into_iter()
+        .map(|assignment| assignment + ", ")
+        .collect::<String>()
+    };
+
+    let mut cjs_deps = vec![];
+    let mut amd_deps = vec![];
+    let mut global_deps = vec![];
+    let mut params = vec![];
+    if has_exports {
+      cjs_deps.push("exports".to_string());
+      amd_deps.push("\"exports\"".to_string());
+      let namespaces = namespaces();
+      global_deps.push(if namespaces.is_empty() {
+        format!("global.{} = {{}}", name())
+      } else {
+        format!("({namespaces}global.{} = {{}})", name())
+      });
+      params.push("exports".to_string());
+    }
+    self.wrapper_external_params(graph, output_options).into_iter().for_each(|param| {
+      cjs_deps.push(format!("require(\"{}\")", param.module_id));
+      amd_deps.push(format!("\"{}\"", param.module_id));
+      global_deps.push(format!("global.{}", param.global_name));
+      params.push(param.name);
+    });
+    self.side_effect_only_external_modules(graph, output_options).into_iter().for_each(
+      |module_id| {
+        cjs_deps.push(format!("require(\"{module_id}\")"));
+        amd_deps.push(format!("\"{module_id}\""));
+      },
+    );
+
+    let cjs_factory_call = format!("factory({})", cjs_deps.join(", "));
+    let global_factory_call = format!("factory({})", global_deps.join(", "));
+    let (cjs_branch, global_branch) = if matches!(export_mode, OutputExports::Default) {
+      (
+        format!("module.exports = {cjs_factory_call}"),
+        format!("{}global.{} = {global_factory_call}", namespaces(), name()),
+      )
+    } else {
+      (cjs_factory_call, global_factory_call)
+    };
+    let amd_branch = if amd_deps.is_empty() {
+      "define(factory)".to_string()
+    } else {
+      format!("define([{}], factory)", amd_deps.join(", "))
+    };
+
+    let intro = format!(
+      "(function(global, factory) {{
+  typeof exports === \"object\" && typeof module !== \"undefined\" ? {cjs_branch} :
+  typeof define === \"function\" && define.amd ? {amd_branch} :
+  (global = typeof globalThis !== \"undefined\" ? globalThis : global || self, {global_branch});
+}})(this, function({}) {{
+\"use strict\";",
+      params.join(", ")
+    );
+    let outro = if has_exports { "return exports;\n});" } else { "});" };
+    (intro, outro.to_string())
+  }
+
+  /// Returns the code placed before and after the content of the chunk for the system format.
+  ///
+  /// The content of the chunk goes into `execute`, which runs after all setters of dependencies are called.
+  pub fn render_system_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    output_options: &OutputOptions,
+  ) -> (String, String) {
+    let dependencies = self.system_dependencies(graph, chunk_graph, output_options);
+    let deps = dependencies
+      .iter()
+      .map(|dep| format!("\"{}\"", dep.module_id))
+      .collect::<Vec<_>>()
+      .join(", ");
+    let setters = dependencies
+      .into_iter()
+      .map(|dep| dep.setter.unwrap_or_else(|| "null".to_string()))
+      .collect::<Vec<_>>()
+      .join(", ");
+    let imported_bindings = self.system_imported_bindings(graph);
+    let var_decl = if imported_bindings.is_empty() {
+      String::new()
+    } else {
+      format!("\nvar {};", imported_bindings.join(", "))
+    };
+    let intro = format!(
+      "System.register([{deps}], function(_export, _context) {{\n\"use strict\";{var_decl}\nreturn {{\nsetters: [{setters}],\nexecute: function() {{"
+    );
+    (intro, "}\n};\n});".to_string())
+  }
+}
+
+/// Assignments that create the namespaces of a dotted `output.name` on `object` if they're missing, like
+/// `this.a = this.a || {}` for `a.b`. Empty if the name isn't dotted.
+fn namespace_assignments(object: &str, name: &str) -> Vec<String> {
+  let segments = name.split('.').collect::<Vec<_>>();
+  (1..segments.len())
+    .map(|len| {
+      let path = segments[..len].join(".");
+      format!("{object}.{path} = {object}.{path} || {{}}")
+    })
+    .collect()
+}
@@ -1,4 +1,5 @@
 use rolldown_error::BuildError;
+use rustc_hash::FxHashSet;
 use smallvec::SmallVec;
 
 #[derive(Debug, Default)]
@@ -13,6 +14,25 @@ impl BatchedErrors {
     self.0.is_empty()
   }
 
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &BuildError> {
+    self.0.iter()
+  }
+
+  /// Order errors by the modules and the locations they point at and drop the duplicated ones, keeping at most `max`
+  /// of them, so a build reports the same errors however its modules are scheduled.
+  pub fn dedup_and_sort(&mut self, max: usize) {
+    let mut errors = std::mem::take(&mut self.0).into_vec();
+    errors.sort_by_cached_key(|err| (err.position(), err.code(), err.to_string()));
+    let mut seen = FxHashSet::default();
+    errors.retain(|err| seen.insert(err.dedup_key()));
+    errors.truncate(max);
+    self.0 = errors.into();
+  }
+
   pub fn push(&mut self, err: BuildError) {
     self.0.push(err);
   }
@@ -59,6 +79,12 @@ impl Extend<BuildError> for BatchedErrors {
   }
 }
 
+impl FromIterator<BuildError> for BatchedErrors {
+  fn from_iter<T: IntoIterator<Item = BuildError>>(iter: T) -> Self {
+    Self(iter.into_iter().collect())
+  }
+}
+
 impl IntoIterator for BatchedErrors {
   type Item = BuildError;
   type IntoIter = smallvec::IntoIter<[BuildError; 1]>;
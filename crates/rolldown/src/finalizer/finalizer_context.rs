@@ -8,19 +8,27 @@ use crate::{
   runtime::RuntimeModuleBrief,
   types::{
     linking_metadata::{LinkingMetadata, LinkingMetadataVec},
-    module_table::NormalModuleVec,
+    module_table::{ExternalModuleVec, NormalModuleVec},
     symbols::Symbols,
   },
+  OutputOptions,
 };
 
 pub struct FinalizerContext<'me> {
   pub id: NormalModuleId,
   pub module: &'me NormalModule,
   pub modules: &'me NormalModuleVec,
+  pub external_modules: &'me ExternalModuleVec,
   pub linking_info: &'me LinkingMetadata,
   pub linking_infos: &'me LinkingMetadataVec,
   pub symbols: &'me Symbols,
   pub canonical_names: &'me FxHashMap<SymbolRef, Rstr>,
   pub runtime: &'me RuntimeModuleBrief,
   pub chunk_graph: &'me ChunkGraph,
+  pub output_options: &'me OutputOptions,
+  /// Exported names of the bindings declared in the chunk. Only collected for the system format, where
+  /// assignments to them are reported with `_export(...)`.
+  pub chunk_export_names: &'me FxHashMap<SymbolRef, Vec<Rstr>>,
+  /// The code replacing `import.meta.ROLLUP_FILE_URL_<referenceId>` of the module, keyed by reference ids.
+  pub file_urls: &'me FxHashMap<String, String>,
 }
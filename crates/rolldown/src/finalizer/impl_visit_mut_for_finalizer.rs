@@ -9,6 +9,8 @@ use oxc::{
 use rolldown_common::{ExportsKind, ModuleId, SymbolRef, WrapKind};
 use rolldown_oxc_utils::{Dummy, ExpressionExt, IntoIn, StatementExt, TakeIn};
 
+use crate::{utils::define::parse_expression, OutputFormat};
+
 use super::Finalizer;
 
 impl<'ast, 'me: 'ast> Finalizer<'me, 'ast> {
@@ -28,6 +30,10 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
       program.body.extend(self.generate_namespace_variable_declaration());
     }
 
+    if let Some(shim_stmt) = self.generate_missing_export_shim_declaration() {
+      program.body.push(shim_stmt);
+    }
+
     let mut stmt_infos = self.ctx.module.stmt_infos.iter();
     stmt_infos.next();
 
@@ -60,9 +66,7 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
             let importee_linking_info = &self.ctx.linking_infos[importee_id];
             let importee = &self.ctx.modules[importee_id];
             if matches!(importee_linking_info.wrap_kind, WrapKind::Esm) {
-              let wrapper_ref_name =
-                self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
-              program.body.push(self.snippet.call_expr_stmt(wrapper_ref_name.to_oxc_atom()));
+              program.body.push(self.snippet.expr_stmt(self.esm_wrapper_call_expr(importee_id)));
             }
 
             match importee.exports_kind {
@@ -263,6 +267,7 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
             wrap_ref_name.to_oxc_atom(),
             esm_ref_name.to_oxc_atom(),
             stmts_inside_closure,
+            self.ctx.linking_info.is_tla_or_contains_tla_dependency,
           ));
         }
         WrapKind::None => {}
@@ -312,6 +317,76 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
 
   #[allow(clippy::collapsible_else_if)]
   fn visit_expression(&mut self, expr: &mut ast::Expression<'ast>) {
+    if let ast::Expression::ImportExpression(import_expr) = expr {
+      if let Some(inlined) = self.try_inline_dynamic_import(import_expr) {
+        *expr = inlined;
+        return;
+      }
+      match self.ctx.output_options.format {
+        OutputFormat::Cjs => {
+          // Rewrite `import('./foo')` to `Promise.resolve().then(() => require('./foo'))`
+          self.visit_import_expression(import_expr);
+          let source = import_expr.source.take_in(self.alloc);
+          *expr = self.snippet.promise_resolve_then_call_expr(
+            self.snippet.call_expr_with_arg_expr_expr("require".into(), source),
+          );
+          return;
+        }
+        OutputFormat::SystemJs => {
+          // Rewrite `import('./foo')` to `_context.import('./foo')`
+          self.visit_import_expression(import_expr);
+          let source = import_expr.source.take_in(self.alloc);
+          *expr =
+            self.snippet.member_call_expr_with_arg_expr("_context".into(), "import".into(), source);
+          return;
+        }
+        _ => {}
+      }
+    }
+
+    if let ast::Expression::NewExpression(new_expr) = expr {
+      if let Some(file_name) = self.ctx.module.asset_urls.get(&new_expr.span) {
+        // Rewrite `new URL('./logo.svg', import.meta.url)` to `new URL('assets/logo-[hash].svg', import.meta.url)`
+        if let Some(path) = new_expr.arguments.first_mut() {
+          *path = ast::Argument::Expression(self.asset_url_expr(file_name));
+        }
+      }
+    }
+    if let Some(reference_id) = expr.as_import_meta_file_url() {
+      if let Some(url) = self.ctx.file_urls.get(reference_id) {
+        *expr = parse_expression(self.alloc, url)
+          .expect("URLs of files should be checked to be valid expressions");
+        return;
+      }
+    }
+    if expr.is_import_meta_url() {
+      if let Some(url) = self.import_meta_url_expr() {
+        *expr = url;
+        return;
+      }
+    }
+
+    if matches!(self.ctx.output_options.format, OutputFormat::SystemJs) {
+      if let ast::Expression::MetaProperty(meta) = expr {
+        if meta.meta.name == "import" && meta.property.name == "meta" {
+          // Rewrite `import.meta` to `_context.meta`
+          *expr = ast::Expression::MemberExpression(
+            self
+              .snippet
+              .literal_prop_access_member_expr("_context".into(), "meta".into())
+              .into_in(self.alloc),
+          );
+          return;
+        }
+      }
+      if let Some(export_names) = self.export_names_for_assignment(expr) {
+        // Children are visited first to rewrite the assignment target to its canonical name
+        self.visit_expression_match(expr);
+        *expr = self.wrap_with_export_calls(expr.take_in(self.alloc), export_names);
+        return;
+      }
+    }
+
     if let Some(call_expr) = expr.as_call_expression() {
       // Rewrite `require(...)` to `require_xxx(...)` or `(init_xxx(), __toCommonJS(xxx_exports))`
       if let ast::Expression::Identifier(callee) = &call_expr.callee {
@@ -421,25 +496,37 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
   }
 
   fn visit_import_expression(&mut self, expr: &mut ast::ImportExpression<'ast>) {
-    // Make sure the import expression is in correct form. If it's not, we should leave it as it is.
-    match &mut expr.source {
-      ast::Expression::StringLiteral(str) if expr.arguments.len() == 0 => {
-        let rec_id = self.ctx.module.imports[&expr.span];
-        let rec = &self.ctx.module.import_records[rec_id];
-        let importee_id = rec.resolved_module;
-        match importee_id {
-          ModuleId::Normal(importee_id) => {
-            let chunk_id = self.ctx.chunk_graph.module_to_chunk[importee_id]
-              .expect("Normal module should belong to a chunk");
-            let chunk = &self.ctx.chunk_graph.chunks[chunk_id];
-            str.value = format!("./{}", chunk.file_name.as_ref().unwrap()).into();
-          }
-          ModuleId::External(_) => {
-            // external module doesn't belong to any chunk, just keep this as it is
+    // Make sure the import expression is in correct form. If it's not, we should leave it as it is. Imports of
+    // expressions only have records if plugins resolved them.
+    if let Some(rec_id) =
+      self.ctx.module.imports.get(&expr.span).filter(|_| expr.arguments.len() == 0)
+    {
+      let rec = &self.ctx.module.import_records[*rec_id];
+      let import_path = match rec.resolved_module {
+        ModuleId::Normal(importee_id) => {
+          let chunk_id = self.ctx.chunk_graph.module_to_chunk[importee_id]
+            .expect("Normal module should belong to a chunk");
+          let importer_chunk_id = self.ctx.chunk_graph.module_to_chunk[self.ctx.id]
+            .expect("Normal module should belong to a chunk");
+          let chunk = &self.ctx.chunk_graph.chunks[chunk_id];
+          Some(self.ctx.chunk_graph.chunks[importer_chunk_id].import_path_for(chunk))
+        }
+        ModuleId::External(importee_id) => {
+          // external module doesn't belong to any chunk, just keep this as it is unless it's remapped
+          let importee = &self.ctx.external_modules[importee_id];
+          match (&self.ctx.output_options.paths, &expr.source) {
+            (Some(paths), _) => Some(paths.call(importee.resource_id.expect_file())),
+            (None, ast::Expression::StringLiteral(_)) => None,
+            (None, _) => Some(importee.resource_id.expect_file().to_string()),
           }
         }
+      };
+      if let Some(import_path) = import_path {
+        match &mut expr.source {
+          ast::Expression::StringLiteral(str) => str.value = import_path.into(),
+          source => *source = self.snippet.string_literal_expr(import_path.into()),
+        }
       }
-      _ => {}
     }
 
     // visit children
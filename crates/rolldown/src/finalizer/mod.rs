@@ -1,17 +1,45 @@
 use oxc::{
   allocator::Allocator,
   ast::ast::{self, IdentifierReference, Statement},
-  span::Atom,
+  span::{Atom, Span},
+  syntax::operator::{BinaryOperator, UpdateOperator},
+};
+use rolldown_common::{
+  AstScope, ImportRecordId, ModuleId, NormalModuleId, OutputFormat, SymbolRef, WrapKind,
 };
-use rolldown_common::{AstScope, ImportRecordId, ModuleId, SymbolRef, WrapKind};
 use rolldown_oxc_utils::{AstSnippet, BindingPatternExt, Dummy, IntoIn, TakeIn};
 
 mod finalizer_context;
 mod impl_visit_mut_for_finalizer;
 pub use finalizer_context::FinalizerContext;
 use rolldown_rstr::Rstr;
+
+use crate::utils::define::parse_expression;
 mod rename;
 
+/// The path of `file_name` relative to the chunk `chunk_file_name`, both relative to the output directory.
+pub fn relative_file_url(chunk_file_name: &str, file_name: &str) -> String {
+  let depth = chunk_file_name.matches('/').count();
+  format!("{}{file_name}", "../".repeat(depth))
+}
+
+/// `import.meta` only exists in ES modules, so `import.meta.url` is rewritten to the URL of the chunk for other
+/// formats. It's the URL of the file in node, and the URL of the script in browsers.
+pub fn import_meta_url_code(format: &OutputFormat, chunk_file_name: &str) -> Option<String> {
+  let node_url = r#"require("url").pathToFileURL(__filename).href"#;
+  let browser_url = format!(
+    "document.currentScript && document.currentScript.src || new URL({chunk_file_name:?}, document.baseURI).href"
+  );
+  match format {
+    OutputFormat::Esm | OutputFormat::SystemJs => None,
+    OutputFormat::Cjs => Some(node_url.to_string()),
+    OutputFormat::Iife => Some(browser_url),
+    OutputFormat::Umd => {
+      Some(format!("typeof document === \"undefined\" ? {node_url} : {browser_url}"))
+    }
+  }
+}
+
 pub struct Finalizer<'me, 'ast> {
   pub alloc: &'ast Allocator,
   pub ctx: FinalizerContext<'me>,
@@ -71,8 +99,7 @@ where
       }
       // Replace the statement with something like `init_foo()`
       WrapKind::Esm => {
-        let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
-        *stmt = self.snippet.call_expr_stmt(wrapper_ref_name.to_oxc_atom());
+        *stmt = self.snippet.expr_stmt(self.esm_wrapper_call_expr(importee_id));
         return false;
       }
     }
@@ -98,6 +125,158 @@ where
     }
   }
 
+  /// With `output.inlineDynamicImports`, the importee of `import('./foo')` lives in the same chunk. Returns
+  /// `Promise.resolve().then(() => foo_exports)` that resolves to the namespace object of the importee.
+  fn try_inline_dynamic_import(
+    &self,
+    expr: &ast::ImportExpression<'ast>,
+  ) -> Option<ast::Expression<'ast>> {
+    if !self.ctx.output_options.inline_dynamic_imports {
+      return None;
+    }
+    let rec_id = self.ctx.module.imports.get(&expr.span)?;
+    let ModuleId::Normal(importee_id) = self.ctx.module.import_records[*rec_id].resolved_module
+    else {
+      return None;
+    };
+    let importee = &self.ctx.modules[importee_id];
+    let importee_linking_info = &self.ctx.linking_infos[importee_id];
+    let ns_name_expr =
+      || self.snippet.id_ref_expr(self.canonical_name_for(importee.namespace_symbol).to_oxc_atom());
+
+    let namespace_expr = match importee_linking_info.wrap_kind {
+      WrapKind::None => ns_name_expr(),
+      WrapKind::Cjs => {
+        // `__toESM(require_foo())`
+        let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
+        self.snippet.call_expr_with_arg_expr_expr(
+          self.canonical_name_for_runtime("__toESM").to_oxc_atom(),
+          self.snippet.call_expr_expr(wrapper_ref_name.to_oxc_atom()),
+        )
+      }
+      // `Promise.resolve().then(() => init_foo()).then(() => foo_exports)`, since the async wrapper returns a
+      // promise
+      WrapKind::Esm if importee_linking_info.is_tla_or_contains_tla_dependency => {
+        let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
+        return Some(self.snippet.promise_then_call_expr(
+          self.snippet.promise_resolve_then_call_expr(
+            self.snippet.call_expr_expr(wrapper_ref_name.to_oxc_atom()),
+          ),
+          ns_name_expr(),
+        ));
+      }
+      WrapKind::Esm => {
+        // `(init_foo(), foo_exports)`
+        let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
+        self.snippet.seq2_in_paren_expr(
+          self.snippet.call_expr_expr(wrapper_ref_name.to_oxc_atom()),
+          ns_name_expr(),
+        )
+      }
+    };
+    Some(self.snippet.promise_resolve_then_call_expr(namespace_expr))
+  }
+
+  /// `init_foo()` that executes the wrapped esm importee, or `await init_foo()` if the importee contains
+  /// top-level await, directly or through its imports.
+  fn esm_wrapper_call_expr(&self, importee_id: NormalModuleId) -> ast::Expression<'ast> {
+    let importee_linking_info = &self.ctx.linking_infos[importee_id];
+    let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
+    let call_expr = self.snippet.call_expr_expr(wrapper_ref_name.to_oxc_atom());
+    if importee_linking_info.is_tla_or_contains_tla_dependency {
+      self.snippet.await_expr(call_expr)
+    } else {
+      call_expr
+    }
+  }
+
+  /// The file name of the chunk of the module, which could still contain hash placeholders.
+  fn chunk_file_name(&self) -> &'me str {
+    let chunk_id =
+      self.ctx.chunk_graph.module_to_chunk[self.ctx.id].expect("Module should be in a chunk");
+    self.ctx.chunk_graph.chunks[chunk_id]
+      .file_name
+      .as_deref()
+      .expect("File names of chunks should be generated")
+  }
+
+  /// `"../assets/logo-[hash].svg"`, the URL of an asset relative to the chunk of the module.
+  fn asset_url_expr(&self, file_name: &str) -> ast::Expression<'ast> {
+    let url = relative_file_url(self.chunk_file_name(), file_name);
+    self.snippet.string_literal_expr(url.into())
+  }
+
+  fn import_meta_url_expr(&self) -> Option<ast::Expression<'ast>> {
+    let url = import_meta_url_code(&self.ctx.output_options.format, self.chunk_file_name())?;
+    Some(
+      parse_expression(self.alloc, &url)
+        .expect("The URL of the chunk should be a valid expression"),
+    )
+  }
+
+  /// Exported names of the binding assigned by `expr`, if it's an assignment or an update of an exported binding.
+  /// Assignments with destructuring patterns are not tracked.
+  fn export_names_for_assignment(&self, expr: &ast::Expression<'ast>) -> Option<&'me [Rstr]> {
+    let target = match expr {
+      ast::Expression::AssignmentExpression(assign_expr) => match &assign_expr.left {
+        ast::AssignmentTarget::SimpleAssignmentTarget(target) => target,
+        ast::AssignmentTarget::AssignmentTargetPattern(_) => return None,
+      },
+      ast::Expression::UpdateExpression(update_expr) => &update_expr.argument,
+      _ => return None,
+    };
+    let ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref) = target else {
+      return None;
+    };
+    let symbol_id = self.scope.symbol_id_for(id_ref.reference_id.get()?)?;
+    let canonical_ref = self.ctx.symbols.par_canonical_ref_for((self.ctx.id, symbol_id).into());
+    self.ctx.chunk_export_names.get(&canonical_ref).map(Vec::as_slice)
+  }
+
+  /// `foo = 1` => `_export("foo", foo = 1)`
+  /// `foo++` => `(_export("foo", foo + 1), foo++)`
+  fn wrap_with_export_calls(
+    &self,
+    expr: ast::Expression<'ast>,
+    export_names: &'me [Rstr],
+  ) -> ast::Expression<'ast> {
+    let wrap = |value| {
+      export_names.iter().fold(value, |value, export_name| {
+        self.snippet.call_expr_with_2arg_expr_expr(
+          "_export".into(),
+          self.snippet.string_literal_expr(export_name.to_oxc_atom()),
+          value,
+        )
+      })
+    };
+    match expr {
+      // The value of a postfix update is the old one, so the new value is computed for `_export`
+      ast::Expression::UpdateExpression(update_expr) if !update_expr.prefix => {
+        let ast::SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref) = &update_expr.argument
+        else {
+          unreachable!("Only identifiers are tracked for exports")
+        };
+        let operator = match update_expr.operator {
+          UpdateOperator::Increment => BinaryOperator::Addition,
+          UpdateOperator::Decrement => BinaryOperator::Subtraction,
+        };
+        let new_value = ast::Expression::BinaryExpression(
+          ast::BinaryExpression {
+            span: Span::default(),
+            left: self.snippet.id_ref_expr(id_ref.name.clone()),
+            operator,
+            right: self.snippet.number_expr(1.0),
+          }
+          .into_in(self.alloc),
+        );
+        self
+          .snippet
+          .seq2_in_paren_expr(wrap(new_value), ast::Expression::UpdateExpression(update_expr))
+      }
+      expr => wrap(expr),
+    }
+  }
+
   fn convert_decl_to_assignment(
     &self,
     decl: &mut ast::Declaration<'ast>,
@@ -176,7 +355,10 @@ where
     let exports_len = self.ctx.linking_info.canonical_exports_len();
 
     if exports_len == 0 {
-      return vec![namespace_decl_stmt];
+      return [Some(namespace_decl_stmt), self.generate_namespace_freeze_stmt(ns_name)]
+        .into_iter()
+        .flatten()
+        .collect();
     }
 
     // construct `{ prop_name: () => returned, ... }`
@@ -216,6 +398,42 @@ where
       .into_in(self.alloc),
     );
 
-    vec![namespace_decl_stmt, export_call_stmt]
+    [
+      Some(namespace_decl_stmt),
+      Some(export_call_stmt),
+      self.generate_namespace_freeze_stmt(ns_name),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+  }
+
+  /// `var _missingExportShim = void 0`, if any missing export of this module is shimmed and used.
+  fn generate_missing_export_shim_declaration(&self) -> Option<ast::Statement<'ast>> {
+    let shim_stmt_info = self.ctx.linking_info.missing_export_shim_stmt_info?;
+    if !self.ctx.module.stmt_infos[shim_stmt_info].is_included {
+      return None;
+    }
+    let shim_name = self.canonical_name_for(self.ctx.linking_info.missing_export_shim_ref?);
+    Some(self.snippet.var_decl_stmt(shim_name.to_oxc_atom(), self.snippet.void_zero_expr()))
+  }
+
+  /// `Object.freeze(ns_name)`, unless the namespace is not frozen. Namespaces with dynamic exports get their
+  /// properties added by `__reExport` later, which would throw on frozen objects.
+  fn generate_namespace_freeze_stmt(&self, ns_name: &Rstr) -> Option<ast::Statement<'ast>> {
+    if !self.ctx.output_options.freeze || self.ctx.linking_info.has_dynamic_exports {
+      return None;
+    }
+    Some(ast::Statement::ExpressionStatement(
+      ast::ExpressionStatement {
+        expression: self.snippet.member_call_expr_with_arg_expr(
+          "Object".into(),
+          "freeze".into(),
+          self.snippet.id_ref_expr(ns_name.to_oxc_atom()),
+        ),
+        ..Dummy::dummy(self.alloc)
+      }
+      .into_in(self.alloc),
+    ))
   }
 }
@@ -11,6 +11,7 @@ mod runtime;
 mod stages;
 mod types;
 mod utils;
+mod watcher;
 
 use std::sync::Arc;
 
@@ -20,10 +21,16 @@ pub(crate) type SharedResolver<T> = Arc<Resolver<T>>;
 
 pub use crate::{
   bundler::{Bundler, RolldownOutput},
-  chunk::render_chunk::PreRenderedChunk,
-  options::{
-    file_name_template::FileNameTemplate,
-    input_options::{External, InputItem, InputOptions},
-    output_options::{OutputFormat, OutputOptions},
-  },
+  error::BatchedErrors,
+  watcher::{WatchOptions, Watcher, WatcherEvent},
 };
+
+pub use rolldown_common::{
+  AddonOutputOption, AssetOptions, ChecksOptions, ChunkFileNamesOption, External, FileNameTemplate,
+  GlobalsOption, InputItem, InputOptions, InteropMode, InteropOption, JsonOptions, JsxOptions,
+  JsxRuntime, LegalComments, Loader, LogAction, LogLevel, ManualChunkMeta, ManualChunksOption,
+  Metafile, ModuleSideEffects, OnLog, OutputExports, OutputFormat, OutputOptions, PathsOption,
+  PreRenderedChunk, PreserveEntrySignatures, PropertyReadSideEffects, RolldownCache,
+  SourcemapPathTransform, TreeshakeOptions, UnresolvedImportCheck,
+};
+pub use rolldown_utils::hash::HashAlgorithm;
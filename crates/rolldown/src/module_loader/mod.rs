@@ -1,3 +1,4 @@
+pub mod module_cache;
 #[allow(clippy::module_inception)]
 pub mod module_loader;
 pub mod module_task_context;
@@ -7,7 +8,9 @@ mod task_result;
 
 pub use module_loader::ModuleLoader;
 
-use crate::error::BatchedErrors;
+use rolldown_common::ImportAttributes;
+
+use crate::{error::BatchedErrors, types::resolved_request_info::ResolvedRequestInfo};
 
 use self::{
   runtime_normal_module_task::RuntimeNormalModuleTaskResult, task_result::NormalModuleTaskResult,
@@ -15,5 +18,9 @@ use self::{
 pub enum Msg {
   NormalModuleDone(NormalModuleTaskResult),
   RuntimeNormalModuleDone(RuntimeNormalModuleTaskResult),
-  Errors(BatchedErrors),
+  /// Errors of a module, along with its imports that are resolved, which are still loaded to find their errors.
+  Errors {
+    errors: BatchedErrors,
+    resolved_deps: Vec<(ResolvedRequestInfo, ImportAttributes)>,
+  },
 }
@@ -0,0 +1,109 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rolldown_common::{CachedModule, RolldownCache};
+use rolldown_fs::FileSystem;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::utils::file_snapshot::snapshot;
+
+/// Whether the cached result is still valid for the loaded `original_code_hash`.
+pub fn is_valid<T: FileSystem + ?Sized>(
+  cached: &CachedModule,
+  fs: &T,
+  original_code_hash: &str,
+) -> bool {
+  cached.original_code_hash == original_code_hash
+    && cached.watch_files.iter().all(|(file, before)| *before == snapshot(fs, file))
+}
+
+/// Results of `transform` hooks in the latest builds of a bundler, keyed by module ids and validated by the hashes
+/// of the loaded code. It only saves calling the hooks: ASTs, import records and symbols aren't cached.
+#[derive(Debug, Default)]
+pub struct ModuleCache {
+  modules: Mutex<FxHashMap<String, CachedModule>>,
+}
+
+impl ModuleCache {
+  fn modules(&self) -> MutexGuard<'_, FxHashMap<String, CachedModule>> {
+    self.modules.lock().expect("ModuleCache lock should not be poisoned")
+  }
+
+  pub fn get(&self, id: &str) -> Option<CachedModule> {
+    self.modules().get(id).cloned()
+  }
+
+  pub fn insert(&self, id: String, module: CachedModule) {
+    self.modules().insert(id, module);
+  }
+
+  pub fn remove(&self, id: &str) {
+    self.modules().remove(id);
+  }
+
+  /// Forget modules that are not part of the latest build.
+  pub fn retain(&self, ids: &FxHashSet<&str>) {
+    self.modules().retain(|id, _| ids.contains(id.as_str()));
+  }
+
+  pub fn to_rolldown_cache(&self) -> RolldownCache {
+    RolldownCache {
+      modules: self.modules().iter().map(|(id, module)| (id.clone(), module.clone())).collect(),
+    }
+  }
+}
+
+impl From<RolldownCache> for ModuleCache {
+  fn from(cache: RolldownCache) -> Self {
+    Self { modules: Mutex::new(cache.modules.into_iter().collect()) }
+  }
+}
+
+pub type SharedModuleCache = Arc<ModuleCache>;
+
+#[cfg(test)]
+mod tests {
+  use std::path::Path;
+
+  use rolldown_common::{CachedModule, RolldownCache};
+  use rolldown_fs::{FileSystem, MemoryFileSystem};
+  use rolldown_sourcemap::SourceMapChain;
+  use rustc_hash::FxHashSet;
+
+  use super::{is_valid, ModuleCache};
+  use crate::utils::file_snapshot::snapshot;
+
+  #[test]
+  fn deserialized_cache_is_validated_by_hashes() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/config.json"), "first");
+    let main = "import { a } from './dep.js'; console.log(a)";
+    let cache = ModuleCache::default();
+    cache.insert(
+      "/project/main.js".to_string(),
+      CachedModule {
+        original_code_hash: CachedModule::hash_code(main),
+        code: format!("{main}\nexport const config = \"first\";"),
+        sourcemap_chain: SourceMapChain::default(),
+        loader: None,
+        watch_files: vec![(
+          "/project/config.json".to_string(),
+          snapshot(&fs, "/project/config.json"),
+        )],
+      },
+    );
+
+    let json = cache.to_rolldown_cache().serialize().unwrap();
+    let cache = ModuleCache::from(RolldownCache::deserialize(&json).unwrap());
+    let cached = cache.get("/project/main.js").unwrap();
+    assert!(cached.code.ends_with("export const config = \"first\";"));
+    assert!(is_valid(&cached, &fs, &CachedModule::hash_code(main)));
+    // Changes of the loaded code or the watch files invalidate the cached result
+    assert!(!is_valid(&cached, &fs, &CachedModule::hash_code("console.log(a)")));
+    fs.write(Path::new("/project/config.json"), b"second").unwrap();
+    assert!(!is_valid(&cached, &fs, &CachedModule::hash_code(main)));
+
+    cache.retain(&FxHashSet::default());
+    assert!(cache.get("/project/main.js").is_none());
+    assert!(RolldownCache::deserialize("{").is_err());
+  }
+}
@@ -2,14 +2,16 @@ use std::sync::Arc;
 
 use index_vec::IndexVec;
 use rolldown_common::{
-  EntryPoint, EntryPointKind, ExternalModule, FilePath, ImportKind, ImportRecordId, ModuleId,
-  NormalModule, NormalModuleId, ResourceId,
+  EntryPoint, EntryPointKind, ExternalModule, FilePath, ImportAttributes, ImportKind,
+  ImportRecordId, ModuleId, ModuleInfo, ModuleType, NormalModule, NormalModuleId, ResourceId,
 };
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
 use rolldown_oxc_utils::OxcProgram;
+use rolldown_plugin::HookResolveIdArgsOptions;
 use rustc_hash::{FxHashMap, FxHashSet};
 
+use super::module_cache::SharedModuleCache;
 use super::normal_module_task::NormalModuleTask;
 use super::runtime_normal_module_task::RuntimeNormalModuleTask;
 use super::task_result::NormalModuleTaskResult;
@@ -22,6 +24,8 @@ use crate::runtime::RuntimeModuleBrief;
 use crate::types::module_table::{ExternalModuleVec, ModuleTable};
 use crate::types::resolved_request_info::ResolvedRequestInfo;
 use crate::types::symbols::Symbols;
+use crate::utils::file_snapshot::SharedFileSnapshots;
+use crate::utils::resolve_id::resolve_id_with_external;
 
 use crate::error::{BatchedErrors, BatchedResult};
 use crate::SharedResolver;
@@ -49,6 +53,9 @@ pub struct ModuleLoader<T: FileSystem + Default> {
   common_data: ModuleTaskCommonData<T>,
   rx: tokio::sync::mpsc::UnboundedReceiver<Msg>,
   visited: FxHashMap<FilePath, ModuleId>,
+  /// Attributes of the first import of each module, along with the importer, to report imports with different
+  /// attributes.
+  import_attributes: FxHashMap<ModuleId, (String, ImportAttributes)>,
   runtime_id: Option<NormalModuleId>,
   remaining: u32,
   intermediate_normal_modules: IntermediateNormalModules,
@@ -73,6 +80,8 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
     plugin_driver: SharedPluginDriver,
     fs: T,
     resolver: SharedResolver<T>,
+    module_cache: SharedModuleCache,
+    file_snapshots: SharedFileSnapshots,
   ) -> Self {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Msg>();
 
@@ -82,6 +91,8 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
       resolver,
       fs,
       plugin_driver,
+      module_cache,
+      file_snapshots,
     };
 
     Self {
@@ -89,6 +100,7 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
       rx,
       input_options,
       visited: FxHashMap::default(),
+      import_attributes: FxHashMap::default(),
       runtime_id: None,
       remaining: 0,
       intermediate_normal_modules: IntermediateNormalModules::new(),
@@ -97,14 +109,30 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
     }
   }
 
-  fn try_spawn_new_task(&mut self, info: ResolvedRequestInfo) -> ModuleId {
+  fn try_spawn_new_task(
+    &mut self,
+    info: ResolvedRequestInfo,
+    is_user_defined_entry: bool,
+    attributes: &ImportAttributes,
+  ) -> ModuleId {
     match self.visited.entry(info.path.path.clone()) {
       std::collections::hash_map::Entry::Occupied(visited) => *visited.get(),
       std::collections::hash_map::Entry::Vacant(not_visited) => {
         if info.is_external {
           let id = self.external_modules.len_idx();
           not_visited.insert(id.into());
-          let ext = ExternalModule::new(id, ResourceId::new(info.path.path));
+          let side_effects = info.module_side_effects.unwrap_or(true);
+          self
+            .common_data
+            .plugin_driver
+            .module_infos()
+            .insert(ModuleInfo::external(info.path.path.to_string(), side_effects));
+          let ext = ExternalModule::new(
+            id,
+            ResourceId::new(info.path.path),
+            side_effects,
+            attributes.clone(),
+          );
           self.external_modules.push(ext);
           id.into()
         } else {
@@ -112,13 +140,22 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
           not_visited.insert(id.into());
           self.remaining += 1;
           let module_path = info.path.clone();
+          // `with { type: "json" }` parses the module as JSON regardless of its extension.
+          let module_type = if attributes.get("type").is_some_and(|ty| ty == "json") {
+            ModuleType::Json
+          } else {
+            info.module_type
+          };
 
           let task = NormalModuleTask::new(
             // safety: Data in `ModuleTaskContext` are alive as long as the `NormalModuleTask`, but rustc doesn't know that.
             unsafe { self.common_data.assume_static() },
             id,
             module_path,
-            info.module_type,
+            module_type,
+            is_user_defined_entry,
+            info.module_side_effects.unwrap_or(true),
+            attributes.clone(),
           );
           tokio::spawn(async move { task.run().await });
           id.into()
@@ -127,6 +164,33 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
     }
   }
 
+  /// Report the import if the module is imported with different attributes elsewhere.
+  fn check_import_attributes(
+    &mut self,
+    id: ModuleId,
+    importer: &str,
+    importee: &str,
+    attributes: &ImportAttributes,
+    errors: &mut BatchedErrors,
+  ) {
+    match self.import_attributes.entry(id) {
+      std::collections::hash_map::Entry::Occupied(existing) => {
+        let (_, existing_attributes) = existing.get();
+        if existing_attributes != attributes {
+          errors.push(BuildError::inconsistent_import_attributes(
+            importer,
+            importee,
+            existing_attributes.clone().into_iter().collect(),
+            attributes.clone().into_iter().collect(),
+          ));
+        }
+      }
+      std::collections::hash_map::Entry::Vacant(not_visited) => {
+        not_visited.insert((importer.to_string(), attributes.clone()));
+      }
+    }
+  }
+
   pub fn try_spawn_runtime_module_task(&mut self) -> NormalModuleId {
     *self.runtime_id.get_or_insert_with(|| {
       let id = self.intermediate_normal_modules.alloc_module_id(&mut self.symbols);
@@ -137,6 +201,54 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
     })
   }
 
+  /// Resolve chunks emitted by plugins and load them as entries.
+  async fn spawn_emitted_chunks(
+    &mut self,
+    entry_points: &mut Vec<EntryPoint>,
+    user_defined_entry_ids: &FxHashSet<NormalModuleId>,
+    emitted_entry_ids: &mut FxHashSet<NormalModuleId>,
+    dynamic_import_entry_ids: &mut FxHashSet<NormalModuleId>,
+    errors: &mut BatchedErrors,
+  ) {
+    let file_emitter = Arc::clone(self.common_data.plugin_driver.file_emitter());
+    for (reference_id, chunk) in file_emitter.take_chunks_for_module_loader() {
+      let info = match resolve_id_with_external(
+        &self.input_options,
+        &self.common_data.resolver,
+        &self.common_data.plugin_driver,
+        &chunk.id,
+        None,
+        HookResolveIdArgsOptions {
+          is_entry: true,
+          kind: ImportKind::Import,
+          attributes: ImportAttributes::default(),
+        },
+        &[],
+      )
+      .await
+      {
+        Ok(info) if info.is_external => {
+          errors.push(BuildError::entry_cannot_be_external(info.path.path.as_str()));
+          continue;
+        }
+        Ok(info) => info,
+        Err(err) => {
+          errors.push(err);
+          continue;
+        }
+      };
+      let name = chunk.name.or_else(|| Some(info.path.path.representative_name().into_owned()));
+      let id = self.try_spawn_new_task(info, true, &ImportAttributes::default()).expect_normal();
+      file_emitter.set_chunk_module_id(&reference_id, id);
+      // Modules that are already entries don't get another chunk.
+      if user_defined_entry_ids.contains(&id) || !emitted_entry_ids.insert(id) {
+        continue;
+      }
+      dynamic_import_entry_ids.remove(&id);
+      entry_points.push(EntryPoint { name, id, kind: EntryPointKind::Emitted });
+    }
+  }
+
   #[allow(clippy::too_many_lines)]
   pub async fn fetch_all_modules(
     mut self,
@@ -167,7 +279,7 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
       .into_iter()
       .map(|(name, info)| EntryPoint {
         name,
-        id: self.try_spawn_new_task(info).expect_normal(),
+        id: self.try_spawn_new_task(info, true, &ImportAttributes::default()).expect_normal(),
         kind: EntryPointKind::UserDefined,
       })
       .inspect(|e| {
@@ -175,15 +287,36 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
       })
       .collect::<Vec<_>>();
 
+    let mut emitted_entry_ids = FxHashSet::default();
     let mut dynamic_import_entry_ids = FxHashSet::default();
 
     let mut runtime_brief: Option<RuntimeModuleBrief> = None;
 
+    // Chunks could be emitted in `buildStart`
+    self
+      .spawn_emitted_chunks(
+        &mut entry_points,
+        &user_defined_entry_ids,
+        &mut emitted_entry_ids,
+        &mut dynamic_import_entry_ids,
+        &mut errors,
+      )
+      .await;
+
+    // Errors are counted by their dedup keys, so a missing package imported by many modules counts once.
+    let mut error_keys = FxHashSet::default();
+    let mut counted_errors = 0;
     while self.remaining > 0 {
       let Some(msg) = self.rx.recv().await else {
         break;
       };
+      error_keys.extend(errors.iter().skip(counted_errors).map(BuildError::dedup_key));
+      counted_errors = errors.len();
+      // No more modules are loaded after there are too many errors, only the in-flight ones are waited for.
+      let reached_max_errors =
+        !error_keys.is_empty() && error_keys.len() >= self.input_options.max_errors;
       match msg {
+        Msg::NormalModuleDone(_) if reached_max_errors => {}
         Msg::NormalModuleDone(task_result) => {
           let NormalModuleTaskResult {
             module_id,
@@ -199,11 +332,23 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
             .into_iter()
             .zip(resolved_deps)
             .map(|(raw_rec, info)| {
-              let id = self.try_spawn_new_task(info);
+              let importee = info.path.path.to_string();
+              let id = self.try_spawn_new_task(info, false, &raw_rec.attributes);
+              // `require` calls don't take attributes, so they don't conflict with any import.
+              if !matches!(raw_rec.kind, ImportKind::Require) {
+                self.check_import_attributes(
+                  id,
+                  builder.path.as_ref().map_or("", |path| path.expect_file().as_str()),
+                  &importee,
+                  &raw_rec.attributes,
+                  &mut errors,
+                );
+              }
               // Dynamic imported module will be considered as an entry
               if let ModuleId::Normal(id) = id {
                 if matches!(raw_rec.kind, ImportKind::DynamicImport)
                   && !user_defined_entry_ids.contains(&id)
+                  && !emitted_entry_ids.contains(&id)
                 {
                   dynamic_import_entry_ids.insert(id);
                 }
@@ -228,17 +373,35 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
           self.symbols.add_ast_symbol(runtime.id(), ast_symbol);
           runtime_brief = Some(runtime);
         }
-        Msg::Errors(errs) => {
+        Msg::Errors { errors: errs, resolved_deps } => {
           errors.extend(errs);
+          if !reached_max_errors {
+            for (info, attributes) in resolved_deps {
+              self.try_spawn_new_task(info, false, &attributes);
+            }
+          }
         }
       }
       self.remaining -= 1;
+      // Chunks emitted while loading the module, like in `transform`, are loaded before the loop ends.
+      self
+        .spawn_emitted_chunks(
+          &mut entry_points,
+          &user_defined_entry_ids,
+          &mut emitted_entry_ids,
+          &mut dynamic_import_entry_ids,
+          &mut errors,
+        )
+        .await;
     }
 
     if !errors.is_empty() {
+      errors.dedup_and_sort(self.input_options.max_errors.max(1));
       return Err(errors);
     }
 
+    self.common_data.module_cache.retain(&self.visited.keys().map(FilePath::as_str).collect());
+
     let modules: IndexVec<NormalModuleId, NormalModule> =
       self.intermediate_normal_modules.modules.into_iter().map(Option::unwrap).collect();
 
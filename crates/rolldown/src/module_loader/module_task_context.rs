@@ -1,11 +1,12 @@
 use rolldown_fs::FileSystem;
 
 use crate::{
+  utils::file_snapshot::SharedFileSnapshots,
   SharedResolver,
   {options::input_options::SharedInputOptions, plugin_driver::SharedPluginDriver},
 };
 
-use super::Msg;
+use super::{module_cache::SharedModuleCache, Msg};
 
 /// Used to store common data shared between all tasks.
 pub struct ModuleTaskCommonData<T: FileSystem + Default> {
@@ -14,6 +15,9 @@ pub struct ModuleTaskCommonData<T: FileSystem + Default> {
   pub resolver: SharedResolver<T>,
   pub fs: T,
   pub plugin_driver: SharedPluginDriver,
+  pub module_cache: SharedModuleCache,
+  /// Snapshots of the files loaded by the build, including the ones of modules that fail.
+  pub file_snapshots: SharedFileSnapshots,
 }
 
 impl<T: FileSystem + Default> ModuleTaskCommonData<T> {
@@ -2,15 +2,23 @@ use std::{path::Path, sync::Arc};
 
 use futures::future::join_all;
 use index_vec::IndexVec;
-use oxc::span::SourceType;
+use oxc::{
+  ast::VisitMut,
+  span::{SourceType, Span},
+};
 use rolldown_common::{
-  AstScope, FilePath, ImportRecordId, ModuleType, NormalModuleId, RawImportRecord, ResolvedPath,
-  ResourceId, SymbolRef,
+  AstScope, CachedModule, ImportAttributes, ImportKind, ImportRecordId, Loader, ModuleInfo,
+  ModuleType, NormalModuleId, ResolvedPath, ResourceId, SymbolRef,
 };
+use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
 use rolldown_oxc_utils::{OxcCompiler, OxcProgram};
-use rolldown_plugin::HookResolveIdArgsOptions;
-use rolldown_resolver::Resolver;
+use rolldown_plugin::{
+  DynamicImportSpecifier, HookResolveIdArgsOptions, HookShouldTransformCachedModuleArgs,
+  SharedTransformDependencies,
+};
+use rolldown_sourcemap::SourceMapChain;
+use rustc_hash::{FxHashMap, FxHashSet};
 use sugar_path::AsPath;
 
 use super::{module_task_context::ModuleTaskCommonData, Msg};
@@ -18,14 +26,26 @@ use crate::{
   error::{BatchedErrors, BatchedResult},
   {
     ast_scanner::{AstScanner, ScanResult},
-    module_loader::NormalModuleTaskResult,
-    options::input_options::SharedInputOptions,
-    plugin_driver::SharedPluginDriver,
+    module_loader::{module_cache, NormalModuleTaskResult},
     types::{
       ast_symbols::AstSymbols, normal_module_builder::NormalModuleBuilder,
       resolved_request_info::ResolvedRequestInfo,
     },
-    utils::{load_source::load_source, resolve_id::resolve_id, transform_source::transform_source},
+    utils::{
+      asset_to_esm::{asset_to_esm, emit_asset},
+      define::DefineReplacer,
+      file_snapshot::snapshot,
+      inject::inject_imports,
+      json_to_esm::json_to_esm,
+      jsx::transform_jsx,
+      load_source::{load_source, LoadedModuleOptions, LoadedSource},
+      resolve_id::{
+        resolve_dynamic_import, resolve_id_with_external, treat_unresolved_import_as_external,
+      },
+      text_to_esm::{base64_to_esm, text_to_esm},
+      transform_source::transform_source,
+      typescript::{elide_type_only_imports, TypeScriptStripper},
+    },
   },
 };
 pub struct NormalModuleTask<'task, T: FileSystem + Default> {
@@ -33,6 +53,13 @@ pub struct NormalModuleTask<'task, T: FileSystem + Default> {
   module_id: NormalModuleId,
   resolved_path: ResolvedPath,
   module_type: ModuleType,
+  is_user_defined_entry: bool,
+  module_side_effects: bool,
+  /// Attributes of the first import of the module, passed to `load` hooks.
+  attributes: ImportAttributes,
+  load_options: LoadedModuleOptions,
+  /// The imports that are resolved if some others of the module couldn't be.
+  resolved_deps_of_failure: Vec<(ResolvedRequestInfo, ImportAttributes)>,
 }
 
 impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
@@ -41,36 +68,62 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
     id: NormalModuleId,
     path: ResolvedPath,
     module_type: ModuleType,
+    is_user_defined_entry: bool,
+    module_side_effects: bool,
+    attributes: ImportAttributes,
   ) -> Self {
-    Self { ctx, module_id: id, resolved_path: path, module_type }
+    Self {
+      ctx,
+      module_id: id,
+      resolved_path: path,
+      module_type,
+      is_user_defined_entry,
+      module_side_effects,
+      attributes,
+      load_options: LoadedModuleOptions::default(),
+      resolved_deps_of_failure: vec![],
+    }
   }
   pub async fn run(mut self) {
-    if let Err(errs) = self.run_inner().await {
-      self.ctx.tx.send(Msg::Errors(errs)).expect("Send should not fail");
+    if let Err(errors) = self.run_inner().await {
+      let resolved_deps = std::mem::take(&mut self.resolved_deps_of_failure);
+      self.ctx.tx.send(Msg::Errors { errors, resolved_deps }).expect("Send should not fail");
     }
   }
 
   async fn run_inner(&mut self) -> BatchedResult<()> {
     tracing::trace!("process {:?}", self.resolved_path);
 
-    let mut sourcemap_chain = vec![];
+    let mut sourcemap_chain = SourceMapChain::default();
     let mut warnings = vec![];
+    let mut loader = None;
 
-    // Run plugin load to get content first, if it is None using read fs as fallback.
-    let source =
-      load_source(&self.ctx.plugin_driver, &self.resolved_path, &self.ctx.fs, &mut sourcemap_chain)
-        .await?;
+    let source = self.load(&mut sourcemap_chain, &mut loader).await?;
 
-    // Run plugin transform.
-    let source: Arc<str> =
-      transform_source(&self.ctx.plugin_driver, &self.resolved_path, source, &mut sourcemap_chain)
-        .await?
-        .into();
+    // Run plugin transform, unless the result of the previous build could be reused.
+    let mut source = self.transform(source, &mut sourcemap_chain, &mut loader).await?;
+    let mut css = None;
+    match self.module_type {
+      ModuleType::Json => {
+        source = json_to_esm(&source, &self.resolved_path.path, &self.ctx.input_options.json)?;
+      }
+      // Only the JavaScript side of CSS modules is parsed, which is empty.
+      ModuleType::Css => css = Some(std::mem::take(&mut source)),
+      _ => {}
+    }
+    let source: Arc<str> = source.into();
 
-    let (ast, scope, scan_result, ast_symbol, namespace_symbol) = self.scan(&source);
+    // TODO: reuse the ASTs and scan results of unchanged modules between builds. ASTs of oxc can't be cloned and the
+    // finalizer mutates them in place, so they can't be kept by the cache yet.
+    let (mut ast, scope, mut scan_result, ast_symbol, namespace_symbol) =
+      self.scan(&source, loader)?;
+    // The codegen would print it without a line break, and it's only valid at the top of the chunk anyway.
+    let hashbang = ast.program_mut().hashbang.take().map(|hashbang| hashbang.value.to_string());
     tracing::trace!("scan {:?}", self.resolved_path);
 
-    let res = self.resolve_dependencies(&scan_result.import_records).await?;
+    let res = self.resolve_dependencies(&mut scan_result, &mut warnings).await?;
+
+    self.module_parsed(&source, &scan_result, &res, &ast).await?;
 
     let ScanResult {
       named_imports,
@@ -82,9 +135,14 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
       imports,
       exports_kind,
       repr_name,
+      asset_urls,
+      file_url_references,
+      top_level_await,
+      dynamic_import_expressions: _,
       warnings: scan_warnings,
     } = scan_result;
     warnings.extend(scan_warnings);
+    let asset_urls = self.emit_url_assets(asset_urls)?;
 
     let builder = NormalModuleBuilder {
       source: Some(source),
@@ -103,6 +161,14 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
       module_type: self.module_type,
       pretty_path: Some(self.resolved_path.prettify(&self.ctx.input_options.cwd)),
       sourcemap_chain,
+      side_effects: Some(self.module_side_effects),
+      hashbang,
+      css,
+      asset_urls,
+      file_url_references,
+      top_level_await,
+      no_treeshake: self.load_options.no_treeshake,
+      synthetic_named_exports: self.load_options.synthetic_named_exports.take().map(Into::into),
       ..Default::default()
     };
 
@@ -123,14 +189,193 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
     Ok(())
   }
 
-  fn scan(&self, source: &Arc<str>) -> (OxcProgram, AstScope, ScanResult, AstSymbols, SymbolRef) {
-    fn determine_oxc_source_type(path: impl AsRef<Path>, ty: ModuleType) -> SourceType {
+  /// Run plugin load to get content first, if it is None using read fs as fallback.
+  async fn load(
+    &mut self,
+    sourcemap_chain: &mut SourceMapChain,
+    loader: &mut Option<Loader>,
+  ) -> BatchedResult<String> {
+    // Text modules are read as strings, while base64 ones are read as they are, like assets.
+    let is_asset_file = match self.module_type {
+      ModuleType::Text => false,
+      ModuleType::Base64 => true,
+      _ => self.is_asset_file(),
+    };
+    let source = load_source(
+      &self.ctx.plugin_driver,
+      &self.resolved_path,
+      &self.attributes,
+      &self.ctx.fs,
+      sourcemap_chain,
+      &mut self.module_side_effects,
+      loader,
+      &mut self.load_options,
+      is_asset_file,
+      &self.ctx.file_snapshots,
+    )
+    .await?;
+    // Assets, text and base64 modules are turned into ES modules before `transform` hooks, like JSON modules.
+    Ok(match source {
+      LoadedSource::Code(code) if matches!(self.module_type, ModuleType::Text) => {
+        text_to_esm(&code)
+      }
+      LoadedSource::Code(code) if matches!(self.module_type, ModuleType::Base64) => {
+        base64_to_esm(code.as_bytes())
+      }
+      LoadedSource::Asset(content) if matches!(self.module_type, ModuleType::Base64) => {
+        base64_to_esm(&content)
+      }
+      LoadedSource::Asset(content) => self.asset_to_esm(content),
+      LoadedSource::Code(code) if *loader == Some(Loader::Asset) => {
+        self.asset_to_esm(code.into_bytes())
+      }
+      LoadedSource::Code(code) => code,
+    })
+  }
+
+  /// Whether the file is imported as an asset by its extension.
+  fn is_asset_file(&self) -> bool {
+    self.resolved_path.path.as_path().extension().is_some_and(|ext| {
+      self.ctx.input_options.asset.extensions.iter().any(|asset_ext| ext == asset_ext.as_str())
+    })
+  }
+
+  fn asset_to_esm(&self, content: Vec<u8>) -> String {
+    asset_to_esm(
+      self.ctx.plugin_driver.file_emitter(),
+      &self.resolved_path.path,
+      content,
+      &self.ctx.input_options.asset,
+    )
+  }
+
+  /// Emit files referenced by `new URL('./file', import.meta.url)` as assets, returning their file names.
+  fn emit_url_assets(
+    &self,
+    asset_urls: Vec<(Span, String)>,
+  ) -> BatchedResult<FxHashMap<Span, String>> {
+    let mut errors = BatchedErrors::default();
+    let mut file_names = FxHashMap::default();
+    for (span, specifier) in asset_urls {
+      let file_name = self
+        .ctx
+        .resolver
+        .resolve(Some(&self.resolved_path.path), &specifier, ImportKind::Import)
+        .and_then(|resolved| {
+          let path = resolved.resolved.path.as_str();
+          let content = self.ctx.fs.read(path.as_path())?;
+          Ok(emit_asset(self.ctx.plugin_driver.file_emitter(), path, content))
+        });
+      match file_name {
+        Ok(file_name) => {
+          file_names.insert(span, file_name);
+        }
+        Err(err) => errors.push(err),
+      }
+    }
+    if errors.is_empty() {
+      Ok(file_names)
+    } else {
+      Err(errors)
+    }
+  }
+
+  async fn transform(
+    &self,
+    source: String,
+    sourcemap_chain: &mut SourceMapChain,
+    loader: &mut Option<Loader>,
+  ) -> BatchedResult<String> {
+    let id = self.resolved_path.path.as_str();
+    let original_code_hash = CachedModule::hash_code(&source);
+    if let Some(cached) = self.ctx.module_cache.get(id) {
+      if module_cache::is_valid(&cached, &self.ctx.fs, &original_code_hash)
+        && !self
+          .ctx
+          .plugin_driver
+          .should_transform_cached_module(&HookShouldTransformCachedModuleArgs {
+            id,
+            code: &cached.code,
+          })
+          .await?
+      {
+        // Watch files of the module are still watched, though its `transform` hooks aren't called
+        cached.watch_files.iter().for_each(|(file, snapshot)| {
+          self.ctx.plugin_driver.watch_files().add(file);
+          self.ctx.file_snapshots.record(file, snapshot.clone());
+        });
+        sourcemap_chain.extend(cached.sourcemap_chain);
+        if cached.loader.is_some() {
+          *loader = cached.loader;
+        }
+        return Ok(cached.code);
+      }
+    }
+
+    let transform_dependencies = SharedTransformDependencies::default();
+    let mut transformed_sourcemap_chain = SourceMapChain::default();
+    let mut transformed_loader = None;
+    let code = transform_source(
+      &self.ctx.plugin_driver,
+      &self.resolved_path,
+      source,
+      sourcemap_chain,
+      &mut transformed_sourcemap_chain,
+      &transform_dependencies,
+      &mut transformed_loader,
+    )
+    .await?;
+    if transformed_loader.is_some() {
+      *loader = transformed_loader;
+    }
+    if transform_dependencies.emits_files() {
+      self.ctx.module_cache.remove(id);
+    } else {
+      let watch_files = transform_dependencies
+        .watch_files()
+        .into_iter()
+        .map(|file| {
+          let snapshot = snapshot(&self.ctx.fs, &file);
+          self.ctx.file_snapshots.record(&file, snapshot.clone());
+          (file, snapshot)
+        })
+        .collect();
+      self.ctx.module_cache.insert(
+        id.to_string(),
+        CachedModule {
+          original_code_hash,
+          code: code.clone(),
+          sourcemap_chain: transformed_sourcemap_chain.clone(),
+          loader: transformed_loader,
+          watch_files,
+        },
+      );
+    }
+    sourcemap_chain.extend(transformed_sourcemap_chain);
+    Ok(code)
+  }
+
+  #[allow(clippy::type_complexity)]
+  fn scan(
+    &self,
+    source: &Arc<str>,
+    loader: Option<Loader>,
+  ) -> BatchedResult<(OxcProgram, AstScope, ScanResult, AstSymbols, SymbolRef)> {
+    fn determine_oxc_source_type(
+      path: impl AsRef<Path>,
+      ty: ModuleType,
+      loader: Option<Loader>,
+    ) -> SourceType {
       // Determine oxc source type for parsing
       let mut default = SourceType::default().with_module(true);
       // Rolldown considers module as esm by default.
       debug_assert!(default.is_module());
       debug_assert!(default.is_javascript());
       debug_assert!(!default.is_jsx());
+      // Text and base64 modules are ES modules generated by `text_to_esm`, whatever the extension is.
+      if matches!(ty, ModuleType::Text | ModuleType::Base64) {
+        return default;
+      }
       let extension = path.as_ref().extension().and_then(std::ffi::OsStr::to_str);
       default = match ty {
         ModuleType::CJS | ModuleType::CjsPackageJson => default.with_script(true),
@@ -138,23 +383,69 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
       };
       if let Some(ext) = extension {
         default = match ext {
-          "cjs" => default.with_script(true),
-          "jsx" => default.with_jsx(true),
+          "cjs" | "cts" => default.with_script(true),
           _ => default,
         };
       };
-      default
+      // Plugins could tell how to parse the code, otherwise it's decided by the extension.
+      match loader.or_else(|| extension.and_then(Loader::from_extension)).unwrap_or_default() {
+        // Assets are ES modules generated by `asset_to_esm`
+        Loader::Js | Loader::Asset => default,
+        Loader::Jsx => default.with_jsx(true),
+        Loader::Ts => default.with_typescript(true),
+        Loader::Tsx => default.with_typescript(true).with_jsx(true),
+      }
     }
 
     let source_type =
-      determine_oxc_source_type(self.resolved_path.path.as_path(), self.module_type);
-    let mut program = OxcCompiler::parse(Arc::clone(source), source_type);
+      determine_oxc_source_type(self.resolved_path.path.as_path(), self.module_type, loader);
+    let (mut program, parse_errors) =
+      OxcCompiler::parse_with_errors(Arc::clone(source), source_type);
+    if !parse_errors.is_empty() {
+      let filename = self.resolved_path.path.to_string();
+      return Err(
+        parse_errors
+          .into_iter()
+          .map(|(reason, span)| {
+            BuildError::parse_error(filename.clone(), Arc::clone(source), span, reason)
+          })
+          .collect(),
+      );
+    }
+    if source_type.is_typescript() {
+      self.strip_typescript(&mut program, source, source_type)?;
+    }
+    if source_type.is_jsx() {
+      let (program, alloc) = program.program_mut_and_allocator();
+      transform_jsx(alloc, program, source, &self.ctx.input_options.jsx, source_type.is_module());
+    }
+    if !self.ctx.input_options.define.is_empty() {
+      // Globals are told apart from local bindings with the scopes before replacing, which get rebuilt after it.
+      let semantic = program.make_semantic(source_type);
+      let (mut symbol_table, scope) = semantic.into_symbol_table_and_scope_tree();
+      let ast_scope = AstScope::new(scope, std::mem::take(&mut symbol_table.references));
+      let (program, alloc) = program.program_mut_and_allocator();
+      DefineReplacer::new(alloc, &ast_scope, &self.ctx.input_options.define).visit_program(program);
+    }
+    // Scripts can't have imports.
+    if !self.ctx.input_options.inject.is_empty() && source_type.is_module() {
+      let globals = program
+        .make_semantic(source_type)
+        .scopes()
+        .root_unresolved_references()
+        .keys()
+        .map(ToString::to_string)
+        .collect::<FxHashSet<_>>();
+      let (program, alloc) = program.program_mut_and_allocator();
+      inject_imports(alloc, program, &self.ctx.input_options.inject, |name| globals.contains(name));
+    }
 
     let semantic = program.make_semantic(source_type);
     let (mut symbol_table, scope) = semantic.into_symbol_table_and_scope_tree();
     let ast_scope = AstScope::new(scope, std::mem::take(&mut symbol_table.references));
     let mut symbol_for_module = AstSymbols::from_symbol_table(symbol_table);
     let repr_name = self.resolved_path.path.representative_name();
+    let treeshake = self.ctx.input_options.treeshake.as_ref();
     let scanner = AstScanner::new(
       self.module_id,
       &ast_scope,
@@ -163,51 +454,136 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
       self.module_type,
       source,
       &self.resolved_path.path,
+    )
+    .with_annotations(treeshake.is_some_and(|treeshake| treeshake.annotations))
+    .with_property_read_side_effects(
+      treeshake.map(|treeshake| treeshake.property_read_side_effects).unwrap_or_default(),
+    )
+    .with_try_catch_deoptimization(
+      treeshake.map_or(true, |treeshake| treeshake.try_catch_deoptimization),
     );
     let namespace_symbol = scanner.namespace_ref;
     program.hoist_import_export_from_stmts();
     let scan_result = scanner.scan(program.program());
 
-    (program, ast_scope, scan_result, symbol_for_module, namespace_symbol)
+    Ok((program, ast_scope, scan_result, symbol_for_module, namespace_symbol))
   }
 
-  #[allow(clippy::option_if_let_else)]
-  pub(crate) async fn resolve_id<F: FileSystem + Default>(
-    input_options: &SharedInputOptions,
-    resolver: &Resolver<F>,
-    plugin_driver: &SharedPluginDriver,
-    importer: &FilePath,
-    specifier: &str,
-    options: HookResolveIdArgsOptions,
-  ) -> BatchedResult<ResolvedRequestInfo> {
-    // Check external with unresolved path
-    if input_options.external.call(specifier.to_string(), Some(importer.to_string()), false).await?
-    {
-      return Ok(ResolvedRequestInfo {
-        path: specifier.to_string().into(),
-        module_type: ModuleType::Unknown,
-        is_external: true,
-      });
-    }
-
-    let mut info =
-      resolve_id(resolver, plugin_driver, specifier, Some(importer), options, false).await?;
-
-    if !info.is_external {
-      // Check external with resolved path
-      info.is_external = input_options
-        .external
-        .call(specifier.to_string(), Some(importer.to_string()), true)
-        .await?;
-    }
-    Ok(info)
+  fn strip_typescript(
+    &self,
+    program: &mut OxcProgram,
+    source: &Arc<str>,
+    source_type: SourceType,
+  ) -> BatchedResult<()> {
+    let (program_mut, alloc) = program.program_mut_and_allocator();
+    let mut stripper = TypeScriptStripper::new(alloc, source);
+    stripper.visit_program(program_mut);
+    if let Some((span, syntax)) = stripper.unsupported {
+      Err(BuildError::unsupported_syntax(
+        self.resolved_path.path.to_string(),
+        Arc::clone(source),
+        span,
+        syntax,
+      ))?;
+    }
+    // Imports only used by types have no references once the types are gone.
+    let semantic = program.make_semantic(source_type);
+    let (symbol_table, scope) = semantic.into_symbol_table_and_scope_tree();
+    elide_type_only_imports(program.program_mut(), &scope, &symbol_table);
+    Ok(())
+  }
+
+  async fn module_parsed(
+    &self,
+    source: &Arc<str>,
+    scan_result: &ScanResult,
+    resolved_deps: &IndexVec<ImportRecordId, ResolvedRequestInfo>,
+    ast: &OxcProgram,
+  ) -> BatchedResult<()> {
+    let module_info = self.module_info(source, scan_result, resolved_deps);
+    // Plugins could look up the module itself in `module_parsed`.
+    self.ctx.plugin_driver.module_infos().insert(module_info.clone());
+    self.ctx.plugin_driver.module_parsed(&module_info, ast).await?;
+    Ok(())
+  }
+
+  fn module_info(
+    &self,
+    source: &Arc<str>,
+    scan_result: &ScanResult,
+    resolved_deps: &IndexVec<ImportRecordId, ResolvedRequestInfo>,
+  ) -> ModuleInfo {
+    let mut imported_ids = vec![];
+    let mut dynamically_imported_ids = vec![];
+    scan_result.import_records.iter().zip(resolved_deps.iter()).for_each(|(rec, info)| {
+      let ids = if matches!(rec.kind, ImportKind::DynamicImport) {
+        &mut dynamically_imported_ids
+      } else {
+        &mut imported_ids
+      };
+      let id = info.path.path.to_string();
+      if !ids.contains(&id) {
+        ids.push(id);
+      }
+    });
+    let mut exported_bindings =
+      scan_result.named_exports.keys().map(ToString::to_string).collect::<Vec<_>>();
+    exported_bindings.sort();
+    ModuleInfo {
+      id: self.resolved_path.path.to_string(),
+      code: Some(Arc::clone(source)),
+      is_entry: self.is_user_defined_entry,
+      is_external: false,
+      // Importers are collected from other modules by the module info table
+      importers: vec![],
+      dynamic_importers: vec![],
+      imported_ids,
+      dynamically_imported_ids,
+      exported_bindings,
+      has_module_side_effects: self.module_side_effects,
+      meta: self.load_options.meta.clone(),
+    }
+  }
+
+  /// Dynamic imports of expressions resolved by plugins become import records of the statements containing them,
+  /// while the others are left as they are.
+  async fn resolve_dynamic_import_expressions(
+    &self,
+    scan_result: &mut ScanResult,
+    resolved_deps: &mut IndexVec<ImportRecordId, ResolvedRequestInfo>,
+  ) -> BatchedResult<()> {
+    for (stmt_info_id, expr) in std::mem::take(&mut scan_result.dynamic_import_expressions) {
+      let Some(info) = resolve_dynamic_import(
+        &self.ctx.input_options,
+        &self.ctx.resolver,
+        &self.ctx.plugin_driver,
+        DynamicImportSpecifier::Expression(&expr.code),
+        &self.resolved_path.path,
+        HookResolveIdArgsOptions {
+          is_entry: false,
+          kind: ImportKind::DynamicImport,
+          attributes: expr.record.attributes.clone(),
+        },
+      )
+      .await?
+      else {
+        continue;
+      };
+      let id = scan_result.import_records.push(expr.record);
+      resolved_deps.push(info);
+      scan_result.stmt_infos.get_mut(stmt_info_id).import_records.push(id);
+      scan_result.imports.insert(expr.span, id);
+    }
+    Ok(())
   }
 
   #[tracing::instrument(skip_all)]
   async fn resolve_dependencies(
     &mut self,
-    dependencies: &IndexVec<ImportRecordId, RawImportRecord>,
+    scan_result: &mut ScanResult,
+    warnings: &mut Vec<BuildError>,
   ) -> BatchedResult<IndexVec<ImportRecordId, ResolvedRequestInfo>> {
+    let dependencies = &scan_result.import_records;
     let jobs = dependencies.iter_enumerated().map(|(idx, item)| {
       let specifier = item.module_request.clone();
       let input_options = Arc::clone(&self.ctx.input_options);
@@ -216,18 +592,36 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
       let plugin_driver = Arc::clone(&self.ctx.plugin_driver);
       let importer = self.resolved_path.clone();
       let kind = item.kind;
+      let attributes = item.attributes.clone();
       // let on_warn = self.input_options.on_warn.clone();
       tokio::spawn(async move {
-        Self::resolve_id(
-          &input_options,
-          &resolver,
-          &plugin_driver,
-          &importer.path,
-          &specifier,
-          HookResolveIdArgsOptions { is_entry: false, kind },
-        )
-        .await
-        .map(|id| (idx, id))
+        let options = HookResolveIdArgsOptions { is_entry: false, kind, attributes };
+        let resolved = if matches!(kind, ImportKind::DynamicImport) {
+          resolve_dynamic_import(
+            &input_options,
+            &resolver,
+            &plugin_driver,
+            DynamicImportSpecifier::String(&specifier),
+            &importer.path,
+            options,
+          )
+          .await
+          .map(|id| id.expect("String specifiers are always resolved"))
+        } else {
+          resolve_id_with_external(
+            &input_options,
+            &resolver,
+            &plugin_driver,
+            &specifier,
+            Some(&importer.path),
+            options,
+            &[],
+          )
+          .await
+        };
+        treat_unresolved_import_as_external(&input_options, &specifier, &importer.path, resolved)
+          .await
+          .map(|(id, warning)| (idx, id, warning))
       })
     });
 
@@ -235,19 +629,29 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
 
     let mut errors = BatchedErrors::default();
     let mut ret = IndexVec::with_capacity(dependencies.len());
+    let mut resolved_indices = vec![];
     resolved_ids.into_iter().for_each(|handle| match handle.expect("Assuming no task panics") {
-      Ok((_idx, item)) => {
+      Ok((idx, item, warning)) => {
         ret.push(item);
+        resolved_indices.push(idx);
+        warnings.extend(warning);
       }
       Err(e) => {
-        errors.extend(e);
+        errors.push(e);
       }
     });
-    debug_assert!(
-      errors.is_empty() && ret.len() == dependencies.len(),
-      "dependencies: {dependencies:#?}, errors: {errors:#?}"
-    );
+    if !errors.is_empty() {
+      // The imports that are resolved are still loaded, so their errors are reported in the same build.
+      self.resolved_deps_of_failure = ret
+        .into_iter()
+        .zip(resolved_indices)
+        .map(|(info, idx)| (info, dependencies[idx].attributes.clone()))
+        .collect();
+      return Err(errors);
+    }
+    debug_assert!(ret.len() == dependencies.len(), "dependencies: {dependencies:#?}");
 
+    self.resolve_dynamic_import_expressions(scan_result, &mut ret).await?;
     Ok(ret)
   }
 }
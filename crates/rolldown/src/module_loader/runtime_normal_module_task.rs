@@ -55,6 +55,10 @@ impl RuntimeNormalModuleTask {
       repr_name,
       import_records: _,
       exports_kind: _,
+      asset_urls: _,
+      file_url_references: _,
+      top_level_await: _,
+      dynamic_import_expressions: _,
       warnings: _,
     } = scan_result;
 
@@ -75,6 +79,7 @@ impl RuntimeNormalModuleTask {
     builder.namespace_symbol = Some(namespace_symbol);
     builder.pretty_path = Some("<runtime>".to_string());
     builder.is_user_defined_entry = Some(false);
+    builder.side_effects = Some(true);
 
     self
       .tx
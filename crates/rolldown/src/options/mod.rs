@@ -1,3 +1 @@
-pub mod file_name_template;
 pub mod input_options;
-pub mod output_options;
@@ -1,35 +1,152 @@
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
-use rolldown_common::Output;
+use rolldown_common::{InputOptions, Loader, LogLevel, ModuleInfo, Output, RenderedChunk};
 use rolldown_error::BuildError;
+use rolldown_fs::FileSystem;
+use rolldown_oxc_utils::OxcProgram;
 use rolldown_plugin::{
-  BoxPlugin, HookBuildEndArgs, HookLoadArgs, HookLoadReturn, HookNoopReturn, HookResolveIdArgs,
-  HookResolveIdReturn, HookTransformArgs, PluginContext, RenderChunkArgs,
+  BoxPlugin, HookBuildStartArgs, HookGenerateBundleArgs, HookLoadArgs, HookLoadReturn,
+  HookModuleParsedArgs, HookNoopReturn, HookResolveDynamicImportArgs, HookResolveFileUrlArgs,
+  HookResolveFileUrlReturn, HookResolveIdArgs, HookResolveIdReturn,
+  HookShouldTransformCachedModuleArgs, HookTransformArgs, HookWatchChangeArgs, HookWriteBundleArgs,
+  PluginContext, RenderChunkArgs, SharedFileEmitter, SharedModuleInfoTable,
+  SharedPluginContextResolver, SharedPluginLogs, SharedTransformDependencies, SharedWatchFiles,
+  SkippedResolveCall, TransformSourcemaps, WatchChangeKind,
 };
-use rolldown_sourcemap::SourceMap;
+use rolldown_sourcemap::SourceMapChain;
 use rolldown_utils::block_on_spawn_all;
 
+use crate::{options::input_options::SharedInputOptions, SharedResolver};
+
+use self::plugin_context_resolver::BundlerPluginContextResolver;
+
+mod plugin_context_resolver;
+
 pub type SharedPluginDriver = Arc<PluginDriver>;
 
 pub struct PluginDriver {
   plugins: Vec<BoxPlugin>,
+  file_emitter: SharedFileEmitter,
+  module_infos: SharedModuleInfoTable,
+  watch_files: SharedWatchFiles,
+  logs: SharedPluginLogs,
+  context_resolver: SharedPluginContextResolver,
 }
 
 impl PluginDriver {
-  pub fn new(plugins: Vec<BoxPlugin>) -> Self {
-    Self { plugins }
+  pub fn new_shared<T: FileSystem + Default + 'static>(
+    plugins: Vec<BoxPlugin>,
+    input_options: SharedInputOptions,
+    resolver: SharedResolver<T>,
+  ) -> SharedPluginDriver {
+    Arc::new_cyclic(|plugin_driver| Self {
+      plugins,
+      file_emitter: SharedFileEmitter::default(),
+      module_infos: SharedModuleInfoTable::default(),
+      watch_files: SharedWatchFiles::default(),
+      logs: SharedPluginLogs::default(),
+      context_resolver: Arc::new(BundlerPluginContextResolver {
+        input_options,
+        resolver,
+        plugin_driver: Weak::clone(plugin_driver),
+      }),
+    })
+  }
+
+  fn new_context(&self, plugin_index: usize) -> PluginContext {
+    self.new_context_with_skipped_resolve_calls(plugin_index, vec![])
+  }
+
+  fn new_context_with_skipped_resolve_calls(
+    &self,
+    plugin_index: usize,
+    skipped_resolve_calls: Vec<SkippedResolveCall>,
+  ) -> PluginContext {
+    PluginContext::new(
+      Arc::clone(&self.file_emitter),
+      Arc::clone(&self.module_infos),
+      Arc::clone(&self.watch_files),
+      Arc::clone(&self.context_resolver),
+      Arc::clone(&self.logs),
+      plugin_index,
+      self.plugins[plugin_index].name().to_string(),
+      skipped_resolve_calls,
+    )
+  }
+
+  pub fn file_emitter(&self) -> &SharedFileEmitter {
+    &self.file_emitter
   }
 
-  pub async fn build_start(&self) -> HookNoopReturn {
-    for plugin in &self.plugins {
-      plugin.build_start(&mut PluginContext::new()).await?;
+  pub fn module_infos(&self) -> &SharedModuleInfoTable {
+    &self.module_infos
+  }
+
+  pub fn watch_files(&self) -> &SharedWatchFiles {
+    &self.watch_files
+  }
+
+  /// Logs emitted by plugins since the last call, along with their levels.
+  pub fn take_logs(&self) -> Vec<(LogLevel, BuildError)> {
+    self.logs.take()
+  }
+
+  /// Move files emitted by plugins so far into the bundle.
+  fn add_emitted_assets_to_bundle(&self, bundle: &mut Vec<Output>) {
+    bundle.extend(
+      self
+        .file_emitter
+        .take_assets_for_bundle()
+        .into_iter()
+        .map(|asset| Output::Asset(Box::new(asset))),
+    );
+  }
+
+  pub async fn build_start(&self, input_options: &InputOptions) -> HookNoopReturn {
+    // Files emitted, modules loaded, files watched and logs emitted by the previous build don't belong to this one
+    self.file_emitter.clear();
+    self.module_infos.clear();
+    self.watch_files.clear();
+    self.logs.take();
+    let args = HookBuildStartArgs { input_options };
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      plugin.build_start(&mut self.new_context(plugin_index), &args).await?;
     }
     Ok(())
   }
 
-  pub async fn resolve_id(&self, args: &HookResolveIdArgs<'_>) -> HookResolveIdReturn {
-    for plugin in &self.plugins {
-      if let Some(r) = plugin.resolve_id(&mut PluginContext::new(), args).await? {
+  /// Run `resolve_id` hooks, except the ones skipped by `this.resolve` for the same specifier and importer.
+  pub async fn resolve_id(
+    &self,
+    args: &HookResolveIdArgs<'_>,
+    skipped_resolve_calls: &[SkippedResolveCall],
+  ) -> HookResolveIdReturn {
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      let is_skipped = skipped_resolve_calls.iter().any(|call| {
+        call.plugin_index == plugin_index
+          && call.specifier == args.source
+          && call.importer.as_deref() == args.importer
+      });
+      if is_skipped {
+        continue;
+      }
+      let mut ctx =
+        self.new_context_with_skipped_resolve_calls(plugin_index, skipped_resolve_calls.to_vec());
+      if let Some(r) = plugin.resolve_id(&mut ctx, args).await? {
+        return Ok(Some(r));
+      }
+    }
+    Ok(None)
+  }
+
+  pub async fn resolve_dynamic_import(
+    &self,
+    args: &HookResolveDynamicImportArgs<'_>,
+  ) -> HookResolveIdReturn {
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      if let Some(r) =
+        plugin.resolve_dynamic_import(&mut self.new_context(plugin_index), args).await?
+      {
         return Ok(Some(r));
       }
     }
@@ -37,66 +154,158 @@ impl PluginDriver {
   }
 
   pub async fn load(&self, args: &HookLoadArgs<'_>) -> HookLoadReturn {
-    for plugin in &self.plugins {
-      if let Some(r) = plugin.load(&mut PluginContext::new(), args).await? {
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      if let Some(r) = plugin.load(&mut self.new_context(plugin_index), args).await? {
         return Ok(Some(r));
       }
     }
     Ok(None)
   }
 
+  /// Run `transform` hooks, recording what they depend on besides the code into `transform_dependencies`. The
+  /// loader is the one of the last hook that sets it. Maps of `load` hooks in `loaded_sourcemap_chain` are only used
+  /// by `get_combined_sourcemap`, the returned chain only has the maps of `transform` hooks.
   pub async fn transform(
     &self,
     args: &HookTransformArgs<'_>,
-  ) -> Result<(String, Vec<SourceMap>), BuildError> {
-    let mut sourcemap_chain = vec![];
+    loaded_sourcemap_chain: &SourceMapChain,
+    transform_dependencies: &SharedTransformDependencies,
+  ) -> Result<(String, SourceMapChain, Option<Loader>), BuildError> {
+    let sourcemaps =
+      Arc::new(TransformSourcemaps::new(args.id, args.code, loaded_sourcemap_chain.clone()));
     let mut code = args.code.to_string();
-    for plugin in &self.plugins {
-      if let Some(r) = plugin
-        .transform(&mut PluginContext::new(), &HookTransformArgs { id: args.id, code: &code })
-        .await?
+    let mut loader = None;
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      let mut ctx = self
+        .new_context(plugin_index)
+        .with_transform_dependencies(Arc::clone(transform_dependencies))
+        .with_transform_sourcemaps(Arc::clone(&sourcemaps));
+      if let Some(r) =
+        plugin.transform(&mut ctx, &HookTransformArgs { id: args.id, code: &code }).await?
       {
         code = r.code;
         if let Some(map) = r.map {
-          sourcemap_chain.push(map);
+          sourcemaps.push(map);
         }
+        loader = r.loader.or(loader);
       }
     }
-    Ok((code, sourcemap_chain))
+    Ok((code, sourcemaps.take_transformed(), loader))
   }
 
-  pub async fn build_end(&self, args: Option<&HookBuildEndArgs>) -> HookNoopReturn {
+  /// Whether a module should be transformed again instead of reusing the result of the previous build.
+  pub async fn should_transform_cached_module(
+    &self,
+    args: &HookShouldTransformCachedModuleArgs<'_>,
+  ) -> Result<bool, BuildError> {
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      if let Some(r) =
+        plugin.should_transform_cached_module(&self.new_context(plugin_index), args).await?
+      {
+        return Ok(r);
+      }
+    }
+    Ok(false)
+  }
+
+  #[allow(clippy::unused_async)]
+  pub async fn module_parsed(&self, module_info: &ModuleInfo, ast: &OxcProgram) -> HookNoopReturn {
+    let args = HookModuleParsedArgs { module_info, ast };
+    let result =
+      block_on_spawn_all(self.plugins.iter().enumerate().map(|(plugin_index, plugin)| {
+        let args = &args;
+        async move { plugin.module_parsed(&self.new_context(plugin_index), args).await }
+      }));
+
+    for value in result {
+      value?;
+    }
+
+    Ok(())
+  }
+
+  pub async fn build_end(&self, error: Option<&BuildError>) -> HookNoopReturn {
     tracing::info!("PluginDriver::build_end");
-    for plugin in &self.plugins {
-      plugin.build_end(&mut PluginContext::new(), args).await?;
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      plugin.build_end(&mut self.new_context(plugin_index), error).await?;
     }
     Ok(())
   }
 
-  pub async fn render_chunk(&self, mut args: RenderChunkArgs<'_>) -> Result<String, BuildError> {
-    for plugin in &self.plugins {
-      if let Some(r) = plugin.render_chunk(&PluginContext::new(), &args).await? {
+  pub async fn render_chunk(
+    &self,
+    mut args: RenderChunkArgs<'_>,
+  ) -> Result<(String, SourceMapChain), BuildError> {
+    let mut sourcemap_chain = SourceMapChain::default();
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      if let Some(r) = plugin.render_chunk(&self.new_context(plugin_index), &args).await? {
         args.code = r.code;
+        if let Some(map) = r.map {
+          sourcemap_chain.push(map);
+        }
+      }
+    }
+    Ok((args.code, sourcemap_chain))
+  }
+
+  /// Strings returned by `augment_chunk_hash` hooks of all plugins for the chunk, joined in the order of plugins.
+  pub async fn augment_chunk_hash(&self, chunk: &RenderedChunk) -> Result<String, BuildError> {
+    let mut augmented = String::new();
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      if let Some(hash) = plugin.augment_chunk_hash(&self.new_context(plugin_index), chunk).await? {
+        augmented.push_str(&hash);
+      }
+    }
+    Ok(augmented)
+  }
+
+  pub async fn resolve_file_url(
+    &self,
+    args: &HookResolveFileUrlArgs<'_>,
+  ) -> HookResolveFileUrlReturn {
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      if let Some(url) = plugin.resolve_file_url(&self.new_context(plugin_index), args).await? {
+        return Ok(Some(url));
       }
     }
-    Ok(args.code)
+    Ok(None)
   }
 
-  pub async fn generate_bundle(&self, bundle: &Vec<Output>, is_write: bool) -> HookNoopReturn {
-    for plugin in &self.plugins {
-      plugin.generate_bundle(&PluginContext::new(), bundle, is_write).await?;
+  pub async fn generate_bundle(&self, args: &mut HookGenerateBundleArgs<'_>) -> HookNoopReturn {
+    self.add_emitted_assets_to_bundle(args.bundle);
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      plugin.generate_bundle(&self.new_context(plugin_index), args).await?;
+      // Files emitted in this hook should be visible to the following plugins
+      self.add_emitted_assets_to_bundle(args.bundle);
     }
     Ok(())
   }
 
   #[allow(clippy::unused_async)]
-  pub async fn write_bundle(&self, bundle: &Vec<Output>) -> HookNoopReturn {
-    let result = block_on_spawn_all(self.plugins.iter().map(|plugin| async move {
-      match plugin.write_bundle(&PluginContext::new(), bundle).await {
-        Ok(()) => Ok(()),
-        Err(e) => Err(e),
-      }
-    }));
+  pub async fn write_bundle(&self, args: &HookWriteBundleArgs<'_>) -> HookNoopReturn {
+    let result = block_on_spawn_all(self.plugins.iter().enumerate().map(
+      |(plugin_index, plugin)| async move {
+        match plugin.write_bundle(&self.new_context(plugin_index), args).await {
+          Ok(()) => Ok(()),
+          Err(e) => Err(e),
+        }
+      },
+    ));
+
+    for value in result {
+      value?;
+    }
+
+    Ok(())
+  }
+
+  #[allow(clippy::unused_async)]
+  pub async fn close_bundle(&self) -> HookNoopReturn {
+    let result = block_on_spawn_all(
+      self.plugins.iter().enumerate().map(|(plugin_index, plugin)| async move {
+        plugin.close_bundle(&self.new_context(plugin_index)).await
+      }),
+    );
 
     for value in result {
       value?;
@@ -104,4 +313,12 @@ impl PluginDriver {
 
     Ok(())
   }
+
+  pub async fn watch_change(&self, id: &str, event: WatchChangeKind) -> HookNoopReturn {
+    let args = HookWatchChangeArgs { id, event };
+    for (plugin_index, plugin) in self.plugins.iter().enumerate() {
+      plugin.watch_change(&self.new_context(plugin_index), &args).await?;
+    }
+    Ok(())
+  }
 }
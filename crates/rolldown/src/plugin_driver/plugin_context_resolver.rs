@@ -0,0 +1,59 @@
+use std::sync::Weak;
+
+use rolldown_common::{FilePath, ImportAttributes, ImportKind};
+use rolldown_error::BuildError;
+use rolldown_fs::FileSystem;
+use rolldown_plugin::{
+  HookResolveIdArgsOptions, PluginContextResolver, ResolvedId, SkippedResolveCall,
+};
+
+use crate::{
+  options::input_options::SharedInputOptions, utils::resolve_id::resolve_id_with_external,
+  SharedResolver,
+};
+
+use super::PluginDriver;
+
+/// Resolves ids for `this.resolve` of plugins, the same way as imports are resolved by the module loader.
+pub struct BundlerPluginContextResolver<T: FileSystem + Default> {
+  pub input_options: SharedInputOptions,
+  pub resolver: SharedResolver<T>,
+  /// The plugin driver owns this resolver, so it's held weakly.
+  pub plugin_driver: Weak<PluginDriver>,
+}
+
+impl<T: FileSystem + Default> std::fmt::Debug for BundlerPluginContextResolver<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("BundlerPluginContextResolver").finish_non_exhaustive()
+  }
+}
+
+#[async_trait::async_trait]
+impl<T: FileSystem + Default + 'static> PluginContextResolver for BundlerPluginContextResolver<T> {
+  async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&str>,
+    kind: ImportKind,
+    skipped_resolve_calls: Vec<SkippedResolveCall>,
+  ) -> Result<ResolvedId, BuildError> {
+    let plugin_driver =
+      self.plugin_driver.upgrade().expect("Plugin driver should outlive plugin contexts");
+    let importer = importer.map(|importer| FilePath::from(importer.to_string()));
+    let info = resolve_id_with_external(
+      &self.input_options,
+      &self.resolver,
+      &plugin_driver,
+      specifier,
+      importer.as_ref(),
+      HookResolveIdArgsOptions { is_entry: false, kind, attributes: ImportAttributes::default() },
+      &skipped_resolve_calls,
+    )
+    .await?;
+    Ok(ResolvedId {
+      id: info.path.path.to_string(),
+      external: info.is_external,
+      module_side_effects: info.module_side_effects,
+    })
+  }
+}
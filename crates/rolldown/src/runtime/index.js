@@ -62,6 +62,11 @@ var __toESM = (mod, isNodeMode, target) => (
         mod)
 )
 
+// Like "__toESM", but a module with a "default" property is used as an ESM
+// namespace as is, even if "__esModule" is not set. This matches the
+// "compat" interop of Rollup.
+var __toESMCompat = mod => mod && typeof mod === 'object' && 'default' in mod ? mod : __toESM(mod, 1)
+
 // Converts the module from ESM to CommonJS. This clones the input module
 // object with the addition of a non-enumerable "__esModule" property set
 // to "true", which overwrites any existing export named "__esModule".
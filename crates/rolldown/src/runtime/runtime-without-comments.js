@@ -38,4 +38,5 @@ var __toESM = (mod, isNodeMode, target) => (
       : target,
     mod)
 )
+var __toESMCompat = mod => mod && typeof mod === 'object' && 'default' in mod ? mod : __toESM(mod, 1)
 var __toCommonJS = mod => __copyProps(__defProp({}, '__esModule', { value: true }), mod)
@@ -1,17 +1,21 @@
-use std::hash::BuildHasherDefault;
+use std::{hash::BuildHasherDefault, path::Path};
 
 use index_vec::IndexVec;
-use rolldown_common::{ChunkId, ChunkKind, ImportKind, ModuleId, NormalModuleId};
+use rolldown_common::{
+  ChunkId, ChunkKind, EntryPointKind, ImportKind, ModuleId, NormalModule, NormalModuleId, WrapKind,
+};
 use rolldown_utils::BitSet;
 use rustc_hash::FxHashMap;
+use sugar_path::SugarPath;
 
 use crate::{
   chunk::{Chunk, ChunksVec},
   chunk_graph::ChunkGraph,
   utils::is_in_rust_test_mode,
+  OutputFormat,
 };
 
-use super::BundleStage;
+use super::{manual_chunks::ManualChunks, BundleStage};
 
 impl<'a> BundleStage<'a> {
   fn determine_reachable_modules_for_entry(
@@ -34,8 +38,8 @@ impl<'a> BundleStage<'a> {
     module.import_records.iter().for_each(|rec| {
       if let ModuleId::Normal(importee_id) = rec.resolved_module {
         // Module imported dynamically will be considered as an entry,
-        // so we don't need to include it in this chunk
-        if rec.kind != ImportKind::DynamicImport {
+        // so we don't need to include it in this chunk, unless it's inlined
+        if rec.kind != ImportKind::DynamicImport || self.output_options.inline_dynamic_imports {
           self.determine_reachable_modules_for_entry(importee_id, entry_index, module_to_bits);
         }
       }
@@ -56,12 +60,158 @@ impl<'a> BundleStage<'a> {
     });
   }
 
-  pub fn generate_chunks(&self) -> ChunkGraph {
+  /// With `preserve_modules`, every included module gets a chunk of its own, named by its path. Modules without
+  /// code to render don't get chunks, unless they are entries.
+  fn generate_chunks_for_preserve_modules(&self) -> ChunkGraph {
+    let entries_len: u32 =
+      self.link_output.entries.len().try_into().expect("Too many entries, u32 overflowed.");
+    let entry_bits = self
+      .link_output
+      .entries
+      .iter()
+      .enumerate()
+      .map(|(entry_index, entry_point)| {
+        let bit: u32 = entry_index.try_into().expect("Too many entries, u32 overflowed.");
+        (entry_point.id, (bit, entry_point))
+      })
+      .collect::<FxHashMap<NormalModuleId, _>>();
+
+    let mut modules = self
+      .link_output
+      .module_table
+      .normal_modules
+      .iter()
+      .filter(|module| module.is_included)
+      .filter(|module| entry_bits.contains_key(&module.id) || self.has_rendered_statements(module))
+      .collect::<Vec<_>>();
+    // Chunks are created by execution order, so chunks imported by a chunk could be rendered in the right order.
+    modules.sort_by_key(|module| module.exec_order);
+
+    let mut chunks = ChunksVec::with_capacity(modules.len());
+    let mut module_to_chunk: IndexVec<NormalModuleId, Option<ChunkId>> = index_vec::index_vec![
+      None;
+      self.link_output.module_table.normal_modules.len()
+    ];
+    for module in modules {
+      let kind = match entry_bits.get(&module.id) {
+        Some((bit, entry_point)) => ChunkKind::EntryPoint {
+          is_user_defined: !matches!(entry_point.kind, EntryPointKind::DynamicImport),
+          bit: *bit,
+          module: module.id,
+        },
+        None => ChunkKind::Common,
+      };
+      // Chunks are linked by the imports of their modules rather than the entries they belong to, so the bits are
+      // left empty.
+      let chunk = chunks.push(Chunk::new(
+        Some(self.preserved_module_name(module)),
+        BitSet::new(entries_len),
+        vec![module.id],
+        kind,
+      ));
+      module_to_chunk[module.id] = Some(chunk);
+    }
+
+    tracing::trace!("Generated chunks: {:#?}", chunks);
+
+    ChunkGraph { chunks, module_to_chunk }
+  }
+
+  /// Whether any statement of the module is rendered, besides imports and re-exports of unwrapped modules. These
+  /// are removed in the output, and chunks importing the module import chunks of the imported modules directly.
+  fn has_rendered_statements(&self, module: &NormalModule) -> bool {
+    module.stmt_infos.iter().filter(|stmt_info| stmt_info.is_included).any(|stmt_info| {
+      stmt_info.import_records.is_empty()
+        || stmt_info.import_records.iter().any(|rec_id| {
+          let rec = &module.import_records[*rec_id];
+          let ModuleId::Normal(importee_id) = rec.resolved_module else {
+            return true;
+          };
+          !matches!(rec.kind, ImportKind::Import)
+            || !matches!(self.link_output.metas[importee_id].wrap_kind, WrapKind::None)
+        })
+    })
+  }
+
+  /// The path of the module relative to the cwd and `preserve_modules_root`, without the extension. Modules without
+  /// paths, like the runtime and virtual modules of plugins, are put into `_virtual`.
+  fn preserved_module_name(&self, module: &NormalModule) -> String {
+    if module.id == self.link_output.runtime.id() {
+      return "_virtual/rolldown_runtime".to_string();
+    }
+    let id = module.resource_id.expect_file().as_str();
+    if !Path::new(id).is_absolute() {
+      return format!("_virtual/{}", virtual_module_name(id));
+    }
+    let mut path = Path::new(id).relative(&self.input_options.cwd);
+    if let Some(stripped) = self
+      .output_options
+      .preserve_modules_root
+      .as_ref()
+      .and_then(|root| path.strip_prefix(root).ok())
+    {
+      path = stripped.to_path_buf();
+    }
+    path.set_extension("");
+    path.to_string_lossy().replace('\\', "/")
+  }
+
+  /// An entry module shared with other entries belongs to a shared chunk, and its entry chunk only re-exports it.
+  /// If entry chunks could export more than their entry modules, an entry chunk that would contain nothing else
+  /// takes the place of the shared chunk instead, and other chunks import from it.
+  fn merge_entry_chunks_into_shared_chunks(
+    &self,
+    chunks: &mut ChunksVec,
+    bits_to_chunk: &mut FxHashMap<BitSet, ChunkId>,
+    module_to_bits: &IndexVec<NormalModuleId, BitSet>,
+    manual_chunks: &ManualChunks,
+  ) {
+    let normal_modules = &self.link_output.module_table.normal_modules;
+    let is_assigned_by_bits = |module: &&NormalModule| {
+      module.is_included && manual_chunks.module_to_manual_chunk[module.id].is_none()
+    };
+    for chunk_id in chunks.indices() {
+      let ChunkKind::EntryPoint { is_user_defined: true, module: entry_id, .. } =
+        chunks[chunk_id].kind
+      else {
+        continue;
+      };
+      let entry_module = &normal_modules[entry_id];
+      let has_exports = !self.link_output.metas[entry_id].is_canonical_exports_empty();
+      if !self.input_options.preserve_entry_signatures.allows_extension(has_exports)
+        || !is_assigned_by_bits(&entry_module)
+      {
+        continue;
+      }
+      let own_bits = &chunks[chunk_id].bits;
+      let shared_bits = &module_to_bits[entry_id];
+      if shared_bits == own_bits
+        || bits_to_chunk.contains_key(shared_bits)
+        || normal_modules
+          .iter()
+          .filter(is_assigned_by_bits)
+          .any(|module| &module_to_bits[module.id] == own_bits)
+      {
+        continue;
+      }
+      bits_to_chunk.remove(own_bits);
+      bits_to_chunk.insert(shared_bits.clone(), chunk_id);
+      chunks[chunk_id].bits = shared_bits.clone();
+    }
+  }
+
+  pub fn generate_chunks(&self, manual_chunks: &ManualChunks) -> ChunkGraph {
+    if self.output_options.preserve_modules {
+      return self.generate_chunks_for_preserve_modules();
+    }
+
     let entries_len: u32 =
       self.link_output.entries.len().try_into().expect("Too many entries, u32 overflowed.");
     // If we are in test environment, to make the runtime module always fall into a standalone chunk,
-    // we create a facade entry point for it.
-    let entries_len = if is_in_rust_test_mode() { entries_len + 1 } else { entries_len };
+    // we create a facade entry point for it. Formats without code splitting are excluded.
+    let is_runtime_standalone = is_in_rust_test_mode()
+      && !matches!(self.output_options.format, OutputFormat::Iife | OutputFormat::Umd);
+    let entries_len = if is_runtime_standalone { entries_len + 1 } else { entries_len };
 
     let mut module_to_bits = index_vec::index_vec![BitSet::new(entries_len); self.link_output.module_table.normal_modules.len()];
     let mut bits_to_chunk = FxHashMap::with_capacity_and_hasher(
@@ -75,13 +225,12 @@ impl<'a> BundleStage<'a> {
       let count: u32 = entry_index.try_into().expect("Too many entries, u32 overflowed.");
       let mut bits = BitSet::new(entries_len);
       bits.set_bit(count);
-      let module = &self.link_output.module_table.normal_modules[entry_point.id];
       let chunk = chunks.push(Chunk::new(
         entry_point.name.clone(),
         bits.clone(),
         vec![],
         ChunkKind::EntryPoint {
-          is_user_defined: module.is_user_defined_entry,
+          is_user_defined: !matches!(entry_point.kind, EntryPointKind::DynamicImport),
           bit: count,
           module: entry_point.id,
         },
@@ -89,7 +238,21 @@ impl<'a> BundleStage<'a> {
       bits_to_chunk.insert(bits, chunk);
     }
 
-    if is_in_rust_test_mode() {
+    // Manual chunks are not looked up by bits, so modules not assigned to them never end up in them.
+    let manual_chunk_ids = manual_chunks
+      .names
+      .iter()
+      .map(|name| {
+        chunks.push(Chunk::new(
+          Some(name.clone()),
+          BitSet::new(entries_len),
+          vec![],
+          ChunkKind::Common,
+        ))
+      })
+      .collect::<Vec<_>>();
+
+    if is_runtime_standalone {
       self.determine_reachable_modules_for_entry(
         self.link_output.runtime.id(),
         entries_len - 1,
@@ -106,6 +269,13 @@ impl<'a> BundleStage<'a> {
       );
     });
 
+    self.merge_entry_chunks_into_shared_chunks(
+      &mut chunks,
+      &mut bits_to_chunk,
+      &module_to_bits,
+      manual_chunks,
+    );
+
     let mut module_to_chunk: IndexVec<NormalModuleId, Option<ChunkId>> = index_vec::index_vec![
       None;
       self.link_output.module_table.normal_modules.len()
@@ -123,6 +293,14 @@ impl<'a> BundleStage<'a> {
         !bits.is_empty(),
         "Empty bits means the module is not reachable, so it should bail out with `is_included: false`"
       );
+      if let Some(index) = manual_chunks.module_to_manual_chunk[normal_module.id] {
+        // A manual chunk belongs to every entry that reaches any of its modules.
+        let chunk = &mut chunks[manual_chunk_ids[index]];
+        chunk.modules.push(normal_module.id);
+        chunk.bits.union(bits);
+        module_to_chunk[normal_module.id] = Some(manual_chunk_ids[index]);
+        continue;
+      }
       if let Some(chunk_id) = bits_to_chunk.get(bits).copied() {
         chunks[chunk_id].modules.push(normal_module.id);
         module_to_chunk[normal_module.id] = Some(chunk_id);
@@ -146,3 +324,16 @@ impl<'a> BundleStage<'a> {
     ChunkGraph { chunks, module_to_chunk }
   }
 }
+
+/// `\0virtual:config.js` => `virtual_config`. Characters that aren't allowed in file names are replaced by `_`.
+fn virtual_module_name(id: &str) -> String {
+  let name = id.trim_start_matches('\0');
+  let name = name.rsplit('/').next().unwrap_or(name);
+  let name = Path::new(name).file_stem().and_then(|stem| stem.to_str()).unwrap_or(name);
+  name
+    .chars()
+    .map(
+      |c| if matches!(c, '\0' | '?' | '*' | ':' | '<' | '>' | '|' | '"' | '\\') { '_' } else { c },
+    )
+    .collect()
+}
@@ -2,14 +2,18 @@ use std::{borrow::Cow, sync::Mutex};
 
 use crate::{
   OutputFormat,
-  {chunk::CrossChunkImportItem, chunk_graph::ChunkGraph, utils::is_in_rust_test_mode},
+  {
+    chunk::CrossChunkImportItem, chunk_graph::ChunkGraph, types::namespace_alias::NamespaceAlias,
+    utils::is_in_rust_test_mode,
+  },
 };
 
 use super::BundleStage;
 use index_vec::{index_vec, IndexVec};
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rolldown_common::{
-  ChunkId, ChunkKind, ExportsKind, ExternalModuleId, ImportKind, ModuleId, NamedImport, SymbolRef,
+  representative_name, ChunkId, ChunkKind, ExportsKind, ExternalModuleId, ImportKind, ModuleId,
+  NamedImport, Specifier, SymbolRef,
 };
 use rolldown_rstr::{Rstr, ToRstr};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -18,8 +22,57 @@ type ChunkMetaImports = IndexVec<ChunkId, FxHashSet<SymbolRef>>;
 type ChunkMetaImportsForExternalModules =
   IndexVec<ChunkId, FxHashMap<ExternalModuleId, Vec<NamedImport>>>;
 type ChunkMetaExports = IndexVec<ChunkId, FxHashSet<SymbolRef>>;
+type ChunkImportsFromOtherChunks = IndexVec<ChunkId, FxHashMap<ChunkId, Vec<CrossChunkImportItem>>>;
 
 impl<'a> BundleStage<'a> {
+  /// Imports from external modules are rendered as `var import_foo = require("foo")` in output formats other than esm.
+  /// To keep live bindings, named imports are rewritten to property accesses on the namespace binding, so
+  /// `import { a } from "foo"; console.log(a)` becomes `console.log(import_foo.a)`.
+  /// - Every chunk gets one namespace binding for each external module it imports
+  fn bind_external_imports_to_namespace(&mut self, chunk_graph: &mut ChunkGraph) {
+    let symbols = &mut self.link_output.symbols;
+    chunk_graph.chunks.iter_mut_enumerated().for_each(|(chunk_id, chunk)| {
+      chunk.modules.iter().copied().for_each(|module_id| {
+        let module = &self.link_output.module_table.normal_modules[module_id];
+        module.import_records.iter().filter(|rec| matches!(rec.kind, ImportKind::Import)).for_each(
+          |rec| {
+            let ModuleId::External(importee_id) = rec.resolved_module else {
+              return;
+            };
+            let namespace_ref =
+              *chunk.external_namespace_refs.entry(importee_id).or_insert_with(|| {
+                let importee = &self.link_output.module_table.external_modules[importee_id];
+                let symbol = symbols.get_mut(rec.namespace_ref);
+                symbol.name =
+                  format!("import_{}", importee.resource_id.expect_file().representative_name())
+                    .into();
+                symbol.chunk_id = Some(chunk_id);
+                rec.namespace_ref
+              });
+            symbols.union(rec.namespace_ref, namespace_ref);
+          },
+        );
+
+        module.named_imports.values().for_each(|import| {
+          let rec = &module.import_records[import.record_id];
+          let ModuleId::External(importee_id) = rec.resolved_module else {
+            return;
+          };
+          let namespace_ref = chunk.external_namespace_refs[&importee_id];
+          match &import.imported {
+            Specifier::Star => {
+              symbols.union(import.imported_as, namespace_ref);
+            }
+            Specifier::Literal(imported) => {
+              symbols.get_mut(import.imported_as).namespace_alias =
+                Some(NamespaceAlias { property_name: imported.clone(), namespace_ref });
+            }
+          }
+        });
+      });
+    });
+  }
+
   /// - Assign each symbol to the chunk it belongs to
   /// - Collect all referenced symbols and consider them potential imports
   fn collect_potential_chunk_imports(
@@ -48,6 +101,7 @@ impl<'a> BundleStage<'a> {
                 .as_external()
                 .map(|id| &self.link_output.module_table.external_modules[id])
             })
+            .filter(|importee| importee.side_effects)
             .for_each(|importee| {
               // Ensure the external module is imported in case it has side effects.
               imports_from_external_modules.entry(importee.id).or_default();
@@ -81,7 +135,7 @@ impl<'a> BundleStage<'a> {
             stmt_info.referenced_symbols.iter().for_each(|referenced| {
               let mut canonical_ref = symbols.par_canonical_ref_for(*referenced);
               if let Some(namespace_alias) = &symbols.get(canonical_ref).namespace_alias {
-                canonical_ref = namespace_alias.namespace_ref;
+                canonical_ref = symbols.par_canonical_ref_for(namespace_alias.namespace_ref);
               }
               chunk_meta_imports.insert(canonical_ref);
             });
@@ -91,18 +145,24 @@ impl<'a> BundleStage<'a> {
         if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &chunk.kind {
           let entry_module = &self.link_output.module_table.normal_modules[*entry_module_id];
           let entry_linking_info = &self.link_output.metas[entry_module.id];
-          if matches!(entry_module.exports_kind, ExportsKind::CommonJs)
-            && matches!(self.output_options.format, OutputFormat::Esm)
-          {
-            chunk_meta_imports
-              .insert(entry_linking_info.wrapper_ref.expect("cjs should be wrapped in esm output"));
+          if matches!(entry_module.exports_kind, ExportsKind::CommonJs) {
+            chunk_meta_imports.insert(
+              entry_linking_info.wrapper_ref.expect("cjs entry should be wrapped in all formats"),
+            );
           }
+          if entry_linking_info.is_entry_signature_dropped {
+            return;
+          }
+          // Re-exported external modules are imported by the entry chunk, even if they have no side effects.
+          entry_linking_info.star_exports_from_external_modules.iter().for_each(|importee_id| {
+            imports_from_external_modules.entry(*importee_id).or_default();
+          });
           let symbols = symbols.lock().expect("ignore poison error");
           for export_ref in entry_linking_info.resolved_exports.values() {
             let mut canonical_ref = symbols.par_canonical_ref_for(export_ref.symbol_ref);
             let symbol = symbols.get(canonical_ref);
             if let Some(ns_alias) = &symbol.namespace_alias {
-              canonical_ref = ns_alias.namespace_ref;
+              canonical_ref = symbols.par_canonical_ref_for(ns_alias.namespace_ref);
             }
             chunk_meta_imports.insert(canonical_ref);
           }
@@ -111,17 +171,140 @@ impl<'a> BundleStage<'a> {
     tracing::info!("collect_potential_chunk_imports end");
   }
 
+  /// With `preserve_modules`, every chunk imports the chunks of the modules it imports, so they are evaluated in
+  /// the same order as the original modules. Modules tree shaken away are skipped by importing the chunks of their
+  /// imported modules instead.
+  fn import_chunks_of_imported_modules(
+    &self,
+    chunk_graph: &ChunkGraph,
+    imports_from_other_chunks_vec: &mut ChunkImportsFromOtherChunks,
+  ) {
+    chunk_graph.chunks.iter_enumerated().for_each(|(chunk_id, chunk)| {
+      let mut visited = FxHashSet::default();
+      let mut stack = chunk.modules.clone();
+      while let Some(module_id) = stack.pop() {
+        let module = &self.link_output.module_table.normal_modules[module_id];
+        module
+          .import_records
+          .iter()
+          .filter(|rec| matches!(rec.kind, ImportKind::Import))
+          .filter_map(|rec| rec.resolved_module.as_normal())
+          .filter(|importee_id| visited.insert(*importee_id))
+          .for_each(|importee_id| match chunk_graph.module_to_chunk[importee_id] {
+            Some(importee_chunk_id) if importee_chunk_id != chunk_id => {
+              imports_from_other_chunks_vec[chunk_id].entry(importee_chunk_id).or_default();
+            }
+            Some(_) => {}
+            None => stack.push(importee_id),
+          });
+      }
+    });
+  }
+
+  /// Exported names and canonical symbols of the entry module of an entry chunk, if its signature is kept.
+  fn entry_exports_of_chunk(&self, chunk_kind: &ChunkKind) -> Vec<(Rstr, SymbolRef)> {
+    let ChunkKind::EntryPoint { module, .. } = chunk_kind else {
+      return vec![];
+    };
+    let meta = &self.link_output.metas[*module];
+    if meta.is_entry_signature_dropped {
+      return vec![];
+    }
+    meta
+      .canonical_exports()
+      .map(|(name, export)| {
+        (name.clone(), self.link_output.symbols.par_canonical_ref_for(export.symbol_ref))
+      })
+      .collect()
+  }
+
+  /// Give every symbol exported to other chunks an alias, which is unique across all chunks.
+  fn generate_cross_chunk_exports(
+    &self,
+    chunk_graph: &mut ChunkGraph,
+    chunk_meta_exports_vec: &ChunkMetaExports,
+  ) {
+    let mut name_count = FxHashMap::default();
+    for (chunk_id, chunk) in chunk_graph.chunks.iter_mut_enumerated() {
+      // Entry chunks export bindings of their entry modules by their exported names, which are reused for other
+      // chunks and never taken by other bindings.
+      let entry_exports = self.entry_exports_of_chunk(&chunk.kind);
+      for export in chunk_meta_exports_vec[chunk_id].iter().copied() {
+        if let Some(exported_name) =
+          entry_exports.iter().find(|(_, export_ref)| *export_ref == export).map(|(name, _)| name)
+        {
+          chunk.exports_to_other_chunks.insert(export, exported_name.clone());
+          continue;
+        }
+        let original_name: rolldown_rstr::Rstr =
+          self.link_output.symbols.get_original_name(export).to_rstr();
+        let key: Cow<'_, Rstr> = Cow::Owned(original_name.clone());
+        let count = name_count.entry(key).or_insert(0u32);
+        let alias = loop {
+          let alias: Rstr = if *count == 0 {
+            original_name.clone()
+          } else {
+            format!("{original_name}${count}").into()
+          };
+          *count += 1;
+          if entry_exports.iter().all(|(name, _)| *name != alias) {
+            break alias;
+          }
+        };
+        chunk.exports_to_other_chunks.insert(export, alias);
+      }
+    }
+  }
+
+  /// Bindings imported from other chunks are rendered as `var shared = require("./shared.js")` in the cjs format,
+  /// and references to them become property accesses on the namespace binding, like `shared.a`. Destructuring would
+  /// copy the values at the time of requiring, which breaks live bindings and chunks requiring each other.
+  /// - Every chunk gets one namespace binding for each chunk it imports bindings from
+  fn bind_chunk_imports_to_namespace(
+    &mut self,
+    chunk_graph: &mut ChunkGraph,
+    imports_from_other_chunks_vec: &ChunkImportsFromOtherChunks,
+  ) {
+    let symbols = &mut self.link_output.symbols;
+    for (chunk_id, imports_from_other_chunks) in imports_from_other_chunks_vec.iter_enumerated() {
+      let mut importee_chunk_ids = imports_from_other_chunks
+        .iter()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(importee_chunk_id, _)| *importee_chunk_id)
+        .collect::<Vec<_>>();
+      importee_chunk_ids.sort_unstable();
+      for importee_chunk_id in importee_chunk_ids {
+        // Names of common chunks aren't generated yet, they are named after their first modules like the names.
+        let importee_chunk = &chunk_graph.chunks[importee_chunk_id];
+        let importee_name = match &importee_chunk.name {
+          Some(name) => representative_name(name).into_owned(),
+          None => {
+            let module = &self.link_output.module_table.normal_modules[importee_chunk.modules[0]];
+            module.resource_id.expect_file().representative_name().into_owned()
+          }
+        };
+        // Entry chunks might have no modules if their entry modules are placed in other chunks.
+        let namespace_ref =
+          symbols.create_symbol(self.link_output.runtime.id(), importee_name.into());
+        let chunk = &mut chunk_graph.chunks[chunk_id];
+        symbols.get_mut(namespace_ref).chunk_id = Some(chunk_id);
+        chunk.imported_chunk_namespace_refs.insert(importee_chunk_id, namespace_ref);
+      }
+    }
+  }
+
   pub fn compute_cross_chunk_links(&mut self, chunk_graph: &mut ChunkGraph) {
+    if !matches!(self.output_options.format, OutputFormat::Esm) {
+      self.bind_external_imports_to_namespace(chunk_graph);
+    }
+
     let mut chunk_meta_imports_vec: ChunkMetaImports =
       index_vec![FxHashSet::<SymbolRef>::default(); chunk_graph.chunks.len()];
     let mut chunk_meta_exports_vec: ChunkMetaExports =
       index_vec![FxHashSet::<SymbolRef>::default(); chunk_graph.chunks.len()];
     let mut chunk_meta_imports_from_external_modules_vec: ChunkMetaImportsForExternalModules = index_vec![FxHashMap::<ExternalModuleId, Vec<NamedImport>>::default(); chunk_graph.chunks.len()];
 
-    let mut imports_from_other_chunks_vec: IndexVec<
-      ChunkId,
-      FxHashMap<ChunkId, Vec<CrossChunkImportItem>>,
-    > = index_vec![FxHashMap::<ChunkId, Vec<CrossChunkImportItem>>::default(); chunk_graph.chunks.len()];
+    let mut imports_from_other_chunks_vec: ChunkImportsFromOtherChunks = index_vec![FxHashMap::<ChunkId, Vec<CrossChunkImportItem>>::default(); chunk_graph.chunks.len()];
 
     self.collect_potential_chunk_imports(
       chunk_graph,
@@ -177,26 +360,15 @@ impl<'a> BundleStage<'a> {
       }
     });
 
+    if self.output_options.preserve_modules {
+      self.import_chunks_of_imported_modules(chunk_graph, &mut imports_from_other_chunks_vec);
+    }
+
     tracing::info!("Generate cross-chunk exports");
     // Generate cross-chunk exports. These must be computed before cross-chunk
     // imports because of export alias renaming, which must consider all export
     // aliases simultaneously to avoid collisions.
-    let mut name_count = FxHashMap::default();
-    for (chunk_id, chunk) in chunk_graph.chunks.iter_mut_enumerated() {
-      for export in chunk_meta_exports_vec[chunk_id].iter().copied() {
-        let original_name: rolldown_rstr::Rstr =
-          self.link_output.symbols.get_original_name(export).to_rstr();
-        let key: Cow<'_, Rstr> = Cow::Owned(original_name.clone());
-        let count = name_count.entry(key).or_insert(0u32);
-        let alias = if *count == 0 {
-          original_name.clone()
-        } else {
-          format!("{original_name}${count}").into()
-        };
-        chunk.exports_to_other_chunks.insert(export, alias.clone());
-        *count += 1;
-      }
-    }
+    self.generate_cross_chunk_exports(chunk_graph, &chunk_meta_exports_vec);
     for chunk_id in chunk_graph.chunks.indices() {
       for (importee_chunk_id, import_items) in &mut imports_from_other_chunks_vec[chunk_id] {
         for item in import_items {
@@ -209,6 +381,10 @@ impl<'a> BundleStage<'a> {
       }
     }
 
+    if matches!(self.output_options.format, OutputFormat::Cjs) {
+      self.bind_chunk_imports_to_namespace(chunk_graph, &imports_from_other_chunks_vec);
+    }
+
     chunk_graph
       .chunks
       .iter_mut()
@@ -0,0 +1,33 @@
+use rolldown_plugin::EmittedAsset;
+
+use crate::{chunk_graph::ChunkGraph, error::BatchedResult};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Concatenate CSS modules of each chunk in execution order and emit them as a `.css` asset named after the
+  /// chunk. The file name of the asset is recorded in `imported_css` of the chunk, since nothing in the JavaScript
+  /// output refers to it.
+  pub fn emit_css_assets(&self, chunk_graph: &mut ChunkGraph) -> BatchedResult<()> {
+    let file_emitter = self.plugin_driver.file_emitter();
+    for chunk in &mut chunk_graph.chunks {
+      let css = chunk
+        .modules
+        .iter()
+        .filter_map(|id| self.link_output.module_table.normal_modules[*id].css.as_deref())
+        .map(str::trim_end)
+        .collect::<Vec<_>>();
+      if css.is_empty() {
+        continue;
+      }
+      let chunk_name = chunk.name.as_deref().expect("Chunk names should be generated");
+      let reference_id = file_emitter.emit_file(EmittedAsset {
+        name: Some(format!("{chunk_name}.css")),
+        file_name: None,
+        source: format!("{}\n", css.join("\n")).into(),
+      });
+      chunk.imported_css.push(file_emitter.get_file_name(&reference_id)?);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,65 @@
+use index_vec::IndexVec;
+use oxc::allocator::Allocator;
+use rolldown_common::NormalModuleId;
+use rolldown_error::BuildError;
+use rolldown_plugin::HookResolveFileUrlArgs;
+use rustc_hash::FxHashMap;
+
+use crate::{
+  chunk_graph::ChunkGraph,
+  error::BatchedResult,
+  finalizer::{import_meta_url_code, relative_file_url},
+  utils::define::parse_expression,
+};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// The code replacing each `import.meta.ROLLUP_FILE_URL_<referenceId>` of modules, which is returned by
+  /// `resolve_file_url` hooks, or the URL of the file resolved against `import.meta.url` of the chunk by default.
+  pub async fn resolve_file_urls(
+    &self,
+    chunk_graph: &ChunkGraph,
+  ) -> BatchedResult<IndexVec<NormalModuleId, FxHashMap<String, String>>> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let mut file_urls: IndexVec<NormalModuleId, FxHashMap<String, String>> =
+      modules.iter().map(|_| FxHashMap::default()).collect();
+    let file_emitter = self.plugin_driver.file_emitter();
+    let format = &self.output_options.format;
+    for chunk in &chunk_graph.chunks {
+      let chunk_file_name =
+        chunk.file_name.as_deref().expect("File names of chunks should be generated");
+      for module_id in &chunk.modules {
+        let module = &modules[*module_id];
+        for reference_id in &module.file_url_references {
+          let file_name = file_emitter.get_file_name(reference_id)?;
+          let relative_path = relative_file_url(chunk_file_name, &file_name);
+          let url = self
+            .plugin_driver
+            .resolve_file_url(&HookResolveFileUrlArgs {
+              reference_id,
+              file_name: &file_name,
+              chunk_id: chunk_file_name,
+              format: format.file_name_str(),
+              module_id: module.resource_id.expect_file().as_str(),
+              relative_path: &relative_path,
+            })
+            .await?;
+          let url = match url {
+            Some(url) if parse_expression(&Allocator::default(), &url).is_none() => {
+              Err(BuildError::invalid_file_url(reference_id, url))?
+            }
+            Some(url) => url,
+            None => {
+              let base = import_meta_url_code(format, chunk_file_name)
+                .unwrap_or_else(|| "import.meta.url".to_string());
+              format!("new URL({relative_path:?}, {base}).href")
+            }
+          };
+          file_urls[*module_id].insert(reference_id.clone(), url);
+        }
+      }
+    }
+    Ok(file_urls)
+  }
+}
@@ -0,0 +1,192 @@
+use std::{collections::BTreeSet, path::Path, sync::Arc};
+
+use index_vec::IndexVec;
+use rolldown_common::{
+  ImportKind, ManualChunkMeta, ManualChunksOption, ModuleId, NormalModule, NormalModuleId,
+};
+use rolldown_error::BuildError;
+use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::SugarPath;
+
+use crate::error::BatchedResult;
+
+use super::BundleStage;
+
+/// Modules assigned to named chunks by `output.manual_chunks`.
+#[derive(Debug)]
+pub struct ManualChunks {
+  /// Names of manual chunks, in the order they are created.
+  pub names: Vec<String>,
+  /// The index of the manual chunk in `names` each module belongs to.
+  pub module_to_manual_chunk: IndexVec<NormalModuleId, Option<usize>>,
+}
+
+impl ManualChunks {
+  fn new(modules_len: usize) -> Self {
+    Self { names: vec![], module_to_manual_chunk: index_vec::index_vec![None; modules_len] }
+  }
+
+  fn assign(&mut self, module_id: NormalModuleId, name: &str) {
+    let index = self.names.iter().position(|n| n == name).unwrap_or_else(|| {
+      self.names.push(name.to_string());
+      self.names.len() - 1
+    });
+    self.module_to_manual_chunk[module_id] = Some(index);
+  }
+}
+
+impl<'a> BundleStage<'a> {
+  /// Assign modules to manual chunks. A module assigned to multiple manual chunks goes to the first one. Entry
+  /// modules could be assigned too, and their entry chunks become facades re-exporting from the manual chunks.
+  pub async fn resolve_manual_chunks(&mut self) -> BatchedResult<ManualChunks> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let mut manual_chunks = ManualChunks::new(modules.len());
+    let option = match &self.output_options.manual_chunks {
+      Some(option) if !self.output_options.preserve_modules => option,
+      _ => return Ok(manual_chunks),
+    };
+
+    let runtime_id = self.link_output.runtime.id();
+    let is_assignable = |module: &NormalModule, manual_chunks: &ManualChunks| {
+      module.is_included
+        && module.id != runtime_id
+        && manual_chunks.module_to_manual_chunk[module.id].is_none()
+    };
+
+    match option {
+      ManualChunksOption::Map(map) => {
+        let path_to_module = modules
+          .iter()
+          .map(|module| (Path::new(module.resource_id.expect_file().as_str()), module.id))
+          .collect::<FxHashMap<_, _>>();
+        let mut names = map.keys().collect::<Vec<_>>();
+        names.sort_unstable();
+        for name in names {
+          for id in &map[name] {
+            let path = self.input_options.cwd.join(id);
+            let Some(module_id) = path_to_module.get(path.normalize().as_ref()).copied() else {
+              continue;
+            };
+            if is_assignable(&modules[module_id], &manual_chunks) {
+              manual_chunks.assign(module_id, name);
+            }
+          }
+        }
+      }
+      ManualChunksOption::Fn(func) => {
+        let meta = ManualChunkMeta::new(Arc::clone(self.plugin_driver.module_infos()));
+        let mut sorted_modules = modules.iter().collect::<Vec<_>>();
+        sorted_modules.sort_by_key(|module| module.exec_order);
+        for module in sorted_modules {
+          if !is_assignable(module, &manual_chunks) {
+            continue;
+          }
+          if let Some(name) =
+            func(module.resource_id.expect_file().to_string(), meta.clone()).await?
+          {
+            manual_chunks.assign(module.id, &name);
+          }
+        }
+      }
+    }
+
+    // Static dependencies of a manual chunk are put into it, unless they are already assigned.
+    let mut assigned = modules
+      .iter()
+      .filter_map(|module| {
+        manual_chunks.module_to_manual_chunk[module.id]
+          .map(|index| (module.exec_order, module.id, index))
+      })
+      .collect::<Vec<_>>();
+    assigned.sort_unstable_by_key(|(exec_order, _, index)| (*index, *exec_order));
+    for (_, module_id, index) in assigned {
+      let mut stack = vec![module_id];
+      while let Some(module_id) = stack.pop() {
+        for importee_id in self.static_importees(module_id) {
+          if is_assignable(&modules[importee_id], &manual_chunks) {
+            manual_chunks.module_to_manual_chunk[importee_id] = Some(index);
+            stack.push(importee_id);
+          }
+        }
+      }
+    }
+
+    self.check_circular_manual_chunks(&manual_chunks);
+
+    Ok(manual_chunks)
+  }
+
+  fn static_importees(
+    &self,
+    module_id: NormalModuleId,
+  ) -> impl Iterator<Item = NormalModuleId> + '_ {
+    self.link_output.module_table.normal_modules[module_id].import_records.iter().filter_map(
+      |rec| match rec.resolved_module {
+        ModuleId::Normal(importee_id) if rec.kind != ImportKind::DynamicImport => Some(importee_id),
+        _ => None,
+      },
+    )
+  }
+
+  /// Manual chunks importing each other, directly or through modules outside of manual chunks, might not be
+  /// executed in the right order. They still work like circular imports between modules, so only warn about them.
+  fn check_circular_manual_chunks(&mut self, manual_chunks: &ManualChunks) {
+    let modules = &self.link_output.module_table.normal_modules;
+    let mut edges = vec![BTreeSet::new(); manual_chunks.names.len()];
+    for (index, edges) in edges.iter_mut().enumerate() {
+      let mut stack = modules
+        .iter()
+        .filter(|module| manual_chunks.module_to_manual_chunk[module.id] == Some(index))
+        .map(|module| module.id)
+        .collect::<Vec<_>>();
+      let mut visited = stack.iter().copied().collect::<FxHashSet<_>>();
+      while let Some(module_id) = stack.pop() {
+        for importee_id in self.static_importees(module_id) {
+          if !modules[importee_id].is_included || !visited.insert(importee_id) {
+            continue;
+          }
+          match manual_chunks.module_to_manual_chunk[importee_id] {
+            Some(importee_index) if importee_index != index => {
+              edges.insert(importee_index);
+            }
+            _ => stack.push(importee_id),
+          }
+        }
+      }
+    }
+
+    let mut visited = vec![false; edges.len()];
+    for index in 0..edges.len() {
+      if let Some(cycle) = find_cycle(index, &edges, &mut visited, &mut vec![]) {
+        let chunk_names =
+          cycle.into_iter().map(|index| manual_chunks.names[index].clone()).collect();
+        self
+          .link_output
+          .warnings
+          .push(BuildError::circular_chunk(chunk_names).with_severity_warning());
+      }
+    }
+  }
+}
+
+/// Depth-first search for a cycle of manual chunks, where chunks on the current path are in `path`.
+fn find_cycle(
+  index: usize,
+  edges: &[BTreeSet<usize>],
+  visited: &mut [bool],
+  path: &mut Vec<usize>,
+) -> Option<Vec<usize>> {
+  if let Some(start) = path.iter().position(|i| *i == index) {
+    let mut cycle = path[start..].to_vec();
+    cycle.push(index);
+    return Some(cycle);
+  }
+  if visited[index] {
+    return None;
+  }
+  visited[index] = true;
+  path.push(index);
+  let cycle = edges[index].iter().find_map(|next| find_cycle(*next, edges, visited, path));
+  path.pop();
+  cycle
+}
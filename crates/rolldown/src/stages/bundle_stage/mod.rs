@@ -1,24 +1,43 @@
 use crate::{
-  error::BatchedResult,
-  InputOptions,
+  error::{BatchedErrors, BatchedResult},
+  InputOptions, OutputFormat,
   {
+    chunk::{add_banner_and_footer, external_global_name},
     chunk_graph::ChunkGraph,
     finalizer::FinalizerContext,
-    options::{
-      file_name_template::FileNameRenderOptions,
-      output_options::{OutputOptions, SourceMapType},
-    },
     plugin_driver::SharedPluginDriver,
     stages::link_stage::LinkStageOutput,
-    utils::{finalize_normal_module, is_in_rust_test_mode, render_chunks::render_chunks},
+    utils::{
+      finalize_normal_module,
+      hash_placeholder::{
+        find_hash_placeholders, replace_hash_placeholders, HashPlaceholderGenerator,
+        MIN_HASH_LENGTH,
+      },
+      is_in_rust_test_mode,
+      render_chunks::render_chunks,
+    },
   },
 };
-use rolldown_common::{ChunkKind, Output, OutputAsset, OutputChunk};
+use index_vec::IndexVec;
+use oxc::syntax::identifier::is_identifier_name;
+use rolldown_common::{
+  ChunkFileNamesOption, ChunkId, ChunkKind, FileNameRenderOptions, FileNameTemplate, InteropMode,
+  NormalModuleId, Output, OutputAsset, OutputChunk, OutputExports, OutputOptions, RenderedChunk,
+  SourceMapType, Specifier,
+};
 use rolldown_error::BuildError;
-use rustc_hash::FxHashSet;
+use rolldown_sourcemap::SourceMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{borrow::Cow, cell::RefCell, collections::BTreeSet, path::Path};
 
 mod code_splitting;
 mod compute_cross_chunk_links;
+mod css_assets;
+mod file_urls;
+mod manual_chunks;
+
+/// The code, source map and info of a chunk after `render_chunk` hooks.
+type RenderedChunkOutput = (String, Option<SourceMap>, RenderedChunk);
 
 pub struct BundleStage<'a> {
   link_output: &'a mut LinkStageOutput,
@@ -41,24 +60,80 @@ impl<'a> BundleStage<'a> {
   pub async fn bundle(&mut self) -> BatchedResult<Vec<Output>> {
     use rayon::prelude::*;
     tracing::info!("Start bundle stage");
-    let mut chunk_graph = self.generate_chunks();
+    // File names of assets emitted during the build depend on output options
+    self.plugin_driver.file_emitter().assign_file_names(&self.output_options.asset_file_names);
 
-    self.generate_chunk_filenames(&mut chunk_graph);
-    tracing::info!("generate_chunk_filenames");
+    let max_hash_length = self.output_options.hash_algorithm.max_len();
+    if !(MIN_HASH_LENGTH..=max_hash_length).contains(&self.output_options.hash_length) {
+      Err(BuildError::invalid_hash_length(self.output_options.hash_length, max_hash_length))?;
+    }
+
+    if self.output_options.inline_dynamic_imports {
+      self.check_inline_dynamic_imports()?;
+    }
 
+    let manual_chunks = self.resolve_manual_chunks().await?;
+    let mut chunk_graph = self.generate_chunks(&manual_chunks);
+
+    // Exports of common chunks are known after this, which are passed to functions in `chunk_file_names`
     self.compute_cross_chunk_links(&mut chunk_graph);
     tracing::info!("compute_cross_chunk_links");
+    self.check_entry_signatures(&chunk_graph)?;
+
+    let placeholder_to_chunk = self.generate_chunk_filenames(&mut chunk_graph).await?;
+    tracing::info!("generate_chunk_filenames");
+    self.emit_css_assets(&mut chunk_graph)?;
+
+    // Emitted chunks are entry chunks of their modules
+    let entry_chunk_file_names = chunk_graph
+      .chunks
+      .iter()
+      .filter_map(|chunk| match chunk.kind {
+        ChunkKind::EntryPoint { module, .. } => Some((module, chunk.file_name.clone()?)),
+        ChunkKind::Common => None,
+      })
+      .collect::<FxHashMap<_, _>>();
+    self
+      .plugin_driver
+      .file_emitter()
+      .assign_chunk_file_names(|module_id| entry_chunk_file_names.get(&module_id).cloned());
+
+    if matches!(self.output_options.format, OutputFormat::Iife | OutputFormat::Umd) {
+      self.check_wrapper_requirements(&chunk_graph)?;
+    }
+    if !matches!(self.output_options.format, OutputFormat::Esm | OutputFormat::SystemJs) {
+      self.check_export_modes(&chunk_graph)?;
+    }
+    self.check_default_only_interop(&chunk_graph)?;
 
     chunk_graph.chunks.iter_mut().par_bridge().for_each(|chunk| {
-      chunk.de_conflict(self.link_output);
+      chunk.de_conflict(self.link_output, self.output_options);
     });
 
+    let chunk_export_names = chunk_graph
+      .chunks
+      .iter()
+      .map(|chunk| {
+        if matches!(self.output_options.format, OutputFormat::SystemJs) {
+          chunk.canonical_export_names(self.link_output)
+        } else {
+          FxHashMap::default()
+        }
+      })
+      .collect::<IndexVec<ChunkId, _>>();
+
+    let file_urls = self.resolve_file_urls(&chunk_graph).await?;
+
     self
       .link_output
       .ast_table
       .iter_mut_enumerated()
       .par_bridge()
-      .filter(|(id, _)| self.link_output.module_table.normal_modules[*id].is_included)
+      // Modules tree shaken away with `preserve_modules` don't belong to any chunk
+      .filter(|(id, _)| {
+        self.link_output.module_table.normal_modules[*id].is_included
+          && chunk_graph.module_to_chunk[*id].is_some()
+      })
       .for_each(|(id, ast)| {
         let module = &self.link_output.module_table.normal_modules[id];
         let chunk_id = chunk_graph.module_to_chunk[module.id].unwrap();
@@ -73,36 +148,466 @@ impl<'a> BundleStage<'a> {
             linking_info,
             module,
             modules: &self.link_output.module_table.normal_modules,
+            external_modules: &self.link_output.module_table.external_modules,
             linking_infos: &self.link_output.metas,
             runtime: &self.link_output.runtime,
             chunk_graph: &chunk_graph,
+            output_options: self.output_options,
+            chunk_export_names: &chunk_export_names[chunk_id],
+            file_urls: &file_urls[id],
           },
           ast,
         );
       });
     tracing::info!("finalizing modules");
 
-    let chunks = chunk_graph.chunks.iter().map(|c| {
+    let mut chunks = Vec::with_capacity(chunk_graph.chunks.len());
+    let mut chunk_addons = Vec::with_capacity(chunk_graph.chunks.len());
+    let mut chunk_legal_comments = Vec::with_capacity(chunk_graph.chunks.len());
+    for c in &chunk_graph.chunks {
       let ret =
-        c.render(self.input_options, self.link_output, &chunk_graph, self.output_options).unwrap();
-      (
-        ret.code,
-        ret.map,
-        c.get_rendered_chunk_info(self.link_output, self.output_options, ret.rendered_modules),
-      )
+        c.render(self.input_options, self.link_output, &chunk_graph, self.output_options).await?;
+      chunks.push((ret.code, ret.map, ret.rendered_chunk));
+      chunk_addons.push(ret.addons);
+      chunk_legal_comments.push(ret.legal_comments);
+    }
+
+    let chunks = render_chunks(self.plugin_driver, chunks.into_iter()).await?;
+    // Hashes are computed after this, so they cover the banner and the footer.
+    let mut chunks = chunks
+      .into_iter()
+      .zip(&chunk_addons)
+      .map(|((code, map, rendered_chunk), addons)| {
+        let (code, map) = add_banner_and_footer(code, map, addons)?;
+        Ok((code, map, rendered_chunk))
+      })
+      .collect::<Result<Vec<_>, BuildError>>()?;
+    let mut hash_augmentations = Vec::with_capacity(chunks.len());
+    for (_, _, rendered_chunk) in &chunks {
+      hash_augmentations.push(self.plugin_driver.augment_chunk_hash(rendered_chunk).await?);
+    }
+    self.finalize_file_names(
+      &mut chunks,
+      &hash_augmentations,
+      &placeholder_to_chunk,
+      &entry_chunk_file_names,
+    )?;
+    let assets = self.generate_outputs(chunks, chunk_legal_comments)?;
+
+    tracing::info!("rendered chunks");
+
+    Ok(assets)
+  }
+
+  /// Entry chunks exporting bindings for other chunks, which their entry modules don't export, are only allowed if
+  /// `preserve_entry_signatures` allows extending them.
+  fn check_entry_signatures(&self, chunk_graph: &ChunkGraph) -> BatchedResult<()> {
+    let mut errors = BatchedErrors::default();
+    for chunk in &chunk_graph.chunks {
+      let ChunkKind::EntryPoint { is_user_defined: true, module, .. } = chunk.kind else {
+        continue;
+      };
+      let meta = &self.link_output.metas[module];
+      if self
+        .input_options
+        .preserve_entry_signatures
+        .allows_extension(!meta.is_canonical_exports_empty())
+      {
+        continue;
+      }
+      let mut extra_exports = chunk
+        .exports_to_other_chunks
+        .values()
+        .filter(|alias| meta.canonical_exports().all(|(name, _)| name != *alias))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+      if extra_exports.is_empty() {
+        continue;
+      }
+      extra_exports.sort_unstable();
+      let module = &self.link_output.module_table.normal_modules[module];
+      errors.push(BuildError::entry_signature_extended(
+        module.resource_id.expect_file().as_str(),
+        extra_exports,
+      ));
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Only default and namespace imports of externals are allowed with the `defaultOnly` interop. ES modules
+  /// importing externals with `import` statements don't need interop.
+  fn check_default_only_interop(&self, chunk_graph: &ChunkGraph) -> BatchedResult<()> {
+    if matches!(self.output_options.format, OutputFormat::Esm | OutputFormat::SystemJs) {
+      return Ok(());
+    }
+    let mut errors = BatchedErrors::default();
+    for chunk in &chunk_graph.chunks {
+      let mut imports_from_external_modules =
+        chunk.imports_from_external_modules.iter().collect::<Vec<_>>();
+      imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+        self.link_output.module_table.external_modules[**module_id].exec_order
+      });
+      for (importee_id, named_imports) in imports_from_external_modules {
+        let module = &self.link_output.module_table.external_modules[*importee_id];
+        if self.output_options.interop.call(module.resource_id.expect_file())
+          != InteropMode::DefaultOnly
+        {
+          continue;
+        }
+        for item in named_imports {
+          if let Specifier::Literal(imported) = &item.imported {
+            if imported.as_str() != "default" {
+              errors.push(BuildError::unexpected_named_import(
+                module.resource_id.expect_file().as_str(),
+                imported.as_str(),
+              ));
+            }
+          }
+        }
+      }
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Warn about entry chunks whose exports are hard to consume in the resolved `output.exports` mode, that is mixed
+  /// default and named exports in the `auto` mode, and exports dropped in the `none` mode. Common chunks and
+  /// dynamic entries of cjs are loaded by other chunks, which always use the named mode.
+  fn check_export_modes(&mut self, chunk_graph: &ChunkGraph) -> BatchedResult<()> {
+    for chunk in &chunk_graph.chunks {
+      let ChunkKind::EntryPoint { is_user_defined, module, .. } = chunk.kind else {
+        continue;
+      };
+      if !is_user_defined && matches!(self.output_options.format, OutputFormat::Cjs) {
+        continue;
+      }
+      let export_mode = chunk.determine_export_mode(self.link_output, self.output_options)?;
+      let export_names = chunk.get_export_names(self.link_output);
+      let module_path =
+        self.link_output.module_table.normal_modules[module].resource_id.expect_file().as_str();
+      let warning = match (self.output_options.exports, export_mode) {
+        (OutputExports::Auto, OutputExports::Named)
+          if export_names.iter().any(|name| name == "default") =>
+        {
+          BuildError::mixed_exports(module_path)
+        }
+        (OutputExports::None, OutputExports::None) if !export_names.is_empty() => {
+          BuildError::invalid_export_option("none", module_path, export_names)
+        }
+        _ => continue,
+      };
+      self.link_output.warnings.push(warning.with_severity_warning());
+    }
+    Ok(())
+  }
+
+  /// `output.inlineDynamicImports` puts everything into one chunk, which conflicts with options creating more chunks.
+  fn check_inline_dynamic_imports(&self) -> BatchedResult<()> {
+    let explanation = if self.link_output.entries.len() > 1 {
+      "multiple inputs are not supported when \"output.inlineDynamicImports\" is true"
+    } else if self.output_options.preserve_modules {
+      "this option is not supported for \"output.preserveModules\""
+    } else if self.output_options.manual_chunks.is_some() {
+      "this option is not supported for \"output.manualChunks\""
+    } else {
+      return Ok(());
+    };
+    Err(BuildError::invalid_option("output.inlineDynamicImports", explanation))?
+  }
+
+  /// Validate options used by formats that wrap the chunk with a function, that is iife and umd.
+  fn check_wrapper_requirements(&mut self, chunk_graph: &ChunkGraph) -> BatchedResult<()> {
+    // Chunks can't import each other without a module loader.
+    if chunk_graph.chunks.len() > 1 {
+      let format =
+        if matches!(self.output_options.format, OutputFormat::Umd) { "umd" } else { "iife" };
+      Err(BuildError::unsupported_code_splitting_format(format))?;
+    }
+
+    // Dotted names, like `MyLib.widgets`, are assigned to nested namespaces.
+    if let Some(name) = &self.output_options.name {
+      if !name.split('.').all(is_identifier_name) {
+        Err(BuildError::invalid_option(
+          "output.name",
+          format!("\"{name}\" is neither an identifier nor a member expression"),
+        ))?;
+      }
+    }
+
+    chunk_graph.chunks.iter().try_for_each(|chunk| -> BatchedResult<()> {
+      if matches!(chunk.kind, ChunkKind::EntryPoint { .. }) {
+        let export_mode = chunk.determine_export_mode(self.link_output, self.output_options)?;
+        if self.output_options.name.is_none() && !matches!(export_mode, OutputExports::None) {
+          if matches!(self.output_options.format, OutputFormat::Umd) {
+            Err(BuildError::missing_name_option_for_umd_export())?;
+          }
+          self
+            .link_output
+            .warnings
+            .push(BuildError::missing_name_option_for_iife_export().with_severity_warning());
+        }
+      }
+
+      let mut external_modules = chunk
+        .imports_from_external_modules
+        .iter()
+        .filter(|(_, named_imports)| !named_imports.is_empty())
+        .map(|(importee_id, _)| &self.link_output.module_table.external_modules[*importee_id])
+        .collect::<Vec<_>>();
+      external_modules.sort_unstable_by_key(|module| module.exec_order);
+      external_modules.into_iter().for_each(|module| {
+        let module_id = module.resource_id.expect_file().as_str();
+        if self.output_options.globals.call(module_id).is_none() {
+          self.link_output.warnings.push(
+            BuildError::missing_global_name(
+              module_id.to_string(),
+              external_global_name(self.output_options, module_id),
+            )
+            .with_severity_warning(),
+          );
+        }
+      });
+      Ok(())
+    })
+  }
+
+  /// Assign unique names to chunks, which are rendered by `[name]`.
+  fn generate_chunk_names(&self, chunk_graph: &mut ChunkGraph) {
+    let mut used_chunk_names = FxHashSet::default();
+    chunk_graph.chunks.iter_mut().for_each(|chunk| {
+      let runtime_id = self.link_output.runtime.id();
+
+      let chunk_name = if is_in_rust_test_mode()
+        && matches!(chunk.kind, ChunkKind::Common)
+        && chunk.modules.first().copied() == Some(runtime_id)
+      {
+        "$runtime$".to_string()
+      } else {
+        chunk.name.clone().unwrap_or_else(|| {
+          let module_id =
+            if let ChunkKind::EntryPoint { module: entry_module_id, is_user_defined, .. } =
+              &chunk.kind
+            {
+              debug_assert!(
+                !*is_user_defined,
+                "User-defined entry point should always have a name"
+              );
+              *entry_module_id
+            } else {
+              // TODO: we currently use the first executed module to calculate the chunk name for common chunks
+              // This is not perfect, should investigate more to find a better solution
+              chunk.modules.first().copied().unwrap()
+            };
+          let module = &self.link_output.module_table.normal_modules[module_id];
+          module.resource_id.expect_file().unique(&self.input_options.cwd)
+        })
+      };
+
+      let mut chunk_name = chunk_name;
+      while used_chunk_names.contains(&chunk_name) {
+        chunk_name = format!("{}-{}", chunk_name, used_chunk_names.len());
+      }
+      used_chunk_names.insert(chunk_name.clone());
+      chunk.name = Some(chunk_name);
+    });
+  }
+
+  /// Assign file names to chunks. Returns the chunks whose file names contain hash placeholders, keyed by the
+  /// placeholders, along with the lengths of the hashes, since hashes are only known once chunks are rendered.
+  async fn generate_chunk_filenames(
+    &self,
+    chunk_graph: &mut ChunkGraph,
+  ) -> BatchedResult<FxHashMap<String, (ChunkId, usize)>> {
+    self.generate_chunk_names(chunk_graph);
+    let max_hash_length = self.output_options.hash_algorithm.max_len();
+    let mut placeholders = HashPlaceholderGenerator::default();
+    let mut placeholder_to_chunk = FxHashMap::default();
+    for (chunk_id, chunk) in chunk_graph.chunks.iter_mut_enumerated() {
+      let file_name_tmp = match chunk.file_name_template(self.output_options) {
+        ChunkFileNamesOption::Template(template) => Cow::Borrowed(template),
+        ChunkFileNamesOption::Fn(f) => Cow::Owned(FileNameTemplate::from(
+          f(chunk.get_pre_rendered_chunk_info(self.link_output, self.output_options)).await?,
+        )),
+      };
+      if let Some(length) = file_name_tmp
+        .hash_lengths()
+        .into_iter()
+        .find(|length| !(MIN_HASH_LENGTH..=max_hash_length).contains(length))
+      {
+        Err(BuildError::invalid_hash_length_in_pattern(
+          length,
+          max_hash_length,
+          file_name_tmp.as_str(),
+        ))?;
+      }
+
+      // Placeholders of the chunk by the lengths of hashes, so `[hash]` and `[hash:8]` share the same one if
+      // `hash_length` is 8.
+      let chunk_placeholders = RefCell::new(FxHashMap::<usize, String>::default());
+      let placeholder_generator = RefCell::new(&mut placeholders);
+      let render_hash = |length: Option<usize>| {
+        let length = length.unwrap_or(self.output_options.hash_length);
+        chunk_placeholders
+          .borrow_mut()
+          .entry(length)
+          .or_insert_with(|| placeholder_generator.borrow_mut().generate(length))
+          .clone()
+      };
+      let chunk_name = chunk.name.as_deref().expect("Chunk names should be generated");
+      chunk.file_name = Some(file_name_tmp.render(&FileNameRenderOptions {
+        name: Some(chunk_name),
+        hash: Some(&render_hash),
+        format: Some(self.output_options.format.file_name_str()),
+        extname: Some(".js"),
+      }));
+      placeholder_to_chunk.extend(
+        chunk_placeholders
+          .into_inner()
+          .into_iter()
+          .map(|(length, placeholder)| (placeholder, (chunk_id, length))),
+      );
+    }
+    Ok(placeholder_to_chunk)
+  }
+
+  /// Replace placeholders of file names in rendered chunks, including hash placeholders in file names of chunks,
+  /// and check the final file names.
+  fn finalize_file_names(
+    &self,
+    chunks: &mut [RenderedChunkOutput],
+    hash_augmentations: &[String],
+    placeholder_to_chunk: &FxHashMap<String, (ChunkId, usize)>,
+    entry_chunk_file_names: &FxHashMap<NormalModuleId, String>,
+  ) -> BatchedResult<()> {
+    let file_emitter = self.plugin_driver.file_emitter();
+    chunks.iter_mut().for_each(|(content, _, _)| {
+      *content = file_emitter.replace_file_name_placeholders(content);
+    });
+
+    let hashes = self.compute_chunk_hashes(chunks, hash_augmentations, placeholder_to_chunk);
+    let mut used_file_names = FxHashSet::default();
+    for (content, _, rendered_chunk) in chunks.iter_mut() {
+      *content = replace_hash_placeholders(content, &hashes);
+      rendered_chunk.file_name = replace_hash_placeholders(&rendered_chunk.file_name, &hashes);
+      for file_name in rendered_chunk.imports.iter_mut().chain(&mut rendered_chunk.dynamic_imports)
+      {
+        *file_name = replace_hash_placeholders(file_name, &hashes);
+      }
+      // Different names could still render to the same file name, like with a pattern without `[name]`.
+      if !used_file_names.insert(rendered_chunk.file_name.clone()) {
+        Err(BuildError::file_name_conflict(rendered_chunk.file_name.clone()))?;
+      }
+    }
+    file_emitter.assign_chunk_file_names(|module_id| {
+      entry_chunk_file_names
+        .get(&module_id)
+        .map(|file_name| replace_hash_placeholders(file_name, &hashes))
     });
+    Ok(())
+  }
+
+  /// Hashes of chunks with `[hash]` in their file names, keyed by their placeholders. The hash of a chunk covers
+  /// its final content and the chunks it references through placeholders, directly or indirectly, so a change in
+  /// a chunk changes the hashes of chunks importing it, even with circular imports. Strings returned by
+  /// `augment_chunk_hash` hooks are hashed along with the content of each chunk.
+  fn compute_chunk_hashes(
+    &self,
+    chunks: &[RenderedChunkOutput],
+    hash_augmentations: &[String],
+    placeholder_to_chunk: &FxHashMap<String, (ChunkId, usize)>,
+  ) -> FxHashMap<String, String> {
+    let algorithm = self.output_options.hash_algorithm;
+    let content_hashes = chunks
+      .iter()
+      .zip(hash_augmentations)
+      .map(|((content, _, _), augmentation)| {
+        algorithm.hash_hex(format!("{content}{augmentation}").as_bytes())
+      })
+      .collect::<Vec<_>>();
+    let referenced_chunks = chunks
+      .iter()
+      .map(|(content, _, _)| {
+        find_hash_placeholders(content)
+          .into_iter()
+          .filter_map(|placeholder| placeholder_to_chunk.get(placeholder).map(|(id, _)| id.index()))
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
 
+    placeholder_to_chunk
+      .iter()
+      .map(|(placeholder, (chunk_id, hash_length))| {
+        let mut visited = BTreeSet::from([chunk_id.index()]);
+        let mut stack = vec![chunk_id.index()];
+        while let Some(index) = stack.pop() {
+          stack.extend(referenced_chunks[index].iter().filter(|index| visited.insert(**index)));
+        }
+        let content =
+          visited.into_iter().map(|index| content_hashes[index].as_str()).collect::<String>();
+        let hash = algorithm.hash_hex(content.as_bytes());
+        (placeholder.clone(), hash[..*hash_length].to_string())
+      })
+      .collect()
+  }
+
+  /// Add source maps and `.LEGAL.txt` assets to rendered chunks and turn them into outputs.
+  fn generate_outputs(
+    &self,
+    chunks: Vec<RenderedChunkOutput>,
+    chunk_legal_comments: Vec<Vec<String>>,
+  ) -> BatchedResult<Vec<Output>> {
     let mut assets = vec![];
 
-    render_chunks(self.plugin_driver, chunks).await?.into_iter().try_for_each(
-      |(mut content, map, rendered_chunk)| -> Result<(), BuildError> {
-        if let Some(mut map) = map {
-          match self.output_options.sourcemap {
-            SourceMapType::File => {
+    chunks.into_iter().zip(chunk_legal_comments).try_for_each(
+      |((mut content, map, rendered_chunk), legal_comments)| -> Result<(), BuildError> {
+        if !legal_comments.is_empty() {
+          let legal_file_name = format!("{}.LEGAL.txt", rendered_chunk.file_name);
+          let legal_base_name =
+            Path::new(&legal_file_name).file_name().map(|name| name.to_string_lossy());
+          content.push_str(&format!(
+            "\n/*! For license information please see {} */",
+            legal_base_name.unwrap_or_default()
+          ));
+          assets.push(Output::Asset(Box::new(OutputAsset {
+            file_name: legal_file_name,
+            source: format!("{}\n", legal_comments.join("\n\n")).into(),
+          })));
+        }
+        if let (Some(mut map), Some(sourcemap)) = (map, &self.output_options.sourcemap) {
+          if self.output_options.sourcemap_exclude_sources {
+            map.exclude_sources_content()?;
+          }
+          if let Some(transform) = &self.output_options.sourcemap_path_transform {
+            let map_path = self
+              .input_options
+              .cwd
+              .join(&self.output_options.dir)
+              .join(format!("{}.map", rendered_chunk.file_name));
+            let map_path = map_path.to_string_lossy();
+            map.map_sources(|source| transform.call(source, &map_path))?;
+          }
+          match sourcemap {
+            SourceMapType::File | SourceMapType::Hidden => {
               if let Some(map) = map.to_json() {
+                let map_file_name = format!("{}.map", rendered_chunk.file_name);
+                if matches!(sourcemap, SourceMapType::File) {
+                  let map_base_name =
+                    Path::new(&map_file_name).file_name().map(|name| name.to_string_lossy());
+                  content.push_str(&format!(
+                    "\n//# sourceMappingURL={}",
+                    map_base_name.unwrap_or_default()
+                  ));
+                }
                 assets.push(Output::Asset(Box::new(OutputAsset {
-                  file_name: format!("{}.map", rendered_chunk.file_name),
-                  source: map?,
+                  file_name: map_file_name,
+                  source: map?.into(),
                 })));
               }
             }
@@ -111,7 +616,6 @@ impl<'a> BundleStage<'a> {
                 content.push_str(&format!("\n//# sourceMappingURL={}", map?));
               }
             }
-            SourceMapType::Hidden => {}
           }
         }
         assets.push(Output::Chunk(Box::new(OutputChunk {
@@ -121,6 +625,9 @@ impl<'a> BundleStage<'a> {
           is_dynamic_entry: rendered_chunk.is_dynamic_entry,
           facade_module_id: rendered_chunk.facade_module_id,
           modules: rendered_chunk.modules,
+          imports: rendered_chunk.imports,
+          dynamic_imports: rendered_chunk.dynamic_imports,
+          imported_css: rendered_chunk.imported_css,
           exports: rendered_chunk.exports,
           module_ids: rendered_chunk.module_ids,
         })));
@@ -128,49 +635,6 @@ impl<'a> BundleStage<'a> {
       },
     )?;
 
-    tracing::info!("rendered chunks");
-
     Ok(assets)
   }
-
-  fn generate_chunk_filenames(&self, chunk_graph: &mut ChunkGraph) {
-    let mut used_chunk_names = FxHashSet::default();
-    chunk_graph.chunks.iter_mut().for_each(|chunk| {
-      let runtime_id = self.link_output.runtime.id();
-
-      let file_name_tmp = chunk.file_name_template(self.output_options);
-      let chunk_name =
-        if is_in_rust_test_mode() && chunk.modules.first().copied() == Some(runtime_id) {
-          "$runtime$".to_string()
-        } else {
-          chunk.name.clone().unwrap_or_else(|| {
-            let module_id =
-              if let ChunkKind::EntryPoint { module: entry_module_id, is_user_defined, .. } =
-                &chunk.kind
-              {
-                debug_assert!(
-                  !*is_user_defined,
-                  "User-defined entry point should always have a name"
-                );
-                *entry_module_id
-              } else {
-                // TODO: we currently use the first executed module to calculate the chunk name for common chunks
-                // This is not perfect, should investigate more to find a better solution
-                chunk.modules.first().copied().unwrap()
-              };
-            let module = &self.link_output.module_table.normal_modules[module_id];
-            module.resource_id.expect_file().unique(&self.input_options.cwd)
-          })
-        };
-
-      let mut chunk_name = chunk_name;
-      while used_chunk_names.contains(&chunk_name) {
-        chunk_name = format!("{}-{}", chunk_name, used_chunk_names.len());
-      }
-      used_chunk_names.insert(chunk_name.clone());
-
-      chunk.file_name =
-        Some(file_name_tmp.render(&FileNameRenderOptions { name: Some(&chunk_name) }));
-    });
-  }
 }
@@ -1,23 +1,35 @@
 // TODO: The current implementation for matching imports is enough so far but incomplete. It needs to be refactored
 // if we want more enhancements related to exports.
 
+use std::{
+  collections::{hash_map::Entry, VecDeque},
+  sync::Arc,
+};
+
+use oxc::span::Span;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rolldown_common::{
   ExportsKind, ModuleId, NamedImport, NormalModule, NormalModuleId, ResolvedExport, Specifier,
-  SymbolRef,
+  StmtInfo, SymbolRef,
 };
+use rolldown_error::BuildError;
+use rolldown_rstr::Rstr;
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::types::{
-  linking_metadata::{LinkingMetadata, LinkingMetadataVec},
-  match_import_kind::MatchImportKind,
-  module_table::NormalModuleVec,
-  namespace_alias::NamespaceAlias,
+use crate::{
+  error::{BatchedErrors, BatchedResult},
+  types::{
+    linking_metadata::{LinkingMetadata, LinkingMetadataVec},
+    match_import_kind::MatchImportKind,
+    module_table::NormalModuleVec,
+    namespace_alias::NamespaceAlias,
+  },
 };
 
 use super::LinkStage;
 
 impl<'a> LinkStage<'a> {
-  pub fn bind_imports_and_exports(&mut self) {
+  pub fn bind_imports_and_exports(&mut self) -> BatchedResult<()> {
     self.module_table.normal_modules.iter().zip(self.metas.iter_mut()).par_bridge().for_each(
       |(module, meta)| {
         meta.resolved_exports = module
@@ -49,6 +61,7 @@ impl<'a> LinkStage<'a> {
     });
 
     // match imports with exports
+    let mut missing_imports = vec![];
     self.module_table.normal_modules.iter().for_each(|importer| {
       importer.named_imports.values().for_each(|import| {
         let import_record = &importer.import_records[import.record_id];
@@ -58,7 +71,18 @@ impl<'a> LinkStage<'a> {
         let importee = &self.module_table.normal_modules[importee_id];
 
         match Self::match_import_with_export(importer, importee, &self.metas[importee.id], import) {
-          MatchImportKind::NotFound => panic!("info {import:#?}"),
+          MatchImportKind::NotFound => {
+            let Specifier::Literal(imported) = &import.imported else {
+              unreachable!("Star imports are always resolved to the namespace")
+            };
+            missing_imports.push((
+              importer.id,
+              importee_id,
+              imported.clone(),
+              import.imported_as,
+              import.span,
+            ));
+          }
           MatchImportKind::PotentiallyAmbiguous(
             symbol_ref,
             mut potentially_ambiguous_symbol_refs,
@@ -90,11 +114,108 @@ impl<'a> LinkStage<'a> {
       });
     });
 
+    self.shim_missing_exports(missing_imports)?;
+
     // Exclude ambiguous from resolved exports
     self.sorted_modules.clone().into_iter().for_each(|id| {
       let linking_info = &mut self.metas[id];
       linking_info.create_exclude_ambiguous_resolved_exports(&self.symbols);
     });
+    Ok(())
+  }
+
+  /// Pretty paths of the modules from the nearest entry to `target`, each importing the next one. It's empty if
+  /// `target` isn't imported by any entry.
+  fn importer_chain(&self, target: NormalModuleId) -> Vec<String> {
+    let modules = &self.module_table.normal_modules;
+    let mut importers = FxHashMap::default();
+    let mut queue = VecDeque::new();
+    for entry in &self.entries {
+      if let Entry::Vacant(vacant) = importers.entry(entry.id) {
+        vacant.insert(None);
+        queue.push_back(entry.id);
+      }
+    }
+    while let Some(id) = queue.pop_front() {
+      if id == target {
+        break;
+      }
+      for record in &modules[id].import_records {
+        if let ModuleId::Normal(importee) = record.resolved_module {
+          if let Entry::Vacant(vacant) = importers.entry(importee) {
+            vacant.insert(Some(id));
+            queue.push_back(importee);
+          }
+        }
+      }
+    }
+    let mut chain = vec![];
+    let mut current = importers.contains_key(&target).then_some(target);
+    while let Some(id) = current {
+      chain.push(modules[id].pretty_path.to_string());
+      current = importers[&id];
+    }
+    chain.reverse();
+    chain
+  }
+
+  /// Named imports that are not exported by their importees are errors, unless `shim_missing_exports` is
+  /// enabled. In that case, they are bound to a `_missingExportShim` binding declared in the importee.
+  fn shim_missing_exports(
+    &mut self,
+    missing_imports: Vec<(NormalModuleId, NormalModuleId, Rstr, SymbolRef, Span)>,
+  ) -> BatchedResult<()> {
+    let mut errors = BatchedErrors::default();
+    let mut shimmed_exports = FxHashSet::default();
+    for (importer_id, importee_id, imported, imported_as, span) in missing_imports {
+      if !self.input_options.shim_missing_exports {
+        let modules = &self.module_table.normal_modules;
+        let mut exported_names = self.metas[importee_id]
+          .resolved_exports
+          .keys()
+          .map(ToString::to_string)
+          .collect::<Vec<_>>();
+        exported_names.sort_unstable();
+        errors.push(BuildError::missing_export(
+          modules[importer_id].pretty_path.as_str(),
+          modules[importee_id].pretty_path.as_str(),
+          imported.as_str(),
+          Arc::from(self.ast_table[importer_id].source()),
+          span,
+          exported_names,
+          self.importer_chain(importer_id),
+        ));
+        continue;
+      }
+      let importee = &mut self.module_table.normal_modules[importee_id];
+      if shimmed_exports.insert((importee_id, imported.clone())) {
+        self.warnings.push(
+          BuildError::shimmed_export(importee.pretty_path.as_str(), imported.as_str())
+            .with_severity_warning(),
+        );
+      }
+      let importee_meta = &mut self.metas[importee_id];
+      let shim_ref = *importee_meta.missing_export_shim_ref.get_or_insert_with(|| {
+        let shim_ref = self.symbols.create_symbol(importee_id, "_missingExportShim".into());
+        importee_meta.missing_export_shim_stmt_info =
+          Some(importee.stmt_infos.add_stmt_info(StmtInfo {
+            stmt_idx: None,
+            declared_symbols: vec![shim_ref],
+            referenced_symbols: vec![],
+            side_effect: false,
+            is_included: false,
+            import_records: Vec::new(),
+            debug_label: None,
+          }));
+        shim_ref
+      });
+      self.symbols.union(imported_as, shim_ref);
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
   }
 
   pub fn match_import_with_export(
@@ -136,6 +257,15 @@ impl<'a> LinkStage<'a> {
       }
     }
 
+    // Unknown names are properties of the synthetic named export, like `default.foo` for `import { foo }`.
+    if let Some(synthetic_export) = importee
+      .synthetic_named_exports
+      .as_ref()
+      .and_then(|name| importee_meta.resolved_exports.get(name))
+    {
+      return MatchImportKind::Namespace(synthetic_export.symbol_ref);
+    }
+
     // If the module has dynamic exports, the unknown export name will be resolved at runtime.
     // The namespace symbol should be importee namespace symbol.
     if importee_meta.has_dynamic_exports {
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use index_vec::IndexVec;
+use rolldown_common::NormalModuleId;
+use rolldown_error::BuildError;
+use rustc_hash::FxHashSet;
+
+use crate::{types::module_table::NormalModuleVec, utils::match_id_pattern};
+
+use super::LinkStage;
+
+impl LinkStage<'_> {
+  /// Warn once for each group of modules importing each other, which is a strongly connected component of the graph
+  /// of static imports and requires, with one of its cycles as the path. Dynamic imports and external modules are
+  /// not followed, since they don't take part in the execution order of the bundle.
+  pub fn warn_circular_dependencies(&mut self) {
+    let modules = &self.module_table.normal_modules;
+    let importees = |id| static_importees(modules, id);
+
+    // Tarjan's algorithm without recursion, since import chains could be deep
+    let mut indexes: IndexVec<NormalModuleId, Option<usize>> =
+      modules.iter().map(|_| None).collect();
+    let mut low_links: IndexVec<NormalModuleId, usize> = modules.iter().map(|_| 0).collect();
+    let mut on_stack: IndexVec<NormalModuleId, bool> = modules.iter().map(|_| false).collect();
+    let mut next_index = 0;
+    let mut stack = vec![];
+    let mut components = vec![];
+    for root in modules.indices() {
+      if indexes[root].is_some() {
+        continue;
+      }
+      let mut work = vec![(root, None)];
+      while let Some((id, iter)) = work.last_mut() {
+        let id = *id;
+        let iter = iter.get_or_insert_with(|| {
+          indexes[id] = Some(next_index);
+          low_links[id] = next_index;
+          next_index += 1;
+          on_stack[id] = true;
+          stack.push(id);
+          importees(id)
+        });
+        if let Some(importee) = iter.next() {
+          match indexes[importee] {
+            None => work.push((importee, None)),
+            Some(index) if on_stack[importee] => low_links[id] = low_links[id].min(index),
+            Some(_) => {}
+          }
+          continue;
+        }
+        work.pop();
+        if let Some((parent, _)) = work.last() {
+          low_links[*parent] = low_links[*parent].min(low_links[id]);
+        }
+        if Some(low_links[id]) == indexes[id] {
+          let mut component = vec![];
+          while let Some(member) = stack.pop() {
+            on_stack[member] = false;
+            component.push(member);
+            if member == id {
+              break;
+            }
+          }
+          if component.len() > 1 || importees(id).any(|importee| importee == id) {
+            components.push(component);
+          }
+        }
+      }
+    }
+
+    let mut cycles = components
+      .into_iter()
+      .map(|component| {
+        let members = component.iter().copied().collect::<FxHashSet<_>>();
+        let start = component
+          .into_iter()
+          .min_by_key(|id| modules[*id].exec_order)
+          .expect("Components should not be empty");
+        shortest_cycle(modules, start, &members)
+      })
+      .collect::<Vec<_>>();
+    cycles.sort_by_key(|cycle| modules[cycle[0]].exec_order);
+
+    let ignored = &self.input_options.ignored_circular_dependencies;
+    for cycle in cycles {
+      let is_ignored = cycle.iter().any(|id| {
+        let module_id = modules[*id].resource_id.expect_file().as_str();
+        ignored.iter().any(|pattern| match_id_pattern(pattern, module_id, &self.input_options.cwd))
+      });
+      if !is_ignored {
+        self.warnings.push(
+          BuildError::circular_dependency(
+            cycle.iter().map(|id| modules[*id].pretty_path.clone()).collect(),
+          )
+          .with_severity_warning(),
+        );
+      }
+    }
+  }
+}
+
+fn static_importees(
+  modules: &NormalModuleVec,
+  id: NormalModuleId,
+) -> impl Iterator<Item = NormalModuleId> + '_ {
+  modules[id]
+    .import_records
+    .iter()
+    .filter(|rec| rec.kind.is_static())
+    .filter_map(|rec| rec.resolved_module.as_normal())
+}
+
+/// The shortest cycle from `start` back to itself, walking importees within `members`.
+fn shortest_cycle(
+  modules: &NormalModuleVec,
+  start: NormalModuleId,
+  members: &FxHashSet<NormalModuleId>,
+) -> Vec<NormalModuleId> {
+  let mut importers: IndexVec<NormalModuleId, Option<NormalModuleId>> =
+    modules.iter().map(|_| None).collect();
+  let mut queue = VecDeque::from([start]);
+  let mut last = start;
+  'search: while let Some(id) = queue.pop_front() {
+    for importee in static_importees(modules, id).filter(|importee| members.contains(importee)) {
+      if importee == start {
+        last = id;
+        break 'search;
+      }
+      if importers[importee].is_none() {
+        importers[importee] = Some(id);
+        queue.push_back(importee);
+      }
+    }
+  }
+  let mut cycle = vec![start, last];
+  while let Some(importer) = importers[*cycle.last().unwrap()] {
+    cycle.push(importer);
+  }
+  cycle.reverse();
+  cycle
+}
@@ -3,20 +3,24 @@ use std::{ptr::addr_of, sync::Mutex};
 use index_vec::IndexVec;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rolldown_common::{
-  EntryPoint, ExportsKind, ImportKind, ModuleId, NormalModule, NormalModuleId, StmtInfo, WrapKind,
+  EntryPoint, EntryPointKind, ExportsKind, ExternalModuleId, ImportKind, ModuleId, NormalModule,
+  NormalModuleId, PreserveEntrySignatures, StmtInfo, SymbolRef, WrapKind,
 };
 use rolldown_error::BuildError;
 use rolldown_oxc_utils::OxcProgram;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
-  InputOptions,
+  InputOptions, OutputFormat, OutputOptions,
   {
+    error::BatchedResult,
     runtime::RuntimeModuleBrief,
     types::{
       linking_metadata::{LinkingMetadata, LinkingMetadataVec},
       module_table::ModuleTable,
       symbols::Symbols,
     },
+    utils::interop::external_interop_helper,
   },
 };
 
@@ -25,7 +29,9 @@ use self::wrapping::create_wrapper;
 use super::scan_stage::ScanStageOutput;
 
 mod bind_imports_and_exports;
+mod circular_dependencies;
 mod sort_modules;
+mod top_level_await;
 mod tree_shaking;
 mod wrapping;
 
@@ -52,10 +58,15 @@ pub struct LinkStage<'a> {
   pub warnings: Vec<BuildError>,
   pub ast_table: IndexVec<NormalModuleId, OxcProgram>,
   pub input_options: &'a InputOptions,
+  pub output_options: &'a OutputOptions,
 }
 
 impl<'a> LinkStage<'a> {
-  pub fn new(scan_stage_output: ScanStageOutput, input_options: &'a InputOptions) -> Self {
+  pub fn new(
+    scan_stage_output: ScanStageOutput,
+    input_options: &'a InputOptions,
+    output_options: &'a OutputOptions,
+  ) -> Self {
     Self {
       sorted_modules: Vec::new(),
       metas: scan_stage_output
@@ -65,22 +76,71 @@ impl<'a> LinkStage<'a> {
         .map(|_| LinkingMetadata::default())
         .collect::<IndexVec<NormalModuleId, _>>(),
       module_table: scan_stage_output.module_table,
-      entries: scan_stage_output.entry_points,
+      // Dynamically imported modules are put into the chunk of their importers, instead of becoming entries.
+      entries: scan_stage_output
+        .entry_points
+        .into_iter()
+        .filter(|entry| {
+          !output_options.inline_dynamic_imports
+            || !matches!(entry.kind, EntryPointKind::DynamicImport)
+        })
+        .collect(),
       symbols: scan_stage_output.symbols,
       runtime: scan_stage_output.runtime,
       warnings: scan_stage_output.warnings,
       ast_table: scan_stage_output.ast_table,
       input_options,
+      output_options,
     }
   }
 
   fn create_exports_for_modules(&mut self) {
+    let dynamically_imported_modules =
+      if matches!(self.input_options.preserve_entry_signatures, PreserveEntrySignatures::False) {
+        self
+          .module_table
+          .normal_modules
+          .iter()
+          .flat_map(|module| module.import_records.iter())
+          .filter(|rec| matches!(rec.kind, ImportKind::DynamicImport))
+          .filter_map(|rec| rec.resolved_module.as_normal())
+          .collect::<FxHashSet<_>>()
+      } else {
+        FxHashSet::default()
+      };
+    let mut star_exports_from_external_modules = self
+      .entries
+      .iter()
+      .map(|entry| {
+        let mut external_modules = vec![];
+        self.collect_star_exports_from_external_modules(
+          entry.id,
+          &mut FxHashSet::default(),
+          &mut external_modules,
+        );
+        (entry.id, external_modules)
+      })
+      .collect::<FxHashMap<_, _>>();
     self.module_table.normal_modules.iter_mut().for_each(|module| {
       let linking_info = &mut self.metas[module.id];
 
       create_wrapper(module, linking_info, &mut self.symbols, &self.runtime);
-      if self.entries.iter().any(|entry| entry.id == module.id) {
-        init_entry_point_stmt_info(module, linking_info);
+      if let Some(entry) = self.entries.iter().find(|entry| entry.id == module.id) {
+        // Namespaces of dynamically imported entries are used by their importers.
+        linking_info.is_entry_signature_dropped =
+          matches!(self.input_options.preserve_entry_signatures, PreserveEntrySignatures::False)
+            && !matches!(entry.kind, EntryPointKind::DynamicImport)
+            && matches!(module.exports_kind, ExportsKind::Esm)
+            && !dynamically_imported_modules.contains(&module.id);
+        if !linking_info.is_entry_signature_dropped {
+          linking_info.star_exports_from_external_modules =
+            star_exports_from_external_modules.remove(&module.id).unwrap_or_default();
+        }
+        // something like `__reExport(exports, require("foo"))` after the exports of the entry chunk
+        let re_export_ref = (!linking_info.star_exports_from_external_modules.is_empty()
+          && matches!(self.output_options.format, OutputFormat::Cjs))
+        .then(|| self.runtime.resolve_symbol("__reExport"));
+        init_entry_point_stmt_info(module, linking_info, re_export_ref);
       }
 
       if matches!(module.exports_kind, ExportsKind::Esm) {
@@ -110,19 +170,51 @@ impl<'a> LinkStage<'a> {
     });
   }
 
-  pub fn link(mut self) -> LinkStageOutput {
+  /// External modules re-exported by `export * from` of the module, following re-exports of all from unwrapped ES
+  /// modules. Re-exports of wrapped modules are resolved at runtime instead.
+  fn collect_star_exports_from_external_modules(
+    &self,
+    module_id: NormalModuleId,
+    visited: &mut FxHashSet<NormalModuleId>,
+    external_modules: &mut Vec<ExternalModuleId>,
+  ) {
+    if !visited.insert(module_id) {
+      return;
+    }
+    for importee in self.module_table.normal_modules[module_id].star_export_modules() {
+      match importee {
+        ModuleId::External(external_id) => {
+          if !external_modules.contains(&external_id) {
+            external_modules.push(external_id);
+          }
+        }
+        ModuleId::Normal(importee_id) => {
+          let importee = &self.module_table.normal_modules[importee_id];
+          if matches!(importee.exports_kind, ExportsKind::Esm)
+            && matches!(self.metas[importee_id].wrap_kind, WrapKind::None)
+          {
+            self.collect_star_exports_from_external_modules(importee_id, visited, external_modules);
+          }
+        }
+      }
+    }
+  }
+
+  pub fn link(mut self) -> BatchedResult<LinkStageOutput> {
     tracing::info!("Start link stage");
     self.sort_modules();
+    self.warn_circular_dependencies();
 
     self.determine_module_exports_kind();
     self.wrap_modules();
-    self.bind_imports_and_exports();
+    self.mark_top_level_await_modules()?;
+    self.bind_imports_and_exports()?;
     tracing::debug!("linking modules {:#?}", self.metas);
     self.create_exports_for_modules();
     self.reference_needed_symbols();
     self.include_statements();
 
-    LinkStageOutput {
+    Ok(LinkStageOutput {
       module_table: self.module_table,
       entries: self.entries,
       sorted_modules: self.sorted_modules,
@@ -131,7 +223,7 @@ impl<'a> LinkStage<'a> {
       runtime: self.runtime,
       warnings: self.warnings,
       ast_table: self.ast_table,
-    }
+    })
   }
 
   fn determine_module_exports_kind(&mut self) {
@@ -217,8 +309,25 @@ impl<'a> LinkStage<'a> {
       stmt_infos.iter_mut().for_each(|stmt_info| {
         stmt_info.import_records.iter().for_each(|rec_id| {
           let rec = &importer.import_records[*rec_id];
-          let ModuleId::Normal(importee_id) = rec.resolved_module else {
-            return;
+          let importee_id = match rec.resolved_module {
+            ModuleId::Normal(importee_id) => importee_id,
+            ModuleId::External(external_id) => {
+              let external = &self.module_table.external_modules[external_id];
+              let interop = self.output_options.interop.call(external.resource_id.expect_file());
+              // Dependencies of system modules are passed in as namespace objects, no interop is needed.
+              if let Some(helper) = external_interop_helper(interop).filter(|_| {
+                matches!(rec.kind, ImportKind::Import)
+                  && !matches!(
+                    self.output_options.format,
+                    OutputFormat::Esm | OutputFormat::SystemJs
+                  )
+                  && (rec.contains_import_default || rec.contains_import_star)
+              }) {
+                // something like `var import_foo = __toESM(require("foo"))`
+                stmt_info.referenced_symbols.push(self.runtime.resolve_symbol(helper));
+              }
+              return;
+            }
           };
           let importee_linking_info = &self.metas[importee_id];
           match rec.kind {
@@ -275,7 +384,28 @@ impl<'a> LinkStage<'a> {
                 stmt_info.referenced_symbols.push(importee.namespace_symbol);
               }
             },
-            ImportKind::DynamicImport => {}
+            ImportKind::DynamicImport => {
+              if !self.output_options.inline_dynamic_imports {
+                return;
+              }
+              // something like `Promise.resolve().then(() => foo_exports)`
+              let importee = &self.module_table.normal_modules[importee_id];
+              match importee_linking_info.wrap_kind {
+                WrapKind::None => {
+                  stmt_info.referenced_symbols.push(importee.namespace_symbol);
+                }
+                WrapKind::Cjs => {
+                  // something like `Promise.resolve().then(() => __toESM(require_foo()))`
+                  stmt_info.referenced_symbols.push(importee_linking_info.wrapper_ref.unwrap());
+                  stmt_info.referenced_symbols.push(self.runtime.resolve_symbol("__toESM"));
+                }
+                WrapKind::Esm => {
+                  // something like `Promise.resolve().then(() => (init_foo(), foo_exports))`
+                  stmt_info.referenced_symbols.push(importee_linking_info.wrapper_ref.unwrap());
+                  stmt_info.referenced_symbols.push(importee.namespace_symbol);
+                }
+              }
+            }
           }
         });
       });
@@ -283,7 +413,11 @@ impl<'a> LinkStage<'a> {
   }
 }
 
-pub fn init_entry_point_stmt_info(module: &mut NormalModule, meta: &mut LinkingMetadata) {
+pub fn init_entry_point_stmt_info(
+  module: &mut NormalModule,
+  meta: &mut LinkingMetadata,
+  re_export_ref: Option<SymbolRef>,
+) {
   let mut referenced_symbols = vec![];
   if matches!(module.exports_kind, ExportsKind::CommonJs) {
     // If a commonjs module becomes an entry point while targeting esm, we need to at least add a `export default require_foo();`
@@ -292,7 +426,11 @@ pub fn init_entry_point_stmt_info(module: &mut NormalModule, meta: &mut LinkingM
   }
 
   // Make sure all exports are included
-  referenced_symbols.extend(meta.canonical_exports().map(|(_, export)| export.symbol_ref));
+  if !meta.is_entry_signature_dropped {
+    referenced_symbols.extend(meta.canonical_exports().map(|(_, export)| export.symbol_ref));
+  }
+  // The runtime helper re-exporting external modules
+  referenced_symbols.extend(re_export_ref);
 
   let stmt_info = StmtInfo {
     stmt_idx: None,
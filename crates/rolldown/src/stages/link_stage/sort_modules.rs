@@ -1,4 +1,4 @@
-use rolldown_common::ModuleId;
+use rolldown_common::{ImportKind, ModuleId};
 use rustc_hash::FxHashSet;
 
 use super::LinkStage;
@@ -24,41 +24,61 @@ impl<'a> LinkStage<'a> {
       .shrink_to(self.module_table.normal_modules.len() + self.module_table.external_modules.len());
     let mut sorted_modules = Vec::with_capacity(self.module_table.normal_modules.len());
     let mut next_exec_order = 0;
-    while let Some(action) = stack.pop() {
-      match action {
-        Action::Enter(id) => {
-          if !entered_ids.contains(&id) {
-            entered_ids.insert(id);
-            stack.push(Action::Exit(id));
-            if let ModuleId::Normal(module_id) = id {
-              let module = &self.module_table.normal_modules[module_id];
-              stack.extend(
-                module
-                  .import_records
-                  .iter()
-                  .filter(|rec| rec.kind.is_static())
-                  .map(|rec| rec.resolved_module)
-                  .rev()
-                  .map(Action::Enter),
-              );
+    // Dynamically imported modules inlined into the chunk are executed after the static module graph
+    let mut dynamic_importees = vec![];
+    loop {
+      while let Some(action) = stack.pop() {
+        match action {
+          Action::Enter(id) => {
+            if !entered_ids.contains(&id) {
+              entered_ids.insert(id);
+              stack.push(Action::Exit(id));
+              if let ModuleId::Normal(module_id) = id {
+                let module = &self.module_table.normal_modules[module_id];
+                stack.extend(
+                  module
+                    .import_records
+                    .iter()
+                    .filter(|rec| rec.kind.is_static())
+                    .map(|rec| rec.resolved_module)
+                    .rev()
+                    .map(Action::Enter),
+                );
+                if self.output_options.inline_dynamic_imports {
+                  dynamic_importees.extend(
+                    module
+                      .import_records
+                      .iter()
+                      .filter(|rec| {
+                        matches!(rec.kind, ImportKind::DynamicImport)
+                          && matches!(rec.resolved_module, ModuleId::Normal(_))
+                      })
+                      .map(|rec| rec.resolved_module),
+                  );
+                }
+              }
             }
           }
-        }
-        Action::Exit(id) => {
-          match id {
-            ModuleId::Normal(id) => {
-              let module = &mut self.module_table.normal_modules[id];
-              module.exec_order = next_exec_order;
-              sorted_modules.push(id);
-            }
-            ModuleId::External(id) => {
-              let module = &mut self.module_table.external_modules[id];
-              module.exec_order = next_exec_order;
+          Action::Exit(id) => {
+            match id {
+              ModuleId::Normal(id) => {
+                let module = &mut self.module_table.normal_modules[id];
+                module.exec_order = next_exec_order;
+                sorted_modules.push(id);
+              }
+              ModuleId::External(id) => {
+                let module = &mut self.module_table.external_modules[id];
+                module.exec_order = next_exec_order;
+              }
             }
+            next_exec_order += 1;
           }
-          next_exec_order += 1;
         }
       }
+      if dynamic_importees.is_empty() {
+        break;
+      }
+      stack.extend(dynamic_importees.drain(..).rev().map(Action::Enter));
     }
     self.sorted_modules = sorted_modules;
     debug_assert_eq!(
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use rolldown_common::{ImportKind, OutputFormat};
+use rolldown_error::BuildError;
+
+use crate::error::{BatchedErrors, BatchedResult};
+
+use super::LinkStage;
+
+impl LinkStage<'_> {
+  /// Mark modules that contain top-level await or statically import modules containing it, whose execution has
+  /// to be awaited by their importers. Only esm output could keep top-level await, and modules marked this way
+  /// can't be required, since `require` can't wait for them.
+  pub fn mark_top_level_await_modules(&mut self) -> BatchedResult<()> {
+    let modules = &self.module_table.normal_modules;
+    let mut errors = BatchedErrors::default();
+    if !matches!(self.output_options.format, OutputFormat::Esm) {
+      modules.iter().for_each(|module| {
+        if let Some(span) = module.top_level_await {
+          errors.push(BuildError::invalid_tla_format(
+            module.resource_id.expect_file().as_str(),
+            Arc::clone(&module.source),
+            span,
+            self.output_options.format.file_name_str(),
+          ));
+        }
+      });
+      return if errors.is_empty() { Ok(()) } else { Err(errors) };
+    }
+
+    for module in modules {
+      self.metas[module.id].is_tla_or_contains_tla_dependency = module.top_level_await.is_some();
+    }
+    // Importees are usually executed before importers, so this settles in one pass without circular imports.
+    let mut changed = true;
+    while changed {
+      changed = false;
+      for module_id in &self.sorted_modules {
+        if self.metas[*module_id].is_tla_or_contains_tla_dependency {
+          continue;
+        }
+        let contains_tla = modules[*module_id].import_records.iter().any(|rec| {
+          matches!(rec.kind, ImportKind::Import)
+            && rec
+              .resolved_module
+              .as_normal()
+              .is_some_and(|importee| self.metas[importee].is_tla_or_contains_tla_dependency)
+        });
+        if contains_tla {
+          self.metas[*module_id].is_tla_or_contains_tla_dependency = true;
+          changed = true;
+        }
+      }
+    }
+
+    modules.iter().for_each(|importer| {
+      importer.import_records.iter().for_each(|rec| {
+        let Some(importee) = rec.resolved_module.as_normal() else { return };
+        if matches!(rec.kind, ImportKind::Require)
+          && self.metas[importee].is_tla_or_contains_tla_dependency
+        {
+          errors.push(BuildError::require_tla(
+            importer.resource_id.expect_file().as_str(),
+            modules[importee].resource_id.expect_file().as_str(),
+          ));
+        }
+      });
+    });
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+}
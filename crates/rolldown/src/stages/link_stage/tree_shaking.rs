@@ -1,5 +1,7 @@
 use index_vec::IndexVec;
-use rolldown_common::{NormalModule, NormalModuleId, StmtInfoId, SymbolRef};
+use rolldown_common::{
+  ImportKind, ModuleId, NormalModule, NormalModuleId, StmtInfoId, SymbolRef, WrapKind,
+};
 
 use crate::types::{module_table::NormalModuleVec, symbols::Symbols};
 
@@ -22,7 +24,7 @@ fn include_module(ctx: &mut Context, module: &NormalModule) {
 
   ctx.is_module_included_vec[module.id] = true;
 
-  if ctx.tree_shaking || module.id == ctx.runtime_id {
+  if (ctx.tree_shaking && !module.no_treeshake) || module.id == ctx.runtime_id {
     module.stmt_infos.iter_enumerated().for_each(|(stmt_info_id, stmt_info)| {
       if stmt_info.side_effect {
         include_statement(ctx, module, stmt_info_id);
@@ -38,21 +40,29 @@ fn include_module(ctx: &mut Context, module: &NormalModule) {
   }
 
   module.import_records.iter().for_each(|import_record| match import_record.resolved_module {
-    rolldown_common::ModuleId::Normal(importee_id) => {
+    ModuleId::Normal(importee_id) => {
       let importee = &ctx.modules[importee_id];
+      // Imported modules without side effects are only included if their exports are used.
+      if ctx.tree_shaking
+        && !importee.side_effects
+        && matches!(import_record.kind, ImportKind::Import)
+      {
+        return;
+      }
       include_module(ctx, importee);
     }
-    rolldown_common::ModuleId::External(_) => {}
+    ModuleId::External(_) => {}
   });
 }
 
 fn include_symbol(ctx: &mut Context, symbol_ref: SymbolRef) {
   let mut canonical_ref = ctx.symbols.par_canonical_ref_for(symbol_ref);
-  let canonical_ref_module = &ctx.modules[canonical_ref.owner];
   let canonical_ref_symbol = ctx.symbols.get(canonical_ref);
   if let Some(namespace_alias) = &canonical_ref_symbol.namespace_alias {
-    canonical_ref = namespace_alias.namespace_ref;
+    // The namespace could be a binding of another module, like the synthetic named export of the importee.
+    canonical_ref = ctx.symbols.par_canonical_ref_for(namespace_alias.namespace_ref);
   }
+  let canonical_ref_module = &ctx.modules[canonical_ref.owner];
   include_module(ctx, canonical_ref_module);
   canonical_ref_module
     .stmt_infos
@@ -84,9 +94,56 @@ fn include_statement(ctx: &mut Context, module: &NormalModule, stmt_info_id: Stm
 }
 
 impl LinkStage<'_> {
+  /// Import and re-export statements are no longer side effects if all their importees are side-effect free, so
+  /// they are only included if the bindings they import are used.
+  ///
+  /// Wrapped importees are excluded, because calling their wrappers is the side effect of the statements, and so
+  /// are importees with dynamic exports, which are re-exported at runtime.
+  fn remove_side_effects_of_side_effect_free_imports(&mut self) {
+    let module_table = &self.module_table;
+    let metas = &self.metas;
+    let side_effect_free_stmts = module_table
+      .normal_modules
+      .iter()
+      .flat_map(|module| {
+        module
+          .stmt_infos
+          .iter_enumerated()
+          .filter(|(_, stmt_info)| stmt_info.side_effect && !stmt_info.import_records.is_empty())
+          .filter(|(_, stmt_info)| {
+            stmt_info.import_records.iter().all(|rec_id| {
+              let rec = &module.import_records[*rec_id];
+              matches!(rec.kind, ImportKind::Import)
+                && match rec.resolved_module {
+                  ModuleId::Normal(importee_id) => {
+                    !module_table.normal_modules[importee_id].side_effects
+                      && matches!(metas[importee_id].wrap_kind, WrapKind::None)
+                      && !metas[importee_id].has_dynamic_exports
+                  }
+                  ModuleId::External(importee_id) => {
+                    !module_table.external_modules[importee_id].side_effects
+                  }
+                }
+            })
+          })
+          .map(|(stmt_info_id, _)| (module.id, stmt_info_id))
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    side_effect_free_stmts.into_iter().for_each(|(module_id, stmt_info_id)| {
+      self.module_table.normal_modules[module_id].stmt_infos.get_mut(stmt_info_id).side_effect =
+        false;
+    });
+  }
+
   pub fn include_statements(&mut self) {
     use rayon::prelude::*;
 
+    if self.input_options.treeshake.is_some() {
+      self.remove_side_effects_of_side_effect_free_imports();
+    }
+
     let mut is_included_vec: IndexVec<NormalModuleId, IndexVec<StmtInfoId, bool>> = self
       .module_table
       .normal_modules
@@ -102,7 +159,7 @@ impl LinkStage<'_> {
       symbols: &self.symbols,
       is_included_vec: &mut is_included_vec,
       is_module_included_vec: &mut is_module_included_vec,
-      tree_shaking: self.input_options.treeshake,
+      tree_shaking: self.input_options.treeshake.is_some(),
       runtime_id: self.runtime.id(),
     };
 
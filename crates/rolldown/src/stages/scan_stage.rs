@@ -1,23 +1,29 @@
 use std::sync::Arc;
 
 use index_vec::IndexVec;
-use rolldown_common::{EntryPoint, ImportKind, NormalModuleId};
+use rolldown_common::{EntryPoint, ImportAttributes, ImportKind, InputItem, NormalModuleId};
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
 use rolldown_oxc_utils::OxcProgram;
 use rolldown_plugin::HookResolveIdArgsOptions;
 use rolldown_utils::block_on_spawn_all;
+use rustc_hash::FxHashMap;
 
 use crate::{
   error::{BatchedResult, IntoBatchedResult},
-  module_loader::{module_loader::ModuleLoaderOutput, ModuleLoader},
+  module_loader::{
+    module_cache::SharedModuleCache, module_loader::ModuleLoaderOutput, ModuleLoader,
+  },
   options::input_options::SharedInputOptions,
   plugin_driver::SharedPluginDriver,
   runtime::RuntimeModuleBrief,
   types::{
     module_table::ModuleTable, resolved_request_info::ResolvedRequestInfo, symbols::Symbols,
   },
-  utils::resolve_id::resolve_id,
+  utils::{
+    define::validate_define, file_snapshot::SharedFileSnapshots, inject::validate_inject,
+    resolve_id::resolve_id_with_external,
+  },
   SharedResolver,
 };
 
@@ -26,6 +32,8 @@ pub struct ScanStage<Fs: FileSystem + Default> {
   plugin_driver: SharedPluginDriver,
   fs: Fs,
   resolver: SharedResolver<Fs>,
+  module_cache: SharedModuleCache,
+  file_snapshots: SharedFileSnapshots,
 }
 
 #[derive(Debug)]
@@ -44,20 +52,28 @@ impl<Fs: FileSystem + Default + 'static> ScanStage<Fs> {
     plugin_driver: SharedPluginDriver,
     fs: Fs,
     resolver: SharedResolver<Fs>,
+    module_cache: SharedModuleCache,
+    file_snapshots: SharedFileSnapshots,
   ) -> Self {
-    Self { input_options, plugin_driver, fs, resolver }
+    Self { input_options, plugin_driver, fs, resolver, module_cache, file_snapshots }
   }
 
   #[tracing::instrument(skip_all)]
   pub async fn scan(&self) -> BatchedResult<ScanStageOutput> {
     tracing::info!("Start scan stage");
     assert!(!self.input_options.input.is_empty(), "You must supply options.input to rolldown");
+    validate_define(&self.input_options.define)?;
+    validate_inject(&self.input_options.inject)?;
+
+    self.plugin_driver.build_start(&self.input_options).await?;
 
     let mut module_loader = ModuleLoader::new(
       Arc::clone(&self.input_options),
       Arc::clone(&self.plugin_driver),
       self.fs.share(),
       Arc::clone(&self.resolver),
+      Arc::clone(&self.module_cache),
+      Arc::clone(&self.file_snapshots),
     );
 
     module_loader.try_spawn_runtime_module_task();
@@ -77,19 +93,34 @@ impl<Fs: FileSystem + Default + 'static> ScanStage<Fs> {
   fn resolve_user_defined_entries(
     &self,
   ) -> BatchedResult<Vec<(Option<String>, ResolvedRequestInfo)>> {
+    let input_options = &self.input_options;
     let resolver = &self.resolver;
     let plugin_driver = &self.plugin_driver;
 
+    // Names are used as `[name]` of entry chunks, so entries with the same name would overwrite each other
+    let mut imports_by_name = FxHashMap::default();
+    for InputItem { name, import } in &input_options.input {
+      let Some(name) = name else { continue };
+      if let Some(first_import) = imports_by_name.insert(name.as_str(), import.as_str()) {
+        return Err(BuildError::duplicate_entry_name(name, first_import, import).into());
+      }
+    }
+
     let resolved_ids =
       block_on_spawn_all(self.input_options.input.iter().map(|input_item| async move {
         let specifier = &input_item.import;
-        match resolve_id(
+        match resolve_id_with_external(
+          input_options,
           resolver,
           plugin_driver,
           specifier,
           None,
-          HookResolveIdArgsOptions { is_entry: true, kind: ImportKind::Import },
-          false,
+          HookResolveIdArgsOptions {
+            is_entry: true,
+            kind: ImportKind::Import,
+            attributes: ImportAttributes::default(),
+          },
+          &[],
         )
         .await
         {
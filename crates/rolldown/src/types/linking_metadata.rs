@@ -1,5 +1,7 @@
 use index_vec::IndexVec;
-use rolldown_common::{NormalModuleId, ResolvedExport, StmtInfoId, SymbolRef, WrapKind};
+use rolldown_common::{
+  ExternalModuleId, NormalModuleId, ResolvedExport, StmtInfoId, SymbolRef, WrapKind,
+};
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
 
@@ -43,6 +45,19 @@ pub struct LinkingMetadata {
   // The unknown export name will be resolved at runtime.
   // esbuild add it to `ExportKind`, but the linker shouldn't mutate the module.
   pub has_dynamic_exports: bool,
+  /// With `shim_missing_exports`, named imports missing in this module are bound to this binding, which is
+  /// declared as `var _missingExportShim = void 0`.
+  pub missing_export_shim_ref: Option<SymbolRef>,
+  pub missing_export_shim_stmt_info: Option<StmtInfoId>,
+  /// Whether the module contains top-level await or statically imports a module containing it. If the module is
+  /// wrapped, its wrapper is an async function, and importers await it.
+  pub is_tla_or_contains_tla_dependency: bool,
+  /// With `preserve_entry_signatures: false`, exports of the entry module are tree shaken like other bindings, and
+  /// its entry chunk only exports bindings imported by other chunks.
+  pub is_entry_signature_dropped: bool,
+  /// External modules re-exported by `export * from` of the entry module, directly or through unwrapped ES modules
+  /// it re-exports all from, in the order they appear. The entry chunk re-exports them too.
+  pub star_exports_from_external_modules: Vec<ExternalModuleId>,
 }
 
 impl LinkingMetadata {
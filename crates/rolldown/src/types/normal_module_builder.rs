@@ -27,8 +27,16 @@ pub struct NormalModuleBuilder {
   pub exports_kind: Option<ExportsKind>,
   pub module_type: ModuleType,
   pub is_user_defined_entry: Option<bool>,
+  pub side_effects: Option<bool>,
   pub pretty_path: Option<String>,
-  pub sourcemap_chain: Vec<rolldown_sourcemap::SourceMap>,
+  pub sourcemap_chain: rolldown_sourcemap::SourceMapChain,
+  pub hashbang: Option<String>,
+  pub css: Option<String>,
+  pub asset_urls: FxHashMap<Span, String>,
+  pub file_url_references: Vec<String>,
+  pub top_level_await: Option<Span>,
+  pub no_treeshake: bool,
+  pub synthetic_named_exports: Option<Rstr>,
 }
 
 impl NormalModuleBuilder {
@@ -51,8 +59,16 @@ impl NormalModuleBuilder {
       exports_kind: self.exports_kind.unwrap_or(ExportsKind::Esm),
       module_type: self.module_type,
       is_user_defined_entry: self.is_user_defined_entry.unwrap(),
+      side_effects: self.side_effects.unwrap(),
       pretty_path: self.pretty_path.unwrap(),
       sourcemap_chain: self.sourcemap_chain,
+      hashbang: self.hashbang,
+      css: self.css,
+      asset_urls: self.asset_urls,
+      file_url_references: self.file_url_references,
+      top_level_await: self.top_level_await,
+      no_treeshake: self.no_treeshake,
+      synthetic_named_exports: self.synthetic_named_exports,
       is_included: false,
     }
   }
@@ -5,4 +5,9 @@ pub struct ResolvedRequestInfo {
   pub path: ResolvedPath,
   pub module_type: ModuleType,
   pub is_external: bool,
+  /// Side effects of the module given by the `resolve_id` hook of plugins. It's decided according to
+  /// `treeshake.module_side_effects` after resolving, if plugins leave it to the bundler.
+  pub module_side_effects: Option<bool>,
+  /// The `sideEffects` field of the nearest `package.json` of the module, only known by the internal resolver.
+  pub package_json_side_effects: Option<bool>,
 }
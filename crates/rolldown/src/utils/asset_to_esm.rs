@@ -0,0 +1,26 @@
+use std::path::Path;
+
+use rolldown_common::AssetOptions;
+use rolldown_plugin::{EmittedAsset, FileEmitter};
+
+/// Emit the content of an asset module as an asset, and turn the module into an ES module whose default export is
+/// the URL of the asset. The file name is a placeholder until output options are known, which is replaced in
+/// rendered chunks. Assets with the same content share the same file.
+pub fn asset_to_esm(
+  file_emitter: &FileEmitter,
+  path: &str,
+  content: Vec<u8>,
+  options: &AssetOptions,
+) -> String {
+  let url = format!("{}{}", options.public_path, emit_asset(file_emitter, path, content));
+  // JSON strings are valid JavaScript strings
+  format!("export default {};\n", serde_json::Value::String(url))
+}
+
+/// Emit the file at `path` as an asset named after it, returning its file name or a placeholder of it.
+pub fn emit_asset(file_emitter: &FileEmitter, path: &str, content: Vec<u8>) -> String {
+  let name = Path::new(path).file_name().map(|name| name.to_string_lossy().into_owned());
+  let reference_id =
+    file_emitter.emit_file(EmittedAsset { name, file_name: None, source: content.into() });
+  file_emitter.get_file_name(&reference_id).expect("The asset should be emitted just now")
+}
@@ -0,0 +1,104 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rolldown_common::ModuleType;
+use rolldown_error::BuildError;
+
+static DATA_URL_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^\s*data:").expect("Init DATA_URL_REGEX failed"));
+
+pub fn is_data_url(specifier: &str) -> bool {
+  DATA_URL_REGEX.is_match(specifier)
+}
+
+/// A module imported by a data URL, like `data:text/javascript,export default 42`. The URL itself is the id of
+/// the module, so the same URL is always the same module.
+#[derive(Debug)]
+pub struct DataUrlModule {
+  pub module_type: ModuleType,
+  pub content: String,
+}
+
+/// Parse `data:[<mime type>][;<parameter>][;base64],<data>`. Only JavaScript, JSON and CSS modules are supported.
+pub fn parse_data_url(url: &str) -> Result<DataUrlModule, BuildError> {
+  let Some((header, data)) =
+    url.trim_start().strip_prefix("data:").and_then(|url| url.split_once(','))
+  else {
+    return Err(BuildError::invalid_data_url(url, "missing the comma before the data"));
+  };
+  let mut params = header.split(';');
+  let mime_type = params.next().unwrap_or_default().trim().to_ascii_lowercase();
+  let module_type = match mime_type.as_str() {
+    "text/javascript" | "application/javascript" => ModuleType::EsmMjs,
+    "application/json" => ModuleType::Json,
+    "text/css" => ModuleType::Css,
+    // The default MIME type is `text/plain`
+    "" => return Err(BuildError::invalid_data_url(url, "unsupported MIME type text/plain")),
+    mime_type => {
+      return Err(BuildError::invalid_data_url(url, format!("unsupported MIME type {mime_type}")))
+    }
+  };
+  let is_base64 = params.any(|param| param.trim().eq_ignore_ascii_case("base64"));
+
+  let bytes = if is_base64 {
+    base64_simd::Base64::STANDARD
+      .decode_to_boxed_bytes(data.as_bytes())
+      .map_err(|_| BuildError::invalid_data_url(url, "invalid base64 data"))?
+      .into_vec()
+  } else {
+    percent_decode(data)
+  };
+  let content = String::from_utf8(bytes)
+    .map_err(|_| BuildError::invalid_data_url(url, "the data is not valid UTF-8"))?;
+  Ok(DataUrlModule { module_type, content })
+}
+
+/// Decode `%XX` escapes. Invalid escapes are kept as they are.
+fn percent_decode(data: &str) -> Vec<u8> {
+  let bytes = data.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    let escaped = (bytes[i] == b'%')
+      .then(|| bytes.get(i + 1..i + 3))
+      .flatten()
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    if let Some(byte) = escaped {
+      decoded.push(byte);
+      i += 3;
+    } else {
+      decoded.push(bytes[i]);
+      i += 1;
+    }
+  }
+  decoded
+}
+
+#[cfg(test)]
+mod tests {
+  use rolldown_common::ModuleType;
+
+  use super::parse_data_url;
+
+  #[test]
+  fn parse_data_urls() {
+    let module = parse_data_url("data:text/javascript,export%20default%2042").unwrap();
+    assert!(matches!(module.module_type, ModuleType::EsmMjs));
+    assert_eq!(module.content, "export default 42");
+
+    let module = parse_data_url("data:application/json;charset=utf-8;base64,eyJhIjoxfQ==").unwrap();
+    assert!(matches!(module.module_type, ModuleType::Json));
+    assert_eq!(module.content, r#"{"a":1}"#);
+
+    let module = parse_data_url("data:text/css,a%7Bcolor:red%7D").unwrap();
+    assert!(matches!(module.module_type, ModuleType::Css));
+    assert_eq!(module.content, "a{color:red}");
+
+    let err = parse_data_url("data:text/html,<p></p>").unwrap_err();
+    assert_eq!(err.code(), "INVALID_DATA_URL");
+    assert_eq!(
+      err.to_string(),
+      "Could not load \"data:text/html,<p></p>\": unsupported MIME type text/html."
+    );
+  }
+}
@@ -0,0 +1,334 @@
+use oxc::{
+  allocator::Allocator,
+  ast::{
+    ast::{
+      Declaration, Expression, IfStatement, MemberExpression, ObjectProperty, Statement,
+      VariableDeclarationKind,
+    },
+    VisitMut,
+  },
+  parser::Parser,
+  span::SourceType,
+  syntax::{
+    identifier::is_identifier_name,
+    operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+  },
+};
+use rolldown_common::AstScope;
+use rolldown_error::BuildError;
+use rolldown_oxc_utils::{Dummy, TakeIn};
+use rustc_hash::FxHashMap;
+
+/// A parsed `define` entry, like `process.env.NODE_ENV` => `"production"`.
+struct DefineEntry<'a> {
+  /// Segments of the replaced identifier or member expression.
+  parts: Vec<&'a str>,
+  value: &'a str,
+}
+
+/// Check `define` ahead of replacing, so a bad entry fails the build once instead of being skipped silently.
+pub fn validate_define(define: &FxHashMap<String, String>) -> Result<(), BuildError> {
+  for (key, value) in define {
+    if !key.split('.').all(is_identifier_name) {
+      Err(BuildError::invalid_option(
+        "define",
+        format!("\"{key}\" is neither an identifier nor a member expression"),
+      ))?;
+    }
+    let allocator = Allocator::default();
+    if parse_expression(&allocator, value).is_none() {
+      Err(BuildError::invalid_option(
+        "define",
+        format!("the value of \"{key}\" is not a valid expression: {value}"),
+      ))?;
+    }
+  }
+  Ok(())
+}
+
+pub(crate) fn parse_expression<'a>(alloc: &'a Allocator, value: &str) -> Option<Expression<'a>> {
+  // Wrapped with parentheses, so objects aren't parsed as blocks.
+  let source = alloc.alloc_str(&format!("({value})"));
+  let ret = Parser::new(alloc, source, SourceType::default()).parse();
+  if !ret.errors.is_empty() {
+    return None;
+  }
+  let mut body = ret.program.body.into_iter();
+  let (Some(Statement::ExpressionStatement(stmt)), None) = (body.next(), body.next()) else {
+    return None;
+  };
+  let Expression::ParenthesizedExpression(paren) = stmt.unbox().expression else {
+    return None;
+  };
+  // The codegen adds parentheses back where they're needed by precedence.
+  Some(paren.unbox().expression)
+}
+
+/// Replaces global identifiers and member expressions with `define`, then removes `if` branches and conditional
+/// expressions whose tests become constants, like `if ("production" !== "production")`.
+///
+/// It's done before the AST gets scanned, so imports only used by removed branches are never considered live.
+pub struct DefineReplacer<'ast, 'a> {
+  alloc: &'ast Allocator,
+  scope: &'a AstScope,
+  entries: Vec<DefineEntry<'a>>,
+}
+
+impl<'ast, 'a> DefineReplacer<'ast, 'a> {
+  pub fn new(
+    alloc: &'ast Allocator,
+    scope: &'a AstScope,
+    define: &'a FxHashMap<String, String>,
+  ) -> Self {
+    let mut entries = define
+      .iter()
+      .map(|(key, value)| DefineEntry { parts: key.split('.').collect(), value })
+      .collect::<Vec<_>>();
+    // More specific entries win, and the order of replacement is stable.
+    entries.sort_by(|a, b| b.parts.len().cmp(&a.parts.len()).then_with(|| a.parts.cmp(&b.parts)));
+    Self { alloc, scope, entries }
+  }
+
+  fn matches(&self, expr: &Expression, parts: &[&str]) -> bool {
+    match (expr, parts) {
+      // Local bindings that shadow the global are left alone.
+      (Expression::Identifier(ident), [name]) => {
+        ident.name.as_str() == *name
+          && ident.reference_id.get().is_some_and(|id| self.scope.is_unresolved(id))
+      }
+      (Expression::MemberExpression(member_expr), [object_parts @ .., prop]) => {
+        let (object, prop_name) = match &**member_expr {
+          MemberExpression::StaticMemberExpression(member_expr) => {
+            (&member_expr.object, member_expr.property.name.as_str())
+          }
+          // `process.env["NODE_ENV"]`
+          MemberExpression::ComputedMemberExpression(member_expr) => {
+            match &member_expr.expression {
+              Expression::StringLiteral(lit) => (&member_expr.object, lit.value.as_str()),
+              _ => return false,
+            }
+          }
+          MemberExpression::PrivateFieldExpression(_) => return false,
+        };
+        prop_name == *prop && !object_parts.is_empty() && self.matches(object, object_parts)
+      }
+      _ => false,
+    }
+  }
+
+  fn replacement_of(&self, expr: &Expression) -> Option<Expression<'ast>> {
+    let entry = self.entries.iter().find(|entry| self.matches(expr, &entry.parts))?;
+    parse_expression(self.alloc, entry.value)
+  }
+}
+
+/// Fold conditional expressions and logical expressions with constant tests, like `__DEV__ && check()`.
+pub(crate) fn fold_branches<'ast>(
+  alloc: &'ast Allocator,
+  expr: &mut Expression<'ast>,
+) -> Option<Expression<'ast>> {
+  match expr {
+    Expression::ConditionalExpression(cond_expr) => {
+      Some(if ConstValue::evaluate(&cond_expr.test)?.is_truthy() {
+        cond_expr.consequent.take_in(alloc)
+      } else {
+        cond_expr.alternate.take_in(alloc)
+      })
+    }
+    Expression::LogicalExpression(logical) => {
+      Some(if ConstValue::evaluate(&logical.left)?.short_circuits(logical.operator) {
+        logical.left.take_in(alloc)
+      } else {
+        logical.right.take_in(alloc)
+      })
+    }
+    _ => None,
+  }
+}
+
+/// Fold `if` statements with constant tests, leaving the branch that's taken.
+pub(crate) fn fold_if_statement<'ast>(
+  alloc: &'ast Allocator,
+  if_stmt: &mut IfStatement<'ast>,
+) -> Option<Statement<'ast>> {
+  let test = ConstValue::evaluate(&if_stmt.test)?.is_truthy();
+  let dead = if test { if_stmt.alternate.as_ref() } else { Some(&if_stmt.consequent) };
+  // `var` declarations are hoisted out of the branch, so removing them would change the meaning.
+  if dead.is_some_and(declares_var) {
+    return None;
+  }
+  Some(if test {
+    if_stmt.consequent.take_in(alloc)
+  } else {
+    if_stmt.alternate.take().unwrap_or_else(|| Dummy::dummy(alloc))
+  })
+}
+
+fn declares_var(stmt: &Statement) -> bool {
+  match stmt {
+    Statement::Declaration(Declaration::VariableDeclaration(decl)) => {
+      decl.kind == VariableDeclarationKind::Var
+    }
+    Statement::BlockStatement(block) => block.body.iter().any(declares_var),
+    Statement::IfStatement(if_stmt) => {
+      declares_var(&if_stmt.consequent) || if_stmt.alternate.as_ref().is_some_and(declares_var)
+    }
+    _ => false,
+  }
+}
+
+/// Values of expressions that are known at compile time.
+pub(crate) enum ConstValue<'a> {
+  Boolean(bool),
+  Number(f64),
+  String(&'a str),
+  Null,
+  Undefined,
+}
+
+impl<'a> ConstValue<'a> {
+  pub(crate) fn evaluate(expr: &'a Expression) -> Option<Self> {
+    match expr {
+      Expression::BooleanLiteral(lit) => Some(Self::Boolean(lit.value)),
+      Expression::NumericLiteral(lit) => Some(Self::Number(lit.value)),
+      Expression::StringLiteral(lit) => Some(Self::String(lit.value.as_str())),
+      Expression::NullLiteral(_) => Some(Self::Null),
+      Expression::ParenthesizedExpression(paren) => Self::evaluate(&paren.expression),
+      Expression::UnaryExpression(unary) => match unary.operator {
+        UnaryOperator::LogicalNot => {
+          Some(Self::Boolean(!Self::evaluate(&unary.argument)?.is_truthy()))
+        }
+        UnaryOperator::Void => Self::evaluate(&unary.argument).map(|_| Self::Undefined),
+        _ => None,
+      },
+      Expression::LogicalExpression(logical) => {
+        let left = Self::evaluate(&logical.left)?;
+        if left.short_circuits(logical.operator) {
+          Some(left)
+        } else {
+          Self::evaluate(&logical.right)
+        }
+      }
+      Expression::BinaryExpression(binary) => {
+        let left = Self::evaluate(&binary.left)?;
+        let right = Self::evaluate(&binary.right)?;
+        if let (Self::Number(a), Self::Number(b)) = (&left, &right) {
+          let value = match binary.operator {
+            BinaryOperator::Addition => Some(a + b),
+            BinaryOperator::Subtraction => Some(a - b),
+            BinaryOperator::Multiplication => Some(a * b),
+            BinaryOperator::Division => Some(a / b),
+            BinaryOperator::Remainder => Some(a % b),
+            BinaryOperator::Exponential if b.is_finite() => Some(a.powf(*b)),
+            BinaryOperator::LessThan => return Some(Self::Boolean(a < b)),
+            BinaryOperator::LessEqualThan => return Some(Self::Boolean(a <= b)),
+            BinaryOperator::GreaterThan => return Some(Self::Boolean(a > b)),
+            BinaryOperator::GreaterEqualThan => return Some(Self::Boolean(a >= b)),
+            _ => None,
+          };
+          if let Some(value) = value {
+            return Some(Self::Number(value));
+          }
+        }
+        let equal = match binary.operator {
+          BinaryOperator::StrictEquality | BinaryOperator::StrictInequality => {
+            left.strict_equals(&right)
+          }
+          BinaryOperator::Equality | BinaryOperator::Inequality => left.loose_equals(&right)?,
+          _ => return None,
+        };
+        let negated =
+          matches!(binary.operator, BinaryOperator::StrictInequality | BinaryOperator::Inequality);
+        Some(Self::Boolean(equal != negated))
+      }
+      _ => None,
+    }
+  }
+
+  /// Whether a logical expression with this value on the left evaluates to it without evaluating the right.
+  fn short_circuits(&self, operator: LogicalOperator) -> bool {
+    match operator {
+      LogicalOperator::And => !self.is_truthy(),
+      LogicalOperator::Or => self.is_truthy(),
+      LogicalOperator::Coalesce => !matches!(self, Self::Null | Self::Undefined),
+    }
+  }
+
+  fn is_truthy(&self) -> bool {
+    match self {
+      Self::Boolean(value) => *value,
+      Self::Number(value) => *value != 0.0 && !value.is_nan(),
+      Self::String(value) => !value.is_empty(),
+      Self::Null | Self::Undefined => false,
+    }
+  }
+
+  #[allow(clippy::float_cmp)]
+  fn strict_equals(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::Boolean(a), Self::Boolean(b)) => a == b,
+      (Self::Number(a), Self::Number(b)) => a == b,
+      (Self::String(a), Self::String(b)) => a == b,
+      (Self::Null, Self::Null) | (Self::Undefined, Self::Undefined) => true,
+      _ => false,
+    }
+  }
+
+  /// `None` if it needs type coercion, which isn't evaluated.
+  fn loose_equals(&self, other: &Self) -> Option<bool> {
+    match (self, other) {
+      (Self::Null | Self::Undefined, Self::Null | Self::Undefined) => Some(true),
+      (Self::Null | Self::Undefined, _) | (_, Self::Null | Self::Undefined) => Some(false),
+      (Self::Boolean(_), Self::Boolean(_))
+      | (Self::Number(_), Self::Number(_))
+      | (Self::String(_), Self::String(_)) => Some(self.strict_equals(other)),
+      _ => None,
+    }
+  }
+}
+
+impl<'ast, 'a> VisitMut<'ast> for DefineReplacer<'ast, 'a> {
+  fn visit_expression(&mut self, expr: &mut Expression<'ast>) {
+    if let Some(replacement) = self.replacement_of(expr) {
+      *expr = replacement;
+      return;
+    }
+    self.visit_expression_match(expr);
+    if let Some(folded) = fold_branches(self.alloc, expr) {
+      *expr = folded;
+    }
+  }
+
+  fn visit_statement(&mut self, stmt: &mut Statement<'ast>) {
+    self.visit_statement_match(stmt);
+    if let Statement::IfStatement(if_stmt) = stmt {
+      if let Some(folded) = fold_if_statement(self.alloc, if_stmt) {
+        *stmt = folded;
+      }
+    }
+  }
+
+  fn visit_object_property(&mut self, prop: &mut ObjectProperty<'ast>) {
+    self.visit_property_key(&mut prop.key);
+    self.visit_expression(&mut prop.value);
+    if let Some(init) = &mut prop.init {
+      self.visit_expression(init);
+    }
+    // `{ DEBUG }` becomes `{ DEBUG: true }`.
+    if prop.shorthand && !matches!(prop.value, Expression::Identifier(_)) {
+      prop.shorthand = false;
+    }
+  }
+}
+
+#[test]
+fn test_validate_define() {
+  let define =
+    |key: &str, value: &str| [(key.to_string(), value.to_string())].into_iter().collect();
+  assert!(validate_define(&define("process.env.NODE_ENV", "\"production\"")).is_ok());
+  assert!(validate_define(&define("__DEV__", "{ \"a\": [1, 2] }")).is_ok());
+  assert!(validate_define(&define("process.env.", "1")).is_err());
+  assert!(validate_define(&define("a-b", "1")).is_err());
+  assert!(validate_define(&define("DEBUG", "1 +")).is_err());
+  assert!(validate_define(&define("DEBUG", "1; 2")).is_err());
+}
@@ -0,0 +1,61 @@
+use std::{
+  path::Path,
+  sync::{Arc, Mutex, MutexGuard},
+};
+
+use rolldown_fs::FileSystem;
+use rolldown_utils::hash::HashAlgorithm;
+use rustc_hash::FxHashMap;
+
+/// A hash of the content of a file, or `None` if it doesn't exist or can't be read.
+pub type FileSnapshot = Option<String>;
+
+pub fn snapshot<T: FileSystem + ?Sized>(fs: &T, file: &str) -> FileSnapshot {
+  // Files are read as bytes, since assets, like images, are modules too.
+  fs.read(Path::new(file)).ok().map(|content| hash_content(&content))
+}
+
+/// The snapshot of a file with `content`, without reading it again.
+pub fn hash_content(content: &[u8]) -> String {
+  HashAlgorithm::default().hash_hex(content)
+}
+
+/// Snapshots of the files a build depends on, taken when they are loaded rather than after the build, so changes
+/// made while building aren't missed in watch mode.
+#[derive(Debug, Default)]
+pub struct FileSnapshots {
+  files: Mutex<FxHashMap<String, FileSnapshot>>,
+}
+
+impl FileSnapshots {
+  fn files(&self) -> MutexGuard<'_, FxHashMap<String, FileSnapshot>> {
+    self.files.lock().expect("FileSnapshots lock should not be poisoned")
+  }
+
+  /// Only the first snapshot of a file is kept, which is the earliest content the build saw.
+  pub fn record(&self, file: &str, snapshot: FileSnapshot) {
+    self.files().entry(file.to_string()).or_insert(snapshot);
+  }
+
+  pub fn contains(&self, file: &str) -> bool {
+    self.files().contains_key(file)
+  }
+
+  /// Snapshots sorted by file paths.
+  pub fn snapshots(&self) -> Vec<(String, FileSnapshot)> {
+    let mut snapshots = self
+      .files()
+      .iter()
+      .map(|(file, snapshot)| (file.clone(), snapshot.clone()))
+      .collect::<Vec<_>>();
+    snapshots.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    snapshots
+  }
+
+  /// Forget all files, since every build loads its own files.
+  pub fn clear(&self) {
+    self.files().clear();
+  }
+}
+
+pub type SharedFileSnapshots = Arc<FileSnapshots>;
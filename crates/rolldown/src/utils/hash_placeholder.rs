@@ -0,0 +1,113 @@
+use rustc_hash::FxHashMap;
+
+// Hashes of chunks depend on their final content, which contains the file names of the chunks they import. So
+// file names are rendered with placeholders first, and the placeholders are replaced once all chunks are rendered.
+
+const PREFIX: &str = "!~[";
+const SUFFIX: &str = "]~";
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// The shortest hash length that placeholders could fit in.
+pub const MIN_HASH_LENGTH: usize = PREFIX.len() + SUFFIX.len() + 1;
+
+/// Generates unique placeholders of the same length as the hashes, so replacing them doesn't shift source map
+/// columns. Placeholders only get longer than hashes if there are too many of them to fit.
+#[derive(Debug, Default)]
+pub struct HashPlaceholderGenerator {
+  next_index: usize,
+}
+
+impl HashPlaceholderGenerator {
+  pub fn generate(&mut self, hash_length: usize) -> String {
+    let mut index = self.next_index;
+    self.next_index += 1;
+    let mut digits = vec![];
+    loop {
+      digits.push(DIGITS[index % DIGITS.len()] as char);
+      index /= DIGITS.len();
+      if index == 0 {
+        break;
+      }
+    }
+    let width = hash_length.saturating_sub(PREFIX.len() + SUFFIX.len());
+    let digits = digits.into_iter().rev().collect::<String>();
+    format!("{PREFIX}{digits:0>width$}{SUFFIX}")
+  }
+}
+
+/// Find the next placeholder in `code`, returning its start and end.
+fn next_placeholder(code: &str) -> Option<(usize, usize)> {
+  let mut offset = 0;
+  while let Some(start) = code[offset..].find(PREFIX).map(|start| start + offset) {
+    let digits_start = start + PREFIX.len();
+    let digits_len = code[digits_start..].bytes().take_while(u8::is_ascii_alphanumeric).count();
+    if digits_len > 0 && code[digits_start + digits_len..].starts_with(SUFFIX) {
+      return Some((start, digits_start + digits_len + SUFFIX.len()));
+    }
+    offset = digits_start;
+  }
+  None
+}
+
+/// Placeholders in `code`, in the order they appear.
+pub fn find_hash_placeholders(code: &str) -> Vec<&str> {
+  let mut placeholders = vec![];
+  let mut rest = code;
+  while let Some((start, end)) = next_placeholder(rest) {
+    placeholders.push(&rest[start..end]);
+    rest = &rest[end..];
+  }
+  placeholders
+}
+
+/// Replace placeholders in `code` with their hashes. Unknown placeholders are kept as is.
+pub fn replace_hash_placeholders(code: &str, hashes: &FxHashMap<String, String>) -> String {
+  let mut result = String::with_capacity(code.len());
+  let mut rest = code;
+  while let Some((start, end)) = next_placeholder(rest) {
+    let placeholder = &rest[start..end];
+    result.push_str(&rest[..start]);
+    result.push_str(hashes.get(placeholder).map_or(placeholder, String::as_str));
+    rest = &rest[end..];
+  }
+  result.push_str(rest);
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use rustc_hash::FxHashMap;
+
+  use super::{
+    find_hash_placeholders, replace_hash_placeholders, HashPlaceholderGenerator, MIN_HASH_LENGTH,
+  };
+
+  #[test]
+  fn placeholders_have_the_length_of_hashes() {
+    let mut generator = HashPlaceholderGenerator::default();
+    let placeholders = (0..100).map(|_| generator.generate(8)).collect::<Vec<_>>();
+    assert!(placeholders.iter().all(|placeholder| placeholder.len() == 8));
+    assert_eq!(placeholders[0], "!~[000]~");
+    assert_ne!(placeholders[1], placeholders[99]);
+    assert_eq!(generator.generate(12), "!~[000001C]~");
+
+    let mut generator = HashPlaceholderGenerator::default();
+    let placeholders = (0..63).map(|_| generator.generate(MIN_HASH_LENGTH)).collect::<Vec<_>>();
+    assert_eq!(placeholders[61], "!~[Z]~");
+    assert_eq!(placeholders[62], "!~[10]~");
+  }
+
+  #[test]
+  fn placeholders_are_replaced() {
+    let mut generator = HashPlaceholderGenerator::default();
+    let (a, b) = (generator.generate(8), generator.generate(8));
+    let code = format!("import './a-{a}.js';\nimport './b-{b}.js';\nconst s = '!~[x';");
+    assert_eq!(find_hash_placeholders(&code), vec![a.as_str(), b.as_str()]);
+
+    let hashes = FxHashMap::from_iter([(a, "12345678".to_string())]);
+    assert_eq!(
+      replace_hash_placeholders(&code, &hashes),
+      format!("import './a-12345678.js';\nimport './b-{b}.js';\nconst s = '!~[x';")
+    );
+  }
+}
@@ -0,0 +1,86 @@
+use std::fmt::Write;
+
+use oxc::{
+  allocator::Allocator,
+  ast::ast::{ModuleDeclaration, Program, Statement},
+  parser::Parser,
+  span::{SourceType, Span},
+  syntax::identifier::is_identifier_name,
+};
+use rolldown_error::BuildError;
+use rolldown_oxc_utils::TakeIn;
+use rustc_hash::FxHashMap;
+
+/// Check `inject` ahead of injecting, so the generated imports always parse.
+pub fn validate_inject(inject: &FxHashMap<String, (String, String)>) -> Result<(), BuildError> {
+  for (key, (_, exported)) in inject {
+    if !is_identifier_name(key) {
+      Err(BuildError::invalid_option("inject", format!("\"{key}\" is not an identifier")))?;
+    }
+    if !(exported == "*" || is_identifier_name(exported)) {
+      Err(BuildError::invalid_option(
+        "inject",
+        format!("the imported name of \"{key}\" is not an identifier: {exported}"),
+      ))?;
+    }
+  }
+  Ok(())
+}
+
+/// Prepends imports for the keys of `inject` that are referenced as globals, like
+/// `import { Promise as Promise } from 'es6-promise'`.
+///
+/// It's done before the AST gets scanned, so the injected imports are linked like the ones written by users.
+pub fn inject_imports<'ast>(
+  alloc: &'ast Allocator,
+  program: &mut Program<'ast>,
+  inject: &FxHashMap<String, (String, String)>,
+  is_global: impl Fn(&str) -> bool,
+) {
+  let mut names = inject.keys().filter(|name| is_global(name)).collect::<Vec<_>>();
+  if names.is_empty() {
+    return;
+  }
+  names.sort_unstable();
+
+  let mut source = String::new();
+  for name in names {
+    let (specifier, exported) = &inject[name];
+    // A JSON string is a valid string literal of JavaScript.
+    let specifier = serde_json::to_string(specifier).expect("a string should be serializable");
+    match exported.as_str() {
+      "default" => writeln!(source, "import {name} from {specifier};"),
+      "*" => writeln!(source, "import * as {name} from {specifier};"),
+      exported => writeln!(source, "import {{ {exported} as {name} }} from {specifier};"),
+    }
+    .expect("writing to a string should not fail");
+  }
+  let source = alloc.alloc_str(&source);
+  let mut body =
+    Parser::new(alloc, source, SourceType::default().with_module(true)).parse().program.body;
+
+  // Imports are recorded by their spans, so the injected ones get empty spans which never collide with the ones in
+  // the source.
+  for (idx, stmt) in body.iter_mut().enumerate() {
+    if let Statement::ModuleDeclaration(decl) = stmt {
+      if let ModuleDeclaration::ImportDeclaration(import_decl) = &mut **decl {
+        let pos = u32::try_from(idx).expect("too many injected imports");
+        import_decl.span = Span::new(pos, pos);
+      }
+    }
+  }
+  body.extend(program.body.take_in(alloc));
+  program.body = body;
+}
+
+#[test]
+fn test_validate_inject() {
+  let inject = |key: &str, exported: &str| {
+    [(key.to_string(), ("polyfill".to_string(), exported.to_string()))].into_iter().collect()
+  };
+  assert!(validate_inject(&inject("Promise", "Promise")).is_ok());
+  assert!(validate_inject(&inject("Buffer", "default")).is_ok());
+  assert!(validate_inject(&inject("polyfills", "*")).is_ok());
+  assert!(validate_inject(&inject("process.env", "env")).is_err());
+  assert!(validate_inject(&inject("Promise", "a-b")).is_err());
+}
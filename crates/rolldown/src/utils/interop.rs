@@ -0,0 +1,20 @@
+use rolldown_common::InteropMode;
+
+/// The runtime helper wrapping `module.exports` of external modules for `output.interop`, or `None` if they are
+/// used as is.
+pub fn external_interop_helper(interop: InteropMode) -> Option<&'static str> {
+  match interop {
+    InteropMode::Auto | InteropMode::Default | InteropMode::DefaultOnly => Some("__toESM"),
+    InteropMode::Compat => Some("__toESMCompat"),
+    InteropMode::EsModule => None,
+  }
+}
+
+/// Call the helper of `external_interop_helper` with `value`, whose name is `helper_name` in the chunk.
+pub fn render_external_interop(interop: InteropMode, helper_name: &str, value: &str) -> String {
+  match interop {
+    // `__toESM` always uses `module.exports` as the default export in the node mode
+    InteropMode::Default | InteropMode::DefaultOnly => format!("{helper_name}({value}, 1)"),
+    _ => format!("{helper_name}({value})"),
+  }
+}
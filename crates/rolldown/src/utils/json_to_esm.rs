@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use oxc::syntax::identifier::is_identifier_name;
+use rolldown_common::JsonOptions;
+use rolldown_error::BuildError;
+use serde_json::Value;
+
+use super::reserved_names::RESERVED_NAMES;
+
+/// Turn the content of a `.json` module into an ES module, whose default export is the parsed value. With
+/// `named_exports`, top-level keys that are valid names of bindings are also exported by their names, and the
+/// default export refers to them.
+pub fn json_to_esm(source: &str, path: &str, options: &JsonOptions) -> Result<String, BuildError> {
+  let value = serde_json::from_str::<Value>(source).map_err(|err| {
+    // Positions are passed to the error separately
+    let message = err.to_string();
+    let reason = message
+      .strip_suffix(&format!(" at line {} column {}", err.line(), err.column()))
+      .unwrap_or(&message);
+    BuildError::invalid_json(path, reason, err.line(), err.column())
+  })?;
+
+  let mut code = String::new();
+  match value {
+    Value::Object(object) if options.named_exports => {
+      let mut default_export = String::from("{\n");
+      for (key, value) in object {
+        if is_identifier_name(&key) && !RESERVED_NAMES.contains(&key.as_str()) {
+          writeln!(code, "export const {key} = {};", render_value(value)).unwrap();
+          writeln!(default_export, "  {key},").unwrap();
+        } else {
+          writeln!(default_export, "  {}: {},", render_key(&key), render_value(value)).unwrap();
+        }
+      }
+      default_export.push('}');
+      writeln!(code, "export default {default_export};").unwrap();
+    }
+    value => writeln!(code, "export default {};", render_value(value)).unwrap(),
+  }
+  Ok(code)
+}
+
+fn render_key(key: &str) -> String {
+  if key == "__proto__" {
+    // `__proto__: value` sets the prototype of an object literal, while it's a normal key in JSON
+    return "[\"__proto__\"]".to_string();
+  }
+  if is_identifier_name(key) {
+    key.to_string()
+  } else {
+    Value::String(key.to_string()).to_string()
+  }
+}
+
+fn render_value(value: Value) -> String {
+  match value {
+    Value::Array(items) => {
+      format!("[{}]", items.into_iter().map(render_value).collect::<Vec<_>>().join(", "))
+    }
+    Value::Object(object) => {
+      let properties = object
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", render_key(&key), render_value(value)))
+        .collect::<Vec<_>>();
+      if properties.is_empty() {
+        "{}".to_string()
+      } else {
+        format!("{{ {} }}", properties.join(", "))
+      }
+    }
+    // JSON strings, numbers and keywords are valid in JavaScript as they are
+    value => value.to_string(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rolldown_common::JsonOptions;
+
+  use super::json_to_esm;
+
+  #[test]
+  fn top_level_keys_are_named_exports() {
+    let code = json_to_esm(
+      r#"{ "name": "pkg", "class": 1, "not-valid": [true, null], "nested": { "__proto__": {} } }"#,
+      "/project/package.json",
+      &JsonOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(
+      code,
+      "export const name = \"pkg\";\nexport const nested = { [\"__proto__\"]: {} };\nexport default {\n  name,\n  class: 1,\n  \"not-valid\": [true, null],\n  nested,\n};\n"
+    );
+
+    let code = json_to_esm(
+      r#"{ "name": "pkg" }"#,
+      "/project/package.json",
+      &JsonOptions { named_exports: false },
+    )
+    .unwrap();
+    assert_eq!(code, "export default { name: \"pkg\" };\n");
+  }
+
+  #[test]
+  fn invalid_json_is_a_parse_error() {
+    let err =
+      json_to_esm("{\n  \"a\": 1,\n}", "/project/data.json", &JsonOptions::default()).unwrap_err();
+    assert_eq!(err.code(), "PARSE_ERROR");
+    assert!(err.to_string().ends_with(":3:1 as JSON: trailing comma."), "{err}");
+  }
+}
@@ -0,0 +1,453 @@
+use std::fmt::Write;
+
+use oxc::{
+  allocator::{self, Allocator},
+  ast::{
+    ast::{
+      Argument, ArrayExpression, ArrayExpressionElement, CallExpression, Declaration, Expression,
+      JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement, JSXElementName,
+      JSXExpression, JSXFragment, JSXMemberExpression, JSXMemberExpressionObject,
+      ModuleDeclaration, ObjectExpression, ObjectProperty, ObjectPropertyKind, Program,
+      PropertyKey, SpreadElement, Statement, StaticMemberExpression,
+    },
+    VisitMut,
+  },
+  parser::Parser,
+  span::{Atom, SourceType, Span},
+  syntax::identifier::is_identifier_name,
+};
+use rolldown_common::{JsxOptions, JsxRuntime};
+use rolldown_oxc_utils::{AstSnippet, Dummy, IntoIn, TakeIn};
+
+const JSX: usize = 0;
+const JSXS: usize = 1;
+const FRAGMENT: usize = 2;
+const RUNTIME_EXPORTS: [&str; 3] = ["jsx", "jsxs", "Fragment"];
+
+/// Lowers JSX to calls of the runtime, so the module is scanned like JavaScript.
+///
+/// The automatic runtime gets `jsx`, `jsxs` and `Fragment` imported from `react/jsx-runtime` on demand, which is
+/// resolved and tree shaken like any other import. The classic runtime calls `React.createElement` instead.
+pub struct JsxTransformer<'ast, 'a> {
+  alloc: &'ast Allocator,
+  snippet: AstSnippet<'ast>,
+  options: &'a JsxOptions,
+  /// Local names of the exports of the automatic runtime, which never appear in the source.
+  names: [Atom<'ast>; 3],
+  used: [bool; 3],
+  /// The span of the first element, taken by the import of the automatic runtime.
+  first_span: Option<Span>,
+}
+
+impl<'ast, 'a> JsxTransformer<'ast, 'a> {
+  pub fn new(alloc: &'ast Allocator, source: &str, options: &'a JsxOptions) -> Self {
+    let names = RUNTIME_EXPORTS.map(|exported| {
+      let base = format!("_{exported}");
+      let mut name = base.clone();
+      let mut idx = 0;
+      while source.contains(&name) {
+        idx += 1;
+        name = format!("{base}{idx}");
+      }
+      Atom::from(&*alloc.alloc_str(&name))
+    });
+    Self {
+      alloc,
+      snippet: AstSnippet::new(alloc),
+      options,
+      names,
+      used: [false; 3],
+      first_span: None,
+    }
+  }
+
+  fn is_automatic(&self) -> bool {
+    self.options.runtime == JsxRuntime::Automatic
+  }
+
+  fn atom(&self, value: &str) -> Atom<'ast> {
+    Atom::from(&*self.alloc.alloc_str(value))
+  }
+
+  fn runtime_ref(&mut self, idx: usize) -> Expression<'ast> {
+    self.used[idx] = true;
+    self.snippet.id_ref_expr(self.names[idx].clone())
+  }
+
+  /// `React.createElement` becomes a member expression, the first part of which should be in scope.
+  fn pragma_expr(&self, pragma: &str) -> Expression<'ast> {
+    let mut parts = pragma.split('.');
+    let mut expr = self.snippet.id_ref_expr(self.atom(parts.next().unwrap_or_default()));
+    for property in parts {
+      expr = Expression::MemberExpression(
+        oxc::ast::ast::MemberExpression::StaticMemberExpression(StaticMemberExpression {
+          object: expr,
+          property: self.snippet.id_name(self.atom(property)),
+          ..Dummy::dummy(self.alloc)
+        })
+        .into_in(self.alloc),
+      );
+    }
+    expr
+  }
+
+  /// Lowercase names like `div` and names with `-` are intrinsic elements, which are passed as strings.
+  fn element_type(&self, name: &JSXElementName<'ast>) -> Expression<'ast> {
+    match name {
+      JSXElementName::Identifier(ident) => {
+        let name = ident.name.as_str();
+        if name.starts_with(|c: char| c.is_ascii_lowercase()) || name.contains('-') {
+          self.snippet.string_literal_expr(ident.name.clone())
+        } else {
+          self.snippet.id_ref_expr(ident.name.clone())
+        }
+      }
+      JSXElementName::NamespacedName(name) => {
+        self.snippet.string_literal_expr(self.atom(&name.to_string()))
+      }
+      JSXElementName::MemberExpression(expr) => self.member_expr(expr),
+    }
+  }
+
+  fn member_expr(&self, expr: &JSXMemberExpression<'ast>) -> Expression<'ast> {
+    let object = match &expr.object {
+      JSXMemberExpressionObject::Identifier(ident) if ident.name == "this" => {
+        Expression::ThisExpression(Dummy::dummy(self.alloc))
+      }
+      JSXMemberExpressionObject::Identifier(ident) => self.snippet.id_ref_expr(ident.name.clone()),
+      JSXMemberExpressionObject::MemberExpression(expr) => self.member_expr(expr),
+    };
+    Expression::MemberExpression(
+      oxc::ast::ast::MemberExpression::StaticMemberExpression(StaticMemberExpression {
+        object,
+        property: self.snippet.id_name(expr.property.name.clone()),
+        ..Dummy::dummy(self.alloc)
+      })
+      .into_in(self.alloc),
+    )
+  }
+
+  fn property(&self, key: &str, value: Expression<'ast>) -> ObjectPropertyKind<'ast> {
+    let key = if is_identifier_name(key) {
+      PropertyKey::Identifier(self.snippet.id_name(self.atom(key)).into_in(self.alloc))
+    } else {
+      PropertyKey::Expression(self.snippet.string_literal_expr(self.atom(key)))
+    };
+    ObjectPropertyKind::ObjectProperty(
+      ObjectProperty { key, value, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+    )
+  }
+
+  /// Turns attributes into properties, and takes out `key`, which is passed separately by the automatic runtime.
+  fn props(
+    &mut self,
+    attributes: allocator::Vec<'ast, JSXAttributeItem<'ast>>,
+  ) -> (allocator::Vec<'ast, ObjectPropertyKind<'ast>>, Option<Expression<'ast>>) {
+    let mut properties = allocator::Vec::new_in(self.alloc);
+    let mut key = None;
+    for attribute in attributes {
+      let attribute = match attribute {
+        JSXAttributeItem::Attribute(attribute) => attribute.unbox(),
+        JSXAttributeItem::SpreadAttribute(spread) => {
+          let mut argument = spread.unbox().argument;
+          self.visit_expression(&mut argument);
+          properties.push(ObjectPropertyKind::SpreadProperty(
+            SpreadElement { span: Span::default(), argument }.into_in(self.alloc),
+          ));
+          continue;
+        }
+      };
+      let name = match &attribute.name {
+        JSXAttributeName::Identifier(ident) => ident.name.to_string(),
+        JSXAttributeName::NamespacedName(name) => name.to_string(),
+      };
+      let value = match attribute.value {
+        None => Expression::BooleanLiteral(
+          oxc::ast::ast::BooleanLiteral { span: Span::default(), value: true }.into_in(self.alloc),
+        ),
+        Some(JSXAttributeValue::StringLiteral(lit)) => {
+          self.snippet.string_literal_expr(self.atom(&decode_entities(&lit.value)))
+        }
+        Some(JSXAttributeValue::ExpressionContainer(container)) => match container.expression {
+          JSXExpression::Expression(mut expr) => {
+            self.visit_expression(&mut expr);
+            expr
+          }
+          JSXExpression::EmptyExpression(_) => continue,
+        },
+        Some(JSXAttributeValue::Element(elem)) => self.lower_element(elem.unbox()),
+        Some(JSXAttributeValue::Fragment(fragment)) => self.lower_fragment(fragment.unbox()),
+      };
+      if name == "key" && self.is_automatic() {
+        key = Some(value);
+      } else {
+        properties.push(self.property(&name, value));
+      }
+    }
+    (properties, key)
+  }
+
+  fn children(&mut self, children: allocator::Vec<'ast, JSXChild<'ast>>) -> Vec<Expression<'ast>> {
+    let mut exprs = vec![];
+    for child in children {
+      match child {
+        JSXChild::Text(text) => {
+          if let Some(text) = clean_jsx_text(&text.value) {
+            exprs.push(self.snippet.string_literal_expr(self.atom(&text)));
+          }
+        }
+        JSXChild::Element(elem) => exprs.push(self.lower_element(elem.unbox())),
+        JSXChild::Fragment(fragment) => exprs.push(self.lower_fragment(fragment.unbox())),
+        JSXChild::ExpressionContainer(container) => {
+          if let JSXExpression::Expression(mut expr) = container.expression {
+            self.visit_expression(&mut expr);
+            exprs.push(expr);
+          }
+        }
+        JSXChild::Spread(spread) => {
+          let mut expr = spread.expression;
+          self.visit_expression(&mut expr);
+          exprs.push(expr);
+        }
+      }
+    }
+    exprs
+  }
+
+  fn call(
+    &self,
+    span: Span,
+    callee: Expression<'ast>,
+    args: Vec<Expression<'ast>>,
+  ) -> Expression<'ast> {
+    let arguments =
+      allocator::Vec::from_iter_in(args.into_iter().map(Argument::Expression), self.alloc);
+    Expression::CallExpression(
+      CallExpression { span, callee, arguments, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+    )
+  }
+
+  fn create(
+    &mut self,
+    span: Span,
+    ty: Expression<'ast>,
+    attributes: allocator::Vec<'ast, JSXAttributeItem<'ast>>,
+    children: allocator::Vec<'ast, JSXChild<'ast>>,
+  ) -> Expression<'ast> {
+    self.first_span.get_or_insert(span);
+    let (mut properties, key) = self.props(attributes);
+    let mut children = self.children(children);
+    if self.is_automatic() {
+      let is_static = children.len() > 1;
+      match children.len() {
+        0 => {}
+        1 => properties.push(self.property("children", children.remove(0))),
+        _ => {
+          let elements = allocator::Vec::from_iter_in(
+            children.into_iter().map(ArrayExpressionElement::Expression),
+            self.alloc,
+          );
+          let array = Expression::ArrayExpression(
+            ArrayExpression { span: Span::default(), elements, trailing_comma: None }
+              .into_in(self.alloc),
+          );
+          properties.push(self.property("children", array));
+        }
+      }
+      let callee = self.runtime_ref(if is_static { JSXS } else { JSX });
+      let props = Expression::ObjectExpression(
+        ObjectExpression { properties, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+      );
+      let mut args = vec![ty, props];
+      args.extend(key);
+      self.call(span, callee, args)
+    } else {
+      let callee = self.pragma_expr(&self.options.pragma);
+      let props = if properties.is_empty() {
+        Expression::NullLiteral(
+          oxc::ast::ast::NullLiteral { span: Span::default() }.into_in(self.alloc),
+        )
+      } else {
+        Expression::ObjectExpression(
+          ObjectExpression { properties, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+        )
+      };
+      let mut args = vec![ty, props];
+      args.extend(children);
+      self.call(span, callee, args)
+    }
+  }
+
+  fn lower_element(&mut self, elem: JSXElement<'ast>) -> Expression<'ast> {
+    let opening = elem.opening_element.unbox();
+    let ty = self.element_type(&opening.name);
+    self.create(elem.span, ty, opening.attributes, elem.children)
+  }
+
+  fn lower_fragment(&mut self, fragment: JSXFragment<'ast>) -> Expression<'ast> {
+    let ty = if self.is_automatic() {
+      self.runtime_ref(FRAGMENT)
+    } else {
+      self.pragma_expr(&self.options.pragma_frag)
+    };
+    self.create(fragment.span, ty, allocator::Vec::new_in(self.alloc), fragment.children)
+  }
+
+  /// Prepends the import of the automatic runtime, or a `require` call for scripts, which can't have imports.
+  fn import_runtime(&self, program: &mut Program<'ast>, is_module: bool) {
+    let Some(span) = self.first_span else {
+      return;
+    };
+    let specifiers = RUNTIME_EXPORTS
+      .iter()
+      .zip(&self.names)
+      .zip(self.used)
+      .filter(|(_, used)| *used)
+      .map(|((exported, local), _)| {
+        if is_module {
+          format!("{exported} as {local}")
+        } else {
+          format!("{exported}: {local}")
+        }
+      })
+      .collect::<Vec<_>>();
+    if specifiers.is_empty() {
+      return;
+    }
+    let specifier = serde_json::to_string(&format!("{}/jsx-runtime", self.options.import_source))
+      .expect("a string should be serializable");
+    let mut code = String::new();
+    if is_module {
+      write!(code, "import {{ {} }} from {specifier};", specifiers.join(", "))
+    } else {
+      write!(code, "var {{ {} }} = require({specifier});", specifiers.join(", "))
+    }
+    .expect("writing to a string should not fail");
+    let code = self.alloc.alloc_str(&code);
+    let source_type = SourceType::default().with_module(is_module);
+    let mut body = Parser::new(self.alloc, code, source_type).parse().program.body;
+
+    // Imports and `require` calls are recorded by their spans, so the generated one takes the span of the first
+    // element, which is never taken by another import.
+    match body.iter_mut().next() {
+      Some(Statement::ModuleDeclaration(decl)) => {
+        if let ModuleDeclaration::ImportDeclaration(import_decl) = &mut **decl {
+          import_decl.span = span;
+        }
+      }
+      Some(Statement::Declaration(Declaration::VariableDeclaration(var_decl))) => {
+        if let Some(Expression::CallExpression(call_expr)) =
+          var_decl.declarations.iter_mut().next().and_then(|declarator| declarator.init.as_mut())
+        {
+          call_expr.span = span;
+        }
+      }
+      _ => {}
+    }
+    body.extend(program.body.take_in(self.alloc));
+    program.body = body;
+  }
+}
+
+impl<'ast, 'a> VisitMut<'ast> for JsxTransformer<'ast, 'a> {
+  fn visit_expression(&mut self, expr: &mut Expression<'ast>) {
+    match expr {
+      Expression::JSXElement(_) => {
+        let Expression::JSXElement(elem) = expr.take_in(self.alloc) else { unreachable!() };
+        *expr = self.lower_element(elem.unbox());
+      }
+      Expression::JSXFragment(_) => {
+        let Expression::JSXFragment(fragment) = expr.take_in(self.alloc) else { unreachable!() };
+        *expr = self.lower_fragment(fragment.unbox());
+      }
+      _ => self.visit_expression_match(expr),
+    }
+  }
+}
+
+/// Lowers the JSX of `program`, see [`JsxTransformer`].
+pub fn transform_jsx<'ast>(
+  alloc: &'ast Allocator,
+  program: &mut Program<'ast>,
+  source: &str,
+  options: &JsxOptions,
+  is_module: bool,
+) {
+  let mut transformer = JsxTransformer::new(alloc, source, options);
+  transformer.visit_program(program);
+  if transformer.is_automatic() {
+    transformer.import_runtime(program, is_module);
+  }
+}
+
+/// Trims the whitespace around lines of JSX text like Babel does. Lines are joined with spaces, while empty
+/// lines are removed.
+fn clean_jsx_text(text: &str) -> Option<String> {
+  let lines =
+    text.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect::<Vec<_>>();
+  let last_non_empty = lines.iter().rposition(|line| line.contains(|c| c != ' ' && c != '\t'));
+  let mut cleaned = String::new();
+  for (idx, line) in lines.iter().enumerate() {
+    let mut line = line.replace('\t', " ");
+    if idx != 0 {
+      line = line.trim_start_matches(' ').to_string();
+    }
+    if idx != lines.len() - 1 {
+      line = line.trim_end_matches(' ').to_string();
+    }
+    if line.is_empty() {
+      continue;
+    }
+    cleaned.push_str(&line);
+    if Some(idx) != last_non_empty {
+      cleaned.push(' ');
+    }
+  }
+  (!cleaned.is_empty()).then(|| decode_entities(&cleaned))
+}
+
+/// Decodes numeric HTML entities and the named ones that are commonly used in JSX.
+fn decode_entities(text: &str) -> String {
+  let mut decoded = String::with_capacity(text.len());
+  let mut rest = text;
+  while let Some(start) = rest.find('&') {
+    decoded.push_str(&rest[..start]);
+    rest = &rest[start..];
+    let entity = rest.find(';').map(|end| &rest[1..end]);
+    let char = entity.and_then(|entity| match entity {
+      "amp" => Some('&'),
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      "nbsp" => Some('\u{a0}'),
+      _ => entity
+        .strip_prefix("#x")
+        .map(|hex| u32::from_str_radix(hex, 16))
+        .or_else(|| entity.strip_prefix('#').map(str::parse))
+        .and_then(Result::ok)
+        .and_then(char::from_u32),
+    });
+    if let (Some(entity), Some(char)) = (entity, char) {
+      decoded.push(char);
+      rest = &rest[entity.len() + 2..];
+    } else {
+      decoded.push('&');
+      rest = &rest[1..];
+    }
+  }
+  decoded.push_str(rest);
+  decoded
+}
+
+#[test]
+fn test_clean_jsx_text() {
+  assert_eq!(clean_jsx_text("hello"), Some("hello".to_string()));
+  assert_eq!(clean_jsx_text("\n  hello\n  world\n"), Some("hello world".to_string()));
+  assert_eq!(clean_jsx_text(" a "), Some(" a ".to_string()));
+  assert_eq!(clean_jsx_text("\n    \n  "), None);
+  assert_eq!(
+    clean_jsx_text("a&nbsp;&amp;&#65;&#x42;&unknown;"),
+    Some("a\u{a0}&AB&unknown;".to_string())
+  );
+}
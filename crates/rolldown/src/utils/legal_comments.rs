@@ -0,0 +1,31 @@
+use oxc::ast::CommentKind;
+use rolldown_oxc_utils::OxcProgram;
+
+/// Legal comments of the module in the order they appear, along with their `//` or `/* */`.
+pub fn collect_legal_comments(ast: &OxcProgram) -> Vec<String> {
+  ast
+    .comments()
+    .iter()
+    .filter_map(|(start, end, kind)| {
+      let content = ast.source().get(*start as usize..*end as usize)?;
+      is_legal_comment(content).then(|| match kind {
+        CommentKind::SingleLine => format!("//{content}"),
+        CommentKind::MultiLine => format!("/*{content}*/"),
+      })
+    })
+    .collect()
+}
+
+/// Comments starting with `!`, like `/*! MIT */`, or containing `@license` or `@preserve`.
+fn is_legal_comment(content: &str) -> bool {
+  content.starts_with('!') || content.contains("@license") || content.contains("@preserve")
+}
+
+#[test]
+fn test_is_legal_comment() {
+  assert!(is_legal_comment("! lib v1.0.0 | MIT"));
+  assert!(is_legal_comment("*\n * @license MIT\n "));
+  assert!(is_legal_comment(" @preserve "));
+  assert!(!is_legal_comment("#__PURE__"));
+  assert!(!is_legal_comment(" license: MIT"));
+}
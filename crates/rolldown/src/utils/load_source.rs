@@ -1,26 +1,89 @@
-use rolldown_common::ResolvedPath;
-use rolldown_plugin::HookLoadArgs;
-use rolldown_sourcemap::SourceMap;
+use rolldown_common::{ImportAttributes, Loader, ResolvedPath};
+use rolldown_error::BuildError;
+use rolldown_plugin::{HookLoadArgs, HookSideEffects};
+use rolldown_sourcemap::SourceMapChain;
+use rustc_hash::FxHashMap;
 use sugar_path::AsPath;
 
-use crate::{error::BatchedErrors, plugin_driver::PluginDriver};
+use crate::{
+  error::BatchedErrors,
+  plugin_driver::PluginDriver,
+  utils::{
+    data_url::{is_data_url, parse_data_url},
+    file_snapshot::{hash_content, snapshot, FileSnapshots},
+  },
+};
 
+/// The loaded content of a module.
+pub enum LoadedSource {
+  Code(String),
+  /// The content of an asset file read from the file system, which is usually binary.
+  Asset(Vec<u8>),
+}
+
+/// What `load` hooks tell about the module besides its code and side effects.
+#[derive(Debug, Default)]
+pub struct LoadedModuleOptions {
+  /// None of the statements of the module are tree shaken.
+  pub no_treeshake: bool,
+  pub synthetic_named_exports: Option<String>,
+  pub meta: FxHashMap<String, serde_json::Value>,
+}
+
+/// Load the module with `load` hooks, or read it from the file system. Files are read as assets with
+/// `is_asset_file`, unless `load` hooks load them. The loaded file is recorded in `snapshots`, even if it can't be
+/// read, so it's watched in watch mode.
+#[allow(clippy::too_many_arguments)]
 pub async fn load_source(
   plugin_driver: &PluginDriver,
   resolved_path: &ResolvedPath,
+  attributes: &ImportAttributes,
   fs: &dyn rolldown_fs::FileSystem,
-  sourcemap_chain: &mut Vec<SourceMap>,
-) -> Result<String, BatchedErrors> {
-  let source =
-    if let Some(r) = plugin_driver.load(&HookLoadArgs { id: &resolved_path.path }).await? {
-      if let Some(map) = r.map {
-        sourcemap_chain.push(map);
+  sourcemap_chain: &mut SourceMapChain,
+  module_side_effects: &mut bool,
+  loader: &mut Option<Loader>,
+  options: &mut LoadedModuleOptions,
+  is_asset_file: bool,
+  snapshots: &FileSnapshots,
+) -> Result<LoadedSource, BatchedErrors> {
+  let args = HookLoadArgs { id: &resolved_path.path, attributes };
+  let path = resolved_path.path.as_str();
+  let source = if let Some(r) = plugin_driver.load(&args).await? {
+    // The code loaded by hooks isn't necessarily the content of the file, which is snapshotted separately.
+    if !resolved_path.path.is_virtual() && !is_data_url(path) {
+      snapshots.record(path, snapshot(fs, path));
+    }
+    if let Some(map) = r.map {
+      sourcemap_chain.push(map);
+    }
+    match r.module_side_effects {
+      Some(HookSideEffects::Bool(side_effects)) => *module_side_effects = side_effects,
+      Some(HookSideEffects::NoTreeshake) => {
+        *module_side_effects = true;
+        options.no_treeshake = true;
       }
-      r.code
-    } else if resolved_path.ignored {
-      String::new()
-    } else {
-      fs.read_to_string(resolved_path.path.as_path())?
-    };
+      None => {}
+    }
+    options.synthetic_named_exports = r.synthetic_named_exports;
+    options.meta = r.meta.unwrap_or_default();
+    if r.loader.is_some() {
+      *loader = r.loader;
+    }
+    LoadedSource::Code(r.code)
+  } else if resolved_path.ignored {
+    LoadedSource::Code(String::new())
+  } else if resolved_path.path.is_virtual() {
+    return Err(BuildError::unloaded_virtual_module(resolved_path.path.as_str()).into());
+  } else if is_data_url(&resolved_path.path) {
+    LoadedSource::Code(parse_data_url(&resolved_path.path)?.content)
+  } else if is_asset_file {
+    let content = fs.read(path.as_path());
+    snapshots.record(path, content.as_ref().ok().map(|content| hash_content(content)));
+    LoadedSource::Asset(content?)
+  } else {
+    let code = fs.read_to_string(path.as_path());
+    snapshots.record(path, code.as_ref().ok().map(|code| hash_content(code.as_bytes())));
+    LoadedSource::Code(code?)
+  };
   Ok(source)
 }
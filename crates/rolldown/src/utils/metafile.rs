@@ -0,0 +1,98 @@
+use rolldown_common::{
+  ImportKind, Metafile, MetafileImport, MetafileInput, MetafileOutput, MetafileOutputInput,
+  ModuleId, NormalModuleId, Output,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::types::module_table::ModuleTable;
+
+/// Report the modules of the build and the outputs generated from them. The runtime module is not an input, though
+/// the code of it in chunks is reported.
+pub fn generate_metafile(
+  module_table: &ModuleTable,
+  runtime_id: NormalModuleId,
+  outputs: &[Output],
+) -> Metafile {
+  let inputs = module_table
+    .normal_modules
+    .iter()
+    .filter(|module| module.id != runtime_id)
+    .map(|module| {
+      let imports = module
+        .import_records
+        .iter()
+        .filter(|rec| rec.resolved_module != ModuleId::Normal(runtime_id))
+        .map(|rec| {
+          let (path, external) = match rec.resolved_module {
+            ModuleId::Normal(id) => (module_table.normal_modules[id].pretty_path.clone(), false),
+            ModuleId::External(id) => {
+              (module_table.external_modules[id].resource_id.expect_file().to_string(), true)
+            }
+          };
+          MetafileImport {
+            path,
+            kind: rec.kind.to_string(),
+            external,
+            original: Some(rec.module_request.to_string()),
+          }
+        })
+        .collect();
+      (module.pretty_path.clone(), MetafileInput { bytes: module.source.len(), imports })
+    })
+    .collect();
+
+  // Modules are keyed by their ids in chunks
+  let pretty_paths = module_table
+    .normal_modules
+    .iter()
+    .map(|module| (module.resource_id.expect_file().as_str(), module.pretty_path.as_str()))
+    .collect::<FxHashMap<_, _>>();
+  let pretty_path =
+    |id: &str| pretty_paths.get(id).map_or_else(|| id.to_string(), ToString::to_string);
+  let file_names = outputs.iter().map(Output::file_name).collect::<FxHashSet<_>>();
+  let outputs = outputs
+    .iter()
+    .map(|output| match output {
+      Output::Chunk(chunk) => {
+        let imports = chunk
+          .imports
+          .iter()
+          .map(|import| (import, ImportKind::Import))
+          .chain(chunk.dynamic_imports.iter().map(|import| (import, ImportKind::DynamicImport)))
+          .map(|(import, kind)| MetafileImport {
+            path: import.clone(),
+            kind: kind.to_string(),
+            external: !file_names.contains(import.as_str()),
+            original: None,
+          })
+          .collect();
+        let inputs = chunk
+          .modules
+          .iter()
+          .filter(|(_, module)| module.rendered_length > 0)
+          .map(|(id, module)| {
+            (pretty_path(id), MetafileOutputInput { bytes_in_output: module.rendered_length })
+          })
+          .collect();
+        let output = MetafileOutput {
+          bytes: chunk.code.len(),
+          inputs,
+          imports,
+          exports: chunk.exports.clone(),
+          entry_point: chunk
+            .facade_module_id
+            .as_deref()
+            .filter(|_| chunk.is_entry)
+            .map(pretty_path),
+        };
+        (chunk.file_name.clone(), output)
+      }
+      Output::Asset(asset) => (
+        asset.file_name.clone(),
+        MetafileOutput { bytes: asset.source.as_bytes().len(), ..Default::default() },
+      ),
+    })
+    .collect();
+
+  Metafile { inputs, outputs }
+}
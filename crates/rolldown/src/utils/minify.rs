@@ -0,0 +1,111 @@
+use oxc::{
+  allocator::Allocator,
+  ast::{
+    ast::{BooleanLiteral, Expression, Program, Statement},
+    VisitMut,
+  },
+  span::Atom,
+  syntax::operator::BinaryOperator,
+};
+use rolldown_oxc_utils::{AstSnippet, Dummy, IntoIn, TakeIn};
+
+use super::define::{fold_branches, fold_if_statement, ConstValue};
+
+const HEAD_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+const TAIL_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$0123456789";
+
+/// The `idx`-th name of `a`, `b`, ..., `$`, `aa`, `ba`, ..., which might be a reserved word.
+pub fn short_name(mut idx: usize) -> String {
+  let mut name = String::new();
+  name.push(HEAD_CHARS[idx % HEAD_CHARS.len()] as char);
+  idx /= HEAD_CHARS.len();
+  while idx > 0 {
+    idx -= 1;
+    name.push(TAIL_CHARS[idx % TAIL_CHARS.len()] as char);
+    idx /= TAIL_CHARS.len();
+  }
+  name
+}
+
+/// Folds constant expressions, like `1 + 2` and `"a" + "b"`, and branches with constant tests.
+pub struct ConstantFolder<'ast> {
+  pub alloc: &'ast Allocator,
+}
+
+impl<'ast> ConstantFolder<'ast> {
+  pub fn fold(alloc: &'ast Allocator, program: &mut Program<'ast>) {
+    Self { alloc }.visit_program(program);
+  }
+
+  fn fold_binary(&self, expr: &Expression<'ast>) -> Option<Expression<'ast>> {
+    let Expression::BinaryExpression(binary) = expr else {
+      return None;
+    };
+    let snippet = AstSnippet::new(self.alloc);
+    if let (
+      BinaryOperator::Addition,
+      Expression::StringLiteral(left),
+      Expression::StringLiteral(right),
+    ) = (binary.operator, &binary.left, &binary.right)
+    {
+      let value = self.alloc.alloc(format!("{}{}", left.value, right.value));
+      return Some(snippet.string_literal_expr(Atom::from(value.as_str())));
+    }
+    match ConstValue::evaluate(expr)? {
+      ConstValue::Boolean(value) => Some(Expression::BooleanLiteral(
+        BooleanLiteral { span: Dummy::dummy(self.alloc), value }.into_in(self.alloc),
+      )),
+      // Negative, fractional and non-finite results are usually longer than the expression.
+      #[allow(clippy::float_cmp)]
+      ConstValue::Number(value)
+        if value.is_finite()
+          && value.fract() == 0.0
+          && value.is_sign_positive()
+          && value < 1e15 =>
+      {
+        Some(snippet.number_expr(value))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl<'ast> VisitMut<'ast> for ConstantFolder<'ast> {
+  fn visit_expression(&mut self, expr: &mut Expression<'ast>) {
+    self.visit_expression_match(expr);
+    if let Expression::ParenthesizedExpression(paren) = expr {
+      if matches!(
+        paren.expression,
+        Expression::BooleanLiteral(_)
+          | Expression::NumericLiteral(_)
+          | Expression::StringLiteral(_)
+          | Expression::NullLiteral(_)
+      ) {
+        *expr = paren.expression.take_in(self.alloc);
+        return;
+      }
+    }
+    if let Some(folded) = self.fold_binary(expr).or_else(|| fold_branches(self.alloc, expr)) {
+      *expr = folded;
+    }
+  }
+
+  fn visit_statement(&mut self, stmt: &mut Statement<'ast>) {
+    self.visit_statement_match(stmt);
+    if let Statement::IfStatement(if_stmt) = stmt {
+      if let Some(folded) = fold_if_statement(self.alloc, if_stmt) {
+        *stmt = folded;
+      }
+    }
+  }
+}
+
+#[test]
+fn test_short_name() {
+  assert_eq!(short_name(0), "a");
+  assert_eq!(short_name(53), "$");
+  assert_eq!(short_name(54), "aa");
+  assert_eq!(short_name(55), "ba");
+  assert_eq!(short_name(54 + 54), "ab");
+  assert_eq!(short_name(54 + 54 * 64), "aaa");
+}
@@ -1,15 +1,34 @@
+use std::path::Path;
+
 use oxc::ast::VisitMut;
 use rolldown_common::NormalModule;
 use rolldown_oxc_utils::{AstSnippet, OxcProgram};
+use rolldown_utils::glob::glob_match;
+use sugar_path::SugarPath;
 
 use super::finalizer::{Finalizer, FinalizerContext};
 
+pub mod asset_to_esm;
+pub mod data_url;
+pub mod define;
+pub mod file_snapshot;
+pub mod hash_placeholder;
+pub mod inject;
+pub mod interop;
+pub mod json_to_esm;
+pub mod jsx;
+pub mod legal_comments;
 pub mod load_source;
+pub mod metafile;
+pub mod minify;
 pub mod renamer;
 pub mod render_chunks;
 pub mod render_normal_module;
+pub mod reserved_names;
 pub mod resolve_id;
+pub mod text_to_esm;
 pub mod transform_source;
+pub mod typescript;
 
 pub(crate) fn is_in_rust_test_mode() -> bool {
   static TEST_MODE: once_cell::sync::Lazy<bool> =
@@ -17,15 +36,30 @@ pub(crate) fn is_in_rust_test_mode() -> bool {
   *TEST_MODE
 }
 
+/// Whether the module `id` matches the glob `pattern` of an option. Patterns starting with `*` or absolute ones are
+/// matched as they are, while other ones are resolved against `cwd`.
+pub(crate) fn match_id_pattern(pattern: &str, id: &str, cwd: &Path) -> bool {
+  if pattern.starts_with('*') || Path::new(pattern).is_absolute() {
+    glob_match(pattern, id)
+  } else {
+    glob_match(&cwd.join(pattern).normalize().to_string_lossy(), id)
+  }
+}
+
 pub fn finalize_normal_module(
   module: &NormalModule,
   ctx: FinalizerContext<'_>,
   ast: &mut OxcProgram,
 ) {
   let (oxc_program, alloc) = ast.program_mut_and_allocator();
+  let minify = ctx.output_options.minify;
 
   let mut finalizer =
     Finalizer { alloc, ctx, scope: &module.scope, snippet: &AstSnippet::new(alloc) };
 
   finalizer.visit_program(oxc_program);
+
+  if minify {
+    minify::ConstantFolder::fold(alloc, oxc_program);
+  }
 }
@@ -5,6 +5,7 @@ use rolldown_common::{NormalModule, NormalModuleId, SymbolRef};
 use rolldown_rstr::{Rstr, ToRstr};
 use rustc_hash::{FxHashMap, FxHashSet};
 
+use super::{minify::short_name, reserved_names::RESERVED_NAMES};
 use crate::types::{module_table::NormalModuleVec, symbols::Symbols};
 
 #[derive(Debug)]
@@ -12,6 +13,10 @@ pub struct Renamer<'name> {
   used_canonical_names: FxHashSet<Cow<'name, Rstr>>,
   canonical_names: FxHashMap<SymbolRef, Rstr>,
   symbols: &'name Symbols,
+  /// Give symbols the shortest names that are available instead of their original names.
+  minify: bool,
+  /// The index of the next short name tried for top-level symbols.
+  next_short_name: usize,
 }
 
 impl<'name> Renamer<'name> {
@@ -19,10 +24,18 @@ impl<'name> Renamer<'name> {
     Self {
       canonical_names: FxHashMap::default(),
       symbols,
-      used_canonical_names: FxHashSet::default(),
+      used_canonical_names: RESERVED_NAMES.iter().map(|name| Cow::Owned((*name).into())).collect(),
+      minify: false,
+      next_short_name: 0,
     }
   }
 
+  #[must_use]
+  pub fn with_minify(mut self, minify: bool) -> Self {
+    self.minify = minify;
+    self
+  }
+
   pub fn reserve(&mut self, name: Cow<'name, Rstr>) {
     self.used_canonical_names.insert(name);
   }
@@ -33,6 +46,16 @@ impl<'name> Renamer<'name> {
       Cow::Owned(self.symbols.get_original_name(canonical_ref).to_rstr());
 
     match self.canonical_names.entry(canonical_ref) {
+      std::collections::hash_map::Entry::Vacant(vacant) if self.minify => {
+        let mut candidate_name: Cow<'_, Rstr> = Cow::Owned(short_name(self.next_short_name).into());
+        while self.used_canonical_names.contains(&candidate_name) {
+          self.next_short_name += 1;
+          candidate_name = Cow::Owned(short_name(self.next_short_name).into());
+        }
+        self.next_short_name += 1;
+        self.used_canonical_names.insert(candidate_name.clone());
+        vacant.insert(candidate_name.into_owned());
+      }
       std::collections::hash_map::Entry::Vacant(vacant) => {
         let mut count = 0;
         let mut candidate_name = original_name.clone();
@@ -62,13 +85,35 @@ impl<'name> Renamer<'name> {
       scope_id: ScopeId,
       stack: &mut Vec<Cow<FxHashSet<Cow<'name, Rstr>>>>,
       canonical_names: &mut FxHashMap<SymbolRef, Rstr>,
+      minify: bool,
     ) {
       let bindings = module.scope.get_bindings(scope_id);
       let mut used_canonical_names_for_this_scope = FxHashSet::default();
       used_canonical_names_for_this_scope.shrink_to(bindings.len());
+      // Short names are tried from the start in each scope, so sibling scopes share them.
+      let mut next_short_name = 0;
       bindings.iter().for_each(|(binding_name, symbol_id)| {
-        used_canonical_names_for_this_scope.insert(Cow::Owned(binding_name.to_rstr()));
         let binding_ref: SymbolRef = (module.id, *symbol_id).into();
+        if minify {
+          if let std::collections::hash_map::Entry::Vacant(slot) =
+            canonical_names.entry(binding_ref)
+          {
+            let mut candidate_name = Cow::Owned(short_name(next_short_name).into());
+            while used_canonical_names_for_this_scope.contains(&candidate_name)
+              || stack
+                .iter()
+                .any(|used_canonical_names| used_canonical_names.contains(&candidate_name))
+            {
+              next_short_name += 1;
+              candidate_name = Cow::Owned(short_name(next_short_name).into());
+            }
+            next_short_name += 1;
+            used_canonical_names_for_this_scope.insert(candidate_name.clone());
+            slot.insert(candidate_name.into_owned());
+          }
+          return;
+        }
+        used_canonical_names_for_this_scope.insert(Cow::Owned(binding_name.to_rstr()));
 
         let mut count = 1;
         let mut candidate_name = Cow::Owned(binding_name.to_rstr());
@@ -96,7 +141,7 @@ impl<'name> Renamer<'name> {
       stack.push(Cow::Owned(used_canonical_names_for_this_scope));
       let child_scopes = module.scope.get_child_ids(scope_id).cloned().unwrap_or_default();
       child_scopes.into_iter().for_each(|scope_id| {
-        rename_symbols_of_nested_scopes(module, scope_id, stack, canonical_names);
+        rename_symbols_of_nested_scopes(module, scope_id, stack, canonical_names, minify);
       });
       stack.pop();
     }
@@ -117,6 +162,7 @@ impl<'name> Renamer<'name> {
             *child_scope_id,
             &mut stack,
             &mut canonical_names,
+            self.minify,
           );
           canonical_names
         })
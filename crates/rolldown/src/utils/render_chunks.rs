@@ -1,6 +1,6 @@
 use rolldown_common::RenderedChunk;
 use rolldown_plugin::RenderChunkArgs;
-use rolldown_sourcemap::SourceMap;
+use rolldown_sourcemap::{SourceMap, SourceMapChain};
 use rolldown_utils::block_on_spawn_all;
 
 use crate::{
@@ -12,16 +12,21 @@ pub async fn render_chunks<'a>(
   plugin_driver: &SharedPluginDriver,
   chunks: impl Iterator<Item = (String, Option<SourceMap>, RenderedChunk)>,
 ) -> Result<Vec<(String, Option<SourceMap>, RenderedChunk)>, BatchedErrors> {
-  // TODO support `render_chunk` hook return map
   let result = block_on_spawn_all(chunks.map(|(content, map, rendered_chunk)| async move {
     tracing::info!("render_chunks");
-    match plugin_driver
-      .render_chunk(RenderChunkArgs { code: content, chunk: &rendered_chunk })
-      .await
-    {
-      Ok(value) => Ok((value, map, rendered_chunk)),
-      Err(e) => Err(e),
-    }
+    let (code, sourcemap_chain) =
+      plugin_driver.render_chunk(RenderChunkArgs { code: content, chunk: &rendered_chunk }).await?;
+    // The chunk map is the base of the chain, the maps returned by `render_chunk` hooks are applied on top of it.
+    // If the chunk doesn't have a map, sourcemaps are disabled and the hook maps are ignored.
+    let map = match map {
+      Some(map) if !sourcemap_chain.is_empty() => {
+        let mut chain = SourceMapChain::from(vec![map]);
+        chain.extend(sourcemap_chain);
+        chain.collapse()?
+      }
+      map => map,
+    };
+    Ok((code, map, rendered_chunk))
   }));
 
   result.into_batched_result()
@@ -1,7 +1,12 @@
+use std::path::Path;
+
+use oxc::syntax::identifier::{is_identifier_part, is_identifier_start};
 use rolldown_common::NormalModule;
+use rolldown_error::BuildError;
 use rolldown_oxc_utils::{OxcCompiler, OxcProgram};
-use rolldown_sourcemap::SourceMap;
+use rolldown_sourcemap::{concat_sourcemaps, SourceMap, SourceMapChain};
 use string_wizard::MagicString;
+use sugar_path::SugarPath;
 
 use crate::types::module_render_context::ModuleRenderContext;
 
@@ -10,20 +15,114 @@ pub struct RenderedNormalModuleOutput {
   pub map: Option<SourceMap>,
 }
 
-#[allow(clippy::unnecessary_wraps)]
+/// Render the module. If `sourcemap_dir` is given, a source map that maps the rendered code back to the
+/// original source of the module is generated, with source paths relative to `sourcemap_dir`.
 pub fn render_normal_module(
   module: &NormalModule,
-  _ctx: &ModuleRenderContext<'_>,
+  ctx: &ModuleRenderContext<'_>,
   ast: &OxcProgram,
-) -> Option<RenderedNormalModuleOutput> {
+  sourcemap_dir: Option<&Path>,
+) -> Result<Option<RenderedNormalModuleOutput>, BuildError> {
   if ast.program().body.is_empty() {
-    None
-  } else {
-    let generated_code = OxcCompiler::print(ast);
-    let mut source = MagicString::new(generated_code);
+    return Ok(None);
+  }
+
+  let minify = ctx.output_options.minify;
+  let compact = ctx.output_options.compact || minify;
+  let (generated_code, map) = match sourcemap_dir {
+    None => (OxcCompiler::print(ast, compact), None),
+    Some(sourcemap_dir) => {
+      let module_id = module.resource_id.expect_file();
+      let module_path = Path::new(module_id.as_str());
+      // Virtual modules aren't on the disk, so their sources are named by their ids without `\0`.
+      let source_name = if module_id.is_virtual() {
+        sanitize_virtual_source(module_id)
+      } else {
+        relative_source_path(module_path, sourcemap_dir)
+      };
+      let (generated_code, codegen_map) =
+        OxcCompiler::print_with_sourcemap(ast, &source_name, compact);
+      // Sources of maps returned by plugins are relative to the module.
+      let module_dir = module_path.parent().unwrap_or(module_path);
+      let mut sourcemap_chain = module
+        .sourcemap_chain
+        .iter()
+        .cloned()
+        .map(|mut map| {
+          map.sources = map
+            .sources
+            .iter()
+            .map(|source| {
+              if module_id.is_virtual() {
+                sanitize_virtual_source(source)
+              } else {
+                relative_source_path(&module_dir.join(source), sourcemap_dir)
+              }
+            })
+            .collect();
+          map
+        })
+        .collect::<SourceMapChain>();
+      // The codegen map points to the code after `load` and `transform` hooks, so it goes on top of their maps.
+      if let Some(codegen_map) = codegen_map {
+        let mut codegen_map = SourceMap::from_json(&codegen_map)?;
+        if minify {
+          restore_original_names(&mut codegen_map, ast.source())?;
+        }
+        sourcemap_chain.push(codegen_map);
+      }
+      (generated_code, sourcemap_chain.collapse()?)
+    }
+  };
 
-    source.prepend(format!("// {}\n", module.pretty_path));
+  // Compact output leaves out the comments of module paths
+  if compact {
+    return Ok(Some(RenderedNormalModuleOutput { code: MagicString::new(generated_code), map }));
+  }
+
+  let comment = format!("// {}", module.pretty_path);
 
-    Some(RenderedNormalModuleOutput { code: source, map: None })
+  match map {
+    None => {
+      let mut source = MagicString::new(generated_code);
+      source.prepend(format!("{comment}\n"));
+      Ok(Some(RenderedNormalModuleOutput { code: source, map: None }))
+    }
+    Some(map) => {
+      // Shift the mappings by the leading comment line.
+      let (code, map) = concat_sourcemaps(&[(comment, None), (generated_code, Some(map))])?;
+      Ok(Some(RenderedNormalModuleOutput { code: MagicString::new(code), map: Some(map) }))
+    }
   }
 }
+
+/// The codegen map carries the shortened names, which are replaced with the identifiers at the original
+/// positions, so debuggers show the names in the source.
+fn restore_original_names(map: &mut SourceMap, source: &str) -> Result<(), BuildError> {
+  let line_starts = std::iter::once(0)
+    .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+    .collect::<Vec<_>>();
+  map.map_names(|line, column, _| {
+    let line = &source[*line_starts.get(line as usize)?..];
+    // Columns are counted in UTF-16 code units.
+    let mut utf16_column = 0;
+    let start = line.char_indices().find_map(|(index, char)| {
+      if utf16_column == column as usize {
+        return Some(index);
+      }
+      utf16_column += char.len_utf16();
+      None
+    })?;
+    let name = &line[start..];
+    let len = name.find(|char| !is_identifier_part(char)).unwrap_or(name.len());
+    (len > 0 && name.starts_with(is_identifier_start)).then(|| name[..len].to_string())
+  })
+}
+
+fn sanitize_virtual_source(source: &str) -> String {
+  source.replace('\0', "")
+}
+
+fn relative_source_path(path: &Path, dir: &Path) -> String {
+  path.relative(dir).to_string_lossy().replace('\\', "/")
+}
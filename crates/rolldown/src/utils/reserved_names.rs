@@ -0,0 +1,55 @@
+/// Keywords and reserved words in both non-strict and strict mode, and globals that can't be shadowed safely, which
+/// aren't given to bindings by the renamer or exported by their names.
+pub const RESERVED_NAMES: &[&str] = &[
+  "do",
+  "if",
+  "in",
+  "for",
+  "let",
+  "new",
+  "try",
+  "var",
+  "NaN",
+  "case",
+  "else",
+  "enum",
+  "eval",
+  "null",
+  "this",
+  "true",
+  "void",
+  "with",
+  "await",
+  "break",
+  "catch",
+  "class",
+  "const",
+  "false",
+  "super",
+  "throw",
+  "while",
+  "yield",
+  "delete",
+  "export",
+  "import",
+  "public",
+  "return",
+  "static",
+  "switch",
+  "typeof",
+  "default",
+  "extends",
+  "finally",
+  "package",
+  "private",
+  "continue",
+  "debugger",
+  "function",
+  "arguments",
+  "interface",
+  "protected",
+  "Infinity",
+  "undefined",
+  "implements",
+  "instanceof",
+];
@@ -1,17 +1,46 @@
+use std::path::Path;
+
 use once_cell::sync::Lazy;
 use regex::Regex;
-use rolldown_common::{FilePath, ModuleType};
+use rolldown_common::{FilePath, ModuleSideEffects, ModuleType, UnresolvedImportCheck};
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
-use rolldown_plugin::{HookResolveIdArgs, HookResolveIdArgsOptions};
+use rolldown_plugin::{
+  DynamicImportSpecifier, HookResolveDynamicImportArgs, HookResolveIdArgs,
+  HookResolveIdArgsOptions, HookResolveIdOutput, SkippedResolveCall,
+};
 use rolldown_resolver::Resolver;
 
-use crate::{plugin_driver::SharedPluginDriver, types::resolved_request_info::ResolvedRequestInfo};
+use crate::{
+  options::input_options::SharedInputOptions,
+  plugin_driver::SharedPluginDriver,
+  types::resolved_request_info::ResolvedRequestInfo,
+  utils::{
+    data_url::{is_data_url, parse_data_url},
+    match_id_pattern,
+  },
+};
 
 static HTTP_URL_REGEX: Lazy<Regex> =
   Lazy::new(|| Regex::new(r"^(https?:)?\/\/").expect("Init HTTP_URL_REGEX failed"));
-static DATA_URL_REGEX: Lazy<Regex> =
-  Lazy::new(|| Regex::new(r"^\s*data:").expect("Init DATA_URL_REGEX failed"));
+
+/// Ids resolved by plugins are only known by their extensions.
+fn resolved_by_plugin(r: HookResolveIdOutput) -> ResolvedRequestInfo {
+  let module_type = r.module_type.unwrap_or_else(|| {
+    match Path::new(&r.id).extension().and_then(std::ffi::OsStr::to_str) {
+      Some("json") => ModuleType::Json,
+      Some("css") => ModuleType::Css,
+      _ => ModuleType::Unknown,
+    }
+  });
+  ResolvedRequestInfo {
+    path: r.id.into(),
+    module_type,
+    is_external: matches!(r.external, Some(true)),
+    module_side_effects: r.module_side_effects,
+    package_json_side_effects: None,
+  }
+}
 
 #[allow(clippy::no_effect_underscore_binding)]
 pub async fn resolve_id<T: FileSystem + Default>(
@@ -20,40 +49,215 @@ pub async fn resolve_id<T: FileSystem + Default>(
   request: &str,
   importer: Option<&FilePath>,
   options: HookResolveIdArgsOptions,
+  skipped_resolve_calls: &[SkippedResolveCall],
   _preserve_symlinks: bool,
 ) -> Result<ResolvedRequestInfo, BuildError> {
+  // `resolve.alias` comes first, so plugins and the internal resolver see the aliased request
+  let import_kind = options.kind;
+  let aliased_request = resolver.apply_alias(request);
+  let request = aliased_request.as_ref();
+
   // Run plugin resolve_id first, if it is None use internal resolver as fallback
   if let Some(r) = plugin_driver
-    .resolve_id(&HookResolveIdArgs {
-      importer: importer.map(std::convert::AsRef::as_ref),
-      source: request,
-      options,
-    })
+    .resolve_id(
+      &HookResolveIdArgs {
+        importer: importer.map(std::convert::AsRef::as_ref),
+        source: request,
+        options,
+      },
+      skipped_resolve_calls,
+    )
     .await?
   {
+    return Ok(resolved_by_plugin(r));
+  }
+
+  // Ids of virtual modules, like the ones that `load` hooks import as they are, are only known by plugins
+  if request.starts_with('\0') {
     return Ok(ResolvedRequestInfo {
-      path: r.id.into(),
+      path: request.to_string().into(),
       module_type: ModuleType::Unknown,
-      is_external: matches!(r.external, Some(true)),
+      is_external: false,
+      module_side_effects: None,
+      package_json_side_effects: None,
+    });
+  }
+
+  // Data urls are modules by themselves, which are never external and never touch the file system
+  if is_data_url(request) {
+    return Ok(ResolvedRequestInfo {
+      path: request.to_string().into(),
+      module_type: parse_data_url(request)?.module_type,
+      is_external: false,
+      module_side_effects: None,
+      package_json_side_effects: None,
     });
   }
 
-  // Auto external http url or data url
-  if HTTP_URL_REGEX.is_match(request) || DATA_URL_REGEX.is_match(request) {
+  // Auto external http url
+  if HTTP_URL_REGEX.is_match(request) {
     return Ok(ResolvedRequestInfo {
       path: request.to_string().into(),
       module_type: ModuleType::Unknown,
       is_external: true,
+      module_side_effects: None,
+      package_json_side_effects: None,
     });
   }
 
   // Rollup external node packages by default.
   // Rolldown will follow esbuild behavior to resolve it by default.
   // See https://github.com/rolldown-rs/rolldown/issues/282
-  let resolved = resolver.resolve(importer, request)?;
+  let resolved = resolver.resolve(importer, request, import_kind)?;
   Ok(ResolvedRequestInfo {
     path: resolved.resolved,
     module_type: resolved.module_type,
     is_external: false,
+    module_side_effects: None,
+    package_json_side_effects: resolved.package_json_side_effects,
   })
 }
+
+/// Resolve the request and check it against `InputOptions.external`, following Rollup. `external` is
+/// called with the raw request first, then with the resolved id if the request isn't external yet.
+/// `resolve_id` hooks in `skipped_resolve_calls` are skipped, which is used by `this.resolve` of plugins.
+pub async fn resolve_id_with_external<T: FileSystem + Default>(
+  input_options: &SharedInputOptions,
+  resolver: &Resolver<T>,
+  plugin_driver: &SharedPluginDriver,
+  request: &str,
+  importer: Option<&FilePath>,
+  options: HookResolveIdArgsOptions,
+  skipped_resolve_calls: &[SkippedResolveCall],
+) -> Result<ResolvedRequestInfo, BuildError> {
+  let importer_id = importer.map(|importer| importer.as_str().to_string());
+
+  // Check external with unresolved path. Data urls are never external.
+  if !is_data_url(request)
+    && input_options.external.call(request.to_string(), importer_id.clone(), false).await?
+  {
+    return unresolved_external(input_options, request).await;
+  }
+
+  let info =
+    resolve_id(resolver, plugin_driver, request, importer, options, skipped_resolve_calls, false)
+      .await?;
+  check_resolved_external(input_options, info, importer_id).await
+}
+
+/// Resolve `import(...)` with `resolve_dynamic_import` hooks first. String specifiers not resolved by them fall
+/// back to `resolve_id_with_external`, while expressions not resolved by them return `None`, which are left as
+/// they are in the output.
+pub async fn resolve_dynamic_import<T: FileSystem + Default>(
+  input_options: &SharedInputOptions,
+  resolver: &Resolver<T>,
+  plugin_driver: &SharedPluginDriver,
+  specifier: DynamicImportSpecifier<'_>,
+  importer: &FilePath,
+  options: HookResolveIdArgsOptions,
+) -> Result<Option<ResolvedRequestInfo>, BuildError> {
+  if let Some(r) = plugin_driver
+    .resolve_dynamic_import(&HookResolveDynamicImportArgs {
+      importer: importer.as_str(),
+      specifier,
+    })
+    .await?
+  {
+    let info = resolved_by_plugin(r);
+    return check_resolved_external(input_options, info, Some(importer.as_str().to_string()))
+      .await
+      .map(Some);
+  }
+  match specifier {
+    DynamicImportSpecifier::String(request) => resolve_id_with_external(
+      input_options,
+      resolver,
+      plugin_driver,
+      request,
+      Some(importer),
+      options,
+      &[],
+    )
+    .await
+    .map(Some),
+    DynamicImportSpecifier::Expression(_) => Ok(None),
+  }
+}
+
+/// With `checks.unresolved_import: WarnExternal`, bare specifiers that can't be resolved become externals along
+/// with a warning, instead of failing the build.
+pub async fn treat_unresolved_import_as_external(
+  input_options: &SharedInputOptions,
+  request: &str,
+  importer: &FilePath,
+  resolved: Result<ResolvedRequestInfo, BuildError>,
+) -> Result<(ResolvedRequestInfo, Option<BuildError>), BuildError> {
+  match resolved {
+    Err(err)
+      if input_options.checks.unresolved_import == UnresolvedImportCheck::WarnExternal
+        && err.code() == "UNRESOLVED_IMPORT"
+        && !request.starts_with(['.', '/'])
+        && !Path::new(request).is_absolute() =>
+    {
+      let warning = BuildError::unresolved_import_treated_as_external(request, importer.as_str())
+        .with_severity_warning();
+      Ok((unresolved_external(input_options, request).await?, Some(warning)))
+    }
+    resolved => resolved.map(|info| (info, None)),
+  }
+}
+
+/// The external module of the request as it is.
+async fn unresolved_external(
+  input_options: &SharedInputOptions,
+  request: &str,
+) -> Result<ResolvedRequestInfo, BuildError> {
+  let mut info = ResolvedRequestInfo {
+    path: request.to_string().into(),
+    module_type: ModuleType::Unknown,
+    is_external: true,
+    module_side_effects: None,
+    package_json_side_effects: None,
+  };
+  info.module_side_effects = Some(module_side_effects(input_options, &info).await?);
+  Ok(info)
+}
+
+/// Check external with the resolved path, then decide side effects of the module if they're still unknown.
+async fn check_resolved_external(
+  input_options: &SharedInputOptions,
+  mut info: ResolvedRequestInfo,
+  importer_id: Option<String>,
+) -> Result<ResolvedRequestInfo, BuildError> {
+  if !info.is_external && !is_data_url(&info.path.path) {
+    info.is_external =
+      input_options.external.call(info.path.path.to_string(), importer_id, true).await?;
+  }
+  if info.module_side_effects.is_none() {
+    info.module_side_effects = Some(module_side_effects(input_options, &info).await?);
+  }
+  Ok(info)
+}
+
+/// Decide side effects of the resolved module by `treeshake.module_side_effects`, which only matters if tree
+/// shaking is enabled.
+async fn module_side_effects(
+  input_options: &SharedInputOptions,
+  info: &ResolvedRequestInfo,
+) -> Result<bool, BuildError> {
+  let Some(treeshake) = &input_options.treeshake else {
+    return Ok(true);
+  };
+  let id = info.path.path.as_str();
+  match &treeshake.module_side_effects {
+    ModuleSideEffects::Boolean(true) => Ok(info.package_json_side_effects.unwrap_or(true)),
+    ModuleSideEffects::Boolean(false) => Ok(false),
+    ModuleSideEffects::NoExternal => {
+      Ok(!info.is_external && info.package_json_side_effects.unwrap_or(true))
+    }
+    ModuleSideEffects::Patterns(patterns) => {
+      Ok(patterns.iter().any(|pattern| match_id_pattern(pattern, id, &input_options.cwd)))
+    }
+    ModuleSideEffects::Fn(f) => f(id.to_string(), info.is_external).await,
+  }
+}
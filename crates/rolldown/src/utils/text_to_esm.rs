@@ -0,0 +1,10 @@
+/// Turn the content of a `text` module into an ES module, whose default export is the content as a string.
+pub fn text_to_esm(content: &str) -> String {
+  // JSON strings are valid JavaScript strings
+  format!("export default {};\n", serde_json::Value::String(content.to_string()))
+}
+
+/// Turn the content of a `base64` module into an ES module, whose default export is the content encoded in base64.
+pub fn base64_to_esm(content: &[u8]) -> String {
+  format!("export default \"{}\";\n", base64_simd::Base64::STANDARD.encode_to_boxed_str(content))
+}
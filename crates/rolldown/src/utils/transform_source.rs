@@ -1,6 +1,6 @@
-use rolldown_common::ResolvedPath;
-use rolldown_plugin::HookTransformArgs;
-use rolldown_sourcemap::SourceMap;
+use rolldown_common::{Loader, ResolvedPath};
+use rolldown_plugin::{HookTransformArgs, SharedTransformDependencies};
+use rolldown_sourcemap::SourceMapChain;
 
 use crate::{error::BatchedErrors, plugin_driver::PluginDriver};
 
@@ -8,12 +8,23 @@ pub async fn transform_source(
   plugin_driver: &PluginDriver,
   resolved_path: &ResolvedPath,
   source: String,
-  sourcemap_chain: &mut Vec<SourceMap>,
+  loaded_sourcemap_chain: &SourceMapChain,
+  sourcemap_chain: &mut SourceMapChain,
+  transform_dependencies: &SharedTransformDependencies,
+  loader: &mut Option<Loader>,
 ) -> Result<String, BatchedErrors> {
-  let (code, map_chain) =
-    plugin_driver.transform(&HookTransformArgs { id: &resolved_path.path, code: &source }).await?;
+  let (code, map_chain, transformed_loader) = plugin_driver
+    .transform(
+      &HookTransformArgs { id: &resolved_path.path, code: &source },
+      loaded_sourcemap_chain,
+      transform_dependencies,
+    )
+    .await?;
 
   sourcemap_chain.extend(map_chain);
+  if transformed_loader.is_some() {
+    *loader = transformed_loader;
+  }
 
   Ok(code)
 }
@@ -0,0 +1,543 @@
+use std::fmt::Write;
+
+use oxc::{
+  allocator::Allocator,
+  ast::{
+    ast::{
+      ArrowFunctionExpression, BindingPattern, BindingPatternKind, CallExpression, Class,
+      ClassElement, Declaration, ExportDefaultDeclarationKind, Expression, FormalParameter,
+      Function, ImportDeclarationSpecifier, MethodDefinitionKind, ModifierKind, ModuleDeclaration,
+      NewExpression, Program, PropertyDefinition, SimpleAssignmentTarget, Statement,
+      TSEnumDeclaration, TSEnumMemberName, TSImportEqualsDeclaration, TSModuleDeclaration,
+      TSModuleDeclarationBody, TSModuleReference, TaggedTemplateExpression,
+    },
+    VisitMut,
+  },
+  parser::Parser,
+  semantic::{ScopeTree, SymbolTable},
+  span::{GetSpan, SourceType, Span},
+  syntax::identifier::is_identifier_name,
+};
+use rolldown_oxc_utils::TakeIn;
+use rustc_hash::FxHashSet;
+
+/// Strips TypeScript syntax, so the module is scanned like JavaScript.
+///
+/// Declarations that only exist in types are removed, like interfaces, type aliases, `declare` blocks and type-only
+/// imports and exports. Type annotations are removed too, so they never reference bindings. Enums are lowered to
+/// objects like `tsc` does, while const enums aren't inlined.
+pub struct TypeScriptStripper<'ast, 'a> {
+  alloc: &'ast Allocator,
+  source: &'a str,
+  /// The first syntax that can't be stripped, like namespaces with values.
+  pub unsupported: Option<(Span, &'static str)>,
+}
+
+impl<'ast, 'a> TypeScriptStripper<'ast, 'a> {
+  pub fn new(alloc: &'ast Allocator, source: &'a str) -> Self {
+    Self { alloc, source, unsupported: None }
+  }
+
+  fn report_unsupported(&mut self, span: Span, syntax: &'static str) {
+    self.unsupported.get_or_insert((span, syntax));
+  }
+
+  /// Parses a statement generated by lowering, which is stripped as well, since it could contain code of the source.
+  fn parse_statement(&mut self, code: &str) -> Statement<'ast> {
+    let code = self.alloc.alloc_str(code);
+    let source_type = SourceType::default().with_module(true).with_typescript(true);
+    let body = Parser::new(self.alloc, code, source_type).parse().program.body;
+    let mut stmt = body.into_iter().next().expect("the generated code should be a statement");
+    self.visit_statement(&mut stmt);
+    stmt
+  }
+
+  /// `enum E { A, B = 'b' }` becomes
+  ///
+  /// ```js
+  /// var E = ((E) => {
+  ///   var A, B;
+  ///   E[E["A"] = A = 0] = "A";
+  ///   E["B"] = B = 'b';
+  ///   return E;
+  /// })(E || {});
+  /// ```
+  ///
+  /// Members are declared as locals, so initializers could refer to previous members by names.
+  fn lower_enum(&mut self, decl: &TSEnumDeclaration<'ast>) -> Statement<'ast> {
+    let name = decl.id.name.as_str();
+    let mut locals = vec![];
+    let mut assignments = String::new();
+    // The previous member and its value, if it's a known number, which decide the value of members without
+    // initializers.
+    let mut prev: Option<(String, Option<f64>)> = None;
+    for member in &decl.members {
+      let key = match &member.id {
+        TSEnumMemberName::Identifier(ident) => ident.name.to_string(),
+        TSEnumMemberName::StringLiteral(lit) => lit.value.to_string(),
+        TSEnumMemberName::ComputedPropertyName(_) | TSEnumMemberName::NumericLiteral(_) => {
+          self.report_unsupported(member.span, "computed enum members");
+          return self.parse_statement(&format!("var {name};"));
+        }
+      };
+      let quoted_key = serde_json::to_string(&key).expect("a string should be serializable");
+      let access = format!("{name}[{quoted_key}]");
+      let (value, is_string, number) = match &member.initializer {
+        Some(init) => {
+          let span = init.span();
+          let number = match init {
+            Expression::NumericLiteral(lit) => Some(lit.value),
+            _ => None,
+          };
+          let is_string =
+            matches!(init, Expression::StringLiteral(_) | Expression::TemplateLiteral(_));
+          (self.source[span.start as usize..span.end as usize].to_string(), is_string, number)
+        }
+        None => match &prev {
+          None => ("0".to_string(), false, Some(0.0)),
+          Some((_, Some(number))) => ((number + 1.0).to_string(), false, Some(number + 1.0)),
+          Some((prev_access, None)) => (format!("{prev_access} + 1"), false, None),
+        },
+      };
+      let local = if is_identifier_name(&key) && key != name {
+        locals.push(key.clone());
+        format!("{key} = ")
+      } else {
+        String::new()
+      };
+      // Only numbers are mapped back to names.
+      if is_string {
+        writeln!(assignments, "{access} = {local}{value};")
+      } else {
+        writeln!(assignments, "{name}[{access} = {local}{value}] = {quoted_key};")
+      }
+      .expect("writing to a string should not fail");
+      prev = Some((access, number));
+    }
+
+    let mut code = format!("var {name} = (({name}) => {{\n");
+    if !locals.is_empty() {
+      writeln!(code, "var {};", locals.join(", ")).expect("writing to a string should not fail");
+    }
+    write!(code, "{assignments}return {name};\n}})({name} || {{}});")
+      .expect("writing to a string should not fail");
+    self.parse_statement(&code)
+  }
+
+  /// `import x = require('x')` becomes `const x = require('x')`, and `import x = A.B` becomes `const x = A.B`.
+  fn lower_import_equals(&mut self, decl: &TSImportEqualsDeclaration<'ast>) -> Statement<'ast> {
+    let name = decl.id.name.as_str();
+    match &*decl.module_reference {
+      TSModuleReference::ExternalModuleReference(reference) => {
+        let specifier = serde_json::to_string(reference.expression.value.as_str())
+          .expect("a string should be serializable");
+        let mut stmt = self.parse_statement(&format!("const {name} = require({specifier});"));
+        // `require` calls are recorded by their spans, so the generated one takes the span of the reference.
+        if let Statement::Declaration(Declaration::VariableDeclaration(var_decl)) = &mut stmt {
+          if let Some(Expression::CallExpression(call_expr)) =
+            var_decl.declarations.iter_mut().next().and_then(|declarator| declarator.init.as_mut())
+          {
+            call_expr.span = reference.span;
+          }
+        }
+        stmt
+      }
+      TSModuleReference::TypeName(type_name) => {
+        let span = type_name.span();
+        let entity = &self.source[span.start as usize..span.end as usize];
+        self.parse_statement(&format!("const {name} = {entity};"))
+      }
+    }
+  }
+
+  /// Turns parameter properties of the constructor, like `constructor(private x)`, into assignments.
+  fn lower_parameter_properties(&mut self, class: &mut Class<'ast>) {
+    let is_derived = class.super_class.is_some();
+    let Some(constructor) = class.body.body.iter_mut().find_map(|element| match element {
+      ClassElement::MethodDefinition(def) if def.kind == MethodDefinitionKind::Constructor => {
+        Some(def)
+      }
+      _ => None,
+    }) else {
+      return;
+    };
+    let mut names = vec![];
+    for param in constructor.value.params.items.iter_mut() {
+      if param.accessibility.is_none() && !param.readonly && !param.r#override {
+        continue;
+      }
+      param.accessibility = None;
+      param.readonly = false;
+      param.r#override = false;
+      let name = match &param.pattern.kind {
+        BindingPatternKind::BindingIdentifier(ident) => &ident.name,
+        BindingPatternKind::AssignmentPattern(pat) => match &pat.left.kind {
+          BindingPatternKind::BindingIdentifier(ident) => &ident.name,
+          _ => continue,
+        },
+        _ => continue,
+      };
+      names.push(name.to_string());
+    }
+    let Some(body) = &mut constructor.value.body else {
+      return;
+    };
+    // `this` can't be used before `super()` in derived classes.
+    let mut idx = if is_derived {
+      body.statements.iter().position(is_super_call).map_or(0, |idx| idx + 1)
+    } else {
+      0
+    };
+    for name in names {
+      let stmt = self.parse_statement(&format!("this.{name} = {name};"));
+      body.statements.insert(idx, stmt);
+      idx += 1;
+    }
+  }
+}
+
+fn is_super_call(stmt: &Statement) -> bool {
+  matches!(
+    stmt,
+    Statement::ExpressionStatement(stmt)
+      if matches!(&stmt.expression, Expression::CallExpression(call_expr) if matches!(call_expr.callee, Expression::Super(_)))
+  )
+}
+
+/// Whether the statement only exists in types, which is removed entirely.
+fn is_type_only_statement(stmt: &Statement) -> bool {
+  match stmt {
+    Statement::Declaration(decl) => is_type_only_declaration(decl),
+    Statement::ModuleDeclaration(decl) => match &**decl {
+      ModuleDeclaration::ImportDeclaration(decl) => {
+        decl.import_kind.is_type()
+          // `import { type A } from './a'` is removed too, so `./a` isn't loaded.
+          || decl.specifiers.as_ref().is_some_and(|specifiers| {
+            !specifiers.is_empty()
+              && specifiers.iter().all(|specifier| {
+                matches!(specifier, ImportDeclarationSpecifier::ImportSpecifier(specifier) if specifier.import_kind.is_type())
+              })
+          })
+      }
+      ModuleDeclaration::ExportNamedDeclaration(decl) => {
+        decl.export_kind.is_type()
+          || decl.declaration.as_ref().is_some_and(is_type_only_declaration)
+          || (!decl.specifiers.is_empty()
+            && decl.specifiers.iter().all(|specifier| specifier.export_kind.is_type()))
+      }
+      ModuleDeclaration::ExportAllDeclaration(decl) => decl.export_kind.is_type(),
+      ModuleDeclaration::ExportDefaultDeclaration(decl) => match &decl.declaration {
+        ExportDefaultDeclarationKind::TSInterfaceDeclaration(_) => true,
+        ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.is_typescript_syntax(),
+        _ => false,
+      },
+      ModuleDeclaration::TSNamespaceExportDeclaration(_) => true,
+      ModuleDeclaration::TSExportAssignment(_) => false,
+    },
+    _ => false,
+  }
+}
+
+fn is_type_only_declaration(decl: &Declaration) -> bool {
+  match decl {
+    Declaration::VariableDeclaration(_)
+    | Declaration::FunctionDeclaration(_)
+    | Declaration::ClassDeclaration(_) => decl.is_typescript_syntax(),
+    Declaration::UsingDeclaration(_) => false,
+    Declaration::TSTypeAliasDeclaration(_) | Declaration::TSInterfaceDeclaration(_) => true,
+    Declaration::TSEnumDeclaration(decl) => decl.modifiers.contains(ModifierKind::Declare),
+    Declaration::TSModuleDeclaration(decl) => !module_has_values(decl),
+    Declaration::TSImportEqualsDeclaration(decl) => decl.import_kind.is_type(),
+  }
+}
+
+fn module_has_values(decl: &TSModuleDeclaration) -> bool {
+  if decl.modifiers.contains(ModifierKind::Declare) {
+    return false;
+  }
+  match &decl.body {
+    TSModuleDeclarationBody::TSModuleDeclaration(decl) => module_has_values(decl),
+    TSModuleDeclarationBody::TSModuleBlock(block) => {
+      block.body.iter().any(|stmt| !is_type_only_statement(stmt))
+    }
+  }
+}
+
+/// The expression wrapped by `x as T`, `x satisfies T`, `<T>x`, `x!` or `f<T>`.
+fn type_wrapped_expression<'b, 'ast>(
+  expr: &'b mut Expression<'ast>,
+) -> Option<&'b mut Expression<'ast>> {
+  match expr {
+    Expression::TSAsExpression(expr) => Some(&mut expr.expression),
+    Expression::TSSatisfiesExpression(expr) => Some(&mut expr.expression),
+    Expression::TSTypeAssertion(expr) => Some(&mut expr.expression),
+    Expression::TSNonNullExpression(expr) => Some(&mut expr.expression),
+    Expression::TSInstantiationExpression(expr) => Some(&mut expr.expression),
+    _ => None,
+  }
+}
+
+impl<'ast, 'a> VisitMut<'ast> for TypeScriptStripper<'ast, 'a> {
+  fn visit_statements(&mut self, stmts: &mut oxc::allocator::Vec<'ast, Statement<'ast>>) {
+    stmts.retain(|stmt| !is_type_only_statement(stmt));
+    for stmt in stmts.iter_mut() {
+      self.visit_statement(stmt);
+    }
+  }
+
+  fn visit_statement(&mut self, stmt: &mut Statement<'ast>) {
+    match stmt {
+      Statement::Declaration(Declaration::TSEnumDeclaration(decl)) => {
+        *stmt = self.lower_enum(decl);
+        return;
+      }
+      Statement::Declaration(Declaration::TSImportEqualsDeclaration(decl)) => {
+        if decl.is_export {
+          self.report_unsupported(decl.span, "`export import` declarations");
+        }
+        *stmt = self.lower_import_equals(decl);
+        return;
+      }
+      Statement::Declaration(Declaration::TSModuleDeclaration(decl)) => {
+        self.report_unsupported(decl.span, "TypeScript namespaces with values");
+      }
+      Statement::ModuleDeclaration(decl) => match &mut **decl {
+        ModuleDeclaration::ExportNamedDeclaration(decl) => {
+          decl.specifiers.retain(|specifier| !specifier.export_kind.is_type());
+          match &decl.declaration {
+            Some(Declaration::TSEnumDeclaration(enum_decl)) => {
+              if let Statement::Declaration(lowered) = self.lower_enum(enum_decl) {
+                decl.declaration = Some(lowered);
+              }
+              return;
+            }
+            Some(Declaration::TSModuleDeclaration(module_decl)) => {
+              self.report_unsupported(module_decl.span, "TypeScript namespaces with values");
+            }
+            _ => {}
+          }
+        }
+        ModuleDeclaration::ImportDeclaration(decl) => {
+          if let Some(specifiers) = &mut decl.specifiers {
+            specifiers.retain(|specifier| {
+              !matches!(specifier, ImportDeclarationSpecifier::ImportSpecifier(specifier) if specifier.import_kind.is_type())
+            });
+          }
+        }
+        ModuleDeclaration::TSExportAssignment(decl) => {
+          self.report_unsupported(decl.span, "`export =` assignments");
+        }
+        _ => {}
+      },
+      _ => {}
+    }
+    self.visit_statement_match(stmt);
+  }
+
+  fn visit_expression(&mut self, expr: &mut Expression<'ast>) {
+    while let Some(inner) = type_wrapped_expression(expr) {
+      let inner = inner.take_in(self.alloc);
+      *expr = inner;
+    }
+    self.visit_expression_match(expr);
+  }
+
+  fn visit_simple_assignment_target(&mut self, target: &mut SimpleAssignmentTarget<'ast>) {
+    let inner = match target {
+      SimpleAssignmentTarget::AssignmentTargetIdentifier(_) => None,
+      SimpleAssignmentTarget::MemberAssignmentTarget(expr) => {
+        self.visit_member_expression(expr);
+        None
+      }
+      SimpleAssignmentTarget::TSAsExpression(expr) => Some(&mut expr.expression),
+      SimpleAssignmentTarget::TSSatisfiesExpression(expr) => Some(&mut expr.expression),
+      SimpleAssignmentTarget::TSNonNullExpression(expr) => Some(&mut expr.expression),
+      SimpleAssignmentTarget::TSTypeAssertion(expr) => Some(&mut expr.expression),
+    };
+    let Some(inner) = inner else {
+      return;
+    };
+    // `(x as any) = 1` becomes `x = 1`.
+    self.visit_expression(inner);
+    match inner.get_inner_expression() {
+      Expression::Identifier(_) | Expression::MemberExpression(_) => {}
+      _ => return,
+    }
+    let mut inner = inner.take_in(self.alloc);
+    while let Expression::ParenthesizedExpression(paren) = inner {
+      inner = paren.unbox().expression;
+    }
+    match inner {
+      Expression::Identifier(ident) => {
+        *target = SimpleAssignmentTarget::AssignmentTargetIdentifier(ident);
+      }
+      Expression::MemberExpression(member_expr) => {
+        *target = SimpleAssignmentTarget::MemberAssignmentTarget(member_expr);
+      }
+      _ => unreachable!("checked above"),
+    }
+  }
+
+  fn visit_binding_pattern(&mut self, pat: &mut BindingPattern<'ast>) {
+    pat.type_annotation = None;
+    pat.optional = false;
+    match &mut pat.kind {
+      BindingPatternKind::BindingIdentifier(ident) => self.visit_binding_identifier(ident),
+      BindingPatternKind::ObjectPattern(pat) => self.visit_object_pattern(pat),
+      BindingPatternKind::ArrayPattern(pat) => self.visit_array_pattern(pat),
+      BindingPatternKind::AssignmentPattern(pat) => self.visit_assignment_pattern(pat),
+    }
+  }
+
+  fn visit_formal_parameter(&mut self, param: &mut FormalParameter<'ast>) {
+    for decorator in param.decorators.iter_mut() {
+      self.visit_decorator(decorator);
+    }
+    self.visit_binding_pattern(&mut param.pattern);
+  }
+
+  fn visit_function(
+    &mut self,
+    func: &mut Function<'ast>,
+    _flags: Option<oxc::semantic::ScopeFlags>,
+  ) {
+    func.type_parameters = None;
+    func.return_type = None;
+    func.this_param = None;
+    if let Some(ident) = &mut func.id {
+      self.visit_binding_identifier(ident);
+    }
+    self.visit_formal_parameters(&mut func.params);
+    if let Some(body) = &mut func.body {
+      self.visit_function_body(body);
+    }
+  }
+
+  fn visit_arrow_expression(&mut self, expr: &mut ArrowFunctionExpression<'ast>) {
+    expr.type_parameters = None;
+    expr.return_type = None;
+    self.visit_formal_parameters(&mut expr.params);
+    self.visit_function_body(&mut expr.body);
+  }
+
+  fn visit_class(&mut self, class: &mut Class<'ast>) {
+    class.type_parameters = None;
+    class.super_type_parameters = None;
+    class.implements = None;
+    class.body.body.retain(|element| {
+      !element.is_typescript_syntax()
+        && !matches!(element, ClassElement::PropertyDefinition(def) if def.declare)
+    });
+    self.lower_parameter_properties(class);
+    for decorator in class.decorators.iter_mut() {
+      self.visit_decorator(decorator);
+    }
+    if let Some(id) = &mut class.id {
+      self.visit_binding_identifier(id);
+    }
+    if let Some(super_class) = &mut class.super_class {
+      self.visit_class_heritage(super_class);
+    }
+    self.visit_class_body(&mut class.body);
+  }
+
+  fn visit_method_definition(&mut self, def: &mut oxc::ast::ast::MethodDefinition<'ast>) {
+    def.accessibility = None;
+    def.optional = false;
+    def.r#override = false;
+    for decorator in def.decorators.iter_mut() {
+      self.visit_decorator(decorator);
+    }
+    self.visit_property_key(&mut def.key);
+    self.visit_function(&mut def.value, None);
+  }
+
+  fn visit_property_definition(&mut self, def: &mut PropertyDefinition<'ast>) {
+    def.type_annotation = None;
+    def.accessibility = None;
+    def.optional = false;
+    def.definite = false;
+    def.readonly = false;
+    def.r#override = false;
+    for decorator in def.decorators.iter_mut() {
+      self.visit_decorator(decorator);
+    }
+    self.visit_property_key(&mut def.key);
+    if let Some(value) = &mut def.value {
+      self.visit_expression(value);
+    }
+  }
+
+  fn visit_call_expression(&mut self, expr: &mut CallExpression<'ast>) {
+    expr.type_parameters = None;
+    for arg in expr.arguments.iter_mut() {
+      self.visit_argument(arg);
+    }
+    self.visit_expression(&mut expr.callee);
+  }
+
+  fn visit_new_expression(&mut self, expr: &mut NewExpression<'ast>) {
+    expr.type_parameters = None;
+    self.visit_expression(&mut expr.callee);
+    for arg in expr.arguments.iter_mut() {
+      self.visit_argument(arg);
+    }
+  }
+
+  fn visit_tagged_template_expression(&mut self, expr: &mut TaggedTemplateExpression<'ast>) {
+    expr.type_parameters = None;
+    self.visit_expression(&mut expr.tag);
+    self.visit_template_literal(&mut expr.quasi);
+  }
+}
+
+/// Removes imported bindings, which are only used by types, with the scopes built after stripping types. Like
+/// TypeScript, imports whose bindings are all removed are removed entirely, so their modules aren't loaded.
+///
+/// Local exports of bindings that don't exist after stripping, like `export { SomeInterface }`, are removed too.
+pub fn elide_type_only_imports(program: &mut Program, scope: &ScopeTree, symbols: &SymbolTable) {
+  let mut exported = FxHashSet::default();
+  for stmt in program.body.iter_mut() {
+    if let Statement::ModuleDeclaration(decl) = stmt {
+      if let ModuleDeclaration::ExportNamedDeclaration(decl) = &mut **decl {
+        if decl.source.is_none() {
+          decl
+            .specifiers
+            .retain(|specifier| scope.get_root_binding(specifier.local.name().as_str()).is_some());
+          exported.extend(decl.specifiers.iter().map(|specifier| specifier.local.name().clone()));
+        }
+      }
+    }
+  }
+
+  let is_used = |local: &oxc::ast::ast::BindingIdentifier| {
+    exported.contains(&local.name)
+      || local.symbol_id.get().map_or(true, |id| !symbols.get_resolved_reference_ids(id).is_empty())
+  };
+  let mut elided = FxHashSet::default();
+  for (idx, stmt) in program.body.iter_mut().enumerate() {
+    let Statement::ModuleDeclaration(decl) = stmt else {
+      continue;
+    };
+    let ModuleDeclaration::ImportDeclaration(decl) = &mut **decl else {
+      continue;
+    };
+    let Some(specifiers) = decl.specifiers.as_mut().filter(|specifiers| !specifiers.is_empty())
+    else {
+      continue;
+    };
+    specifiers.retain(|specifier| match specifier {
+      ImportDeclarationSpecifier::ImportSpecifier(specifier) => is_used(&specifier.local),
+      ImportDeclarationSpecifier::ImportDefaultSpecifier(specifier) => is_used(&specifier.local),
+      ImportDeclarationSpecifier::ImportNamespaceSpecifier(specifier) => is_used(&specifier.local),
+    });
+    if specifiers.is_empty() {
+      elided.insert(idx);
+    }
+  }
+  let mut idx = 0;
+  program.body.retain(|_| {
+    idx += 1;
+    !elided.contains(&(idx - 1))
+  });
+}
@@ -0,0 +1,541 @@
+use std::{
+  pin::Pin,
+  task::{Context, Poll},
+  time::{Duration, Instant},
+};
+
+use futures::Stream;
+use rolldown_fs::FileSystem;
+use rolldown_plugin::WatchChangeKind;
+use tokio::{
+  sync::{mpsc, watch},
+  task::JoinHandle,
+};
+
+use crate::{
+  error::{BatchedErrors, BatchedResult},
+  utils::file_snapshot::{snapshot, FileSnapshot},
+  Bundler, OutputOptions, RolldownOutput,
+};
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+  /// How often watched files are checked for changes.
+  pub poll_interval: Duration,
+  /// How long to wait for more changes once a change is detected, so a burst of changes, like saving multiple
+  /// files at once, triggers a single rebuild.
+  pub build_delay: Duration,
+}
+
+impl Default for WatchOptions {
+  fn default() -> Self {
+    Self { poll_interval: Duration::from_millis(100), build_delay: Duration::from_millis(50) }
+  }
+}
+
+/// Events of [`Watcher`], like the ones of `rollup.watch`. Every build sends `Start`, `BundleStart`, then
+/// `BundleEnd` or `Error`, and `End` once it's done.
+pub enum WatcherEvent {
+  /// A build starts, either the initial one or a rebuild triggered by changes.
+  Start,
+  BundleStart,
+  BundleEnd {
+    duration: Duration,
+    output: RolldownOutput,
+  },
+  /// The build or a `watch_change` hook failed. The watcher keeps watching, so fixing the error triggers a rebuild.
+  Error(BatchedErrors),
+  /// The build is done, and the watcher waits for changes.
+  End,
+}
+
+/// Rebuilds in the background whenever watched files change. Changes are detected by polling the content of
+/// loaded modules and files added by `this.addWatchFile`, so any [`FileSystem`] could be watched.
+pub struct Watcher {
+  events: mpsc::UnboundedReceiver<WatcherEvent>,
+  close_tx: watch::Sender<bool>,
+  handle: JoinHandle<BatchedResult<()>>,
+}
+
+impl Watcher {
+  pub(crate) fn new<T: FileSystem + Default + 'static>(
+    bundler: Bundler<T>,
+    output_options: OutputOptions,
+    options: WatchOptions,
+  ) -> Self {
+    let (events_tx, events) = mpsc::unbounded_channel();
+    let (close_tx, close_rx) = watch::channel(false);
+    let handle = tokio::spawn(run(bundler, output_options, options, events_tx, close_rx));
+    Self { events, close_tx, handle }
+  }
+
+  /// Wait for the next event. Returns `None` once the watcher is closed.
+  pub async fn recv(&mut self) -> Option<WatcherEvent> {
+    self.events.recv().await
+  }
+
+  /// Stop watching. The ongoing build, if any, is finished first, then `close_bundle` hooks are called.
+  pub async fn close(self) -> BatchedResult<()> {
+    _ = self.close_tx.send(true);
+    self.handle.await.expect("The watcher task should not panic")
+  }
+}
+
+/// Events could also be consumed as a stream, which ends once the watcher is closed.
+impl Stream for Watcher {
+  type Item = WatcherEvent;
+
+  fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    self.events.poll_recv(cx)
+  }
+}
+
+async fn run<T: FileSystem + Default + 'static>(
+  mut bundler: Bundler<T>,
+  output_options: OutputOptions,
+  options: WatchOptions,
+  events: mpsc::UnboundedSender<WatcherEvent>,
+  mut close_rx: watch::Receiver<bool>,
+) -> BatchedResult<()> {
+  // Events are dropped if nobody listens to them anymore
+  let emit = |event| _ = events.send(event);
+  loop {
+    emit(WatcherEvent::Start);
+    emit(WatcherEvent::BundleStart);
+    let start = Instant::now();
+    let ret = bundler.write_output(&output_options).await;
+    // Files are snapshotted when they are loaded, so changes made during the build trigger a rebuild too
+    let snapshots = bundler.watch_files();
+    match ret {
+      Ok(output) => emit(WatcherEvent::BundleEnd { duration: start.elapsed(), output }),
+      Err(errors) => emit(WatcherEvent::Error(errors)),
+    }
+    emit(WatcherEvent::End);
+
+    let Some(changes) = wait_for_changes(bundler.fs(), &snapshots, &options, &mut close_rx).await
+    else {
+      break;
+    };
+    for (id, kind) in changes {
+      if let Err(err) = bundler.plugin_driver().watch_change(&id, kind).await {
+        emit(WatcherEvent::Error(BatchedErrors::with_error(err)));
+      }
+    }
+  }
+  bundler.close().await
+}
+
+fn collect_changes<T: FileSystem>(
+  fs: &T,
+  snapshots: &[(String, FileSnapshot)],
+) -> Vec<(String, WatchChangeKind)> {
+  snapshots
+    .iter()
+    .filter_map(|(file, before)| {
+      let kind = match (before, snapshot(fs, file)) {
+        (None, Some(_)) => WatchChangeKind::Create,
+        (Some(_), None) => WatchChangeKind::Delete,
+        (Some(before), Some(after)) if *before != after => WatchChangeKind::Update,
+        _ => return None,
+      };
+      Some((file.clone(), kind))
+    })
+    .collect()
+}
+
+/// Wait until watched files change and the changes settle. Returns `None` if the watcher is closed meanwhile.
+async fn wait_for_changes<T: FileSystem>(
+  fs: &T,
+  snapshots: &[(String, FileSnapshot)],
+  options: &WatchOptions,
+  close_rx: &mut watch::Receiver<bool>,
+) -> Option<Vec<(String, WatchChangeKind)>> {
+  loop {
+    sleep_unless_closed(options.poll_interval, close_rx).await?;
+    if collect_changes(fs, snapshots).is_empty() {
+      continue;
+    }
+    sleep_unless_closed(options.build_delay, close_rx).await?;
+    // Changes are compared with the files of the last build, so a file changed back and forth is not a change.
+    let changes = collect_changes(fs, snapshots);
+    if !changes.is_empty() {
+      return Some(changes);
+    }
+  }
+}
+
+/// Returns `None` if the watcher is closed, or dropped, before `duration` passes.
+async fn sleep_unless_closed(
+  duration: Duration,
+  close_rx: &mut watch::Receiver<bool>,
+) -> Option<()> {
+  if *close_rx.borrow() {
+    return None;
+  }
+  tokio::select! {
+    () = tokio::time::sleep(duration) => Some(()),
+    _ = close_rx.changed() => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+      atomic::{AtomicBool, Ordering},
+      Arc, Mutex,
+    },
+    time::Duration,
+  };
+
+  use futures::StreamExt;
+  use oxc_resolver::{FileMetadata, FileSystem as OxcResolverFileSystem};
+  use rolldown_common::{AssetOptions, InputItem, Output, ResolverOptions};
+  use rolldown_fs::{FileSystem, MemoryFileSystem};
+  use rolldown_plugin::{
+    BoxPlugin, HookLoadOutput, HookNoopReturn, HookTransformArgs, HookTransformReturn,
+    HookWatchChangeArgs, Plugin, PluginContext, WatchChangeKind,
+  };
+
+  use super::{WatchOptions, Watcher, WatcherEvent};
+  use crate::{error::BatchedErrors, Bundler, InputOptions, OutputOptions};
+
+  type Changes = Arc<Mutex<Vec<(String, WatchChangeKind)>>>;
+
+  /// Watches `/project/config.json` while transforming `main.js`, and records changes passed to `watch_change`.
+  #[derive(Debug)]
+  struct WatchPlugin {
+    changes: Changes,
+  }
+
+  #[async_trait::async_trait]
+  impl Plugin for WatchPlugin {
+    fn name(&self) -> std::borrow::Cow<'static, str> {
+      "watch".into()
+    }
+
+    async fn transform(
+      &self,
+      ctx: &mut PluginContext,
+      args: &HookTransformArgs,
+    ) -> HookTransformReturn {
+      if !args.id.ends_with("main.js") {
+        return Ok(None);
+      }
+      ctx.add_watch_file("/project/config.json");
+      assert_eq!(ctx.get_watch_files(), vec!["/project/config.json"]);
+      Ok(Some(HookLoadOutput {
+        code: args.code.to_string(),
+        map: None,
+        module_side_effects: None,
+        loader: None,
+        synthetic_named_exports: None,
+        meta: None,
+      }))
+    }
+
+    async fn watch_change(
+      &self,
+      _ctx: &PluginContext,
+      args: &HookWatchChangeArgs,
+    ) -> HookNoopReturn {
+      self.changes.lock().unwrap().push((args.id.to_string(), args.event));
+      Ok(())
+    }
+  }
+
+  /// Edits `/project/a.js` while the first build transforms it, like a file saved during a build.
+  struct EditPlugin {
+    fs: MemoryFileSystem,
+    edited: AtomicBool,
+  }
+
+  impl std::fmt::Debug for EditPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      f.debug_struct("EditPlugin").field("edited", &self.edited).finish_non_exhaustive()
+    }
+  }
+
+  #[async_trait::async_trait]
+  impl Plugin for EditPlugin {
+    fn name(&self) -> std::borrow::Cow<'static, str> {
+      "edit".into()
+    }
+
+    async fn transform(
+      &self,
+      _ctx: &mut PluginContext,
+      args: &HookTransformArgs,
+    ) -> HookTransformReturn {
+      if args.id.ends_with("a.js") && !self.edited.swap(true, Ordering::SeqCst) {
+        self.fs.write(Path::new("/project/a.js"), b"export const a = 'edited'").unwrap();
+      }
+      Ok(None)
+    }
+  }
+
+  /// A file system whose files can't be written, like a read-only disk.
+  #[derive(Default)]
+  struct ReadOnlyFileSystem(MemoryFileSystem);
+
+  impl FileSystem for ReadOnlyFileSystem {
+    fn share(&self) -> Self {
+      Self(self.0.share())
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+      self.0.remove_dir_all(path)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+      self.0.create_dir_all(path)
+    }
+
+    fn write(&self, _path: &Path, _content: &[u8]) -> io::Result<()> {
+      Err(io::Error::new(io::ErrorKind::PermissionDenied, "read-only file system"))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+      self.0.read(path)
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+      self.0.exists(path)
+    }
+  }
+
+  impl OxcResolverFileSystem for ReadOnlyFileSystem {
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+      self.0.read_to_string(path)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+      self.0.metadata(path)
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+      self.0.symlink_metadata(path)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+      self.0.canonicalize(path)
+    }
+  }
+
+  fn watch(fs: &MemoryFileSystem, changes: &Changes) -> Watcher {
+    watch_with_plugins(fs, changes, vec![])
+  }
+
+  fn watch_with_plugins<T: FileSystem + Default + 'static>(
+    fs: &T,
+    changes: &Changes,
+    mut plugins: Vec<BoxPlugin>,
+  ) -> Watcher {
+    let input_options = InputOptions {
+      input: vec![InputItem { name: Some("main".into()), import: "./main.js".into() }],
+      cwd: PathBuf::from("/project"),
+      resolve: Some(ResolverOptions {
+        alias: None,
+        alias_fields: None,
+        condition_names: None,
+        exports_fields: None,
+        extensions: None,
+        main_fields: None,
+        main_files: None,
+        modules: None,
+        symlinks: Some(false),
+        tsconfig_filename: None,
+      }),
+      asset: AssetOptions { extensions: vec!["png".into()], public_path: String::new() },
+      ..Default::default()
+    };
+    plugins.push(Box::new(WatchPlugin { changes: Arc::clone(changes) }));
+    Bundler::with_plugins_and_fs(input_options, plugins, fs.share()).watch(
+      OutputOptions::default(),
+      WatchOptions {
+        poll_interval: Duration::from_millis(10),
+        build_delay: Duration::from_millis(10),
+      },
+    )
+  }
+
+  async fn next_event(watcher: &mut Watcher) -> WatcherEvent {
+    tokio::time::timeout(Duration::from_secs(10), watcher.recv())
+      .await
+      .expect("The watcher should rebuild")
+      .expect("The watcher should not be closed")
+  }
+
+  /// Wait for a build, returning the code of `main.js`.
+  async fn next_build(watcher: &mut Watcher) -> String {
+    assert!(matches!(next_event(watcher).await, WatcherEvent::Start));
+    assert!(matches!(next_event(watcher).await, WatcherEvent::BundleStart));
+    let WatcherEvent::BundleEnd { output, .. } = next_event(watcher).await else {
+      panic!("The build should succeed");
+    };
+    assert!(matches!(next_event(watcher).await, WatcherEvent::End));
+    output
+      .assets
+      .into_iter()
+      .find_map(|output| match output {
+        Output::Chunk(chunk) if chunk.file_name == "main.js" => Some(chunk.code),
+        _ => None,
+      })
+      .expect("main.js should be in the output")
+  }
+
+  /// Wait for a build that fails, returning its errors.
+  async fn next_failed_build(watcher: &mut Watcher) -> BatchedErrors {
+    assert!(matches!(next_event(watcher).await, WatcherEvent::Start));
+    assert!(matches!(next_event(watcher).await, WatcherEvent::BundleStart));
+    let WatcherEvent::Error(errors) = next_event(watcher).await else {
+      panic!("The build should fail");
+    };
+    assert!(matches!(next_event(watcher).await, WatcherEvent::End));
+    errors
+  }
+
+  #[test]
+  fn changes_of_modules_and_watch_files_trigger_rebuilds() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/main.js"), "import { a } from './a.js'; console.log(a)");
+    fs.add_file(Path::new("/project/a.js"), "export const a = 'first'");
+    let changes = Changes::default();
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+      let mut watcher = watch(&fs, &changes);
+      assert!(next_build(&mut watcher).await.contains("'first'"));
+
+      fs.write(Path::new("/project/a.js"), b"export const a = 'second'").unwrap();
+      assert!(next_build(&mut watcher).await.contains("'second'"));
+      assert_eq!(
+        changes.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![("/project/a.js".to_string(), WatchChangeKind::Update)]
+      );
+
+      // Watch files don't have to be modules, or even exist
+      fs.write(Path::new("/project/config.json"), b"{}").unwrap();
+      next_build(&mut watcher).await;
+      assert_eq!(
+        changes.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![("/project/config.json".to_string(), WatchChangeKind::Create)]
+      );
+
+      watcher.close().await.unwrap_or_else(|_| panic!("closing should succeed"));
+    });
+  }
+
+  #[test]
+  fn changes_of_binary_assets_trigger_rebuilds() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/main.js"), "import logo from './logo.png'; console.log(logo)");
+    fs.add_file(Path::new("/project/logo.png"), "");
+    // Not valid UTF-8, like most images
+    fs.write(Path::new("/project/logo.png"), b"\x89PNG\r\n\x1a\n\xff\x00").unwrap();
+    let changes = Changes::default();
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+      let mut watcher = watch(&fs, &changes);
+      let first = next_build(&mut watcher).await;
+
+      fs.write(Path::new("/project/logo.png"), b"\x89PNG\r\n\x1a\n\xfe\x00").unwrap();
+      // The file name of the asset is hashed from its content
+      assert_ne!(next_build(&mut watcher).await, first);
+      assert_eq!(
+        changes.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![("/project/logo.png".to_string(), WatchChangeKind::Update)]
+      );
+
+      watcher.close().await.unwrap_or_else(|_| panic!("closing should succeed"));
+    });
+  }
+
+  #[test]
+  fn modules_of_failed_builds_are_watched() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/main.js"), "import { a } from './a.js'; console.log(a)");
+    fs.add_file(Path::new("/project/a.js"), "export const a =");
+    let changes = Changes::default();
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+      let mut watcher = watch(&fs, &changes);
+      next_failed_build(&mut watcher).await;
+
+      fs.write(Path::new("/project/a.js"), b"export const a = 'fixed'").unwrap();
+      assert!(next_build(&mut watcher).await.contains("'fixed'"));
+      assert_eq!(
+        changes.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![("/project/a.js".to_string(), WatchChangeKind::Update)]
+      );
+
+      watcher.close().await.unwrap_or_else(|_| panic!("closing should succeed"));
+    });
+  }
+
+  #[test]
+  fn changes_during_builds_trigger_rebuilds() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/main.js"), "import { a } from './a.js'; console.log(a)");
+    fs.add_file(Path::new("/project/a.js"), "export const a = 'first'");
+    let changes = Changes::default();
+    let plugin = EditPlugin { fs: fs.share(), edited: AtomicBool::new(false) };
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+      let mut watcher = watch_with_plugins(&fs, &changes, vec![Box::new(plugin)]);
+      assert!(next_build(&mut watcher).await.contains("'first'"));
+      // The file is snapshotted as it was loaded, before the edit
+      assert!(next_build(&mut watcher).await.contains("'edited'"));
+
+      watcher.close().await.unwrap_or_else(|_| panic!("closing should succeed"));
+    });
+  }
+
+  #[test]
+  fn failed_writes_are_reported() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/main.js"), "console.log('first')");
+    let changes = Changes::default();
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+      let mut watcher = watch_with_plugins(&ReadOnlyFileSystem(fs.share()), &changes, vec![]);
+      let errors = next_failed_build(&mut watcher).await;
+      assert!(errors.get().unwrap().to_string().contains("/project/dist/main.js"));
+
+      // The watcher keeps watching
+      fs.write(Path::new("/project/main.js"), b"console.log('second')").unwrap();
+      next_failed_build(&mut watcher).await;
+      assert_eq!(
+        changes.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![("/project/main.js".to_string(), WatchChangeKind::Update)]
+      );
+
+      watcher.close().await.unwrap_or_else(|_| panic!("closing should succeed"));
+    });
+  }
+
+  #[test]
+  fn events_are_streamed() {
+    let mut fs = MemoryFileSystem::default();
+    fs.add_file(Path::new("/project/main.js"), "console.log('first')");
+    let changes = Changes::default();
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+      let mut watcher = watch(&fs, &changes);
+      let mut builds = 0;
+      while let Some(event) = watcher.next().await {
+        if let WatcherEvent::End = event {
+          builds += 1;
+          if builds == 2 {
+            break;
+          }
+          fs.write(Path::new("/project/main.js"), b"console.log('second')").unwrap();
+        }
+      }
+      assert_eq!(builds, 2);
+      assert_eq!(changes.lock().unwrap().len(), 1);
+      watcher.close().await.unwrap_or_else(|_| panic!("closing should succeed"));
+    });
+  }
+}
@@ -71,7 +71,7 @@ impl Case {
         [
           Cow::Owned(format!("## {}\n", asset.file_name())),
           "```js".into(),
-          Cow::Borrowed(asset.content().trim()),
+          Cow::Owned(String::from_utf8_lossy(asset.content_as_bytes()).trim().to_string()),
           "```".into(),
         ]
       })
@@ -86,20 +86,28 @@ impl Case {
 
   fn render_stats_to_snapshot(&mut self, assets: Vec<Output>) {
     self.snapshot.append("\n\n## Output Stats\n\n");
+    let dir = self.fixture.dir_path().to_str().unwrap();
     let stats = assets
       .into_iter()
       .flat_map(|asset| match asset {
         Output::Chunk(chunk) => {
+          let mut modules = chunk
+            .modules
+            .iter()
+            .map(|(id, module)| format!("{}: {}", id.replace(dir, "$DIR$"), module.rendered_length))
+            .collect::<Vec<_>>();
+          modules.sort();
           vec![Cow::Owned(format!(
-            "- {}, is_entry {}, is_dynamic_entry {}, facade_module_id {:?}, exports {:?}",
+            "- {}, is_entry {}, is_dynamic_entry {}, facade_module_id {:?}, exports {:?}, imports {:?}, dynamic_imports {:?}, module_ids {:?}, modules {:?}",
             chunk.file_name,
             chunk.is_entry,
             chunk.is_dynamic_entry,
-            chunk
-              .facade_module_id
-              .clone()
-              .map(|v| v.replace(self.fixture.dir_path().to_str().unwrap(), "$DIR$")),
-            chunk.exports
+            chunk.facade_module_id.clone().map(|v| v.replace(dir, "$DIR$")),
+            chunk.exports,
+            chunk.imports,
+            chunk.dynamic_imports,
+            chunk.module_ids.iter().map(|id| id.replace(dir, "$DIR$")).collect::<Vec<_>>(),
+            modules,
           ))]
         }
         Output::Asset(_) => vec![],
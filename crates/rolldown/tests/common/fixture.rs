@@ -4,14 +4,32 @@ use std::{
   process::Command,
 };
 
-use rolldown::{Bundler, External, FileNameTemplate, InputOptions, OutputOptions, RolldownOutput};
+use rolldown::{
+  AddonOutputOption, AssetOptions, Bundler, ChecksOptions, ChunkFileNamesOption, External,
+  GlobalsOption, InputOptions, InteropMode, InteropOption, JsonOptions, JsxOptions,
+  ManualChunksOption, OutputFormat, OutputOptions, PathsOption, RolldownOutput,
+  SourcemapPathTransform, TreeshakeOptions,
+};
+use rolldown_common::{AliasFind, AliasItem};
 use rolldown_error::BuildError;
-use rolldown_testing::TestConfig;
+use rolldown_testing::{
+  Globals, Interop, ModuleSideEffects, ModuleSideEffectsKeyword, PreserveEntrySignatures,
+  PropertyReadSideEffects, PropertyReadSideEffectsKeyword, ResolveOptions, TestConfig, Treeshake,
+};
 
 fn default_test_input_item() -> rolldown_testing::InputItem {
   rolldown_testing::InputItem { name: "main".to_string(), import: "./main.js".to_string() }
 }
 
+// Let node know how to execute the compiled artifacts by extensions.
+fn output_extension(format: &OutputFormat) -> &'static str {
+  match format {
+    OutputFormat::Esm => "mjs",
+    OutputFormat::Cjs => "cjs",
+    OutputFormat::Iife | OutputFormat::Umd | OutputFormat::SystemJs => "js",
+  }
+}
+
 pub struct Fixture {
   fixture_path: PathBuf,
 }
@@ -48,21 +66,26 @@ impl Fixture {
 
     let dist_folder = self.dir_path().join("dist");
     let test_script = self.dir_path().join("_test.mjs");
+    let format: OutputFormat = test_config.output.format.into();
+    let ext = output_extension(&format);
 
     let compiled_entries = test_config
       .input
       .input
       .unwrap_or_else(|| vec![default_test_input_item()])
       .iter()
-      .map(|item| format!("{}.mjs", item.name))
+      .map(|item| format!("{}.{ext}", item.name))
       .map(|name| dist_folder.join(name))
       .collect::<Vec<_>>();
 
     let mut command = Command::new("node");
-    compiled_entries.iter().for_each(|entry| {
-      command.arg("--import");
-      command.arg(entry);
-    });
+    // Iife, umd and system bundles are scripts rather than modules, `_test.mjs` is responsible for evaluating them.
+    if !matches!(format, OutputFormat::Iife | OutputFormat::Umd | OutputFormat::SystemJs) {
+      compiled_entries.iter().for_each(|entry| {
+        command.arg("--import");
+        command.arg(entry);
+      });
+    }
 
     if test_script.exists() {
       command.arg(test_script);
@@ -83,6 +106,8 @@ impl Fixture {
     }
   }
 
+  // Options are mapped one by one, which is long but straightforward.
+  #[allow(clippy::too_many_lines)]
   pub async fn compile(&mut self) -> Result<RolldownOutput, Vec<BuildError>> {
     let fixture_path = self.dir_path();
 
@@ -105,31 +130,187 @@ impl Fixture {
         .unwrap(),
       cwd: fixture_path.to_path_buf(),
       external: test_config.input.external.map(External::ArrayString).unwrap_or_default(),
-      treeshake: test_config.input.treeshake.unwrap_or(true),
-      resolve: test_config.input.resolve.map(|value| rolldown_resolver::ResolverOptions {
-        alias: value.alias.map(|alias| alias.into_iter().collect::<Vec<_>>()),
-        alias_fields: value.alias_fields,
-        condition_names: value.condition_names,
-        exports_fields: value.exports_fields,
-        extensions: value.extensions,
-        main_fields: value.main_fields,
-        main_files: value.main_files,
-        modules: value.modules,
-        symlinks: value.symlinks,
+      treeshake: match test_config.input.treeshake {
+        Some(Treeshake::Boolean(false)) => None,
+        Some(Treeshake::Options(options)) => Some(treeshake_options(options)),
+        Some(Treeshake::Boolean(true)) | None => Some(TreeshakeOptions::default()),
+      },
+      shim_missing_exports: test_config.input.shim_missing_exports.unwrap_or_default(),
+      resolve: test_config.input.resolve.map(resolver_options),
+      json: test_config.input.json.map_or_else(JsonOptions::default, |value| JsonOptions {
+        named_exports: value.named_exports.unwrap_or(true),
       }),
+      jsx: test_config.input.jsx.map_or_else(JsxOptions::default, jsx_options),
+      asset: test_config.input.asset.map_or_else(AssetOptions::default, |value| AssetOptions {
+        extensions: value.extensions.unwrap_or_default(),
+        public_path: value.public_path.unwrap_or_default(),
+      }),
+      define: test_config
+        .input
+        .define
+        .map(|define| define.into_iter().collect())
+        .unwrap_or_default(),
+      inject: test_config
+        .input
+        .inject
+        .map(|inject| inject.into_iter().collect())
+        .unwrap_or_default(),
+      cache: None,
+      ignored_circular_dependencies: test_config
+        .input
+        .ignored_circular_dependencies
+        .unwrap_or_default(),
+      checks: test_config.input.checks.map_or_else(ChecksOptions::default, checks_options),
+      preserve_entry_signatures: test_config
+        .input
+        .preserve_entry_signatures
+        .map_or_else(Default::default, preserve_entry_signatures),
+      max_errors: test_config.input.max_errors.unwrap_or(100),
+      ..Default::default()
     });
 
     if fixture_path.join("dist").is_dir() {
       std::fs::remove_dir_all(fixture_path.join("dist")).unwrap();
     }
 
+    let format: OutputFormat = test_config.output.format.into();
+    let ext = output_extension(&format);
     let value = bundler
       .write(OutputOptions {
-        entry_file_names: FileNameTemplate::from("[name].mjs".to_string()),
-        chunk_file_names: FileNameTemplate::from("[name].mjs".to_string()),
+        entry_file_names: ChunkFileNamesOption::from(
+          test_config.output.entry_file_names.unwrap_or_else(|| format!("[name].{ext}")),
+        ),
+        chunk_file_names: ChunkFileNamesOption::from(
+          test_config.output.chunk_file_names.unwrap_or_else(|| format!("[name].{ext}")),
+        ),
+        format,
+        exports: test_config.output.export_mode.into(),
+        interop: interop_option(test_config.output.interop),
+        name: test_config.output.name,
+        extend: test_config.output.extend,
+        globals: test_config.output.globals.map(globals_option).unwrap_or_default(),
+        paths: test_config.output.paths.map(|paths| PathsOption::Map(paths.into_iter().collect())),
+        sourcemap: test_config.output.sourcemap.map(Into::into),
+        sourcemap_path_transform: test_config.output.sourcemap_path_transform.map(|prefix| {
+          SourcemapPathTransform::new(Box::new(move |source, _| {
+            format!("{prefix}{}", source.trim_start_matches("../"))
+          }))
+        }),
+        sourcemap_exclude_sources: test_config.output.sourcemap_exclude_sources,
+        compact: test_config.output.compact,
+        minify: test_config.output.minify,
+        legal_comments: test_config.output.legal_comments.map(Into::into).unwrap_or_default(),
+        freeze: test_config.output.freeze,
+        inline_dynamic_imports: test_config.output.inline_dynamic_imports,
+        banner: test_config.output.banner.map(AddonOutputOption::String).unwrap_or_default(),
+        footer: test_config.output.footer.map(AddonOutputOption::String).unwrap_or_default(),
+        intro: test_config.output.intro.map(AddonOutputOption::String).unwrap_or_default(),
+        outro: test_config.output.outro.map(AddonOutputOption::String).unwrap_or_default(),
+        preserve_modules: test_config.output.preserve_modules,
+        preserve_modules_root: test_config.output.preserve_modules_root,
+        manual_chunks: test_config
+          .output
+          .manual_chunks
+          .map(|manual_chunks| ManualChunksOption::Map(manual_chunks.into_iter().collect())),
+        hash_length: test_config.output.hash_length.unwrap_or(8),
+        hash_algorithm: test_config.output.hash_algorithm.map(Into::into).unwrap_or_default(),
         ..Default::default()
       })
       .await?;
     Ok(value)
   }
 }
+
+fn resolver_options(value: ResolveOptions) -> rolldown_resolver::ResolverOptions {
+  rolldown_resolver::ResolverOptions {
+    alias: value.alias.map(|alias| {
+      alias
+        .into_iter()
+        .map(|item| AliasItem {
+          find: match (item.find, item.find_regex) {
+            (_, Some(regex)) => AliasFind::Regex(regex::Regex::new(&regex).unwrap()),
+            (find, None) => AliasFind::String(find.expect("alias should have a `find`")),
+          },
+          replacement: item.replacement,
+        })
+        .collect()
+    }),
+    alias_fields: value.alias_fields,
+    condition_names: value.condition_names,
+    exports_fields: value.exports_fields,
+    extensions: value.extensions,
+    main_fields: value.main_fields,
+    main_files: value.main_files,
+    modules: value.modules,
+    symlinks: value.symlinks,
+    tsconfig_filename: value.tsconfig_filename.map(Into::into),
+  }
+}
+
+fn globals_option(value: Globals) -> GlobalsOption {
+  match value {
+    Globals::Map(globals) => GlobalsOption::Map(globals.into_iter().collect()),
+    Globals::Template(template) => {
+      GlobalsOption::Fn(Box::new(move |id| template.replace("[id]", id)))
+    }
+  }
+}
+
+fn interop_option(value: Interop) -> InteropOption {
+  match value {
+    Interop::Mode(mode) => mode.into(),
+    Interop::PerModule(modes) => InteropOption::Fn(Box::new(move |id| {
+      modes.get(id).map_or(InteropMode::Auto, |mode| mode.clone().into())
+    })),
+  }
+}
+
+fn jsx_options(value: rolldown_testing::JsxOptions) -> JsxOptions {
+  let default = JsxOptions::default();
+  JsxOptions {
+    runtime: value.runtime.map_or(default.runtime, Into::into),
+    import_source: value.import_source.unwrap_or(default.import_source),
+    pragma: value.pragma.unwrap_or(default.pragma),
+    pragma_frag: value.pragma_frag.unwrap_or(default.pragma_frag),
+  }
+}
+
+fn checks_options(value: rolldown_testing::ChecksOptions) -> ChecksOptions {
+  ChecksOptions {
+    unresolved_import: value.unresolved_import.map_or_else(Default::default, Into::into),
+  }
+}
+
+fn preserve_entry_signatures(value: PreserveEntrySignatures) -> rolldown::PreserveEntrySignatures {
+  match value {
+    PreserveEntrySignatures::Boolean(false) => rolldown::PreserveEntrySignatures::False,
+    PreserveEntrySignatures::Boolean(true) => rolldown::PreserveEntrySignatures::default(),
+    PreserveEntrySignatures::Keyword(value) => value.into(),
+  }
+}
+
+fn treeshake_options(value: rolldown_testing::TreeshakeOptions) -> TreeshakeOptions {
+  TreeshakeOptions {
+    module_side_effects: match value.module_side_effects {
+      Some(ModuleSideEffects::Boolean(value)) => rolldown::ModuleSideEffects::Boolean(value),
+      Some(ModuleSideEffects::Keyword(ModuleSideEffectsKeyword::NoExternal)) => {
+        rolldown::ModuleSideEffects::NoExternal
+      }
+      Some(ModuleSideEffects::Patterns(patterns)) => {
+        rolldown::ModuleSideEffects::Patterns(patterns)
+      }
+      None => rolldown::ModuleSideEffects::default(),
+    },
+    annotations: value.annotations.unwrap_or(true),
+    property_read_side_effects: match value.property_read_side_effects {
+      Some(PropertyReadSideEffects::Boolean(value)) => {
+        rolldown::PropertyReadSideEffects::Boolean(value)
+      }
+      Some(PropertyReadSideEffects::Keyword(PropertyReadSideEffectsKeyword::Always)) => {
+        rolldown::PropertyReadSideEffects::Always
+      }
+      None => rolldown::PropertyReadSideEffects::default(),
+    },
+    try_catch_deoptimization: value.try_catch_deoptimization.unwrap_or(true),
+  }
+}
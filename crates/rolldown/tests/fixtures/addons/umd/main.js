@@ -0,0 +1,3 @@
+import { foo } from './foo.js'
+
+export const value = foo
@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import logo from './logo.svg'
+import sameLogo from './same-logo.svg'
+import icon from './icon.png'
+
+assert.match(logo, /^\/static\/assets\/logo-\w+\.svg$/)
+// Files with the same content are emitted once
+assert.strictEqual(sameLogo, logo)
+assert.match(icon, /^\/static\/assets\/icon-\w+\.png$/)
@@ -0,0 +1,11 @@
+import assert from 'node:assert'
+import { readFileSync } from 'node:fs'
+
+const logo = new URL('./logo.svg', import.meta.url)
+assert.match(logo.href, /\/dist\/assets\/logo-\w+\.svg$/)
+assert.strictEqual(readFileSync(logo, 'utf8'), '<svg></svg>\n')
+
+// Absolute URLs and paths that aren't string literals are left as is
+assert.strictEqual(new URL('https://example.com/logo.svg', import.meta.url).href, 'https://example.com/logo.svg')
+const name = 'logo'
+assert.match(new URL(`./${name}.svg`, import.meta.url).href, /\/dist\/logo\.svg$/)
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import { readFileSync } from 'node:fs'
+
+// The URL is relative to the chunk in `js`
+const logo = new URL('./logo.svg', import.meta.url)
+assert.match(logo.href, /\/dist\/assets\/logo-\w+\.svg$/)
+assert.strictEqual(readFileSync(logo, 'utf8'), '<svg></svg>\n')
@@ -0,0 +1,11 @@
+import assert from 'node:assert'
+import { main, loadFoo, loadCjs } from './dist/main.mjs'
+
+assert.strictEqual(main, 'main-shared')
+const foo = await loadFoo()
+assert.strictEqual(foo.foo, 'foo')
+assert.strictEqual(foo.default, 'foo-default')
+assert.strictEqual(foo.reexported, 'reexported')
+assert.strictEqual(foo.renamed, 'shared')
+const cjs = await loadCjs()
+assert.strictEqual(cjs.cjs, 'cjs')
@@ -0,0 +1,4 @@
+export * from './reexported.js'
+export { shared as renamed } from './shared.js'
+export const foo = 'foo'
+export default 'foo-default'
@@ -0,0 +1,5 @@
+import { shared } from './shared.js'
+
+export const main = `main-${shared}`
+export const loadFoo = () => import('./foo.js')
+export const loadCjs = () => import('./cjs.js')
@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import { main, loadA } from './dist/main.mjs'
+
+assert.strictEqual(main, 'main-shared')
+const { a, loadB } = await loadA()
+assert.strictEqual(a, 'a-shared')
+const b = await loadB()
+assert.strictEqual(b.b, 'b-shared')
+assert.strictEqual(b.default, 'b-default')
@@ -0,0 +1,4 @@
+import { shared } from './shared.js'
+
+export const a = `a-${shared}`
+export const loadB = () => import('./b.js')
@@ -0,0 +1,4 @@
+import { shared } from './shared.js'
+
+export const b = `b-${shared}`
+export default 'b-default'
@@ -0,0 +1,4 @@
+import { shared } from './shared.js'
+
+export const main = `main-${shared}`
+export const loadA = () => import('./a.js')
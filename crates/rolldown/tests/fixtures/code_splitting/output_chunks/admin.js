@@ -0,0 +1,4 @@
+import { shared } from './shared'
+import './unused'
+
+export { shared as sharedFromAdmin }
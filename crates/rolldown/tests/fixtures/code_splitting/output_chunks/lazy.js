@@ -0,0 +1,3 @@
+import { shared } from './shared'
+
+export const lazy = shared * 2
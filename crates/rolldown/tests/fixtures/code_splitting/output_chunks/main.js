@@ -0,0 +1,7 @@
+import { shared } from './shared'
+
+export const main = shared + 1
+
+export default main
+
+import('./lazy').then(({ lazy }) => console.log(lazy))
@@ -0,0 +1,3 @@
+export const shared = 1
+
+export const unusedShared = 2
@@ -0,0 +1,6 @@
+import assert from 'assert'
+import { main } from './dist/main.mjs'
+import * as other from './dist/other.mjs'
+
+assert.strictEqual(main, 'helper helper')
+assert.strictEqual(other.value, 'helper')
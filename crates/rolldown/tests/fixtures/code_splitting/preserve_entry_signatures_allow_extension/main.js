@@ -0,0 +1,4 @@
+import { value } from './other.js'
+import { helper } from './helper.js'
+
+export const main = `${value} ${helper()}`
@@ -0,0 +1,5 @@
+import assert from 'assert'
+import * as other from './dist/other.mjs'
+
+assert.strictEqual(globalThis.main, 'helper')
+assert.deepStrictEqual(Object.keys(other), ['value'])
@@ -0,0 +1,4 @@
+import './main.js'
+import { helper } from './helper.js'
+
+export const value = helper()
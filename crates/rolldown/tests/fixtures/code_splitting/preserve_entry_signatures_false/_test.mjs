@@ -0,0 +1,6 @@
+import assert from 'assert'
+import * as main from './dist/main.mjs'
+import * as other from './dist/other.mjs'
+
+assert.deepStrictEqual(Object.keys(main), [])
+assert.strictEqual(other.value, 'helper')
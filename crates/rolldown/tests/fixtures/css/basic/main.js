@@ -0,0 +1,6 @@
+import './reset.css'
+import { label } from './button.js'
+import assert from 'node:assert'
+
+assert.strictEqual(label, 'button')
+import('./lazy.js')
@@ -0,0 +1 @@
+globalThis.devOnly = true
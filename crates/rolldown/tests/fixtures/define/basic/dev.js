@@ -0,0 +1 @@
+export const dev = 'dev'
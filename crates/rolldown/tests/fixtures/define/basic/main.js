@@ -0,0 +1,19 @@
+import assert from 'node:assert'
+import { dev } from './dev.js'
+import { prod } from './prod.js'
+
+let mode
+if (process.env.NODE_ENV === 'production') {
+  mode = prod
+} else {
+  mode = dev
+}
+if (__DEV__) {
+  import('./dev-only.js')
+}
+const flag = FEATURE_FLAG ? 'on' : 'off'
+const obj = { FEATURE_FLAG, process: 1 }
+
+assert.equal(mode, 'prod')
+assert.equal(flag, 'on')
+assert.deepEqual(obj, { FEATURE_FLAG: true, process: 1 })
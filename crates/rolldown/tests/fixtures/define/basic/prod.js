@@ -0,0 +1 @@
+export const prod = 'prod'
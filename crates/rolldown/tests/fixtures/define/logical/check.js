@@ -0,0 +1,3 @@
+export function check() {
+  throw new Error('`check` should be removed')
+}
@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import { check } from './check.js'
+
+__DEV__ && check()
+const level = process.env['LOG_LEVEL'] || 'info'
+const name = process['env'].APP_NAME ?? 'app'
+
+assert.equal(level, 'warn')
+assert.equal(name, 'app')
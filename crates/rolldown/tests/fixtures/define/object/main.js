@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+
+assert.equal(process.env.NODE_ENV, 'production')
+assert.equal(process.env.DEBUG, 'verbose')
+assert.equal(process.platform, process.platform)
+
+const env = process.env
+assert.deepEqual(env, { NODE_ENV: 'production' })
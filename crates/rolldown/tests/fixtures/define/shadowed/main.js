@@ -0,0 +1,10 @@
+import assert from 'node:assert'
+
+function getMode(process) {
+  return process.env.NODE_ENV
+}
+const __DEV__ = 'local'
+
+assert.equal(getMode({ env: { NODE_ENV: 'test' } }), 'test')
+assert.equal(__DEV__, 'local')
+assert.equal(process.env.NODE_ENV, 'production')
@@ -0,0 +1,4 @@
+import data from './data.json' with { type: 'json' }
+import './other.js'
+
+console.log(data)
@@ -0,0 +1 @@
+export const a = () => import('./b.js')
@@ -0,0 +1 @@
+import 'data:text/html,<p>hello</p>'
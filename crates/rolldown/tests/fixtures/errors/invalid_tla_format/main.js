@@ -0,0 +1,3 @@
+import { value } from './tla.js'
+
+console.log(value)
@@ -0,0 +1,2 @@
+import 'missing-pkg'
+import './deep/d.js'
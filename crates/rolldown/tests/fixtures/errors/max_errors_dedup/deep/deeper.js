@@ -0,0 +1,2 @@
+import './not-found.js'
+import './syntax.js'
@@ -0,0 +1,2 @@
+import 'missing-pkg'
+import './deeper.js'
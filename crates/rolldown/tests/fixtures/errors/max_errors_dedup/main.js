@@ -0,0 +1,5 @@
+import './a.js'
+import './b.js'
+import './c.js'
+import './d.js'
+import './e.js'
@@ -0,0 +1,3 @@
+import './c.js'
+import './b.js'
+import './a.js'
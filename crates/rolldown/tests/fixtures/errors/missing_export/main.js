@@ -0,0 +1,3 @@
+import { missing } from './foo.js'
+
+console.log(missing)
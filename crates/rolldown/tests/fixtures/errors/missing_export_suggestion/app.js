@@ -0,0 +1,3 @@
+import { Buton } from './ui.js'
+
+console.log(Buton)
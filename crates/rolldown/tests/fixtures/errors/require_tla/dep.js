@@ -0,0 +1 @@
+export const base = await Promise.resolve(1)
@@ -0,0 +1,3 @@
+const { value } = require('./tla.js')
+
+console.log(value)
@@ -0,0 +1,3 @@
+import { base } from './dep.js'
+
+export const value = base + 1
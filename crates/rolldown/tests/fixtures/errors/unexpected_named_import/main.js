@@ -0,0 +1,3 @@
+import lib, { named } from 'lib'
+
+console.log(lib, named)
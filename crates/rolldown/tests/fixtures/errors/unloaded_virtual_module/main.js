@@ -0,0 +1 @@
+import '\0virtual:missing'
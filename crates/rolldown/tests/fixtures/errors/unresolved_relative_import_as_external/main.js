@@ -0,0 +1,4 @@
+import { value } from './missing.js'
+import 'missing-pkg'
+
+export { value }
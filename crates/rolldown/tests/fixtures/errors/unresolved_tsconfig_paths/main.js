@@ -0,0 +1,4 @@
+import { a } from '@pkg/a'
+import { b } from 'unknown-pkg'
+
+console.log(a, b)
@@ -0,0 +1,12 @@
+// Namespaces with only types are removed, while the ones with values aren't supported yet
+namespace Types {
+  export interface Point {
+    x: number
+  }
+}
+
+namespace Values {
+  export const origin = 0
+}
+
+console.log(Values.origin)
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import * as path from 'node:path'
+import * as url from 'node:url'
+import * as main from './dist/main.mjs'
+
+assert.strictEqual(main.join, path.join)
+assert.strictEqual(main.fileURLToPath, url.fileURLToPath)
+assert.strictEqual(main.sep, 'local')
@@ -0,0 +1,3 @@
+export * from 'node:path'
+export * from 'node:url'
+export const sep = 'local'
@@ -0,0 +1,5 @@
+import React from 'react'
+import 'polyfill'
+
+console.log(React)
+export const lazy = () => import('lazy')
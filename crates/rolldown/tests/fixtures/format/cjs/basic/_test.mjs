@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+
+assert.strictEqual(main.name, 'main')
+assert.strictEqual(main.sep, path.sep)
+assert.strictEqual(typeof main.increase, 'function')
+// Reassigned exports are live
+assert.strictEqual(main.count, 1)
+main.increase()
+assert.strictEqual(main.count, 2)
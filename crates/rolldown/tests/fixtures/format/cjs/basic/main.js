@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { count, increase } from './counter'
+export { sep } from 'node:path'
+export { count, increase }
+export const name = 'main'
+
+increase()
+assert.strictEqual(count, 1)
@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import isPlainObject from './dist/main.cjs'
+
+assert(isPlainObject({}))
+assert(!isPlainObject([]))
@@ -0,0 +1,3 @@
+module.exports = function isPlainObject(value) {
+  return Object.prototype.toString.call(value) === '[object Object]'
+}
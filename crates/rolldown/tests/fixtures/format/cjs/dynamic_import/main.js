@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+
+import('./foo').then((mod) => {
+  assert.strictEqual(mod.foo, 'foo')
+})
+import('node:assert').then((mod) => {
+  assert.strictEqual(mod.strictEqual, assert.strictEqual)
+})
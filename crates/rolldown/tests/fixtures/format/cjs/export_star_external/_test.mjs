@@ -0,0 +1,12 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+import * as path from 'node:path'
+import * as url from 'node:url'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+
+assert.strictEqual(main.join, path.join)
+assert.strictEqual(main.fileURLToPath, url.fileURLToPath)
+assert.strictEqual(main.sep, 'local')
+assert.strictEqual(main.default, undefined)
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const greet = require('./dist/main.cjs')
+
+assert.strictEqual(greet('world'), 'hello world')
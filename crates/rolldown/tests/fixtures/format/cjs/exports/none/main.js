@@ -0,0 +1 @@
+globalThis.greeted = 'hello world'
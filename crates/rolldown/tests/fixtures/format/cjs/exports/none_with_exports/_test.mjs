@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+
+assert.strictEqual(globalThis.greeted, 'hello world')
+assert.deepStrictEqual(Object.keys(main), [])
@@ -0,0 +1,3 @@
+globalThis.greeted = 'hello world'
+
+export const unused = 'dropped'
@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import { readFileSync } from 'node:fs'
+
+assert.match(import.meta.url, /^file:\/\/.*\/dist\/main\.cjs$/)
+assert.strictEqual(readFileSync(new URL('./data.txt', import.meta.url), 'utf8'), 'data\n')
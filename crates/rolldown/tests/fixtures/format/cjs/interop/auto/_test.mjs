@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+const plain = require('./plain.cjs')
+
+// Only modules marked with `__esModule` have their own default exports
+assert.strictEqual(main.flagged, 'flagged default')
+assert.strictEqual(main.flaggedNamed, 'flagged named')
+assert.strictEqual(main.plain, plain)
+assert.strictEqual(main.plainNs.default, plain)
+assert.strictEqual(main.plainNs.named, 'plain named')
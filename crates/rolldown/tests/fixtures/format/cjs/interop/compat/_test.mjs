@@ -0,0 +1,12 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+const plain = require('./plain.cjs')
+
+// Objects with a `default` property are used as namespaces as is
+assert.strictEqual(main.flagged, 'flagged default')
+assert.strictEqual(main.flaggedNamed, 'flagged named')
+assert.strictEqual(main.plain, 'plain default')
+assert.strictEqual(main.plainNs, plain)
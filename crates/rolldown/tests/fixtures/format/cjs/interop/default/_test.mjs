@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+const flagged = require('./flagged.cjs')
+const plain = require('./plain.cjs')
+
+// Default exports are always `module.exports`
+assert.strictEqual(main.flagged, flagged)
+assert.strictEqual(main.flaggedNamed, 'flagged named')
+assert.strictEqual(main.plain, plain)
+assert.strictEqual(main.plainNs.default, plain)
+assert.strictEqual(main.plainNs.named, 'plain named')
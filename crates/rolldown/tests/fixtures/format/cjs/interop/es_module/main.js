@@ -0,0 +1,4 @@
+import flagged, { named as flaggedNamed } from '../flagged.cjs'
+import plain, * as plainNs from '../plain.cjs'
+
+export { flagged, flaggedNamed, plain, plainNs }
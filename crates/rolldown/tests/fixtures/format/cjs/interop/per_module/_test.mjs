@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+const plain = require('./plain.cjs')
+
+// `flagged.cjs` is used as a namespace as is
+assert.strictEqual(main.flagged, 'flagged default')
+assert.strictEqual(main.flaggedNamed, 'flagged named')
+// The default export of `plain.cjs` is always `module.exports`
+assert.strictEqual(main.plain, plain)
+assert.strictEqual(main.plainNs.default, plain)
+assert.strictEqual(main.plainNs.named, 'plain named')
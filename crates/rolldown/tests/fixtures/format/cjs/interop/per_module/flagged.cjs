@@ -0,0 +1,3 @@
+Object.defineProperty(exports, '__esModule', { value: true })
+exports.default = 'flagged default'
+exports.named = 'flagged named'
@@ -0,0 +1 @@
+module.exports = { default: 'plain default', named: 'plain named' }
@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.cjs')
+
+// Chunks requiring each other read the bindings of each other on access
+assert.strictEqual(main.readA(), 'a')
+assert.strictEqual(main.readB(), 'b')
+
+// Reassigned bindings of other chunks are live
+assert.strictEqual(main.readCount(), 0)
+main.increase()
+assert.strictEqual(main.readCount(), 1)
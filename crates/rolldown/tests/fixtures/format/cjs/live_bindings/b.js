@@ -0,0 +1,7 @@
+import { a } from './a'
+
+export const b = 'b'
+
+export function readA() {
+  return a
+}
@@ -0,0 +1,9 @@
+import { readB } from './a'
+import { readA } from './b'
+import { count, increase } from './shared'
+
+export function readCount() {
+  return count
+}
+
+export { readA, readB, increase }
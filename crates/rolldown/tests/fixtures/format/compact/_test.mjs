@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { main } from './dist/main.mjs'
+
+assert.strictEqual(main(), 'Hello, rolldown')
@@ -0,0 +1,13 @@
+import { greet } from './greet.js'
+
+const config = {
+  name: 'rolldown',
+  tags: ['fast', 'small'],
+}
+
+export function main() {
+  if (config.tags.length > 1) {
+    return greet(config.name)
+  }
+  return null
+}
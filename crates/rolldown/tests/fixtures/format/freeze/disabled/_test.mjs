@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { ns } from './dist/main.mjs'
+
+assert(!Object.isFrozen(ns))
+ns.other = 'other'
+assert.strictEqual(ns.other, 'other')
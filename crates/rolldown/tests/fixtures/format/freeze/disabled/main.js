@@ -0,0 +1,3 @@
+import * as ns from './foo.js'
+
+export { ns }
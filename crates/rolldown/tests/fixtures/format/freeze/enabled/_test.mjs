@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { ns } from './dist/main.mjs'
+
+assert(Object.isFrozen(ns))
+assert.throws(() => {
+  'use strict'
+  ns.other = 'other'
+}, TypeError)
@@ -0,0 +1,17 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+const context = {
+  React: { version: '18.0.0', createElement: (type) => ({ type }) },
+}
+vm.runInNewContext(code, context)
+
+const { MyLib } = context
+assert.strictEqual(MyLib.reactVersion, '18.0.0')
+assert.deepStrictEqual(MyLib.render(), { type: 'div' })
+assert.strictEqual(MyLib.count, 0)
+assert.strictEqual(typeof MyLib.increase, 'function')
+MyLib.increase()
+assert.strictEqual(MyLib.count, 1)
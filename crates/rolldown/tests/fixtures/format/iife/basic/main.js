@@ -0,0 +1,8 @@
+import React, { version } from 'react'
+import { count, increase } from './counter'
+export { count, increase }
+export const reactVersion = version
+
+export function render() {
+  return React.createElement('div')
+}
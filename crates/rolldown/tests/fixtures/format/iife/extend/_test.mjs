@@ -0,0 +1,15 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+
+// Exports are added to the existing global
+const context = { MyLib: { existing: true } }
+vm.runInNewContext(code, context)
+assert.deepStrictEqual({ ...context.MyLib }, { existing: true, foo: 'foo' })
+
+// The global is created if it's missing
+const emptyContext = {}
+vm.runInNewContext(code, emptyContext)
+assert.strictEqual(emptyContext.MyLib.foo, 'foo')
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+const context = {}
+vm.runInNewContext(code, context)
+assert.strictEqual(context.MyLib, 'foo')
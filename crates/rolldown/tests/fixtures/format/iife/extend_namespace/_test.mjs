@@ -0,0 +1,15 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+
+// Exports are added to the existing nested namespace
+const context = { MyLib: { widgets: { existing: true } } }
+vm.runInNewContext(code, context)
+assert.deepStrictEqual({ ...context.MyLib.widgets }, { existing: true, foo: 'foo' })
+
+// Parent namespaces are created if they're missing
+const emptyContext = {}
+vm.runInNewContext(code, emptyContext)
+assert.strictEqual(emptyContext.MyLib.widgets.foo, 'foo')
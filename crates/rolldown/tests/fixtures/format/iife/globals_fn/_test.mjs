@@ -0,0 +1,10 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+const logs = []
+const jquery = () => {}
+vm.runInNewContext(code, { window: { jquery }, console: { log: (value) => logs.push(value) } })
+
+assert.deepStrictEqual(logs, [jquery])
@@ -0,0 +1,16 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+
+// The URL of the script is used when it's known
+const context = { URL, document: { currentScript: { src: 'https://example.com/lib/main.js' } } }
+vm.runInNewContext(code, context)
+assert.strictEqual(context.MyLib.url, 'https://example.com/lib/main.js')
+assert.match(context.MyLib.dataUrl, /^https:\/\/example\.com\/lib\/assets\/data-\w+\.txt$/)
+
+// Otherwise it's resolved against the base URL of the document
+const lazyContext = { URL, document: { currentScript: null, baseURI: 'https://example.com/app/' } }
+vm.runInNewContext(code, lazyContext)
+assert.strictEqual(lazyContext.MyLib.url, 'https://example.com/app/main.js')
@@ -0,0 +1,2 @@
+export const url = import.meta.url
+export const dataUrl = new URL('./data.txt', import.meta.url).href
@@ -0,0 +1,3 @@
+import { render } from 'react-dom'
+
+export const mount = (el) => render(null, el)
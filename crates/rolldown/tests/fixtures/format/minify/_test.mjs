@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { main, day, isLarge } from './dist/main.mjs'
+
+assert.strictEqual(main('rolldown'), 'Hello, rolldown')
+assert.strictEqual(day, 86400)
+assert.strictEqual(isLarge, 'yes')
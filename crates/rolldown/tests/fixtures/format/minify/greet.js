@@ -0,0 +1,6 @@
+const separator = ', '
+
+export function greet(greeting, name) {
+  const message = greeting + separator + name
+  return message
+}
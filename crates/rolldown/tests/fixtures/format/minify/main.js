@@ -0,0 +1,15 @@
+import { greet } from './greet.js'
+
+const SECONDS_PER_DAY = 60 * 60 * 24
+const prefix = 'Hello' + ' '
+
+export function main(name) {
+  const count = [1, 2, 3].map((value) => value * 2).length
+  if (count > 2) {
+    return greet(prefix.trim(), name)
+  }
+  return JSON.stringify(count)
+}
+
+export const day = SECONDS_PER_DAY
+export const isLarge = 2 ** 3 > 7 ? 'yes' : 'no'
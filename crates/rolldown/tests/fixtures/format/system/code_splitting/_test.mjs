@@ -0,0 +1,16 @@
+import assert from 'node:assert'
+import { createLoader } from '../system_loader.mjs'
+
+const loader = createLoader(new URL('./dist', import.meta.url).pathname)
+const main1 = loader.import('main1.js')
+const main2 = loader.import('main2.js')
+
+assert.strictEqual(main1.state, 'initial')
+assert.strictEqual(main2.getState(), 'initial')
+main1.update('updated')
+// Bindings imported from other chunks are kept live by the setters
+assert.strictEqual(main1.state, 'updated')
+assert.strictEqual(main2.getState(), 'updated')
+
+const lazy = await main1.lazy()
+assert.strictEqual(lazy.name, 'lazy')
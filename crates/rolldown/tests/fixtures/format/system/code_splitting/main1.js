@@ -0,0 +1,9 @@
+import { state, setState } from './shared'
+
+export { state }
+
+export function update(value) {
+  setState(value)
+}
+
+export const lazy = () => import('./lazy')
@@ -0,0 +1,3 @@
+import { state } from './shared'
+
+export const getState = () => state
@@ -0,0 +1,5 @@
+export let state = 'initial'
+
+export function setState(value) {
+  state = value
+}
@@ -0,0 +1,26 @@
+import assert from 'node:assert'
+import { createLoader } from '../system_loader.mjs'
+
+const loader = createLoader(new URL('./dist', import.meta.url).pathname, {
+  ext: { default: 'hello', version: '1.0.0' },
+  polyfill: {},
+})
+const main = loader.import('main.js')
+
+assert.strictEqual(main.message, 'hello 1.0.0')
+assert.strictEqual(main.extVersion, '1.0.0')
+assert.match(main.default(), /main\.js$/)
+
+assert.strictEqual(main.count, 0)
+main.increment()
+assert.strictEqual(main.count, 1)
+assert.strictEqual(main.value, 1)
+main.reset()
+assert.strictEqual(main.count, 0)
+assert.strictEqual(main.value, 0)
+
+// Re-exported bindings of externals are updated by the setters
+const ext = loader.records.get('ext')
+ext.namespace.version = '2.0.0'
+ext.setters.forEach((setter) => setter(ext.namespace))
+assert.strictEqual(main.extVersion, '2.0.0')
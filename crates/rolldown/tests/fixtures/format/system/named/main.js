@@ -0,0 +1,10 @@
+import 'polyfill'
+import greeting, { version } from 'ext'
+export { count as value, count, increment, reset } from './counter'
+export { version as extVersion } from 'ext'
+
+export const message = `${greeting} ${version}`
+
+export default function url() {
+  return import.meta.url
+}
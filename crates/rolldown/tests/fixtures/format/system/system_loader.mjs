@@ -0,0 +1,63 @@
+// A minimal loader of the system format, enough to check the output of rolldown without SystemJS itself.
+import fs from 'node:fs'
+import path from 'node:path'
+import vm from 'node:vm'
+
+export function createLoader(dir, externals = {}) {
+  const records = new Map()
+
+  function getRecord(id, parentFile) {
+    if (id in externals) {
+      if (!records.has(id)) {
+        records.set(id, { namespace: externals[id], setters: [] })
+      }
+      return records.get(id)
+    }
+    return load(path.resolve(path.dirname(parentFile), id))
+  }
+
+  function load(file) {
+    if (records.has(file)) {
+      return records.get(file)
+    }
+    const record = { namespace: Object.create(null), setters: [] }
+    records.set(file, record)
+
+    let registration
+    const System = {
+      register(deps, declare) {
+        registration = { deps, declare }
+      },
+    }
+    vm.runInNewContext(fs.readFileSync(file, 'utf-8'), { System, console })
+
+    const _export = (name, value) => {
+      if (typeof name === 'object') {
+        Object.assign(record.namespace, name)
+      } else {
+        record.namespace[name] = value
+      }
+      record.setters.forEach((setter) => setter(record.namespace))
+      return value
+    }
+    const _context = {
+      import: async (id) => getRecord(id, file).namespace,
+      meta: { url: `file://${file}` },
+    }
+    const { setters, execute } = registration.declare(_export, _context)
+    registration.deps.forEach((dep, i) => {
+      const depRecord = getRecord(dep, file)
+      if (setters[i]) {
+        depRecord.setters.push(setters[i])
+        setters[i](depRecord.namespace)
+      }
+    })
+    execute()
+    return record
+  }
+
+  return {
+    import: (file) => load(path.resolve(dir, file)).namespace,
+    records,
+  }
+}
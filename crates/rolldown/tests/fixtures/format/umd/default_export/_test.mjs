@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const greet = require('./dist/main.js')
+
+// `module.exports` is the default export itself rather than an object with a `default` key
+assert.strictEqual(typeof greet, 'function')
+assert.strictEqual(greet('rolldown'), 'Hello, rolldown')
@@ -0,0 +1,45 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+const React = { version: '18.0.0', createElement: (type) => ({ type }) }
+
+function assertLib(lib) {
+  assert.strictEqual(lib.reactVersion, '18.0.0')
+  assert.deepStrictEqual(lib.render(), { type: 'div' })
+}
+
+// commonjs
+{
+  const required = []
+  const module = { exports: {} }
+  const require = (id) => {
+    required.push(id)
+    return id === 'react' ? React : {}
+  }
+  vm.runInNewContext(code, { module, exports: module.exports, require })
+  assert.deepStrictEqual(required, ['react', 'polyfill'])
+  assertLib(module.exports)
+}
+
+// amd
+{
+  let lib
+  const define = (deps, factory) => {
+    assert.deepStrictEqual([...deps], ['exports', 'react', 'polyfill'])
+    const exports = {}
+    factory(exports, React, {})
+    lib = exports
+  }
+  define.amd = true
+  vm.runInNewContext(code, { define })
+  assertLib(lib)
+}
+
+// global variables
+{
+  const context = { React }
+  vm.runInNewContext(code, context)
+  assertLib(context.MyLib)
+}
@@ -0,0 +1,8 @@
+import 'polyfill'
+import React, { version } from 'react'
+
+export const reactVersion = version
+
+export function render() {
+  return React.createElement('div')
+}
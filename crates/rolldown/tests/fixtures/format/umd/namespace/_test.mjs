@@ -0,0 +1,16 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+
+// Exports are assigned to a nested namespace of the global variables
+const context = { MyLib: { existing: true } }
+vm.runInNewContext(code, context)
+assert.strictEqual(context.MyLib.existing, true)
+assert.strictEqual(context.MyLib.widgets.foo, 'foo')
+
+// Parent namespaces are created if they're missing
+const emptyContext = {}
+vm.runInNewContext(code, emptyContext)
+assert.strictEqual(emptyContext.MyLib.widgets.foo, 'foo')
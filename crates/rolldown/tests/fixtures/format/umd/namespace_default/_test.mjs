@@ -0,0 +1,10 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+
+// The default export is assigned to a nested namespace of the global variables
+const context = {}
+vm.runInNewContext(code, context)
+assert.strictEqual(context.MyLib.widgets('rolldown'), 'Hello, rolldown')
@@ -0,0 +1,31 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(new URL('./dist/main.js', import.meta.url), 'utf-8')
+
+function assertExports(lib) {
+  assert.strictEqual(lib.name, 'MyLib')
+  assert.strictEqual(lib.greet('rolldown'), 'Hello, rolldown')
+}
+
+// CommonJS
+const module = { exports: {} }
+vm.runInNewContext(code, { module, exports: module.exports })
+assertExports(module.exports)
+
+// AMD
+let amdExports
+const define = (deps, factory) => {
+  assert.deepStrictEqual([...deps], ['exports'])
+  amdExports = {}
+  factory(amdExports)
+}
+define.amd = true
+vm.runInNewContext(code, { define })
+assertExports(amdExports)
+
+// Global variable
+const context = {}
+vm.runInNewContext(code, context)
+assertExports(context.MyLib)
@@ -0,0 +1,5 @@
+export const name = 'MyLib'
+
+export function greet(who) {
+  return `Hello, ${who}`
+}
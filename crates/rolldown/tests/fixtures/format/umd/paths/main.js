@@ -0,0 +1,4 @@
+import React from 'react'
+import 'polyfill'
+
+export const element = React.createElement('div')
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import data from './data.json' with { type: 'json' }
+import external from '../external.json' with { type: 'json' }
+
+assert.deepStrictEqual(data, { name: 'data' })
+assert.deepStrictEqual(external, { name: 'external' })
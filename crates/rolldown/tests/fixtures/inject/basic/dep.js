@@ -0,0 +1 @@
+export const promise = new Promise()
@@ -0,0 +1,11 @@
+import assert from 'node:assert'
+import { promise } from './dep.js'
+
+function getMode(process) {
+  return process.env.NODE_ENV
+}
+
+assert.ok(promise instanceof Promise)
+assert.ok(new Buffer() instanceof polyfills.Promise === false)
+assert.equal(polyfills.Promise, Promise)
+assert.equal(getMode({ env: { NODE_ENV: 'local' } }), 'local')
@@ -0,0 +1,2 @@
+export class Promise {}
+export const process = { env: { NODE_ENV: 'polyfill' } }
@@ -0,0 +1,9 @@
+import pkg from './package.json'
+import assert from 'node:assert'
+
+assert.deepStrictEqual(pkg, {
+  name: 'pkg',
+  version: '1.0.0',
+  description: 'Unused keys are tree shaken',
+  'dev-dependencies': {},
+})
@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import manifest from './app.webmanifest' with { type: 'json' }
+
+assert.deepStrictEqual(manifest, { name: 'app', display: 'standalone' })
@@ -0,0 +1,5 @@
+import { name, version } from './package.json'
+import assert from 'node:assert'
+
+assert.strictEqual(name, 'pkg')
+assert.strictEqual(version, '1.0.0')
@@ -0,0 +1,7 @@
+interface Props {
+  name: string
+}
+
+export function Greeting(props: Props) {
+  return <p class="greeting">Hello, {props.name}!</p>
+}
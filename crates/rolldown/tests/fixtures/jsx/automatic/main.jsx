@@ -0,0 +1,20 @@
+import assert from 'node:assert'
+import { Fragment } from './runtime/jsx-runtime.js'
+import { Greeting } from './greeting.tsx'
+
+const el = (
+  <>
+    <Greeting name="world" key="a" {...{ id: 1 }} />
+    <span data-x="&amp;" hidden>
+      text
+    </span>
+  </>
+)
+
+assert.equal(el.type, Fragment)
+assert.equal(el.props.children.length, 2)
+assert.equal(el.props.children[0].type, Greeting)
+assert.equal(el.props.children[0].key, 'a')
+assert.deepEqual(el.props.children[0].props, { name: 'world', id: 1 })
+assert.deepEqual(el.props.children[1].props, { 'data-x': '&', hidden: true, children: 'text' })
+assert.deepEqual(Greeting({ name: 'world' }).props.children, ['Hello, ', 'world', '!'])
@@ -0,0 +1,7 @@
+export const Fragment = Symbol('Fragment')
+
+export function jsx(type, props, key) {
+  return { type, props, key }
+}
+
+export const jsxs = jsx
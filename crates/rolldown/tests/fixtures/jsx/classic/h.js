@@ -0,0 +1,4 @@
+export const h = {
+  Fragment: Symbol('Fragment'),
+  create: (type, props, ...children) => ({ type, props, children }),
+}
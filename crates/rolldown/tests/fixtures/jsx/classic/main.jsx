@@ -0,0 +1,19 @@
+import assert from 'node:assert'
+import { h } from './h.js'
+
+const Item = ({ label }) => <li>{label}</li>
+
+const el = (
+  <>
+    <ul class="list">
+      <Item label="a" key="a" />
+      {/* comments are dropped */}
+    </ul>
+  </>
+)
+
+assert.equal(el.type, h.Fragment)
+assert.equal(el.props, null)
+assert.deepEqual(el.children[0].props, { class: 'list' })
+assert.deepEqual(el.children[0].children[0].props, { label: 'a', key: 'a' })
+assert.deepEqual(Item({ label: 'a' }).children, ['a'])
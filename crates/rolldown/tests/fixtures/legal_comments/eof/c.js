@@ -0,0 +1,4 @@
+/**
+ * @license Apache-2.0
+ */
+export const c = 'c'
@@ -0,0 +1,2 @@
+/*! lib v1.0.0 | MIT License */
+export const b = 'b'
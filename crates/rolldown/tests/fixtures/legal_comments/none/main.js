@@ -0,0 +1,8 @@
+//! main | ISC
+// A regular comment, which is dropped.
+import assert from 'node:assert'
+import { a } from './a.js'
+import { b } from './b.js'
+import { c } from './c.js'
+
+assert.equal(a + b + c, 'abc')
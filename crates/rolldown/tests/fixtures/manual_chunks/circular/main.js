@@ -0,0 +1,4 @@
+import { a } from './a.js'
+import { getA } from './b.js'
+
+export const result = [a, getA()]
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import mainDefault, { main } from './dist/main.mjs'
+import { other } from './dist/other.mjs'
+
+assert.strictEqual(main, 'main-dep')
+assert.strictEqual(mainDefault, 'default')
+assert.strictEqual(other, 'other-main-dep')
@@ -0,0 +1,4 @@
+import { dep } from './dep.js'
+
+export const main = `main-${dep}`
+export default 'default'
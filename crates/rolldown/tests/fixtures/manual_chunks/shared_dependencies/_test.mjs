@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { main } from './dist/main.mjs'
+import { other } from './dist/other.mjs'
+
+assert.strictEqual(main, 'main-lib-dep-shared')
+assert.strictEqual(other, 'other-lib-dep-shared')
@@ -0,0 +1,4 @@
+import { lib } from './lib.js'
+import { shared } from './shared.js'
+
+export const main = `main-${lib}-${shared}`
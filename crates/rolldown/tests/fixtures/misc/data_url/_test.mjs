@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { a } from './dist/main.mjs'
+
+assert.strictEqual(a, 'json')
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+
+// Dynamic imports of expressions that no plugin resolves are left as they are
+export const load = (lang) => import(`./locales/${lang}.js`)
+export const c = import('./c.js')
+
+assert.strictEqual((await c).default, 'c')
@@ -0,0 +1,4 @@
+#!/usr/bin/env node
+import { greet } from "./greet.js"
+
+console.log(greet("rolldown"))
@@ -0,0 +1,5 @@
+#!/usr/bin/env node
+// Not an entry, so its hashbang is dropped
+export function greet(name) {
+  return `Hello, ${name}`
+}
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { sum, Button, lazy } from './dist/main.mjs'
+
+// Modules are evaluated in the same order as the original modules.
+assert.deepStrictEqual(globalThis.executed, ['side_effect', 'math', 'button'])
+assert.strictEqual(sum, 3)
+assert.strictEqual(Button, 'button-1')
+assert.strictEqual((await lazy()).name, 'lazy')
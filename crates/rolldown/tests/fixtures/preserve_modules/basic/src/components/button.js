@@ -0,0 +1,5 @@
+import { add } from '../utils/math.js'
+
+globalThis.executed = (globalThis.executed ?? []).concat('button')
+
+export const Button = `button-${add(0, 1)}`
@@ -0,0 +1,7 @@
+import './side_effect.js'
+import { add } from './utils/math.js'
+import { Button } from './components/button.js'
+
+export const sum = add(1, 2)
+export { Button }
+export const lazy = () => import('./components/lazy.js')
@@ -0,0 +1 @@
+globalThis.executed = (globalThis.executed ?? []).concat('side_effect')
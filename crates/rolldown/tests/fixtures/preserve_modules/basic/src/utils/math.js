@@ -0,0 +1,7 @@
+globalThis.executed = (globalThis.executed ?? []).concat('math')
+
+export function add(a, b) {
+  return a + b
+}
+
+export function unused() {}
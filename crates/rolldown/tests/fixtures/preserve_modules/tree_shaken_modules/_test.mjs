@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import { result } from './dist/main.mjs'
+
+assert.deepStrictEqual(result, ['value', 1])
+// Modules whose statements are all tree shaken are not emitted
+assert(!fs.existsSync(new URL('./dist/empty.mjs', import.meta.url)))
+assert(!fs.existsSync(new URL('./dist/reexport.mjs', import.meta.url)))
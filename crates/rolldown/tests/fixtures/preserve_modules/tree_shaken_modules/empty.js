@@ -0,0 +1,3 @@
+import './side_effect.js'
+
+export const unused = 'unused'
@@ -0,0 +1,4 @@
+import { value } from './reexport.js'
+import './empty.js'
+
+export const result = [value, globalThis.sideEffects]
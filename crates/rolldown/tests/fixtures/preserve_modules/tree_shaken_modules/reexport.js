@@ -0,0 +1 @@
+export { value } from './value.js'
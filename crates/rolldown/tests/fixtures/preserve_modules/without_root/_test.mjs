@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import value from './dist/main.mjs'
+
+assert.strictEqual(value, 'value')
@@ -0,0 +1,3 @@
+import { value } from './nested/value.js'
+
+export default value
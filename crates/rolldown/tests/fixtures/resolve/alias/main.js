@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import { utils } from '~utils'
+import { a } from '#lib/a'
+import { b } from '#lib/b.js'
+import { nested } from '~utils/nested.js'
+
+assert.equal(utils, 'utils')
+assert.equal(a, 'a')
+assert.equal(b, 'b')
+assert.equal(nested, 'nested')
+// Only `~utils` itself and paths under it are matched
+import('~utilsx').catch(() => {})
+import('~lazy').then(({ lazy }) => assert.equal(lazy, 'lazy'))
@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import value from 'pkg'
+
+assert.equal(value, 'import')
+assert.equal(require('pkg'), 'require')
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { a } from '@pkg/a'
+import { double } from '~utils'
+
+assert.equal(a, 'a')
+assert.equal(double(2), 4)
@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { result } from './dist/main.mjs'
+
+assert.deepStrictEqual(result, ['foo', undefined, undefined])
@@ -0,0 +1,4 @@
+import { foo, missing } from './foo.js'
+import { missing as missingAgain } from './foo.js'
+
+export const result = [foo, missing, missingAgain]
@@ -0,0 +1,83 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import path from 'node:path'
+import { result } from './dist/main.mjs'
+
+assert.deepStrictEqual(result, ['main', 'foo'])
+
+const dist = new URL('./dist/', import.meta.url)
+const code = fs.readFileSync(new URL('main.mjs', dist), 'utf-8')
+const map = JSON.parse(fs.readFileSync(new URL('main.mjs.map', dist), 'utf-8'))
+
+assert(code.endsWith('//# sourceMappingURL=main.mjs.map'))
+
+const BASE64 = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/'
+
+function decodeVlq(segment) {
+  const values = []
+  let value = 0
+  let shift = 0
+  for (const char of segment) {
+    const digit = BASE64.indexOf(char)
+    value += (digit & 31) << shift
+    if (digit & 32) {
+      shift += 5
+    } else {
+      values.push(value & 1 ? -(value >>> 1) : value >>> 1)
+      value = 0
+      shift = 0
+    }
+  }
+  return values
+}
+
+// Decode mappings into `[generatedColumn, sourceIndex, originalLine, originalColumn]` per line
+function decodeMappings(mappings) {
+  let sourceIndex = 0
+  let originalLine = 0
+  let originalColumn = 0
+  return mappings.split(';').map((line) => {
+    let generatedColumn = 0
+    return line
+      .split(',')
+      .filter(Boolean)
+      .map((segment) => {
+        const [column, source, oLine, oColumn] = decodeVlq(segment)
+        generatedColumn += column
+        sourceIndex += source
+        originalLine += oLine
+        originalColumn += oColumn
+        return [generatedColumn, sourceIndex, originalLine, originalColumn]
+      })
+  })
+}
+
+const lines = decodeMappings(map.mappings)
+
+// Find the original position of the first occurrence of `needle` in the output
+function lookup(needle) {
+  const outputLines = code.split('\n')
+  const line = outputLines.findIndex((l) => l.includes(needle))
+  const column = outputLines[line].indexOf(needle)
+  const segment = lines[line].findLast(([generatedColumn]) => generatedColumn <= column)
+  assert(segment, `no mapping for ${needle}`)
+  const [, sourceIndex, originalLine, originalColumn] = segment
+  return {
+    source: path.basename(map.sources[sourceIndex]),
+    line: originalLine,
+    column: originalColumn,
+  }
+}
+
+// `value` of foo.js is renamed to `value$1`
+assert.deepStrictEqual(lookup("value$1 = 'foo'"), { source: 'foo.js', line: 0, column: 6 })
+assert.deepStrictEqual(lookup('function getFoo'), { source: 'foo.js', line: 2, column: 7 })
+assert.deepStrictEqual(lookup("value = 'main'"), { source: 'main.js', line: 2, column: 6 })
+assert.deepStrictEqual(lookup('result ='), { source: 'main.js', line: 4, column: 13 })
+
+for (const [index, source] of map.sources.entries()) {
+  assert.strictEqual(
+    map.sourcesContent[index],
+    fs.readFileSync(new URL(source, dist), 'utf-8'),
+  )
+}
@@ -0,0 +1,5 @@
+const value = 'foo'
+
+export function getFoo() {
+  return value
+}
@@ -0,0 +1,5 @@
+import { getFoo } from './foo.js'
+
+const value = 'main'
+
+export const result = [value, getFoo()]
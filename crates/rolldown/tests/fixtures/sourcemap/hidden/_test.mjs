@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+
+const code = fs.readFileSync(new URL('./dist/main.mjs', import.meta.url), 'utf-8')
+const map = JSON.parse(fs.readFileSync(new URL('./dist/main.mjs.map', import.meta.url), 'utf-8'))
+
+assert(!code.includes('//# sourceMappingURL='))
+assert.deepStrictEqual(map.sources, ['../main.js'])
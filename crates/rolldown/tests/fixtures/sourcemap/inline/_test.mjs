@@ -0,0 +1,11 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+
+const code = fs.readFileSync(new URL('./dist/main.mjs', import.meta.url), 'utf-8')
+const prefix = '//# sourceMappingURL=data:application/json;charset=utf-8;base64,'
+const comment = code.split('\n').at(-1)
+
+assert(comment.startsWith(prefix))
+const map = JSON.parse(Buffer.from(comment.slice(prefix.length), 'base64').toString())
+assert.deepStrictEqual(map.sources, ['../main.js'])
+assert(!fs.existsSync(new URL('./dist/main.mjs.map', import.meta.url)))
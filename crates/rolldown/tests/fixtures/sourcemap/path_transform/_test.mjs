@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+
+const map = JSON.parse(fs.readFileSync(new URL('./dist/main.mjs.map', import.meta.url), 'utf-8'))
+
+assert.deepStrictEqual(map.sources, ['webpack://app/src/dep.js'])
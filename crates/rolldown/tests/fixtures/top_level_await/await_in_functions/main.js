@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+
+async function load() {
+  return await Promise.resolve(1)
+}
+const loadArrow = async () => {
+  for await (const value of [Promise.resolve(2)]) {
+    return value
+  }
+}
+
+load().then((value) => assert.strictEqual(value, 1))
+loadArrow().then((value) => assert.strictEqual(value, 2))
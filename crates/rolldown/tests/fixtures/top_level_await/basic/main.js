@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { value } from './tla.js'
+
+assert.strictEqual(value, 1)
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { order, value } from './shared.js'
+
+order.push('main')
+assert.strictEqual(value, 1)
+assert.deepStrictEqual(order, ['shared', 'main'])
@@ -0,0 +1,4 @@
+import { order, value } from './shared.js'
+
+order.push('other')
+export { order, value }
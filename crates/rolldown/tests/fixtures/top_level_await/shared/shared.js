@@ -0,0 +1,3 @@
+export const order = []
+export const value = await new Promise((resolve) => setTimeout(() => resolve(1)))
+order.push('shared')
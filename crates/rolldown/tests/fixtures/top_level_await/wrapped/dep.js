@@ -0,0 +1,3 @@
+export const order = []
+export const base = await Promise.resolve(1)
+order.push('dep')
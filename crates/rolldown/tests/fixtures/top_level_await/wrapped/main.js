@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+
+const { load } = require('./lazy.js')
+load().then(({ value, order }) => {
+  assert.strictEqual(value, 2)
+  assert.deepStrictEqual(order, ['dep', 'tla'])
+})
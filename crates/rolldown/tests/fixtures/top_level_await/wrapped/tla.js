@@ -0,0 +1,5 @@
+import { base, order } from './dep.js'
+
+export const value = await Promise.resolve(base + 1)
+order.push('tla')
+export { order }
@@ -0,0 +1,12 @@
+function factory(value) {
+  globalThis.called = true
+  return value
+}
+
+class Foo {}
+
+export const unusedCall = /*#__PURE__*/ factory('unused')
+export const unusedNew = /*@__PURE__*/ new Foo()
+export const unusedParenthesized = /*#__PURE__*/ (factory('unused'))
+export const unusedNested = /*#__PURE__*/ factory(/*#__PURE__*/ factory('unused'))
+export const used = /*#__PURE__*/ factory('used')
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import 'external'
+import './unused.js'
+import { used } from './used.js'
+
+assert.equal(used, 'used')
@@ -0,0 +1 @@
+globalThis.unused = true
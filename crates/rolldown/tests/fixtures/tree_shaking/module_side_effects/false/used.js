@@ -0,0 +1,3 @@
+import './unused.js'
+globalThis.used = true
+export const used = 'used'
@@ -0,0 +1,6 @@
+import assert from "node:assert"
+import { a } from 'lib'
+import 'lib/polyfill.js'
+
+assert.equal(a, 'a')
+assert.equal(globalThis.polyfilled, true)
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import { used } from 'pure-lib'
+// Imported only for its side effects, which the package says it doesn't have
+import 'pure-lib/setup.js'
+
+assert.equal(used, 'used')
+assert.equal(globalThis.setup, undefined)
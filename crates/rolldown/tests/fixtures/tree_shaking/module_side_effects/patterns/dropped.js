@@ -0,0 +1 @@
+globalThis.dropped = true
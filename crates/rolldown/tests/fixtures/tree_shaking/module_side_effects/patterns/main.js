@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import './src/kept.js'
+import './dropped.js'
+
+assert.equal(globalThis.kept, true)
+assert.equal(globalThis.dropped, undefined)
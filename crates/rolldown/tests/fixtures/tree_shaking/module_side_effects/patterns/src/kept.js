@@ -0,0 +1 @@
+globalThis.kept = true
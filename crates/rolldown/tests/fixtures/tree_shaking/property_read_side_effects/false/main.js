@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+
+const config = { mode: { name: 'production' } }
+config.mode.name
+const unused = config['mode']
+const builtin = Object.create
+globalThis.kept
+
+assert.equal(config.mode.name, 'production')
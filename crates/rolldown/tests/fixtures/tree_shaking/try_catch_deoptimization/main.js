@@ -0,0 +1,15 @@
+import assert from 'node:assert'
+
+let value = 0
+try {
+  const unused = 1
+} catch {
+  const unused = 2
+}
+try {
+  value = 1
+} finally {
+  const unused = 3
+}
+
+assert.equal(value, 1)
@@ -0,0 +1,17 @@
+import assert from 'node:assert'
+import { area, Direction, Kind, Shape, Square } from './shapes'
+
+interface Point {
+  x: number
+}
+
+const point = { x: 1 } satisfies Point
+const square: Shape = new Square(<number>(point.x as unknown) + 1)
+
+assert.strictEqual(area(square)!, 4)
+assert.strictEqual(Kind.Circle, 0)
+assert.strictEqual(Kind.Triangle, 5)
+assert.strictEqual(Kind[5], 'Triangle')
+assert.strictEqual(Kind.Label, 'label')
+assert.strictEqual(Direction.Down, 2)
+assert.strictEqual((square as Square).kind, Kind.Square)
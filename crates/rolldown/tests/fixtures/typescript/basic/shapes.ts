@@ -0,0 +1,38 @@
+import type { Shape } from './types'
+import { Unit } from './types'
+
+export enum Kind {
+  Circle,
+  Square = 4,
+  Triangle,
+  Label = 'label',
+}
+
+export const enum Direction {
+  Up = 1,
+  Down = Up * 2,
+}
+
+declare const unknownGlobal: number
+
+declare namespace Declared {
+  const value: number
+}
+
+export class Square implements Shape {
+  readonly kind: Kind = Kind.Square
+  declare unit?: Unit
+
+  constructor(private readonly size: number) {}
+
+  area(): number {
+    return this.size ** 2
+  }
+}
+
+export function area<T extends Shape>(shape: T): number
+export function area(shape: Shape): number {
+  return shape.area()
+}
+
+export type { Shape }
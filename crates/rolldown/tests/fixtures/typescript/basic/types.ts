@@ -0,0 +1,6 @@
+// Only types, which is never loaded by the importer
+export interface Shape {
+  area(): number
+}
+
+export type Unit = 'm' | 'cm'
@@ -0,0 +1,6 @@
+import { b } from './b.js'
+
+export const a = 'a'
+export function getB() {
+  return b
+}
@@ -0,0 +1,4 @@
+import { getB } from './a.js'
+
+export const b = 'b'
+console.log(getB)
@@ -0,0 +1,4 @@
+import * as self from './c.js'
+
+export const c = 'c'
+console.log(self)
@@ -0,0 +1,2 @@
+// Dynamic imports don't form cycles
+export const lazy = () => import('./main.js')
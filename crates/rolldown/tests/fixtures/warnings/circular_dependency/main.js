@@ -0,0 +1,5 @@
+import { a } from './a.js'
+import { c } from './c.js'
+import './dynamic.js'
+
+console.log(a, c)
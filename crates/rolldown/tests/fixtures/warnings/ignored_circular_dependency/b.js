@@ -0,0 +1,4 @@
+import { getC } from './c.js'
+
+export const b = 'b'
+export const c = () => getC()
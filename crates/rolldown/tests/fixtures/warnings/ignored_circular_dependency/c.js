@@ -0,0 +1,3 @@
+import { c } from './b.js'
+
+export const getC = () => c
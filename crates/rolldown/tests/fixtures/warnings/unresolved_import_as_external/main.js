@@ -0,0 +1,5 @@
+import { debounce } from 'missing-pkg'
+
+export const lazy = import('missing-pkg/lazy')
+
+debounce(() => {})
@@ -8,7 +8,7 @@ export interface InputItem {
   import: string
 }
 export interface ResolveOptions {
-  alias?: Record<string, Array<string>>
+  alias?: Array<AliasItem>
   aliasFields?: Array<Array<string>>
   conditionNames?: Array<string>
   exportsFields?: Array<Array<string>>
@@ -17,6 +17,13 @@ export interface ResolveOptions {
   mainFiles?: Array<string>
   modules?: Array<string>
   symlinks?: boolean
+  tsconfigFilename?: string
+}
+export interface AliasItem {
+  find?: string
+  /** The source of a regex, used instead of `find`. */
+  findRegex?: string
+  replacement: string
 }
 export interface InputOptions {
   external?:
@@ -29,50 +36,223 @@ export interface InputOptions {
   input: Array<InputItem>
   plugins: Array<PluginOptions>
   resolve?: ResolveOptions
+  json?: JsonOptions
+  jsx?: JsxOptions
+  asset?: AssetOptions
+  preserveEntrySignatures?: 'strict' | 'allow-extension' | 'exports-only' | 'false'
+  shimMissingExports?: boolean
+  /** Tree shaking is always enabled. */
+  treeshake?: TreeshakeOptions
   cwd: string
+  define?: Record<string, string>
+  inject?: Record<string, InjectImport>
+  /** Globs of modules whose circular dependencies are not warned about. */
+  ignoredCircularDependencies?: Array<string>
+  checks?: ChecksOptions
+  logLevel?: 'silent' | 'warn' | 'info' | 'debug'
+  onLog?: (
+    level: 'warn' | 'info' | 'debug',
+    log: BindingLog,
+  ) => Promise<undefined | 'keep' | 'suppress' | 'error'>
+  /** Errors of modules are collected until there are this many of them. Defaults to 100. */
+  maxErrors?: number
+}
+/** A warning of the build or a log emitted by a plugin, passed to `onLog`. */
+export interface BindingLog {
+  code: string
+  message: string
+  location?: BindingDiagnosticLocation
+  /** Modules from an entry to the module of `location`, each importing the next one. */
+  importerChain: Array<string>
+  /** A suggestion on fixing the problem. */
+  help?: string
+}
+/** Where in a module a log points at. */
+export interface BindingDiagnosticLocation {
+  path: string
+  /** Byte offsets in the code of the module. */
+  start: number
+  end: number
+  /** 1-based line and 0-based column of `start`. */
+  line: number
+  column: number
+}
+export interface TreeshakeOptions {
+  moduleSideEffects?: boolean
+  /** The array form of `moduleSideEffects`, globs of modules that have side effects. */
+  moduleSideEffectsPatterns?: Array<string>
+  /** `moduleSideEffects: 'no-external'`, which takes precedence over `moduleSideEffects`. */
+  moduleSideEffectsNoExternal?: boolean
+  /** The function form of `moduleSideEffects`. */
+  moduleSideEffectsFn?: (id: string, isExternal: boolean) => boolean
+  annotations?: boolean
+  propertyReadSideEffects?: boolean
+  /** `propertyReadSideEffects: 'always'`, which takes precedence over `propertyReadSideEffects`. */
+  propertyReadSideEffectsAlways?: boolean
+  tryCatchDeoptimization?: boolean
+}
+export interface JsonOptions {
+  namedExports?: boolean
+}
+export interface JsxOptions {
+  runtime?: 'automatic' | 'classic'
+  importSource?: string
+  pragma?: string
+  pragmaFrag?: string
+}
+export interface AssetOptions {
+  /** Extensions of files imported as assets without the leading `.`, like `png`. */
+  extensions?: Array<string>
+  publicPath?: string
+}
+export interface ChecksOptions {
+  unresolvedImport?: 'error' | 'warn-external'
+}
+export interface InjectImport {
+  from: string
+  /** `default` and `*` import the default export and the namespace. */
+  imported: string
 }
 export interface OutputOptions {
   entryFileNames?: string
+  /** The function form of `entryFileNames`, returning the pattern of the file name of each chunk. */
+  entryFileNamesFn?: (chunk: PreRenderedChunk) => Promise<string>
   chunkFileNames?: string
+  /** The function form of `chunkFileNames`. */
+  chunkFileNamesFn?: (chunk: PreRenderedChunk) => Promise<string>
+  assetFileNames?: string
+  banner?: (chunk: RenderedChunk) => Promise<string | undefined>
+  compact?: boolean
+  /** Shortens local names and folds constant expressions besides what `compact` does. */
+  minify?: boolean
   dir?: string
   exports?: 'default' | 'named' | 'none' | 'auto'
-  format?: 'esm' | 'cjs'
+  extend?: boolean
+  footer?: (chunk: RenderedChunk) => Promise<string | undefined>
+  format?: 'esm' | 'cjs' | 'iife' | 'umd' | 'system'
+  freeze?: boolean
+  /** Only the object form is supported. */
+  globals?: Record<string, string>
+  hashAlgorithm?: 'xxhash' | 'blake3'
+  hashLength?: number
+  inlineDynamicImports?: boolean
+  interop?: 'auto' | 'esModule' | 'default' | 'defaultOnly' | 'compat'
+  intro?: (chunk: RenderedChunk) => Promise<string | undefined>
+  manualChunks?: (
+    id: string,
+    meta: BindingManualChunkMeta,
+  ) => Promise<string | undefined>
+  legalComments?: 'inline' | 'eof' | 'none' | 'external'
+  name?: string
+  outro?: (chunk: RenderedChunk) => Promise<string | undefined>
+  /** Only the object form is supported. */
+  paths?: Record<string, string>
+  preserveModules?: boolean
+  preserveModulesRoot?: string
   sourcemap?: 'file' | 'inline' | 'hidden'
+  sourcemapExcludeSources?: boolean
+  /** Generate the metafile of esbuild, which is returned along with the outputs. */
+  metafile?: boolean
 }
 export interface PluginOptions {
   name: string
-  buildStart?: () => Promise<void>
+  buildStart?: (ctx: BindingPluginContext) => Promise<void>
   resolveId?: (
+    ctx: BindingPluginContext,
     specifier: string,
     importer?: string,
     options?: HookResolveIdArgsOptions,
   ) => Promise<undefined | ResolveIdResult>
-  load?: (id: string) => Promise<undefined | SourceResult>
-  transform?: (id: string, code: string) => Promise<undefined | SourceResult>
+  /** Only called for dynamic imports of string literals, since expressions aren't passed to the node side. */
+  resolveDynamicImport?: (
+    ctx: BindingPluginContext,
+    specifier: string,
+    importer: string,
+  ) => Promise<undefined | ResolveIdResult>
+  load?: (
+    ctx: BindingPluginContext,
+    id: string,
+  ) => Promise<undefined | SourceResult>
+  transform?: (
+    ctx: BindingPluginContext,
+    code: string,
+    id: string,
+  ) => Promise<undefined | SourceResult>
+  shouldTransformCachedModule?: (
+    ctx: BindingPluginContext,
+    id: string,
+    code: string,
+  ) => Promise<undefined | boolean>
+  moduleParsed?: (moduleInfo: BindingModuleInfo) => Promise<void>
   buildEnd?: (error: string) => Promise<void>
   renderChunk?: (
+    ctx: BindingPluginContext,
     code: string,
     chunk: RenderedChunk,
   ) => Promise<undefined | HookRenderChunkOutput>
-  generateBundle?: (bundle: Outputs, isWrite: boolean) => Promise<void>
+  augmentChunkHash?: (
+    ctx: BindingPluginContext,
+    chunk: RenderedChunk,
+  ) => Promise<undefined | string>
+  resolveFileUrl?: (
+    ctx: BindingPluginContext,
+    options: ResolveFileUrlOptions,
+  ) => Promise<undefined | string>
+  generateBundle?: (
+    ctx: BindingPluginContext,
+    bundle: Outputs,
+    isWrite: boolean,
+  ) => Promise<undefined | HookGenerateBundleOutput>
   writeBundle?: (bundle: Outputs) => Promise<void>
+  closeBundle?: () => Promise<void>
 }
 export interface HookResolveIdArgsOptions {
   isEntry: boolean
   kind: string
+  attributes: Record<string, string>
+}
+/** The emitted file referenced by `import.meta.ROLLUP_FILE_URL_<referenceId>`. */
+export interface ResolveFileUrlOptions {
+  referenceId: string
+  fileName: string
+  chunkId: string
+  format: string
+  moduleId: string
+  relativePath: string
 }
 export interface ResolveIdResult {
   id: string
   external?: boolean
+  moduleSideEffects?: boolean
+  /** `text` or `base64`, which loads the module as a string of its content. */
+  moduleType?: string
 }
 export interface SourceResult {
   code: string
   map?: SourceMap
+  /** Only used by `load` hooks. */
+  moduleSideEffects?: boolean
+  /** `moduleSideEffects: 'no-treeshake'`, which takes precedence over `moduleSideEffects`. Only used by `load` hooks. */
+  moduleSideEffectsNoTreeshake?: boolean
+  /** The name of the export, `default` for `true`. Only used by `load` hooks. */
+  syntheticNamedExports?: string
+  /** The JSON of `meta`. Only used by `load` hooks. */
+  meta?: string
+  /** `js`, `jsx`, `ts`, `tsx` or `asset`, which overrides the loader decided by the extension of the module. */
+  loader?: string
 }
 export interface HookRenderChunkOutput {
   code: string
+  map?: SourceMap
+}
+export interface HookGenerateBundleOutput {
+  /** Assets emitted by `this.emitFile` or added to the bundle directly. */
+  emittedAssets?: Array<BindingOutputAsset>
+  /** Files deleted from the bundle. */
+  deletedFileNames?: Array<string>
 }
 export interface PreRenderedChunk {
+  name: string
   isEntry: boolean
   isDynamicEntry: boolean
   facadeModuleId?: string
@@ -87,6 +267,9 @@ export interface RenderedChunk {
   exports: Array<string>
   fileName: string
   modules: Record<string, BindingRenderedModule>
+  imports: Array<string>
+  dynamicImports: Array<string>
+  importedCss: Array<string>
 }
 export interface SourceMap {
   mappings: string
@@ -97,7 +280,7 @@ export interface SourceMap {
 }
 export interface BindingOutputAsset {
   fileName: string
-  source: string
+  source: string | Buffer
 }
 export interface BindingOutputChunk {
   isEntry: boolean
@@ -107,18 +290,82 @@ export interface BindingOutputChunk {
   exports: Array<string>
   fileName: string
   modules: Record<string, BindingRenderedModule>
+  imports: Array<string>
+  dynamicImports: Array<string>
+  importedCss: Array<string>
   code: string
 }
+export interface BindingModuleInfo {
+  id: string
+  code?: string
+  isEntry: boolean
+  isExternal: boolean
+  importedIds: Array<string>
+  dynamicallyImportedIds: Array<string>
+  importers: Array<string>
+  dynamicImporters: Array<string>
+  exportedBindings: Array<string>
+  hasModuleSideEffects: boolean
+  /** The JSON of custom data returned by `load` hooks. */
+  meta: string
+}
 export interface BindingOutputs {
   chunks: Array<BindingOutputChunk>
   assets: Array<BindingOutputAsset>
+  /** The JSON of the metafile, only generated with `metafile` of output options. */
+  metafile?: string
+}
+export interface BindingEmittedAsset {
+  name?: string
+  fileName?: string
+  source: string | Buffer
+}
+export interface BindingEmittedChunk {
+  name?: string
+  id: string
+}
+export interface BindingPluginContextResolveOptions {
+  skipSelf?: boolean
+  kind?: 'import-statement' | 'dynamic-import' | 'require-call'
+}
+export interface BindingResolvedId {
+  id: string
+  external: boolean
+  moduleSideEffects?: boolean
 }
 export interface BindingRenderedModule {
   code?: string
+  renderedLength: number
 }
 export class Bundler {
   constructor(inputOpts: InputOptions)
   write(opts: OutputOptions): Promise<BindingOutputs>
   generate(opts: OutputOptions): Promise<BindingOutputs>
   scan(): Promise<void>
+  close(): Promise<void>
+}
+/** The second argument of the function form of `manualChunks`. */
+export class BindingManualChunkMeta {
+  getModuleInfo(id: string): BindingModuleInfo | null
+  getModuleIds(): Array<string>
+}
+/** Methods of the plugin context that are implemented on the rust side. */
+export class BindingPluginContext {
+  emitFile(file: BindingEmittedAsset): string
+  emitChunk(chunk: BindingEmittedChunk): string
+  getFileName(referenceId: string): string
+  getModuleInfo(id: string): BindingModuleInfo | null
+  getModuleIds(): Array<string>
+  warn(message: string): void
+  info(message: string): void
+  debug(message: string): void
+  /** Only available in `transform` hooks. */
+  getCombinedSourcemap(): SourceMap
+  addWatchFile(id: string): void
+  getWatchFiles(): Array<string>
+  resolve(
+    specifier: string,
+    importer?: string | undefined | null,
+    options?: BindingPluginContextResolveOptions | undefined | null,
+  ): Promise<BindingResolvedId>
 }
@@ -1,4 +1,6 @@
-use napi::{tokio::sync::Mutex, Env};
+use std::sync::Arc;
+
+use napi::{tokio::sync::Mutex, Env, JsObject};
 use napi_derive::napi;
 use rolldown::Bundler as NativeBundler;
 use rolldown_fs::OsFileSystem;
@@ -13,7 +15,7 @@ use crate::{
 
 #[napi]
 pub struct Bundler {
-  inner: Mutex<NativeBundler<OsFileSystem>>,
+  inner: Arc<Mutex<NativeBundler<OsFileSystem>>>,
 }
 
 #[napi]
@@ -24,20 +26,31 @@ impl Bundler {
     Self::new_impl(env, input_opts)
   }
 
-  #[napi]
-  pub async fn write(&self, opts: OutputOptions) -> napi::Result<BindingOutputs> {
-    self.write_impl(opts).await
+  // Output options may contain js functions, which have to be converted on the main thread. So these
+  // methods are not `async fn`, but spawn the future themselves after the conversion.
+  #[napi(ts_return_type = "Promise<BindingOutputs>")]
+  pub fn write(&self, env: Env, opts: OutputOptions) -> napi::Result<JsObject> {
+    let output_opts = NAPI_ENV.set(&env, || opts.try_into())?;
+    let inner = Arc::clone(&self.inner);
+    env.spawn_future(async move { Self::write_impl(&inner, output_opts).await })
   }
 
-  #[napi]
-  pub async fn generate(&self, opts: OutputOptions) -> napi::Result<BindingOutputs> {
-    self.generate_impl(opts).await
+  #[napi(ts_return_type = "Promise<BindingOutputs>")]
+  pub fn generate(&self, env: Env, opts: OutputOptions) -> napi::Result<JsObject> {
+    let output_opts = NAPI_ENV.set(&env, || opts.try_into())?;
+    let inner = Arc::clone(&self.inner);
+    env.spawn_future(async move { Self::generate_impl(&inner, output_opts).await })
   }
 
   #[napi]
   pub async fn scan(&self) -> napi::Result<()> {
     self.scan_impl().await
   }
+
+  #[napi]
+  pub async fn close(&self) -> napi::Result<()> {
+    self.close_impl().await
+  }
 }
 
 impl Bundler {
@@ -45,7 +58,7 @@ impl Bundler {
     NAPI_ENV.set(&env, || {
       let (opts, plugins) = input_opts.into();
 
-      Ok(Self { inner: Mutex::new(NativeBundler::with_plugins(opts?, plugins?)) })
+      Ok(Self { inner: Arc::new(Mutex::new(NativeBundler::with_plugins(opts?, plugins?))) })
     })
   }
 
@@ -69,12 +82,15 @@ impl Bundler {
 
   #[instrument(skip_all)]
   #[allow(clippy::significant_drop_tightening)]
-  pub async fn write_impl(&self, output_opts: OutputOptions) -> napi::Result<BindingOutputs> {
-    let mut bundler_core = self.inner.try_lock().map_err(|_| {
+  pub async fn write_impl(
+    inner: &Mutex<NativeBundler<OsFileSystem>>,
+    output_opts: rolldown::OutputOptions,
+  ) -> napi::Result<BindingOutputs> {
+    let mut bundler_core = inner.try_lock().map_err(|_| {
       napi::Error::from_reason("Failed to lock the bundler. Is another operation in progress?")
     })?;
 
-    let maybe_outputs = bundler_core.write(output_opts.into()).await;
+    let maybe_outputs = bundler_core.write(output_opts).await;
 
     let outputs = match maybe_outputs {
       Ok(outputs) => outputs,
@@ -85,17 +101,20 @@ impl Bundler {
       }
     };
 
-    Ok(outputs.assets.into())
+    outputs.try_into()
   }
 
   #[instrument(skip_all)]
   #[allow(clippy::significant_drop_tightening)]
-  pub async fn generate_impl(&self, output_opts: OutputOptions) -> napi::Result<BindingOutputs> {
-    let mut bundler_core = self.inner.try_lock().map_err(|_| {
+  pub async fn generate_impl(
+    inner: &Mutex<NativeBundler<OsFileSystem>>,
+    output_opts: rolldown::OutputOptions,
+  ) -> napi::Result<BindingOutputs> {
+    let mut bundler_core = inner.try_lock().map_err(|_| {
       napi::Error::from_reason("Failed to lock the bundler. Is another operation in progress?")
     })?;
 
-    let maybe_outputs = bundler_core.generate(output_opts.into()).await;
+    let maybe_outputs = bundler_core.generate(output_opts).await;
 
     let outputs = match maybe_outputs {
       Ok(outputs) => outputs,
@@ -106,6 +125,24 @@ impl Bundler {
       }
     };
 
-    Ok(outputs.assets.into())
+    outputs.try_into()
+  }
+
+  #[instrument(skip_all)]
+  #[allow(clippy::significant_drop_tightening)]
+  pub async fn close_impl(&self) -> napi::Result<()> {
+    let mut bundler_core = self.inner.try_lock().map_err(|_| {
+      napi::Error::from_reason("Failed to lock the bundler. Is another operation in progress?")
+    })?;
+
+    let result = bundler_core.close().await;
+
+    if let Err(err) = result {
+      // TODO: better handing errors
+      eprintln!("{err:?}");
+      return Err(napi::Error::from_reason("Close failed"));
+    }
+
+    Ok(())
   }
 }
@@ -27,12 +27,59 @@ impl From<InputItem> for rolldown::InputItem {
 }
 
 pub type ExternalFn = JsCallback<(String, Option<String>, bool), bool>;
+pub type ModuleSideEffectsFn = JsCallback<(String, bool), bool>;
+pub type OnLogFn = JsCallback<(String, BindingLog), Option<String>>;
+
+/// A warning of the build or a log emitted by a plugin, passed to `onLog`.
+#[napi(object)]
+#[derive(Debug)]
+pub struct BindingLog {
+  pub code: String,
+  pub message: String,
+  pub location: Option<BindingDiagnosticLocation>,
+  /// Modules from an entry to the module of `location`, each importing the next one.
+  pub importer_chain: Vec<String>,
+  /// A suggestion on fixing the problem.
+  pub help: Option<String>,
+}
+
+impl From<&rolldown_error::BuildError> for BindingLog {
+  fn from(value: &rolldown_error::BuildError) -> Self {
+    let diagnostic = value.to_diagnostic();
+    Self {
+      code: value.code().to_string(),
+      message: value.to_string(),
+      location: diagnostic.location().map(|location| BindingDiagnosticLocation {
+        path: location.path.clone(),
+        start: location.start,
+        end: location.end,
+        line: u32::try_from(location.line).unwrap_or(u32::MAX),
+        column: u32::try_from(location.column).unwrap_or(u32::MAX),
+      }),
+      importer_chain: diagnostic.importer_chain().to_vec(),
+      help: diagnostic.help().map(ToString::to_string),
+    }
+  }
+}
+
+/// Where in a module a log points at.
+#[napi(object)]
+#[derive(Debug)]
+pub struct BindingDiagnosticLocation {
+  pub path: String,
+  /// Byte offsets in the code of the module.
+  pub start: u32,
+  pub end: u32,
+  /// 1-based line and 0-based column of `start`.
+  pub line: u32,
+  pub column: u32,
+}
 
 #[napi(object)]
 #[derive(Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ResolveOptions {
-  pub alias: Option<HashMap<String, Vec<String>>>,
+  pub alias: Option<Vec<AliasItem>>,
   pub alias_fields: Option<Vec<Vec<String>>>,
   pub condition_names: Option<Vec<String>>,
   pub exports_fields: Option<Vec<Vec<String>>>,
@@ -41,24 +88,191 @@ pub struct ResolveOptions {
   pub main_files: Option<Vec<String>>,
   pub modules: Option<Vec<String>>,
   pub symlinks: Option<bool>,
+  pub tsconfig_filename: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AliasItem {
+  pub find: Option<String>,
+  /// The source of a regex, used instead of `find`.
+  pub find_regex: Option<String>,
+  pub replacement: String,
+}
+
+impl ResolveOptions {
+  fn into_resolver_options(self) -> napi::Result<rolldown_resolver::ResolverOptions> {
+    let alias = self
+      .alias
+      .map(|alias| {
+        alias
+          .into_iter()
+          .map(|item| {
+            let find = match (item.find, item.find_regex) {
+              (_, Some(regex)) => rolldown_common::AliasFind::Regex(
+                regex::Regex::new(&regex)
+                  .map_err(|e| napi::Error::from_reason(format!("Invalid alias regex: {e}")))?,
+              ),
+              (Some(find), None) => rolldown_common::AliasFind::String(find),
+              (None, None) => {
+                return Err(napi::Error::from_reason("Alias should have `find` or `findRegex`"))
+              }
+            };
+            Ok(rolldown_common::AliasItem { find, replacement: item.replacement })
+          })
+          .collect::<napi::Result<Vec<_>>>()
+      })
+      .transpose()?;
+    Ok(rolldown_resolver::ResolverOptions {
+      alias,
+      alias_fields: self.alias_fields,
+      condition_names: self.condition_names,
+      exports_fields: self.exports_fields,
+      extensions: self.extensions,
+      main_fields: self.main_fields,
+      main_files: self.main_files,
+      modules: self.modules,
+      symlinks: self.symlinks,
+      tsconfig_filename: self.tsconfig_filename.map(Into::into),
+    })
+  }
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonOptions {
+  pub named_exports: Option<bool>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JsxOptions {
+  #[napi(ts_type = "'automatic' | 'classic'")]
+  pub runtime: Option<String>,
+  pub import_source: Option<String>,
+  pub pragma: Option<String>,
+  pub pragma_frag: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetOptions {
+  /// Extensions of files imported as assets without the leading `.`, like `png`.
+  pub extensions: Option<Vec<String>>,
+  pub public_path: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecksOptions {
+  #[napi(ts_type = "'error' | 'warn-external'")]
+  pub unresolved_import: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InjectImport {
+  pub from: String,
+  /// `default` and `*` import the default export and the namespace.
+  pub imported: String,
+}
+
+impl From<JsonOptions> for rolldown::JsonOptions {
+  fn from(value: JsonOptions) -> Self {
+    let default = Self::default();
+    Self { named_exports: value.named_exports.unwrap_or(default.named_exports) }
+  }
+}
+
+impl From<ChecksOptions> for rolldown::ChecksOptions {
+  fn from(value: ChecksOptions) -> Self {
+    let default = Self::default();
+    Self {
+      unresolved_import: value.unresolved_import.map_or(default.unresolved_import, Into::into),
+    }
+  }
+}
+
+impl From<AssetOptions> for rolldown::AssetOptions {
+  fn from(value: AssetOptions) -> Self {
+    Self {
+      extensions: value.extensions.unwrap_or_default(),
+      public_path: value.public_path.unwrap_or_default(),
+    }
+  }
 }
 
-impl From<ResolveOptions> for rolldown_resolver::ResolverOptions {
-  fn from(value: ResolveOptions) -> Self {
+impl From<JsxOptions> for rolldown::JsxOptions {
+  fn from(value: JsxOptions) -> Self {
+    let default = Self::default();
     Self {
-      alias: value.alias.map(|alias| alias.into_iter().collect::<Vec<_>>()),
-      alias_fields: value.alias_fields,
-      condition_names: value.condition_names,
-      exports_fields: value.exports_fields,
-      extensions: value.extensions,
-      main_fields: value.main_fields,
-      main_files: value.main_files,
-      modules: value.modules,
-      symlinks: value.symlinks,
+      runtime: value.runtime.map_or(default.runtime, Into::into),
+      import_source: value.import_source.unwrap_or(default.import_source),
+      pragma: value.pragma.unwrap_or(default.pragma),
+      pragma_frag: value.pragma_frag.unwrap_or(default.pragma_frag),
     }
   }
 }
 
+#[napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct TreeshakeOptions {
+  pub module_side_effects: Option<bool>,
+  /// The array form of `moduleSideEffects`, globs of modules that have side effects.
+  pub module_side_effects_patterns: Option<Vec<String>>,
+  /// `moduleSideEffects: 'no-external'`, which takes precedence over `moduleSideEffects`.
+  pub module_side_effects_no_external: Option<bool>,
+  /// The function form of `moduleSideEffects`.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string, isExternal: boolean) => boolean")]
+  pub module_side_effects_fn: Option<JsFunction>,
+  pub annotations: Option<bool>,
+  pub property_read_side_effects: Option<bool>,
+  /// `propertyReadSideEffects: 'always'`, which takes precedence over `propertyReadSideEffects`.
+  pub property_read_side_effects_always: Option<bool>,
+  pub try_catch_deoptimization: Option<bool>,
+}
+
+impl TreeshakeOptions {
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  fn into_treeshake_options(self) -> napi::Result<rolldown::TreeshakeOptions> {
+    let module_side_effects = if let Some(js_fn) = self.module_side_effects_fn {
+      let cb = Box::new(ModuleSideEffectsFn::new(&js_fn)?);
+      rolldown::ModuleSideEffects::Fn(Box::new(move |id, is_external| {
+        let ts_fn = Box::clone(&cb);
+        Box::pin(async move {
+          ts_fn.call_async((id, is_external)).await.map_err(|e| e.into_bundle_error())
+        })
+      }))
+    } else if let Some(patterns) = self.module_side_effects_patterns {
+      rolldown::ModuleSideEffects::Patterns(patterns)
+    } else if self.module_side_effects_no_external.unwrap_or_default() {
+      rolldown::ModuleSideEffects::NoExternal
+    } else {
+      rolldown::ModuleSideEffects::Boolean(self.module_side_effects.unwrap_or(true))
+    };
+    Ok(rolldown::TreeshakeOptions {
+      module_side_effects,
+      annotations: self.annotations.unwrap_or(true),
+      property_read_side_effects: if self.property_read_side_effects_always.unwrap_or_default() {
+        rolldown::PropertyReadSideEffects::Always
+      } else {
+        rolldown::PropertyReadSideEffects::Boolean(self.property_read_side_effects.unwrap_or(true))
+      },
+      try_catch_deoptimization: self.try_catch_deoptimization.unwrap_or(true),
+    })
+  }
+}
+
 #[napi(object)]
 #[derive(Deserialize, Default, Derivative)]
 #[serde(rename_all = "camelCase")]
@@ -91,20 +305,64 @@ pub struct InputOptions {
   // perf?: boolean;
   pub plugins: Vec<PluginOptions>,
   pub resolve: Option<ResolveOptions>,
-  // preserveEntrySignatures?: PreserveEntrySignaturesOption;
+  pub json: Option<JsonOptions>,
+  pub jsx: Option<JsxOptions>,
+  pub asset: Option<AssetOptions>,
+  #[napi(ts_type = "'strict' | 'allow-extension' | 'exports-only' | 'false'")]
+  pub preserve_entry_signatures: Option<String>,
   // /** @deprecated Use the "preserveModules" output option instead. */
   // preserveModules?: boolean;
   // pub preserve_symlinks: bool,
-  // pub shim_missing_exports: bool,
+  pub shim_missing_exports: Option<bool>,
   // strictDeprecations?: boolean;
-  // pub treeshake: Option<bool>,
+  /// Tree shaking is always enabled.
+  pub treeshake: Option<TreeshakeOptions>,
   // watch?: WatcherOptions | false;
 
   // extra
   pub cwd: String,
+  pub define: Option<HashMap<String, String>>,
+  pub inject: Option<HashMap<String, InjectImport>>,
+  /// Globs of modules whose circular dependencies are not warned about.
+  pub ignored_circular_dependencies: Option<Vec<String>>,
+  pub checks: Option<ChecksOptions>,
+  #[napi(ts_type = "'silent' | 'warn' | 'info' | 'debug'")]
+  pub log_level: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(level: 'warn' | 'info' | 'debug', log: BindingLog) => Promise<undefined | 'keep' | 'suppress' | 'error'>"
+  )]
+  pub on_log: Option<JsFunction>,
+  /// Errors of modules are collected until there are this many of them. Defaults to 100.
+  pub max_errors: Option<u32>,
   // pub builtins: BuiltinsOptions,
 }
 
+fn into_log_level(log_level: Option<String>) -> Option<rolldown::LogLevel> {
+  match log_level {
+    None => Some(rolldown::LogLevel::Info),
+    Some(log_level) if log_level == "silent" => None,
+    Some(log_level) => Some(log_level.into()),
+  }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+fn into_on_log(js_fn: &JsFunction) -> napi::Result<rolldown::OnLog> {
+  let cb = Box::new(OnLogFn::new(js_fn)?);
+  Ok(rolldown::OnLog::new(Box::new(move |level, log| {
+    let ts_fn = Box::clone(&cb);
+    let log = BindingLog::from(log);
+    Box::pin(async move {
+      let action = ts_fn
+        .call_async((level.as_str().to_string(), log))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+      Ok(action.map_or(rolldown::LogAction::Keep, Into::into))
+    })
+  })))
+}
+
 #[allow(clippy::redundant_closure_for_method_calls)]
 impl From<InputOptions>
   for (napi::Result<rolldown::InputOptions>, napi::Result<Vec<rolldown_plugin::BoxPlugin>>)
@@ -133,13 +391,49 @@ impl From<InputOptions>
       rolldown::External::default()
     };
 
+    let treeshake = match value.treeshake.unwrap_or_default().into_treeshake_options() {
+      Ok(treeshake) => treeshake,
+      Err(e) => return (Err(e), Ok(vec![])),
+    };
+
+    let resolve = match value.resolve.map(ResolveOptions::into_resolver_options).transpose() {
+      Ok(resolve) => resolve,
+      Err(e) => return (Err(e), Ok(vec![])),
+    };
+
+    let on_log = match value.on_log.as_ref().map(into_on_log).transpose() {
+      Ok(on_log) => on_log,
+      Err(e) => return (Err(e), Ok(vec![])),
+    };
+
     (
       Ok(rolldown::InputOptions {
         input: value.input.into_iter().map(Into::into).collect::<Vec<_>>(),
         cwd,
         external,
-        treeshake: true,
-        resolve: value.resolve.map(Into::into),
+        treeshake: Some(treeshake),
+        shim_missing_exports: value.shim_missing_exports.unwrap_or_default(),
+        resolve,
+        json: value.json.map(Into::into).unwrap_or_default(),
+        jsx: value.jsx.map(Into::into).unwrap_or_default(),
+        asset: value.asset.map(Into::into).unwrap_or_default(),
+        preserve_entry_signatures: value
+          .preserve_entry_signatures
+          .map(Into::into)
+          .unwrap_or_default(),
+        define: value.define.map(|define| define.into_iter().collect()).unwrap_or_default(),
+        inject: value
+          .inject
+          .map(|inject| {
+            inject.into_iter().map(|(key, import)| (key, (import.from, import.imported))).collect()
+          })
+          .unwrap_or_default(),
+        cache: None,
+        ignored_circular_dependencies: value.ignored_circular_dependencies.unwrap_or_default(),
+        checks: value.checks.map(Into::into).unwrap_or_default(),
+        log_level: into_log_level(value.log_level),
+        on_log,
+        max_errors: value.max_errors.map_or(100, |max_errors| max_errors as usize),
       }),
       value.plugins.into_iter().map(JsAdapterPlugin::new_boxed).collect::<napi::Result<Vec<_>>>(),
     )
@@ -3,4 +3,4 @@ pub use input_options::*;
 mod output_options;
 pub use output_options::*;
 mod plugin;
-mod sourcemap;
+pub(crate) mod sourcemap;
@@ -1,54 +1,106 @@
+use std::collections::HashMap;
+
+use derivative::Derivative;
+use napi::JsFunction;
 use napi_derive::napi;
 use serde::Deserialize;
 
+use crate::{
+  options::plugin::{PreRenderedChunk, RenderedChunk},
+  types::binding_manual_chunk_meta::BindingManualChunkMeta,
+  utils::{napi_error_ext::NapiErrorExt, JsCallback},
+};
+
+pub type AddonCallback = JsCallback<(RenderedChunk,), Option<String>>;
+pub type ManualChunksCallback = JsCallback<(String, BindingManualChunkMeta), Option<String>>;
+pub type ChunkFileNamesCallback = JsCallback<(PreRenderedChunk,), String>;
+
 #[napi(object)]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Derivative)]
 #[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
 pub struct OutputOptions {
   // --- Options Rolldown doesn't need to be supported
   // /** @deprecated Use the "renderDynamicImport" plugin hook instead. */
   // dynamicImportFunction: string | undefined;
   pub entry_file_names: Option<String>,
+  /// The function form of `entryFileNames`, returning the pattern of the file name of each chunk.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: PreRenderedChunk) => Promise<string>")]
+  pub entry_file_names_fn: Option<JsFunction>,
   pub chunk_file_names: Option<String>,
+  /// The function form of `chunkFileNames`.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: PreRenderedChunk) => Promise<string>")]
+  pub chunk_file_names_fn: Option<JsFunction>,
 
   // amd: NormalizedAmdOptions;
-  // assetFileNames: string | ((chunkInfo: PreRenderedAsset) => string);
-  // banner: () => string | Promise<string>;
-  // chunkFileNames: string | ((chunkInfo: PreRenderedChunk) => string);
-  // compact: boolean;
+  pub asset_file_names: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<string | undefined>")]
+  pub banner: Option<JsFunction>,
+  pub compact: Option<bool>,
+  /// Shortens local names and folds constant expressions besides what `compact` does.
+  pub minify: Option<bool>,
   pub dir: Option<String>,
-  // pub entry_file_names: String, // | ((chunkInfo: PreRenderedChunk) => string)
   // esModule: boolean;
   #[napi(ts_type = "'default' | 'named' | 'none' | 'auto'")]
   pub exports: Option<String>,
-  // extend: boolean;
+  pub extend: Option<bool>,
   // externalLiveBindings: boolean;
-  // footer: () => string | Promise<string>;
-  #[napi(ts_type = "'esm' | 'cjs'")]
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<string | undefined>")]
+  pub footer: Option<JsFunction>,
+  #[napi(ts_type = "'esm' | 'cjs' | 'iife' | 'umd' | 'system'")]
   pub format: Option<String>,
-  // freeze: boolean;
+  pub freeze: Option<bool>,
   // generatedCode: NormalizedGeneratedCodeOptions;
   // globals: GlobalsOption;
+  /// Only the object form is supported.
+  pub globals: Option<HashMap<String, String>>,
+  #[napi(ts_type = "'xxhash' | 'blake3'")]
+  pub hash_algorithm: Option<String>,
+  pub hash_length: Option<u32>,
   // hoistTransitiveImports: boolean;
   // indent: true | string;
-  // inlineDynamicImports: boolean;
-  // interop: GetInterop;
-  // intro: () => string | Promise<string>;
-  // manualChunks: ManualChunksOption;
+  pub inline_dynamic_imports: Option<bool>,
+  #[napi(ts_type = "'auto' | 'esModule' | 'default' | 'defaultOnly' | 'compat'")]
+  pub interop: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<string | undefined>")]
+  pub intro: Option<JsFunction>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string, meta: BindingManualChunkMeta) => Promise<string | undefined>")]
+  pub manual_chunks: Option<JsFunction>,
+  #[napi(ts_type = "'inline' | 'eof' | 'none' | 'external'")]
+  pub legal_comments: Option<String>,
   // minifyInternalExports: boolean;
-  // name: string | undefined;
+  pub name: Option<String>,
   // namespaceToStringTag: boolean;
   // noConflict: boolean;
-  // outro: () => string | Promise<string>;
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<string | undefined>")]
+  pub outro: Option<JsFunction>,
   // paths: OptionsPaths;
+  /// Only the object form is supported.
+  pub paths: Option<HashMap<String, String>>,
   // plugins: OutputPlugin[];
   // preferConst: boolean;
-  // preserveModules: boolean;
-  // preserveModulesRoot: string | undefined;
+  pub preserve_modules: Option<bool>,
+  pub preserve_modules_root: Option<String>,
   // sanitizeFileName: (fileName: string) => string;
   #[napi(ts_type = "'file' | 'inline' | 'hidden'")]
   pub sourcemap: Option<String>,
-  // sourcemapExcludeSources: boolean;
+  pub sourcemap_exclude_sources: Option<bool>,
+  /// Generate the metafile of esbuild, which is returned along with the outputs.
+  pub metafile: Option<bool>,
   // sourcemapFile: string | undefined;
   // sourcemapPathTransform: SourcemapPathTransformOption | undefined;
   // strict: boolean;
@@ -58,26 +110,136 @@ pub struct OutputOptions {
   // pub minify: bool,
 }
 
-impl From<OutputOptions> for rolldown::OutputOptions {
-  fn from(value: OutputOptions) -> Self {
+#[allow(clippy::redundant_closure_for_method_calls)]
+fn normalize_addon_option(addon: Option<&JsFunction>) -> napi::Result<rolldown::AddonOutputOption> {
+  let Some(js_fn) = addon else {
+    return Ok(rolldown::AddonOutputOption::default());
+  };
+  let cb = Box::new(AddonCallback::new(js_fn)?);
+  Ok(rolldown::AddonOutputOption::Fn(Box::new(move |chunk| {
+    let ts_fn = Box::clone(&cb);
+    Box::pin(async move {
+      ts_fn
+        .call_async((chunk.into(),))
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| e.into_bundle_error())
+    })
+  })))
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+fn normalize_manual_chunks_option(
+  manual_chunks: Option<&JsFunction>,
+) -> napi::Result<Option<rolldown::ManualChunksOption>> {
+  let Some(js_fn) = manual_chunks else {
+    return Ok(None);
+  };
+  let cb = Box::new(ManualChunksCallback::new(js_fn)?);
+  Ok(Some(rolldown::ManualChunksOption::Fn(Box::new(move |id, meta| {
+    let ts_fn = Box::clone(&cb);
+    Box::pin(async move {
+      ts_fn
+        .call_async((id, BindingManualChunkMeta::new(meta)))
+        .await
+        .map_err(|e| e.into_bundle_error())
+    })
+  }))))
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+fn normalize_chunk_file_names_option(
+  template: Option<String>,
+  chunk_file_names: Option<&JsFunction>,
+) -> napi::Result<Option<rolldown::ChunkFileNamesOption>> {
+  let Some(js_fn) = chunk_file_names else {
+    return Ok(template.map(rolldown::ChunkFileNamesOption::from));
+  };
+  let cb = Box::new(ChunkFileNamesCallback::new(js_fn)?);
+  Ok(Some(rolldown::ChunkFileNamesOption::Fn(Box::new(move |chunk| {
+    let ts_fn = Box::clone(&cb);
+    Box::pin(
+      async move { ts_fn.call_async((chunk.into(),)).await.map_err(|e| e.into_bundle_error()) },
+    )
+  }))))
+}
+
+// Creating js callbacks requires `NAPI_ENV` to be set.
+impl TryFrom<OutputOptions> for rolldown::OutputOptions {
+  type Error = napi::Error;
+
+  fn try_from(value: OutputOptions) -> napi::Result<Self> {
     let mut options = Self::default();
 
-    if let Some(entry_file_names) = value.entry_file_names {
-      options.entry_file_names = rolldown::FileNameTemplate::from(entry_file_names);
+    if let Some(entry_file_names) =
+      normalize_chunk_file_names_option(value.entry_file_names, value.entry_file_names_fn.as_ref())?
+    {
+      options.entry_file_names = entry_file_names;
     }
 
-    if let Some(chunk_file_names) = value.chunk_file_names {
-      options.chunk_file_names = rolldown::FileNameTemplate::from(chunk_file_names);
+    if let Some(chunk_file_names) =
+      normalize_chunk_file_names_option(value.chunk_file_names, value.chunk_file_names_fn.as_ref())?
+    {
+      options.chunk_file_names = chunk_file_names;
+    }
+
+    if let Some(asset_file_names) = value.asset_file_names {
+      options.asset_file_names = rolldown::FileNameTemplate::from(asset_file_names);
     }
 
     if let Some(dir) = value.dir {
       options.dir = dir;
     }
 
-    if let Some(sourcemap) = value.sourcemap {
-      options.sourcemap = sourcemap.into();
+    if let Some(exports) = value.exports {
+      options.exports = exports.into();
+    }
+
+    if let Some(format) = value.format {
+      options.format = format.into();
     }
 
-    options
+    if let Some(interop) = value.interop {
+      options.interop = interop.into();
+    }
+
+    options.sourcemap = value.sourcemap.map(Into::into);
+    options.sourcemap_exclude_sources = value.sourcemap_exclude_sources.unwrap_or_default();
+    options.metafile = value.metafile.unwrap_or_default();
+    options.compact = value.compact.unwrap_or_default();
+    options.minify = value.minify.unwrap_or_default();
+    options.legal_comments = value.legal_comments.map(Into::into).unwrap_or_default();
+    options.freeze = value.freeze.unwrap_or(true);
+    options.inline_dynamic_imports = value.inline_dynamic_imports.unwrap_or_default();
+
+    options.name = value.name;
+
+    options.extend = value.extend.unwrap_or_default();
+
+    if let Some(globals) = value.globals {
+      options.globals = rolldown::GlobalsOption::Map(globals.into_iter().collect());
+    }
+
+    options.paths =
+      value.paths.map(|paths| rolldown::PathsOption::Map(paths.into_iter().collect()));
+
+    if let Some(hash_algorithm) = value.hash_algorithm {
+      options.hash_algorithm = hash_algorithm.into();
+    }
+
+    if let Some(hash_length) = value.hash_length {
+      options.hash_length = hash_length as usize;
+    }
+
+    options.preserve_modules = value.preserve_modules.unwrap_or_default();
+    options.preserve_modules_root = value.preserve_modules_root;
+
+    options.banner = normalize_addon_option(value.banner.as_ref())?;
+    options.footer = normalize_addon_option(value.footer.as_ref())?;
+    options.intro = normalize_addon_option(value.intro.as_ref())?;
+    options.outro = normalize_addon_option(value.outro.as_ref())?;
+    options.manual_chunks = normalize_manual_chunks_option(value.manual_chunks.as_ref())?;
+
+    Ok(options)
   }
 }
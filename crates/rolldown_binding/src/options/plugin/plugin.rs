@@ -4,7 +4,12 @@ use derivative::Derivative;
 use napi::JsFunction;
 use serde::Deserialize;
 
-use crate::{options::sourcemap::SourceMap, types::binding_rendered_module::BindingRenderedModule};
+use crate::{
+  options::sourcemap::SourceMap,
+  types::{
+    binding_output_asset::BindingOutputAsset, binding_rendered_module::BindingRenderedModule,
+  },
+};
 
 #[napi_derive::napi(object)]
 #[derive(Deserialize, Default, Derivative)]
@@ -15,26 +20,48 @@ pub struct PluginOptions {
 
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
-  #[napi(ts_type = "() => Promise<void>")]
+  #[napi(ts_type = "(ctx: BindingPluginContext) => Promise<void>")]
   pub build_start: Option<JsFunction>,
 
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
-    ts_type = "(specifier: string, importer?: string, options?: HookResolveIdArgsOptions) => Promise<undefined | ResolveIdResult>"
+    ts_type = "(ctx: BindingPluginContext, specifier: string, importer?: string, options?: HookResolveIdArgsOptions) => Promise<undefined | ResolveIdResult>"
   )]
   pub resolve_id: Option<JsFunction>,
 
+  /// Only called for dynamic imports of string literals, since expressions aren't passed to the node side.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(ctx: BindingPluginContext, specifier: string, importer: string) => Promise<undefined | ResolveIdResult>"
+  )]
+  pub resolve_dynamic_import: Option<JsFunction>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
-  #[napi(ts_type = "(id: string) => Promise<undefined | SourceResult>")]
+  #[napi(ts_type = "(ctx: BindingPluginContext, id: string) => Promise<undefined | SourceResult>")]
   pub load: Option<JsFunction>,
 
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
-  #[napi(ts_type = "(id: string, code: string) => Promise<undefined | SourceResult>")]
+  #[napi(
+    ts_type = "(ctx: BindingPluginContext, code: string, id: string) => Promise<undefined | SourceResult>"
+  )]
   pub transform: Option<JsFunction>,
 
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(ctx: BindingPluginContext, id: string, code: string) => Promise<undefined | boolean>"
+  )]
+  pub should_transform_cached_module: Option<JsFunction>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(moduleInfo: BindingModuleInfo) => Promise<void>")]
+  pub module_parsed: Option<JsFunction>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(error: string) => Promise<void>")]
@@ -43,19 +70,40 @@ pub struct PluginOptions {
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
-    ts_type = "(code: string, chunk: RenderedChunk) => Promise<undefined | HookRenderChunkOutput>"
+    ts_type = "(ctx: BindingPluginContext, code: string, chunk: RenderedChunk) => Promise<undefined | HookRenderChunkOutput>"
   )]
   pub render_chunk: Option<JsFunction>,
 
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
-  #[napi(ts_type = "(bundle: Outputs, isWrite: boolean) => Promise<void>")]
+  #[napi(
+    ts_type = "(ctx: BindingPluginContext, chunk: RenderedChunk) => Promise<undefined | string>"
+  )]
+  pub augment_chunk_hash: Option<JsFunction>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(ctx: BindingPluginContext, options: ResolveFileUrlOptions) => Promise<undefined | string>"
+  )]
+  pub resolve_file_url: Option<JsFunction>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(ctx: BindingPluginContext, bundle: Outputs, isWrite: boolean) => Promise<undefined | HookGenerateBundleOutput>"
+  )]
   pub generate_bundle: Option<JsFunction>,
 
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(bundle: Outputs) => Promise<void>")]
   pub write_bundle: Option<JsFunction>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "() => Promise<void>")]
+  pub close_bundle: Option<JsFunction>,
 }
 
 #[napi_derive::napi(object)]
@@ -65,11 +113,43 @@ pub struct PluginOptions {
 pub struct HookResolveIdArgsOptions {
   pub is_entry: bool,
   pub kind: String,
+  pub attributes: HashMap<String, String>,
 }
 
 impl From<rolldown_plugin::HookResolveIdArgsOptions> for HookResolveIdArgsOptions {
   fn from(value: rolldown_plugin::HookResolveIdArgsOptions) -> Self {
-    Self { is_entry: value.is_entry, kind: value.kind.to_string() }
+    Self {
+      is_entry: value.is_entry,
+      kind: value.kind.to_string(),
+      attributes: value.attributes.into_iter().collect(),
+    }
+  }
+}
+
+/// The emitted file referenced by `import.meta.ROLLUP_FILE_URL_<referenceId>`.
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct ResolveFileUrlOptions {
+  pub reference_id: String,
+  pub file_name: String,
+  pub chunk_id: String,
+  pub format: String,
+  pub module_id: String,
+  pub relative_path: String,
+}
+
+impl From<&rolldown_plugin::HookResolveFileUrlArgs<'_>> for ResolveFileUrlOptions {
+  fn from(value: &rolldown_plugin::HookResolveFileUrlArgs) -> Self {
+    Self {
+      reference_id: value.reference_id.to_string(),
+      file_name: value.file_name.to_string(),
+      chunk_id: value.chunk_id.to_string(),
+      format: value.format.to_string(),
+      module_id: value.module_id.to_string(),
+      relative_path: value.relative_path.to_string(),
+    }
   }
 }
 
@@ -80,11 +160,19 @@ impl From<rolldown_plugin::HookResolveIdArgsOptions> for HookResolveIdArgsOption
 pub struct ResolveIdResult {
   pub id: String,
   pub external: Option<bool>,
+  pub module_side_effects: Option<bool>,
+  /// `text` or `base64`, which loads the module as a string of its content.
+  pub module_type: Option<String>,
 }
 
 impl From<ResolveIdResult> for rolldown_plugin::HookResolveIdOutput {
   fn from(value: ResolveIdResult) -> Self {
-    Self { id: value.id, external: value.external }
+    Self {
+      id: value.id,
+      external: value.external,
+      module_side_effects: value.module_side_effects,
+      module_type: value.module_type.as_deref().and_then(rolldown_common::ModuleType::from_name),
+    }
   }
 }
 
@@ -95,11 +183,33 @@ impl From<ResolveIdResult> for rolldown_plugin::HookResolveIdOutput {
 pub struct SourceResult {
   pub code: String,
   pub map: Option<SourceMap>,
+  /// Only used by `load` hooks.
+  pub module_side_effects: Option<bool>,
+  /// `moduleSideEffects: 'no-treeshake'`, which takes precedence over `moduleSideEffects`. Only used by `load` hooks.
+  pub module_side_effects_no_treeshake: Option<bool>,
+  /// The name of the export, `default` for `true`. Only used by `load` hooks.
+  pub synthetic_named_exports: Option<String>,
+  /// The JSON of `meta`. Only used by `load` hooks.
+  pub meta: Option<String>,
+  /// `js`, `jsx`, `ts`, `tsx` or `asset`, which overrides the loader decided by the extension of the module.
+  pub loader: Option<String>,
 }
 
 impl From<SourceResult> for rolldown_plugin::HookLoadOutput {
   fn from(value: SourceResult) -> Self {
-    Self { code: value.code, map: value.map.map(Into::into) }
+    Self {
+      code: value.code,
+      map: value.map.map(Into::into),
+      module_side_effects: if value.module_side_effects_no_treeshake.unwrap_or_default() {
+        Some(rolldown_plugin::HookSideEffects::NoTreeshake)
+      } else {
+        value.module_side_effects.map(rolldown_plugin::HookSideEffects::Bool)
+      },
+      synthetic_named_exports: value.synthetic_named_exports,
+      // The JSON is serialized by the node side, so it's always valid.
+      meta: value.meta.and_then(|meta| serde_json::from_str(&meta).ok()),
+      loader: value.loader.as_deref().and_then(rolldown_common::Loader::from_name),
+    }
   }
 }
 
@@ -109,20 +219,31 @@ impl From<SourceResult> for rolldown_plugin::HookLoadOutput {
 #[derivative(Debug)]
 pub struct HookRenderChunkOutput {
   pub code: String,
+  pub map: Option<SourceMap>,
 }
 
 impl From<HookRenderChunkOutput> for rolldown_plugin::HookRenderChunkOutput {
   fn from(value: HookRenderChunkOutput) -> Self {
-    Self { code: value.code }
+    Self { code: value.code, map: value.map.map(Into::into) }
   }
 }
 
+#[napi_derive::napi(object)]
+#[derive(Default, Derivative)]
+#[derivative(Debug)]
+pub struct HookGenerateBundleOutput {
+  /// Assets emitted by `this.emitFile` or added to the bundle directly.
+  pub emitted_assets: Option<Vec<BindingOutputAsset>>,
+  /// Files deleted from the bundle.
+  pub deleted_file_names: Option<Vec<String>>,
+}
+
 #[napi_derive::napi(object)]
 #[derive(Deserialize, Default, Derivative)]
 #[serde(rename_all = "camelCase")]
 #[derive(Debug)]
 pub struct PreRenderedChunk {
-  // pub name: String,
+  pub name: String,
   pub is_entry: bool,
   pub is_dynamic_entry: bool,
   pub facade_module_id: Option<String>,
@@ -133,6 +254,7 @@ pub struct PreRenderedChunk {
 impl From<rolldown::PreRenderedChunk> for PreRenderedChunk {
   fn from(value: rolldown::PreRenderedChunk) -> Self {
     Self {
+      name: value.name,
       is_entry: value.is_entry,
       is_dynamic_entry: value.is_dynamic_entry,
       facade_module_id: value.facade_module_id,
@@ -157,6 +279,9 @@ pub struct RenderedChunk {
   pub file_name: String,
   #[serde(skip)]
   pub modules: HashMap<String, BindingRenderedModule>,
+  pub imports: Vec<String>,
+  pub dynamic_imports: Vec<String>,
+  pub imported_css: Vec<String>,
 }
 
 impl From<rolldown_common::RenderedChunk> for RenderedChunk {
@@ -169,6 +294,9 @@ impl From<rolldown_common::RenderedChunk> for RenderedChunk {
       exports: value.exports,
       file_name: value.file_name,
       modules: value.modules.into_iter().map(|(key, value)| (key, value.into())).collect(),
+      imports: value.imports,
+      dynamic_imports: value.dynamic_imports,
+      imported_css: value.imported_css,
     }
   }
 }
@@ -1,24 +1,45 @@
 use std::borrow::Cow;
 
 use crate::utils::JsCallback;
-use crate::{types::binding_outputs::BindingOutputs, utils::napi_error_ext::NapiErrorExt};
+use crate::{
+  types::{
+    binding_module_info::BindingModuleInfo, binding_outputs::BindingOutputs,
+    binding_plugin_context::BindingPluginContext,
+  },
+  utils::napi_error_ext::NapiErrorExt,
+};
 use derivative::Derivative;
 use rolldown_plugin::Plugin;
 
 use super::plugin::{
-  HookRenderChunkOutput, HookResolveIdArgsOptions, PluginOptions, RenderedChunk, ResolveIdResult,
-  SourceResult,
+  HookGenerateBundleOutput, HookRenderChunkOutput, HookResolveIdArgsOptions, PluginOptions,
+  RenderedChunk, ResolveFileUrlOptions, ResolveIdResult, SourceResult,
 };
 
-pub type BuildStartCallback = JsCallback<(), ()>;
-pub type ResolveIdCallback =
-  JsCallback<(String, Option<String>, HookResolveIdArgsOptions), Option<ResolveIdResult>>;
-pub type LoadCallback = JsCallback<(String,), Option<SourceResult>>;
-pub type TransformCallback = JsCallback<(String, String), Option<SourceResult>>;
+pub type BuildStartCallback = JsCallback<(BindingPluginContext,), ()>;
+pub type ResolveIdCallback = JsCallback<
+  (BindingPluginContext, String, Option<String>, HookResolveIdArgsOptions),
+  Option<ResolveIdResult>,
+>;
+pub type ResolveDynamicImportCallback =
+  JsCallback<(BindingPluginContext, String, String), Option<ResolveIdResult>>;
+pub type LoadCallback = JsCallback<(BindingPluginContext, String), Option<SourceResult>>;
+pub type TransformCallback =
+  JsCallback<(BindingPluginContext, String, String), Option<SourceResult>>;
+pub type ShouldTransformCachedModuleCallback =
+  JsCallback<(BindingPluginContext, String, String), Option<bool>>;
+pub type ModuleParsedCallback = JsCallback<(BindingModuleInfo,), ()>;
 pub type BuildEndCallback = JsCallback<(Option<String>,), ()>;
-pub type RenderChunkCallback = JsCallback<(String, RenderedChunk), Option<HookRenderChunkOutput>>;
-pub type GenerateBundleCallback = JsCallback<(BindingOutputs, bool), Option<HookRenderChunkOutput>>;
+pub type RenderChunkCallback =
+  JsCallback<(BindingPluginContext, String, RenderedChunk), Option<HookRenderChunkOutput>>;
+pub type AugmentChunkHashCallback =
+  JsCallback<(BindingPluginContext, RenderedChunk), Option<String>>;
+pub type ResolveFileUrlCallback =
+  JsCallback<(BindingPluginContext, ResolveFileUrlOptions), Option<String>>;
+pub type GenerateBundleCallback =
+  JsCallback<(BindingPluginContext, BindingOutputs, bool), Option<HookGenerateBundleOutput>>;
 pub type WriteBundleCallback = JsCallback<(BindingOutputs,), ()>;
+pub type CloseBundleCallback = JsCallback<(), ()>;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -29,40 +50,72 @@ pub struct JsAdapterPlugin {
   #[derivative(Debug = "ignore")]
   resolve_id_fn: Option<ResolveIdCallback>,
   #[derivative(Debug = "ignore")]
+  resolve_dynamic_import_fn: Option<ResolveDynamicImportCallback>,
+  #[derivative(Debug = "ignore")]
   load_fn: Option<LoadCallback>,
   #[derivative(Debug = "ignore")]
   transform_fn: Option<TransformCallback>,
   #[derivative(Debug = "ignore")]
+  should_transform_cached_module_fn: Option<ShouldTransformCachedModuleCallback>,
+  #[derivative(Debug = "ignore")]
+  module_parsed_fn: Option<ModuleParsedCallback>,
+  #[derivative(Debug = "ignore")]
   build_end_fn: Option<BuildEndCallback>,
   #[derivative(Debug = "ignore")]
   render_chunk_fn: Option<RenderChunkCallback>,
   #[derivative(Debug = "ignore")]
+  augment_chunk_hash_fn: Option<AugmentChunkHashCallback>,
+  #[derivative(Debug = "ignore")]
+  resolve_file_url_fn: Option<ResolveFileUrlCallback>,
+  #[derivative(Debug = "ignore")]
   generate_bundle_fn: Option<GenerateBundleCallback>,
   #[derivative(Debug = "ignore")]
   write_bundle_fn: Option<WriteBundleCallback>,
+  #[derivative(Debug = "ignore")]
+  close_bundle_fn: Option<CloseBundleCallback>,
 }
 
 impl JsAdapterPlugin {
   pub fn new(option: PluginOptions) -> napi::Result<Self> {
     let build_start_fn = option.build_start.as_ref().map(BuildStartCallback::new).transpose()?;
     let resolve_id_fn = option.resolve_id.as_ref().map(ResolveIdCallback::new).transpose()?;
+    let resolve_dynamic_import_fn =
+      option.resolve_dynamic_import.as_ref().map(ResolveDynamicImportCallback::new).transpose()?;
     let load_fn = option.load.as_ref().map(LoadCallback::new).transpose()?;
     let transform_fn = option.transform.as_ref().map(TransformCallback::new).transpose()?;
+    let should_transform_cached_module_fn = option
+      .should_transform_cached_module
+      .as_ref()
+      .map(ShouldTransformCachedModuleCallback::new)
+      .transpose()?;
+    let module_parsed_fn =
+      option.module_parsed.as_ref().map(ModuleParsedCallback::new).transpose()?;
     let build_end_fn = option.build_end.as_ref().map(BuildEndCallback::new).transpose()?;
     let render_chunk_fn = option.render_chunk.as_ref().map(RenderChunkCallback::new).transpose()?;
+    let augment_chunk_hash_fn =
+      option.augment_chunk_hash.as_ref().map(AugmentChunkHashCallback::new).transpose()?;
+    let resolve_file_url_fn =
+      option.resolve_file_url.as_ref().map(ResolveFileUrlCallback::new).transpose()?;
     let generate_bundle_fn =
       option.generate_bundle.as_ref().map(GenerateBundleCallback::new).transpose()?;
     let write_bundle_fn = option.write_bundle.as_ref().map(WriteBundleCallback::new).transpose()?;
+    let close_bundle_fn = option.close_bundle.as_ref().map(CloseBundleCallback::new).transpose()?;
     Ok(Self {
       name: option.name,
       build_start_fn,
       resolve_id_fn,
+      resolve_dynamic_import_fn,
       load_fn,
       transform_fn,
+      should_transform_cached_module_fn,
+      module_parsed_fn,
       build_end_fn,
       render_chunk_fn,
+      augment_chunk_hash_fn,
+      resolve_file_url_fn,
       generate_bundle_fn,
       write_bundle_fn,
+      close_bundle_fn,
     })
   }
 
@@ -80,10 +133,12 @@ impl Plugin for JsAdapterPlugin {
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn build_start(
     &self,
-    _ctx: &mut rolldown_plugin::PluginContext,
+    ctx: &mut rolldown_plugin::PluginContext,
+    _args: &rolldown_plugin::HookBuildStartArgs,
   ) -> rolldown_plugin::HookNoopReturn {
+    // The normalized input options are passed to the js hook on the node side.
     if let Some(cb) = &self.build_start_fn {
-      cb.call_async(()).await.map_err(|e| e.into_bundle_error())?;
+      cb.call_async((BindingPluginContext::new(ctx),)).await.map_err(|e| e.into_bundle_error())?;
     }
     Ok(())
   }
@@ -91,12 +146,13 @@ impl Plugin for JsAdapterPlugin {
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn resolve_id(
     &self,
-    _ctx: &mut rolldown_plugin::PluginContext,
+    ctx: &mut rolldown_plugin::PluginContext,
     args: &rolldown_plugin::HookResolveIdArgs,
   ) -> rolldown_plugin::HookResolveIdReturn {
     if let Some(cb) = &self.resolve_id_fn {
       let res = cb
         .call_async((
+          BindingPluginContext::new(ctx),
           args.source.to_string(),
           args.importer.map(|s| s.to_string()),
           args.options.clone().into(),
@@ -110,14 +166,39 @@ impl Plugin for JsAdapterPlugin {
     }
   }
 
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn resolve_dynamic_import(
+    &self,
+    ctx: &mut rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookResolveDynamicImportArgs,
+  ) -> rolldown_plugin::HookResolveIdReturn {
+    let (Some(cb), rolldown_plugin::DynamicImportSpecifier::String(specifier)) =
+      (&self.resolve_dynamic_import_fn, args.specifier)
+    else {
+      return Ok(None);
+    };
+    let res = cb
+      .call_async((
+        BindingPluginContext::new(ctx),
+        specifier.to_string(),
+        args.importer.to_string(),
+      ))
+      .await
+      .map_err(|e| e.into_bundle_error())?;
+    Ok(res.map(Into::into))
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn load(
     &self,
-    _ctx: &mut rolldown_plugin::PluginContext,
+    ctx: &mut rolldown_plugin::PluginContext,
     args: &rolldown_plugin::HookLoadArgs,
   ) -> rolldown_plugin::HookLoadReturn {
     if let Some(cb) = &self.load_fn {
-      let res = cb.call_async((args.id.to_string(),)).await.map_err(|e| e.into_bundle_error())?;
+      let res = cb
+        .call_async((BindingPluginContext::new(ctx), args.id.to_string()))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
       Ok(res.map(Into::into))
     } else {
       Ok(None)
@@ -127,12 +208,12 @@ impl Plugin for JsAdapterPlugin {
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn transform(
     &self,
-    _ctx: &mut rolldown_plugin::PluginContext,
+    ctx: &mut rolldown_plugin::PluginContext,
     args: &rolldown_plugin::HookTransformArgs,
   ) -> rolldown_plugin::HookTransformReturn {
     if let Some(cb) = &self.transform_fn {
       let res = cb
-        .call_async((args.code.to_string(), args.id.to_string()))
+        .call_async((BindingPluginContext::new(ctx), args.code.to_string(), args.id.to_string()))
         .await
         .map_err(|e| e.into_bundle_error())?;
       Ok(res.map(Into::into))
@@ -141,16 +222,42 @@ impl Plugin for JsAdapterPlugin {
     }
   }
 
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn should_transform_cached_module(
+    &self,
+    ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookShouldTransformCachedModuleArgs,
+  ) -> rolldown_plugin::HookShouldTransformCachedModuleReturn {
+    if let Some(cb) = &self.should_transform_cached_module_fn {
+      cb.call_async((BindingPluginContext::new(ctx), args.id.to_string(), args.code.to_string()))
+        .await
+        .map_err(|e| e.into_bundle_error())
+    } else {
+      Ok(None)
+    }
+  }
+
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn module_parsed(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookModuleParsedArgs,
+  ) -> rolldown_plugin::HookNoopReturn {
+    // The ast isn't passed to the js side for now.
+    if let Some(cb) = &self.module_parsed_fn {
+      cb.call_async((args.module_info.into(),)).await.map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn build_end(
     &self,
     _ctx: &mut rolldown_plugin::PluginContext,
-    args: Option<&rolldown_plugin::HookBuildEndArgs>,
+    error: Option<&rolldown_error::BuildError>,
   ) -> rolldown_plugin::HookNoopReturn {
     if let Some(cb) = &self.build_end_fn {
-      cb.call_async((args.map(|a| a.error.to_string()),))
-        .await
-        .map_err(|e| e.into_bundle_error())?;
+      cb.call_async((error.map(ToString::to_string),)).await.map_err(|e| e.into_bundle_error())?;
     }
     Ok(())
   }
@@ -158,12 +265,16 @@ impl Plugin for JsAdapterPlugin {
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn render_chunk(
     &self,
-    _ctx: &rolldown_plugin::PluginContext,
+    ctx: &rolldown_plugin::PluginContext,
     args: &rolldown_plugin::RenderChunkArgs,
   ) -> rolldown_plugin::HookRenderChunkReturn {
     if let Some(cb) = &self.render_chunk_fn {
       let res = cb
-        .call_async((args.code.to_string(), args.chunk.clone().into()))
+        .call_async((
+          BindingPluginContext::new(ctx),
+          args.code.to_string(),
+          args.chunk.clone().into(),
+        ))
         .await
         .map_err(|e| e.into_bundle_error())?;
       return Ok(res.map(Into::into));
@@ -171,15 +282,61 @@ impl Plugin for JsAdapterPlugin {
     Ok(None)
   }
 
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn augment_chunk_hash(
+    &self,
+    ctx: &rolldown_plugin::PluginContext,
+    chunk: &rolldown_common::RenderedChunk,
+  ) -> rolldown_plugin::HookAugmentChunkHashReturn {
+    if let Some(cb) = &self.augment_chunk_hash_fn {
+      return cb
+        .call_async((BindingPluginContext::new(ctx), chunk.clone().into()))
+        .await
+        .map_err(|e| e.into_bundle_error());
+    }
+    Ok(None)
+  }
+
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn resolve_file_url(
+    &self,
+    ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookResolveFileUrlArgs,
+  ) -> rolldown_plugin::HookResolveFileUrlReturn {
+    if let Some(cb) = &self.resolve_file_url_fn {
+      return cb
+        .call_async((BindingPluginContext::new(ctx), args.into()))
+        .await
+        .map_err(|e| e.into_bundle_error());
+    }
+    Ok(None)
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn generate_bundle(
     &self,
-    _ctx: &rolldown_plugin::PluginContext,
-    bundle: &Vec<rolldown_common::Output>,
-    is_write: bool,
+    ctx: &rolldown_plugin::PluginContext,
+    args: &mut rolldown_plugin::HookGenerateBundleArgs,
   ) -> rolldown_plugin::HookNoopReturn {
     if let Some(cb) = &self.generate_bundle_fn {
-      cb.call_async((bundle.clone().into(), is_write)).await.map_err(|e| e.into_bundle_error())?;
+      let output = cb
+        .call_async((BindingPluginContext::new(ctx), args.bundle.clone().into(), args.is_write))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+      if let Some(output) = output {
+        if let Some(deleted_file_names) = output.deleted_file_names {
+          args
+            .bundle
+            .retain(|item| !deleted_file_names.iter().any(|name| name == item.file_name()));
+        }
+        output.emitted_assets.into_iter().flatten().for_each(|asset| {
+          ctx.emit_file(rolldown_plugin::EmittedAsset {
+            name: None,
+            file_name: Some(asset.file_name.clone()),
+            source: asset.into(),
+          });
+        });
+      }
     }
     Ok(())
   }
@@ -188,10 +345,20 @@ impl Plugin for JsAdapterPlugin {
   async fn write_bundle(
     &self,
     _ctx: &rolldown_plugin::PluginContext,
-    bundle: &Vec<rolldown_common::Output>,
+    args: &rolldown_plugin::HookWriteBundleArgs,
   ) -> rolldown_plugin::HookNoopReturn {
     if let Some(cb) = &self.write_bundle_fn {
-      cb.call_async((bundle.clone().into(),)).await.map_err(|e| e.into_bundle_error())?;
+      cb.call_async((args.bundle.clone().into(),)).await.map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn close_bundle(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+  ) -> rolldown_plugin::HookNoopReturn {
+    if let Some(cb) = &self.close_bundle_fn {
+      cb.call_async(()).await.map_err(|e| e.into_bundle_error())?;
     }
     Ok(())
   }
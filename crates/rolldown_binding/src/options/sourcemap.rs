@@ -11,6 +11,7 @@ pub struct SourceMap {
   pub names: Vec<String>,
   pub source_root: Option<String>,
   pub sources: Vec<String>,
+  #[serde(default)]
   pub sources_content: Vec<String>,
   // pub version: u32,
   // #[serde(rename = "x_google_ignoreList")]
@@ -22,3 +23,23 @@ impl From<SourceMap> for rolldown_sourcemap::SourceMap {
     Self::new(value.mappings, value.names, value.source_root, value.sources, value.sources_content)
   }
 }
+
+impl TryFrom<rolldown_sourcemap::SourceMap> for SourceMap {
+  type Error = napi::Error;
+
+  fn try_from(mut value: rolldown_sourcemap::SourceMap) -> napi::Result<Self> {
+    match value.to_json() {
+      Some(json) => {
+        let json = json.map_err(|err| napi::Error::from_reason(err.to_string()))?;
+        serde_json::from_str(&json).map_err(|err| napi::Error::from_reason(err.to_string()))
+      }
+      None => Ok(Self {
+        mappings: value.mappings,
+        names: value.names,
+        source_root: value.source_root,
+        sources: value.sources,
+        sources_content: value.sources_content,
+      }),
+    }
+  }
+}
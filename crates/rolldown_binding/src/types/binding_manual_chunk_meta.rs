@@ -0,0 +1,31 @@
+use napi_derive::napi;
+use rolldown_common::ManualChunkMeta;
+
+use super::binding_module_info::BindingModuleInfo;
+
+/// The second argument of the function form of `manualChunks`.
+#[napi]
+#[derive(Debug)]
+pub struct BindingManualChunkMeta {
+  inner: ManualChunkMeta,
+}
+
+impl BindingManualChunkMeta {
+  pub fn new(meta: ManualChunkMeta) -> Self {
+    Self { inner: meta }
+  }
+}
+
+#[napi]
+impl BindingManualChunkMeta {
+  #[napi]
+  #[allow(clippy::needless_pass_by_value)]
+  pub fn get_module_info(&self, id: String) -> Option<BindingModuleInfo> {
+    self.inner.get_module_info(&id).as_ref().map(Into::into)
+  }
+
+  #[napi]
+  pub fn get_module_ids(&self) -> Vec<String> {
+    self.inner.get_module_ids().collect()
+  }
+}
@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+
+use napi_derive::napi;
+
+#[napi(object)]
+pub struct BindingModuleInfo {
+  pub id: String,
+  pub code: Option<String>,
+  pub is_entry: bool,
+  pub is_external: bool,
+  pub importers: Vec<String>,
+  pub dynamic_importers: Vec<String>,
+  pub imported_ids: Vec<String>,
+  pub dynamically_imported_ids: Vec<String>,
+  pub exported_bindings: Vec<String>,
+  pub has_module_side_effects: bool,
+  /// The JSON of custom data returned by `load` hooks.
+  pub meta: String,
+}
+
+impl Debug for BindingModuleInfo {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("BindingModuleInfo")
+      .field("id", &self.id)
+      .field("code", &"...")
+      .field("is_entry", &self.is_entry)
+      .field("is_external", &self.is_external)
+      .field("importers", &self.importers)
+      .field("dynamic_importers", &self.dynamic_importers)
+      .field("imported_ids", &self.imported_ids)
+      .field("dynamically_imported_ids", &self.dynamically_imported_ids)
+      .field("exported_bindings", &self.exported_bindings)
+      .field("has_module_side_effects", &self.has_module_side_effects)
+      .field("meta", &self.meta)
+      .finish()
+  }
+}
+
+impl From<&rolldown_common::ModuleInfo> for BindingModuleInfo {
+  fn from(value: &rolldown_common::ModuleInfo) -> Self {
+    Self {
+      id: value.id.clone(),
+      code: value.code.as_ref().map(ToString::to_string),
+      is_entry: value.is_entry,
+      is_external: value.is_external,
+      importers: value.importers.clone(),
+      dynamic_importers: value.dynamic_importers.clone(),
+      imported_ids: value.imported_ids.clone(),
+      dynamically_imported_ids: value.dynamically_imported_ids.clone(),
+      exported_bindings: value.exported_bindings.clone(),
+      has_module_side_effects: value.has_module_side_effects,
+      meta: serde_json::to_string(&value.meta).unwrap_or_default(),
+    }
+  }
+}
@@ -1,17 +1,30 @@
 use derivative::Derivative;
-use serde::Deserialize;
+use napi::{bindgen_prelude::Buffer, Either};
 
 #[napi_derive::napi(object)]
-#[derive(Deserialize, Default, Derivative)]
-#[serde(rename_all = "camelCase")]
+#[derive(Derivative)]
 #[derivative(Debug)]
 pub struct BindingOutputAsset {
   pub file_name: String,
-  pub source: String,
+  #[derivative(Debug = "ignore")]
+  pub source: Either<String, Buffer>,
 }
 
 impl From<Box<rolldown_common::OutputAsset>> for BindingOutputAsset {
   fn from(chunk: Box<rolldown_common::OutputAsset>) -> Self {
-    Self { source: chunk.source, file_name: chunk.file_name }
+    let source = match chunk.source {
+      rolldown_common::AssetSource::String(source) => Either::A(source),
+      rolldown_common::AssetSource::Buffer(source) => Either::B(source.into()),
+    };
+    Self { source, file_name: chunk.file_name }
+  }
+}
+
+impl From<BindingOutputAsset> for rolldown_common::AssetSource {
+  fn from(asset: BindingOutputAsset) -> Self {
+    match asset.source {
+      Either::A(source) => Self::String(source),
+      Either::B(source) => Self::Buffer(source.to_vec()),
+    }
   }
 }
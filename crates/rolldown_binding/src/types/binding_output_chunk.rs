@@ -20,6 +20,9 @@ pub struct BindingOutputChunk {
   pub file_name: String,
   #[serde(skip_deserializing)]
   pub modules: HashMap<String, BindingRenderedModule>,
+  pub imports: Vec<String>,
+  pub dynamic_imports: Vec<String>,
+  pub imported_css: Vec<String>,
   // OutputChunk
   pub code: String,
 }
@@ -35,6 +38,9 @@ impl From<Box<rolldown_common::OutputChunk>> for BindingOutputChunk {
       modules: chunk.modules.into_iter().map(|(key, value)| (key, value.into())).collect(),
       exports: chunk.exports,
       module_ids: chunk.module_ids,
+      imports: chunk.imports,
+      dynamic_imports: chunk.dynamic_imports,
+      imported_css: chunk.imported_css,
     }
   }
 }
@@ -1,15 +1,15 @@
 use derivative::Derivative;
-use serde::Deserialize;
 
 use super::{binding_output_asset::BindingOutputAsset, binding_output_chunk::BindingOutputChunk};
 
 #[napi_derive::napi(object)]
-#[derive(Deserialize, Default, Derivative)]
-#[serde(rename_all = "camelCase")]
+#[derive(Default, Derivative)]
 #[derivative(Debug)]
 pub struct BindingOutputs {
   pub chunks: Vec<BindingOutputChunk>,
   pub assets: Vec<BindingOutputAsset>,
+  /// The JSON of the metafile, only generated with `metafile` of output options.
+  pub metafile: Option<String>,
 }
 
 impl From<Vec<rolldown_common::Output>> for BindingOutputs {
@@ -22,6 +22,19 @@ impl From<Vec<rolldown_common::Output>> for BindingOutputs {
       rolldown_common::Output::Asset(asset) => assets.push(asset.into()),
     });
 
-    Self { chunks, assets }
+    Self { chunks, assets, metafile: None }
+  }
+}
+
+impl TryFrom<rolldown::RolldownOutput> for BindingOutputs {
+  type Error = napi::Error;
+
+  fn try_from(output: rolldown::RolldownOutput) -> napi::Result<Self> {
+    let metafile = output
+      .metafile
+      .map(|metafile| serde_json::to_string(&metafile))
+      .transpose()
+      .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+    Ok(Self { metafile, ..output.assets.into() })
   }
 }
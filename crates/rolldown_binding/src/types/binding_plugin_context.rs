@@ -0,0 +1,167 @@
+use napi::{bindgen_prelude::Buffer, Either};
+use napi_derive::napi;
+use rolldown_common::{AssetSource, ImportKind};
+use rolldown_plugin::{
+  EmittedAsset, EmittedChunk, PluginContext, PluginContextResolveOptions, ResolvedId,
+};
+
+use super::binding_module_info::BindingModuleInfo;
+use crate::options::sourcemap::SourceMap;
+
+#[napi(object)]
+pub struct BindingEmittedAsset {
+  pub name: Option<String>,
+  pub file_name: Option<String>,
+  pub source: Either<String, Buffer>,
+}
+
+impl From<BindingEmittedAsset> for EmittedAsset {
+  fn from(value: BindingEmittedAsset) -> Self {
+    let source = match value.source {
+      Either::A(source) => AssetSource::String(source),
+      Either::B(source) => AssetSource::Buffer(source.to_vec()),
+    };
+    Self { name: value.name, file_name: value.file_name, source }
+  }
+}
+
+#[napi(object)]
+pub struct BindingEmittedChunk {
+  pub name: Option<String>,
+  pub id: String,
+}
+
+impl From<BindingEmittedChunk> for EmittedChunk {
+  fn from(value: BindingEmittedChunk) -> Self {
+    Self { name: value.name, id: value.id }
+  }
+}
+
+#[napi(object)]
+pub struct BindingPluginContextResolveOptions {
+  pub skip_self: Option<bool>,
+  #[napi(ts_type = "'import-statement' | 'dynamic-import' | 'require-call'")]
+  pub kind: Option<String>,
+}
+
+impl TryFrom<BindingPluginContextResolveOptions> for PluginContextResolveOptions {
+  type Error = napi::Error;
+
+  fn try_from(value: BindingPluginContextResolveOptions) -> napi::Result<Self> {
+    let kind = match value.kind.as_deref() {
+      None | Some("import-statement") => ImportKind::Import,
+      Some("dynamic-import") => ImportKind::DynamicImport,
+      Some("require-call") => ImportKind::Require,
+      Some(kind) => return Err(napi::Error::from_reason(format!("Unknown import kind: {kind}"))),
+    };
+    Ok(Self { skip_self: value.skip_self.unwrap_or_default(), kind })
+  }
+}
+
+#[napi(object)]
+pub struct BindingResolvedId {
+  pub id: String,
+  pub external: bool,
+  pub module_side_effects: Option<bool>,
+}
+
+impl From<ResolvedId> for BindingResolvedId {
+  fn from(value: ResolvedId) -> Self {
+    Self { id: value.id, external: value.external, module_side_effects: value.module_side_effects }
+  }
+}
+
+/// Methods of the plugin context that are implemented on the rust side.
+#[napi]
+#[derive(Debug)]
+pub struct BindingPluginContext {
+  inner: PluginContext,
+}
+
+impl BindingPluginContext {
+  pub fn new(ctx: &PluginContext) -> Self {
+    Self { inner: ctx.clone() }
+  }
+}
+
+#[napi]
+impl BindingPluginContext {
+  #[napi]
+  pub fn emit_file(&self, file: BindingEmittedAsset) -> String {
+    self.inner.emit_file(EmittedAsset::from(file))
+  }
+
+  #[napi]
+  pub fn emit_chunk(&self, chunk: BindingEmittedChunk) -> String {
+    self.inner.emit_chunk(chunk.into())
+  }
+
+  #[napi]
+  #[allow(clippy::needless_pass_by_value)]
+  pub fn get_file_name(&self, reference_id: String) -> napi::Result<String> {
+    self.inner.get_file_name(&reference_id).map_err(|err| napi::Error::from_reason(err.to_string()))
+  }
+
+  #[napi]
+  #[allow(clippy::needless_pass_by_value)]
+  pub fn get_module_info(&self, id: String) -> Option<BindingModuleInfo> {
+    self.inner.get_module_info(&id).as_ref().map(Into::into)
+  }
+
+  #[napi]
+  pub fn get_module_ids(&self) -> Vec<String> {
+    self.inner.get_module_ids().collect()
+  }
+
+  #[napi]
+  pub fn warn(&self, message: String) {
+    self.inner.warn(message, None);
+  }
+
+  #[napi]
+  pub fn info(&self, message: String) {
+    self.inner.info(message, None);
+  }
+
+  #[napi]
+  pub fn debug(&self, message: String) {
+    self.inner.debug(message, None);
+  }
+
+  /// Only available in `transform` hooks.
+  #[napi]
+  pub fn get_combined_sourcemap(&self) -> napi::Result<SourceMap> {
+    self
+      .inner
+      .get_combined_sourcemap()
+      .map_err(|err| napi::Error::from_reason(err.to_string()))?
+      .try_into()
+  }
+
+  #[napi]
+  #[allow(clippy::needless_pass_by_value)]
+  pub fn add_watch_file(&self, id: String) {
+    self.inner.add_watch_file(&id);
+  }
+
+  #[napi]
+  pub fn get_watch_files(&self) -> Vec<String> {
+    self.inner.get_watch_files()
+  }
+
+  #[napi]
+  pub async fn resolve(
+    &self,
+    specifier: String,
+    importer: Option<String>,
+    options: Option<BindingPluginContextResolveOptions>,
+  ) -> napi::Result<BindingResolvedId> {
+    let options = options.map(TryInto::try_into).transpose()?.unwrap_or_default();
+    self
+      .inner
+      .resolve(&specifier, importer.as_deref(), options)
+      .await
+      .map(Into::into)
+      .map_err(|err| napi::Error::from_reason(err.to_string()))
+  }
+}
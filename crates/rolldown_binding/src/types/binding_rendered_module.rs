@@ -5,6 +5,7 @@ use napi_derive::napi;
 #[napi(object)]
 pub struct BindingRenderedModule {
   pub code: Option<String>,
+  pub rendered_length: u32,
 }
 
 impl Debug for BindingRenderedModule {
@@ -15,6 +16,9 @@ impl Debug for BindingRenderedModule {
 
 impl From<rolldown_common::RenderedModule> for BindingRenderedModule {
   fn from(value: rolldown_common::RenderedModule) -> Self {
-    Self { code: value.code }
+    Self {
+      code: value.code,
+      rendered_length: u32::try_from(value.rendered_length).unwrap_or(u32::MAX),
+    }
   }
 }
@@ -1,4 +1,7 @@
+pub mod binding_manual_chunk_meta;
+pub mod binding_module_info;
 pub mod binding_output_asset;
 pub mod binding_output_chunk;
 pub mod binding_outputs;
+pub mod binding_plugin_context;
 pub mod binding_rendered_module;
@@ -9,14 +9,15 @@ use napi::{
 use super::IntoJsUnknownVec;
 use crate::NAPI_ENV;
 
-pub trait JsCallbackArgs: IntoJsUnknownVec + Send + Sync + 'static {}
-impl<T: IntoJsUnknownVec + Send + Sync + 'static> JsCallbackArgs for T {}
+pub trait JsCallbackArgs: IntoJsUnknownVec + Send + 'static {}
+impl<T: IntoJsUnknownVec + Send + 'static> JsCallbackArgs for T {}
 pub trait JsCallbackRet: FromNapiValue + ValidateNapiValue + Send + 'static {}
 impl<T: FromNapiValue + ValidateNapiValue + Send + 'static> JsCallbackRet for T {}
 
 pub struct JsCallback<Args: JsCallbackArgs, Ret: JsCallbackRet> {
-  _args: PhantomData<Args>,
-  _ret: PhantomData<Ret>,
+  // Arguments and return values are only moved through calls, so they, like `Buffer`, don't need to be `Sync`.
+  _args: PhantomData<fn(Args)>,
+  _ret: PhantomData<fn() -> Ret>,
   ts_fn: ThreadsafeFunction<Args, ErrorStrategy::Fatal>,
 }
 
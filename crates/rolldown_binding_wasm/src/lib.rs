@@ -1,10 +1,14 @@
 use rolldown_fs::MemoryFileSystem;
+use rustc_hash::FxHashMap;
 use std::panic;
 use std::path::Path;
 
 use wasm_bindgen::prelude::*;
 
-use rolldown::{Bundler, External, InputItem, InputOptions, OutputOptions};
+use rolldown::{
+  AssetOptions, Bundler, ChecksOptions, External, InputItem, InputOptions, JsonOptions, JsxOptions,
+  LogLevel, OutputOptions, PreserveEntrySignatures,
+};
 #[wasm_bindgen]
 pub struct FileItem {
   path: String,
@@ -65,8 +69,21 @@ pub fn bundle(file_list: Vec<FileItem>) -> Vec<AssetItem> {
           input,
           cwd: "/".into(),
           external: External::ArrayString(vec![]),
-          treeshake: false,
+          treeshake: None,
+          shim_missing_exports: false,
           resolve: None,
+          json: JsonOptions::default(),
+          jsx: JsxOptions::default(),
+          asset: AssetOptions::default(),
+          preserve_entry_signatures: PreserveEntrySignatures::default(),
+          define: FxHashMap::default(),
+          inject: FxHashMap::default(),
+          cache: None,
+          ignored_circular_dependencies: vec![],
+          checks: ChecksOptions::default(),
+          log_level: Some(LogLevel::Info),
+          on_log: None,
+          max_errors: 100,
         },
         vec![],
         memory_fs,
@@ -78,7 +95,7 @@ pub fn bundle(file_list: Vec<FileItem>) -> Vec<AssetItem> {
           .into_iter()
           .map(|item| AssetItem {
             name: item.file_name().to_string(),
-            content: item.content().to_owned(),
+            content: String::from_utf8_lossy(item.content_as_bytes()).into_owned(),
           })
           .collect::<Vec<_>>(),
         Err(err) => {
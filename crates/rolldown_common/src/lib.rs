@@ -11,20 +11,43 @@ pub use crate::{
   types::entry_point::{EntryPoint, EntryPointKind},
   types::exports_kind::ExportsKind,
   types::external_module_id::ExternalModuleId,
+  types::file_name_template::{FileNameRenderOptions, FileNameTemplate, RenderHash},
   types::file_path::{representative_name, FilePath},
-  types::import_record::{ImportKind, ImportRecord, ImportRecordId, RawImportRecord},
+  types::import_record::{
+    ImportAttributes, ImportKind, ImportRecord, ImportRecordId, RawImportRecord,
+  },
+  types::input_options::{
+    AssetOptions, ChecksOptions, External, ExternalFn, InputItem, InputOptions, JsonOptions,
+    JsxOptions, JsxRuntime, LogAction, LogLevel, ModuleSideEffects, ModuleSideEffectsFn, OnLog,
+    OnLogFn, PreserveEntrySignatures, PropertyReadSideEffects, TreeshakeOptions,
+    UnresolvedImportCheck,
+  },
+  types::loader::Loader,
+  types::manual_chunk_meta::ManualChunkMeta,
+  types::metafile::{Metafile, MetafileImport, MetafileInput, MetafileOutput, MetafileOutputInput},
   types::module_id::ModuleId,
+  types::module_info::ModuleInfo,
+  types::module_info_table::{ModuleInfoTable, SharedModuleInfoTable},
   types::module_path::ResourceId,
   types::module_type::ModuleType,
   types::named_export::LocalExport,
   types::named_import::{NamedImport, Specifier},
   types::normal_module_id::NormalModuleId,
-  types::output::{Output, OutputAsset},
+  types::output::{AssetSource, Output, OutputAsset},
   types::output_chunk::OutputChunk,
+  types::output_options::{
+    AddonFn, AddonOutputOption, ChunkFileNamesFn, ChunkFileNamesOption, GlobalsFn, GlobalsOption,
+    InteropFn, InteropMode, InteropOption, LegalComments, ManualChunksFn, ManualChunksOption,
+    OutputExports, OutputFormat, OutputOptions, PathsFn, PathsOption, SourceMapType,
+    SourcemapPathTransform, SourcemapPathTransformFn,
+  },
+  types::pre_rendered_chunk::PreRenderedChunk,
   types::rendered_chunk::RenderedChunk,
   types::rendered_module::RenderedModule,
   types::resolved_export::ResolvedExport,
   types::resolved_path::ResolvedPath,
+  types::resolver_options::{AliasFind, AliasItem, ResolverOptions},
+  types::rolldown_cache::{CachedModule, RolldownCache},
   types::stmt_info::{DebugStmtInfoForTreeShaking, StmtInfo, StmtInfoId, StmtInfos},
   types::symbol_ref::SymbolRef,
   types::wrap_kind::WrapKind,
@@ -1,4 +1,4 @@
-use crate::{ExternalModuleId, ImportRecord, ImportRecordId, ResourceId};
+use crate::{ExternalModuleId, ImportAttributes, ImportRecord, ImportRecordId, ResourceId};
 use index_vec::IndexVec;
 
 #[derive(Debug)]
@@ -7,10 +7,26 @@ pub struct ExternalModule {
   pub exec_order: u32,
   pub resource_id: ResourceId,
   pub import_records: IndexVec<ImportRecordId, ImportRecord>,
+  /// External modules without side effects are only imported if any of their exports is imported.
+  pub side_effects: bool,
+  /// Attributes the module is imported with, which are kept in the imports of the output.
+  pub attributes: ImportAttributes,
 }
 
 impl ExternalModule {
-  pub fn new(id: ExternalModuleId, resource_id: ResourceId) -> Self {
-    Self { id, exec_order: u32::MAX, resource_id, import_records: IndexVec::default() }
+  pub fn new(
+    id: ExternalModuleId,
+    resource_id: ResourceId,
+    side_effects: bool,
+    attributes: ImportAttributes,
+  ) -> Self {
+    Self {
+      id,
+      exec_order: u32::MAX,
+      resource_id,
+      import_records: IndexVec::default(),
+      side_effects,
+      attributes,
+    }
   }
 }
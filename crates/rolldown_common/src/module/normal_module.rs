@@ -11,6 +11,7 @@ use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
 
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct NormalModule {
   pub exec_order: u32,
   pub source: Arc<str>,
@@ -35,8 +36,28 @@ pub struct NormalModule {
   pub exports_kind: ExportsKind,
   pub scope: AstScope,
   pub default_export_ref: SymbolRef,
-  pub sourcemap_chain: Vec<rolldown_sourcemap::SourceMap>,
+  pub sourcemap_chain: rolldown_sourcemap::SourceMapChain,
+  /// The `#!` line of the module without `#!`, which is taken out of the ast and re-emitted as the first line of
+  /// the entry chunk of the module.
+  pub hashbang: Option<String>,
+  /// The content of a CSS module, which is concatenated into the CSS asset of its chunk.
+  pub css: Option<String>,
+  /// File names of assets referenced by `new URL('./file', import.meta.url)`, keyed by the spans of the `new`
+  /// expressions. They are placeholders until file names of assets are assigned.
+  pub asset_urls: FxHashMap<Span, String>,
+  /// Reference ids of emitted files whose URLs are referenced by `import.meta.ROLLUP_FILE_URL_<referenceId>`.
+  pub file_url_references: Vec<String>,
+  /// The span of the first top-level `await` of the module.
+  pub top_level_await: Option<Span>,
   pub is_included: bool,
+  /// Whether the module is kept for its side effects if none of its exports is used, see
+  /// `treeshake.module_side_effects`.
+  pub side_effects: bool,
+  /// None of the statements are tree shaken, set by `moduleSideEffects: 'no-treeshake'` of `load` hooks.
+  pub no_treeshake: bool,
+  /// The export whose properties are importable as named exports that the module doesn't declare, set by
+  /// `syntheticNamedExports` of `load` hooks.
+  pub synthetic_named_exports: Option<Rstr>,
 }
 
 impl NormalModule {
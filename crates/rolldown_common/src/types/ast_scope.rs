@@ -19,6 +19,14 @@ impl AstScope {
   pub fn symbol_id_for(&self, reference_id: ReferenceId) -> Option<SymbolId> {
     self.references[reference_id].symbol_id()
   }
+
+  /// Whether the symbol is assigned or updated after its declaration.
+  pub fn is_reassigned(&self, symbol_id: SymbolId) -> bool {
+    self
+      .references
+      .iter()
+      .any(|reference| reference.symbol_id() == Some(symbol_id) && reference.is_write())
+  }
 }
 
 impl std::ops::Deref for AstScope {
@@ -2,7 +2,13 @@ use crate::NormalModuleId;
 
 #[derive(Debug)]
 pub enum ChunkKind {
-  EntryPoint { is_user_defined: bool, bit: u32, module: NormalModuleId },
+  /// `is_user_defined` is true for entries in `input` and entries emitted by plugins, which are rendered with
+  /// `entry_file_names`. Entries created by dynamic imports are not user defined.
+  EntryPoint {
+    is_user_defined: bool,
+    bit: u32,
+    module: NormalModuleId,
+  },
   Common,
 }
 
@@ -10,5 +10,7 @@ pub struct EntryPoint {
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum EntryPointKind {
   UserDefined,
+  /// Emitted by plugins via `this.emitFile({ type: "chunk" })`.
+  Emitted,
   DynamicImport,
 }
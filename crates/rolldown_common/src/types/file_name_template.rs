@@ -0,0 +1,102 @@
+#[derive(Debug, Clone)]
+pub struct FileNameTemplate {
+  template: String,
+}
+
+impl FileNameTemplate {
+  #[allow(dead_code)]
+  pub fn new(template: String) -> Self {
+    Self { template }
+  }
+
+  pub fn as_str(&self) -> &str {
+    &self.template
+  }
+}
+
+impl From<String> for FileNameTemplate {
+  fn from(template: String) -> Self {
+    Self { template }
+  }
+}
+
+/// Renders `[hash]`, with `None`, or `[hash:<length>]`, with the length.
+pub type RenderHash<'me> = &'me dyn Fn(Option<usize>) -> String;
+
+#[derive(Default)]
+pub struct FileNameRenderOptions<'me> {
+  pub name: Option<&'me str>,
+  pub hash: Option<RenderHash<'me>>,
+  /// The output format, like `es` or `cjs`. Used by `[format]`.
+  pub format: Option<&'me str>,
+  /// Extension with the leading dot, like `.png`. Used by `[extname]`, and by `[ext]` without the dot.
+  pub extname: Option<&'me str>,
+}
+
+const HASH_WITH_LENGTH_PREFIX: &str = "[hash:";
+
+impl FileNameTemplate {
+  /// Whether the template contains `[hash]` or `[hash:<length>]`, so callers could skip computing hashes that are
+  /// not used.
+  pub fn has_hash_pattern(&self) -> bool {
+    self.template.contains("[hash]") || !self.hash_lengths().is_empty()
+  }
+
+  /// Lengths of `[hash:<length>]` placeholders in the template.
+  pub fn hash_lengths(&self) -> Vec<usize> {
+    self
+      .template
+      .match_indices(HASH_WITH_LENGTH_PREFIX)
+      .filter_map(|(start, _)| parse_hash_length(&self.template[start..]).map(|(length, _)| length))
+      .collect()
+  }
+
+  pub fn render(&self, options: &FileNameRenderOptions) -> String {
+    let mut tmp = self.template.clone();
+    if let Some(name) = options.name {
+      tmp = tmp.replace("[name]", name);
+    }
+    if let Some(hash) = options.hash {
+      if tmp.contains("[hash]") {
+        tmp = tmp.replace("[hash]", &hash(None));
+      }
+      tmp = render_hash_with_length(&tmp, hash);
+    }
+    if let Some(format) = options.format {
+      tmp = tmp.replace("[format]", format);
+    }
+    if let Some(extname) = options.extname {
+      tmp = tmp.replace("[extname]", extname).replace("[ext]", extname.trim_start_matches('.'));
+    }
+    tmp
+  }
+}
+
+/// Parse `[hash:<length>]` at the start of `s`, returning the length and the length of the placeholder.
+fn parse_hash_length(s: &str) -> Option<(usize, usize)> {
+  let rest = s.strip_prefix(HASH_WITH_LENGTH_PREFIX)?;
+  let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+  if digits == 0 || !rest[digits..].starts_with(']') {
+    return None;
+  }
+  let length = rest[..digits].parse().ok()?;
+  Some((length, HASH_WITH_LENGTH_PREFIX.len() + digits + 1))
+}
+
+fn render_hash_with_length(template: &str, hash: RenderHash) -> String {
+  let mut result = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some(start) = rest.find(HASH_WITH_LENGTH_PREFIX) {
+    result.push_str(&rest[..start]);
+    rest = &rest[start..];
+    if let Some((length, placeholder_len)) = parse_hash_length(rest) {
+      result.push_str(&hash(Some(length)));
+      rest = &rest[placeholder_len..];
+    } else {
+      result.push_str(HASH_WITH_LENGTH_PREFIX);
+      rest = &rest[HASH_WITH_LENGTH_PREFIX.len()..];
+    }
+  }
+  result.push_str(rest);
+  result
+}
@@ -19,6 +19,11 @@ impl FilePath {
   pub fn as_str(&self) -> &str {
     &self.0
   }
+
+  /// Ids starting with `\0` belong to virtual modules provided by plugins, which aren't files on the disk.
+  pub fn is_virtual(&self) -> bool {
+    self.0.starts_with('\0')
+  }
 }
 
 impl AsRef<str> for FilePath {
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
 
 use rolldown_rstr::Rstr;
 
@@ -31,11 +31,15 @@ impl Display for ImportKind {
   }
 }
 
+/// Import attributes of a module request, like `with { type: "json" }`, keyed by their names.
+pub type ImportAttributes = BTreeMap<String, String>;
+
 #[derive(Debug)]
 pub struct RawImportRecord {
   // Module Request
   pub module_request: Rstr,
   pub kind: ImportKind,
+  pub attributes: ImportAttributes,
   pub namespace_ref: SymbolRef,
   pub contains_import_star: bool,
   pub contains_import_default: bool,
@@ -46,6 +50,7 @@ impl RawImportRecord {
     Self {
       module_request: specifier,
       kind,
+      attributes: ImportAttributes::default(),
       namespace_ref,
       contains_import_default: false,
       contains_import_star: false,
@@ -57,6 +62,7 @@ impl RawImportRecord {
       module_request: self.module_request,
       resolved_module,
       kind: self.kind,
+      attributes: self.attributes,
       namespace_ref: self.namespace_ref,
       contains_import_star: self.contains_import_star,
       contains_import_default: self.contains_import_default,
@@ -70,6 +76,7 @@ pub struct ImportRecord {
   pub module_request: Rstr,
   pub resolved_module: ModuleId,
   pub kind: ImportKind,
+  pub attributes: ImportAttributes,
   pub namespace_ref: SymbolRef,
   pub contains_import_star: bool,
   pub contains_import_default: bool,
@@ -0,0 +1,426 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use rolldown_error::BuildError;
+use rustc_hash::FxHashMap;
+
+use crate::{ResolverOptions, RolldownCache};
+
+pub type ExternalFn = dyn Fn(
+    String,
+    Option<String>,
+    bool,
+  ) -> Pin<Box<(dyn Future<Output = Result<bool, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+pub enum External {
+  ArrayString(Vec<String>),
+  Fn(Box<ExternalFn>),
+}
+
+impl Debug for External {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::ArrayString(value) => write!(f, "External::ArrayString({value:?})"),
+      Self::Fn(_) => write!(f, "External::Fn(...)"),
+    }
+  }
+}
+
+impl Default for External {
+  fn default() -> Self {
+    Self::ArrayString(vec![])
+  }
+}
+
+impl External {
+  pub async fn call(
+    &self,
+    source: String,
+    importer: Option<String>,
+    is_resolved: bool,
+  ) -> Result<bool, BuildError> {
+    match self {
+      Self::ArrayString(value) => {
+        let result = value.iter().any(|item| item == &source);
+        Ok(result)
+      }
+      Self::Fn(value) => value(source, importer, is_resolved).await,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct InputItem {
+  pub name: Option<String>,
+  pub import: String,
+}
+
+impl From<String> for InputItem {
+  fn from(value: String) -> Self {
+    Self { name: None, import: value }
+  }
+}
+
+/// An entry of the object form of `input`, which maps names of entries to their imports. Names containing `/`
+/// put entry chunks into nested directories.
+impl<N: Into<String>, I: Into<String>> From<(N, I)> for InputItem {
+  fn from((name, import): (N, I)) -> Self {
+    Self { name: Some(name.into()), import: import.into() }
+  }
+}
+
+/// How `.json` modules are turned into ES modules.
+#[derive(Debug, Clone)]
+pub struct JsonOptions {
+  /// Export top-level keys that are valid identifiers as named exports besides the default export, so unused keys
+  /// could be tree shaken.
+  pub named_exports: bool,
+}
+
+impl Default for JsonOptions {
+  fn default() -> Self {
+    Self { named_exports: true }
+  }
+}
+
+/// How files imported as assets, like images, are turned into ES modules, whose default exports are the URLs of the
+/// files. The files are copied into the output as assets named by `output.assetFileNames`.
+#[derive(Debug, Clone, Default)]
+pub struct AssetOptions {
+  /// Extensions of files imported as assets without the leading `.`, like `png`.
+  pub extensions: Vec<String>,
+  /// Prepended to file names of assets in their URLs, like `/static/`.
+  pub public_path: String,
+}
+
+/// How JSX of `.jsx` and `.tsx` modules is turned into function calls.
+#[derive(Debug, Clone)]
+pub struct JsxOptions {
+  pub runtime: JsxRuntime,
+  /// The package the automatic runtime imports `jsx`, `jsxs` and `Fragment` from, with `/jsx-runtime` appended.
+  pub import_source: String,
+  /// The function called for elements by the classic runtime.
+  pub pragma: String,
+  /// The component used for fragments by the classic runtime.
+  pub pragma_frag: String,
+}
+
+impl Default for JsxOptions {
+  fn default() -> Self {
+    Self {
+      runtime: JsxRuntime::default(),
+      import_source: "react".to_string(),
+      pragma: "React.createElement".to_string(),
+      pragma_frag: "React.Fragment".to_string(),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JsxRuntime {
+  /// `<div />` becomes `jsx("div", {})`, where `jsx` is imported from `react/jsx-runtime` like any other import,
+  /// so it's resolved by plugins and could be external.
+  #[default]
+  Automatic,
+  /// `<div />` becomes `React.createElement("div", null)`, where `React` should be in scope.
+  Classic,
+}
+
+impl From<String> for JsxRuntime {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "automatic" => JsxRuntime::Automatic,
+      "classic" => JsxRuntime::Classic,
+      _ => unreachable!("unknown jsx runtime"),
+    }
+  }
+}
+
+/// How problems found while scanning modules are reported.
+#[derive(Debug, Clone, Default)]
+pub struct ChecksOptions {
+  pub unresolved_import: UnresolvedImportCheck,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnresolvedImportCheck {
+  /// Imports that can't be resolved fail the build.
+  #[default]
+  Error,
+  /// Bare specifiers that can't be resolved, like `lodash`, are treated as externals with a warning, following
+  /// Rollup. Relative or absolute specifiers are still errors, since they're most likely typos.
+  WarnExternal,
+}
+
+impl From<String> for UnresolvedImportCheck {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "error" => UnresolvedImportCheck::Error,
+      "warn-external" => UnresolvedImportCheck::WarnExternal,
+      _ => unreachable!("unknown unresolved import check"),
+    }
+  }
+}
+
+/// How the exports of entry chunks relate to the exports of their entry modules, like `preserveEntrySignatures` of
+/// rollup. Dynamic entries are always imported as namespaces, so they aren't affected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PreserveEntrySignatures {
+  /// Entry chunks export exactly the exports of their entry modules. Entry modules shared with other entries stay
+  /// in shared chunks, which the entry chunks re-export.
+  #[default]
+  Strict,
+  /// Entry chunks could export more bindings for other chunks, so an entry module shared with other entries is
+  /// put into its entry chunk if there is nothing else in it, instead of a separate shared chunk.
+  AllowExtension,
+  /// `Strict` for entry modules with exports, `AllowExtension` for the others.
+  ExportsOnly,
+  /// Exports of entry modules are tree shaken like any other bindings, and entry chunks only export bindings
+  /// imported by other chunks.
+  False,
+}
+
+impl PreserveEntrySignatures {
+  /// Whether the entry chunk of a module with `has_exports` could export bindings its entry module doesn't.
+  pub fn allows_extension(self, has_exports: bool) -> bool {
+    match self {
+      Self::Strict => false,
+      Self::AllowExtension | Self::False => true,
+      Self::ExportsOnly => !has_exports,
+    }
+  }
+}
+
+impl From<String> for PreserveEntrySignatures {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "strict" => PreserveEntrySignatures::Strict,
+      "allow-extension" => PreserveEntrySignatures::AllowExtension,
+      "exports-only" => PreserveEntrySignatures::ExportsOnly,
+      "false" => PreserveEntrySignatures::False,
+      _ => unreachable!("unknown preserve entry signatures"),
+    }
+  }
+}
+
+/// The level of a log, from the least verbose to the most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+  Warn,
+  Info,
+  Debug,
+}
+
+impl LogLevel {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      LogLevel::Warn => "warn",
+      LogLevel::Info => "info",
+      LogLevel::Debug => "debug",
+    }
+  }
+}
+
+impl From<String> for LogLevel {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "warn" => LogLevel::Warn,
+      "info" => LogLevel::Info,
+      "debug" => LogLevel::Debug,
+      _ => unreachable!("unknown log level"),
+    }
+  }
+}
+
+/// What is done with a log passed to `on_log`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogAction {
+  /// Returned in `RolldownOutput`, like logs without `on_log`.
+  Keep,
+  /// Dropped, like logs already forwarded to another logger.
+  Suppress,
+  /// Turned into an error, which fails the build.
+  Error,
+}
+
+impl From<String> for LogAction {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "keep" => LogAction::Keep,
+      "suppress" => LogAction::Suppress,
+      "error" => LogAction::Error,
+      _ => unreachable!("unknown log action"),
+    }
+  }
+}
+
+pub type OnLogFn = dyn Fn(
+    LogLevel,
+    &BuildError,
+  ) -> Pin<Box<(dyn Future<Output = Result<LogAction, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Called with each warning of the build and each log of plugins that passes `log_level`, deciding what is done
+/// with it.
+pub struct OnLog(Box<OnLogFn>);
+
+impl Debug for OnLog {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "OnLog(...)")
+  }
+}
+
+impl OnLog {
+  pub fn new(f: Box<OnLogFn>) -> Self {
+    Self(f)
+  }
+
+  pub async fn call(&self, level: LogLevel, log: &BuildError) -> Result<LogAction, BuildError> {
+    (self.0)(level, log).await
+  }
+}
+
+pub type ModuleSideEffectsFn = dyn Fn(String, bool) -> Pin<Box<(dyn Future<Output = Result<bool, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Which modules have side effects, which decides whether modules are kept when none of their exports is used.
+/// `resolve_id` and `load` hooks of plugins could still decide it for each module.
+pub enum ModuleSideEffects {
+  /// `true` assumes modules have side effects unless the `sideEffects` field of their nearest `package.json` says
+  /// otherwise. `false` assumes no module has side effects, including external ones.
+  Boolean(bool),
+  /// `"no-external"`, like `true` but external modules have no side effects, so imports of them are removed if
+  /// nothing imported is used.
+  NoExternal,
+  /// Only modules whose ids match any of the glob patterns have side effects. Relative patterns are resolved
+  /// against `cwd`.
+  Patterns(Vec<String>),
+  /// Called with the id of each module and whether it's external.
+  Fn(Box<ModuleSideEffectsFn>),
+}
+
+impl Debug for ModuleSideEffects {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Boolean(value) => write!(f, "ModuleSideEffects::Boolean({value:?})"),
+      Self::NoExternal => write!(f, "ModuleSideEffects::NoExternal"),
+      Self::Patterns(value) => write!(f, "ModuleSideEffects::Patterns({value:?})"),
+      Self::Fn(_) => write!(f, "ModuleSideEffects::Fn(...)"),
+    }
+  }
+}
+
+impl Default for ModuleSideEffects {
+  fn default() -> Self {
+    Self::Boolean(true)
+  }
+}
+
+/// Whether reading properties could have side effects, like calling getters or throwing on `null`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyReadSideEffects {
+  /// `true` assumes reads have side effects, except some known builtins like `Object.create`. `false` assumes
+  /// no read has side effects, only the object being read decides it.
+  Boolean(bool),
+  /// `"always"`, reads of known builtins are assumed to have side effects too.
+  Always,
+}
+
+impl Default for PropertyReadSideEffects {
+  fn default() -> Self {
+    Self::Boolean(true)
+  }
+}
+
+#[derive(Debug)]
+pub struct TreeshakeOptions {
+  pub module_side_effects: ModuleSideEffects,
+  /// Treat calls and `new` expressions annotated with `/*#__PURE__*/` or `/*@__PURE__*/` as side-effect-free.
+  pub annotations: bool,
+  pub property_read_side_effects: PropertyReadSideEffects,
+  /// Whether statements in `try` blocks are kept regardless of their side effects, since any of them could throw
+  /// into the `catch` clause, like what rollup does by default.
+  ///
+  /// Turning it off analyzes `try` statements like normal blocks, which removes more dead code, but it could
+  /// remove code that's expected to throw, like feature detection with `try { new Proxy({}, {}) } catch {}`.
+  pub try_catch_deoptimization: bool,
+}
+
+impl Default for TreeshakeOptions {
+  fn default() -> Self {
+    Self {
+      module_side_effects: ModuleSideEffects::default(),
+      annotations: true,
+      property_read_side_effects: PropertyReadSideEffects::default(),
+      try_catch_deoptimization: true,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct InputOptions {
+  pub input: Vec<InputItem>,
+  pub cwd: PathBuf,
+  pub external: External,
+  /// `None` disables tree shaking.
+  pub treeshake: Option<TreeshakeOptions>,
+  /// Bind named imports that are not exported by their ES module importees to `undefined` with a warning, instead
+  /// of failing the build.
+  pub shim_missing_exports: bool,
+  pub resolve: Option<ResolverOptions>,
+  pub json: JsonOptions,
+  pub jsx: JsxOptions,
+  pub asset: AssetOptions,
+  pub preserve_entry_signatures: PreserveEntrySignatures,
+  /// Replaces global identifiers or member expressions, like `process.env.NODE_ENV`, with expressions before the
+  /// modules get scanned. Dead branches of `if` statements and conditional expressions are removed afterwards.
+  pub define: FxHashMap<String, String>,
+  /// Maps global identifiers to `(module_specifier, exported_name)`. Modules that reference one of them get
+  /// `import { exported_name as identifier } from 'module_specifier'` prepended. `default` and `*` import the
+  /// default export and the namespace.
+  pub inject: FxHashMap<String, (String, String)>,
+  /// Transformed modules of a previous build, usually returned by `Bundler::cache` and possibly read from disk,
+  /// which are reused if they don't change.
+  pub cache: Option<RolldownCache>,
+  /// Circular dependencies are warned about unless any module in the cycle matches one of the glob patterns, like
+  /// vendor packages that are intentionally circular. Relative patterns are resolved against `cwd`.
+  pub ignored_circular_dependencies: Vec<String>,
+  pub checks: ChecksOptions,
+  /// Logs more verbose than the level are dropped before reaching `on_log`. `None` is `silent`, which drops
+  /// warnings too.
+  pub log_level: Option<LogLevel>,
+  pub on_log: Option<OnLog>,
+  /// Errors of modules are collected until there are this many distinct ones, rather than stopping at the first one.
+  pub max_errors: usize,
+}
+
+impl Default for InputOptions {
+  fn default() -> Self {
+    Self {
+      input: vec![],
+      cwd: std::env::current_dir().unwrap(),
+      external: External::default(),
+      treeshake: Some(TreeshakeOptions::default()),
+      shim_missing_exports: false,
+      resolve: None,
+      json: JsonOptions::default(),
+      jsx: JsxOptions::default(),
+      asset: AssetOptions::default(),
+      preserve_entry_signatures: PreserveEntrySignatures::default(),
+      define: FxHashMap::default(),
+      inject: FxHashMap::default(),
+      cache: None,
+      ignored_circular_dependencies: vec![],
+      checks: ChecksOptions::default(),
+      log_level: Some(LogLevel::Info),
+      on_log: None,
+      max_errors: 100,
+    }
+  }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// How the code of a module is parsed. It's decided by the extension of the module, unless `load` or `transform`
+/// hooks tell it otherwise.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Loader {
+  #[default]
+  Js,
+  Jsx,
+  // TypeScript syntax is stripped before the module gets scanned
+  Ts,
+  Tsx,
+  // The content is copied into the output as an asset, and the module default exports the URL of it
+  Asset,
+}
+
+impl Loader {
+  /// Plugins name loaders by extensions too, like `ts`.
+  pub fn from_extension(ext: &str) -> Option<Self> {
+    match ext {
+      "js" | "mjs" | "cjs" => Some(Self::Js),
+      "jsx" => Some(Self::Jsx),
+      "ts" | "mts" | "cts" => Some(Self::Ts),
+      "tsx" => Some(Self::Tsx),
+      _ => None,
+    }
+  }
+
+  /// Names of loaders given by plugins, that is extensions and `asset`.
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "asset" => Some(Self::Asset),
+      name => Self::from_extension(name),
+    }
+  }
+
+  pub fn is_typescript(self) -> bool {
+    matches!(self, Self::Ts | Self::Tsx)
+  }
+
+  pub fn is_jsx(self) -> bool {
+    matches!(self, Self::Jsx | Self::Tsx)
+  }
+}
@@ -0,0 +1,23 @@
+use crate::{ModuleInfo, SharedModuleInfoTable};
+
+/// Passed to the function form of `output.manual_chunks`, like the second argument of `manualChunks` of rollup.
+#[derive(Debug, Clone)]
+pub struct ManualChunkMeta {
+  module_infos: SharedModuleInfoTable,
+}
+
+impl ManualChunkMeta {
+  pub fn new(module_infos: SharedModuleInfoTable) -> Self {
+    Self { module_infos }
+  }
+
+  /// The info of a module in the module graph, like `this.getModuleInfo` of plugins.
+  pub fn get_module_info(&self, id: &str) -> Option<ModuleInfo> {
+    self.module_infos.get(id)
+  }
+
+  /// Ids of all modules in the module graph, including external ones.
+  pub fn get_module_ids(&self) -> impl Iterator<Item = String> {
+    self.module_infos.ids().into_iter()
+  }
+}
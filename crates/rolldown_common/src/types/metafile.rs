@@ -0,0 +1,52 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+/// A report of the inputs and outputs of a build in the format of the metafile of esbuild, so it could be fed to
+/// analyzers like `esbuild-visualizer`. Paths of modules are relative to the cwd, and paths of outputs are file
+/// names relative to the output directory.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Metafile {
+  pub inputs: BTreeMap<String, MetafileInput>,
+  pub outputs: BTreeMap<String, MetafileOutput>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MetafileInput {
+  /// The length of the code of the module after `transform` hooks.
+  pub bytes: usize,
+  pub imports: Vec<MetafileImport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MetafileImport {
+  /// The path of the imported module, or the id of an external module.
+  pub path: String,
+  /// `import-statement`, `dynamic-import` or `require-call`.
+  pub kind: String,
+  #[serde(skip_serializing_if = "std::ops::Not::not")]
+  pub external: bool,
+  /// The specifier of the import. Omitted for imports between outputs.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub original: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetafileOutput {
+  pub bytes: usize,
+  /// Modules of the chunk that are left after tree shaking, along with the length of their rendered code.
+  pub inputs: BTreeMap<String, MetafileOutputInput>,
+  /// Chunks imported by the chunk.
+  pub imports: Vec<MetafileImport>,
+  pub exports: Vec<String>,
+  /// The module of the entry chunk.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub entry_point: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetafileOutputInput {
+  pub bytes_in_output: usize,
+}
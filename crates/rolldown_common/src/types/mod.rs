@@ -4,9 +4,16 @@ pub mod chunk_kind;
 pub mod entry_point;
 pub mod exports_kind;
 pub mod external_module_id;
+pub mod file_name_template;
 pub mod file_path;
 pub mod import_record;
+pub mod input_options;
+pub mod loader;
+pub mod manual_chunk_meta;
+pub mod metafile;
 pub mod module_id;
+pub mod module_info;
+pub mod module_info_table;
 pub mod module_path;
 pub mod module_type;
 pub mod named_export;
@@ -14,10 +21,14 @@ pub mod named_import;
 pub mod normal_module_id;
 pub mod output;
 pub mod output_chunk;
+pub mod output_options;
+pub mod pre_rendered_chunk;
 pub mod rendered_chunk;
 pub mod rendered_module;
 pub mod resolved_export;
 pub mod resolved_path;
+pub mod resolver_options;
+pub mod rolldown_cache;
 pub mod stmt_info;
 pub mod symbol_ref;
 pub mod wrap_kind;
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+
+/// A snapshot of a module, passed to plugins once the module is parsed.
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+  pub id: String,
+  /// `None` for external modules.
+  pub code: Option<Arc<str>>,
+  pub is_entry: bool,
+  pub is_external: bool,
+  /// Ids of modules statically importing this module. Only modules loaded so far are taken into account.
+  pub importers: Vec<String>,
+  /// Ids of modules dynamically importing this module. Only modules loaded so far are taken into account.
+  pub dynamic_importers: Vec<String>,
+  /// Resolved ids of static imports and re-exports, in source order.
+  pub imported_ids: Vec<String>,
+  /// Resolved ids of dynamic imports, in source order.
+  pub dynamically_imported_ids: Vec<String>,
+  /// Names exported by the module, sorted.
+  pub exported_bindings: Vec<String>,
+  /// Whether the module is kept for its side effects if none of its exports is used.
+  pub has_module_side_effects: bool,
+  /// Custom data returned by `load` hooks.
+  pub meta: FxHashMap<String, serde_json::Value>,
+}
+
+impl ModuleInfo {
+  pub fn external(id: String, has_module_side_effects: bool) -> Self {
+    Self {
+      id,
+      code: None,
+      is_entry: false,
+      is_external: true,
+      importers: vec![],
+      dynamic_importers: vec![],
+      imported_ids: vec![],
+      dynamically_imported_ids: vec![],
+      exported_bindings: vec![],
+      has_module_side_effects,
+      meta: FxHashMap::default(),
+    }
+  }
+}
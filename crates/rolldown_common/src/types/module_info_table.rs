@@ -0,0 +1,105 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rustc_hash::FxHashMap;
+
+use crate::ModuleInfo;
+
+#[derive(Debug, Default)]
+struct ModuleInfoTableState {
+  /// In the order modules are loaded.
+  infos: Vec<ModuleInfo>,
+  id_to_index: FxHashMap<String, usize>,
+}
+
+/// Infos of modules loaded by the module loader, so plugins could walk the module graph with `this.getModuleInfo`
+/// and `this.getModuleIds`. Hooks running before the graph is complete, like `transform`, only see modules loaded
+/// so far.
+#[derive(Debug, Default)]
+pub struct ModuleInfoTable {
+  state: Mutex<ModuleInfoTableState>,
+}
+
+impl ModuleInfoTable {
+  fn state(&self) -> MutexGuard<'_, ModuleInfoTableState> {
+    self.state.lock().expect("ModuleInfoTable lock should not be poisoned")
+  }
+
+  /// Add the info of a loaded module. Importers of the info are ignored, since they are collected from other modules.
+  pub fn insert(&self, info: ModuleInfo) {
+    let mut state = self.state();
+    if let Some(index) = state.id_to_index.get(&info.id).copied() {
+      state.infos[index] = info;
+    } else {
+      let index = state.infos.len();
+      state.id_to_index.insert(info.id.clone(), index);
+      state.infos.push(info);
+    }
+  }
+
+  /// The info of a loaded module, with importers among modules loaded so far.
+  pub fn get(&self, id: &str) -> Option<ModuleInfo> {
+    let state = self.state();
+    let mut info = state.id_to_index.get(id).map(|index| state.infos[*index].clone())?;
+    info.importers = state
+      .infos
+      .iter()
+      .filter(|importer| importer.imported_ids.iter().any(|imported| imported == id))
+      .map(|importer| importer.id.clone())
+      .collect();
+    info.dynamic_importers = state
+      .infos
+      .iter()
+      .filter(|importer| importer.dynamically_imported_ids.iter().any(|imported| imported == id))
+      .map(|importer| importer.id.clone())
+      .collect();
+    Some(info)
+  }
+
+  /// Ids of all loaded modules, including external ones, in the order they are loaded.
+  pub fn ids(&self) -> Vec<String> {
+    self.state().infos.iter().map(|info| info.id.clone()).collect()
+  }
+
+  /// Forget all modules, since every build loads its own modules.
+  pub fn clear(&self) {
+    *self.state() = ModuleInfoTableState::default();
+  }
+}
+
+pub type SharedModuleInfoTable = Arc<ModuleInfoTable>;
+
+#[cfg(test)]
+mod tests {
+  use super::ModuleInfoTable;
+  use crate::ModuleInfo;
+
+  fn info(id: &str, imported_ids: &[&str], dynamically_imported_ids: &[&str]) -> ModuleInfo {
+    ModuleInfo {
+      imported_ids: imported_ids.iter().map(ToString::to_string).collect(),
+      dynamically_imported_ids: dynamically_imported_ids.iter().map(ToString::to_string).collect(),
+      is_external: false,
+      code: Some("".into()),
+      ..ModuleInfo::external(id.to_string(), true)
+    }
+  }
+
+  #[test]
+  fn importers_are_collected_from_loaded_modules() {
+    let table = ModuleInfoTable::default();
+    table.insert(info("/main.js", &["/a.js", "ext"], &["/lazy.js"]));
+    table.insert(info("/a.js", &["ext"], &[]));
+    table.insert(ModuleInfo::external("ext".to_string(), true));
+
+    assert_eq!(table.ids(), vec!["/main.js", "/a.js", "ext"]);
+    let ext = table.get("ext").unwrap();
+    assert!(ext.is_external);
+    assert_eq!(ext.importers, vec!["/main.js", "/a.js"]);
+    // Not loaded yet
+    assert!(table.get("/lazy.js").is_none());
+    table.insert(info("/lazy.js", &[], &[]));
+    assert_eq!(table.get("/lazy.js").unwrap().dynamic_importers, vec!["/main.js"]);
+
+    table.clear();
+    assert!(table.ids().is_empty());
+  }
+}
@@ -10,6 +10,14 @@ pub enum ModuleType {
   EsmMjs,
   // "type: module" in package.json
   EsmPackageJson,
+  // ".json", which is turned into an ES module with a default export
+  Json,
+  // ".css", whose content is collected into the CSS asset of the chunk, while the module itself is empty
+  Css,
+  // `moduleType: "text"` of `resolve_id` hooks, which default exports the content as a string
+  Text,
+  // `moduleType: "base64"` of `resolve_id` hooks, which default exports the content encoded in base64
+  Base64,
 }
 
 impl ModuleType {
@@ -20,4 +28,13 @@ impl ModuleType {
   pub fn is_commonjs(&self) -> bool {
     matches!(self, Self::CJS | Self::CjsPackageJson)
   }
+
+  /// Module types that `resolve_id` hooks could ask for, like `text` for the `?raw` imports of Vite.
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "text" => Some(Self::Text),
+      "base64" => Some(Self::Base64),
+      _ => None,
+    }
+  }
 }
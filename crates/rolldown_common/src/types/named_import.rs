@@ -1,3 +1,4 @@
+use oxc::span::Span;
 use rolldown_rstr::Rstr;
 
 use crate::SymbolRef;
@@ -19,6 +20,8 @@ pub struct NamedImport {
   /// For case C, the `imported_as` is a `SymbolRef` from `foo2`.
   pub imported_as: SymbolRef,
   pub record_id: ImportRecordId,
+  /// The span of the specifier in the importer, like `foo as foo2` of case C, which diagnostics point at.
+  pub span: Span,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -1,9 +1,36 @@
 use crate::OutputChunk;
 
+#[derive(Debug, Clone)]
+pub enum AssetSource {
+  String(String),
+  Buffer(Vec<u8>),
+}
+
+impl AssetSource {
+  pub fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::String(value) => value.as_bytes(),
+      Self::Buffer(value) => value,
+    }
+  }
+}
+
+impl From<String> for AssetSource {
+  fn from(value: String) -> Self {
+    Self::String(value)
+  }
+}
+
+impl From<Vec<u8>> for AssetSource {
+  fn from(value: Vec<u8>) -> Self {
+    Self::Buffer(value)
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct OutputAsset {
   pub file_name: String,
-  pub source: String,
+  pub source: AssetSource,
 }
 
 #[derive(Debug, Clone)]
@@ -20,10 +47,10 @@ impl Output {
     }
   }
 
-  pub fn content(&self) -> &str {
+  pub fn content_as_bytes(&self) -> &[u8] {
     match self {
-      Self::Chunk(chunk) => &chunk.code,
-      Self::Asset(asset) => &asset.source,
+      Self::Chunk(chunk) => chunk.code.as_bytes(),
+      Self::Asset(asset) => asset.source.as_bytes(),
     }
   }
 }
@@ -14,6 +14,9 @@ pub struct OutputChunk {
   // RenderedChunk
   pub file_name: String,
   pub modules: FxHashMap<String, RenderedModule>,
+  pub imports: Vec<String>,
+  pub dynamic_imports: Vec<String>,
+  pub imported_css: Vec<String>,
   // OutputChunk
   pub code: String,
 }
@@ -0,0 +1,465 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+use rolldown_error::BuildError;
+use rolldown_utils::hash::HashAlgorithm;
+use rustc_hash::FxHashMap;
+
+use crate::{FileNameTemplate, ManualChunkMeta, PreRenderedChunk, RenderedChunk};
+
+#[derive(Debug)]
+pub enum OutputFormat {
+  Esm,
+  Cjs,
+  Iife,
+  Umd,
+  SystemJs,
+}
+
+impl OutputFormat {
+  /// The name of the format used by `[format]` in file name patterns, which is `es` for esm like rollup.
+  pub fn file_name_str(&self) -> &'static str {
+    match self {
+      OutputFormat::Esm => "es",
+      OutputFormat::Cjs => "cjs",
+      OutputFormat::Iife => "iife",
+      OutputFormat::Umd => "umd",
+      OutputFormat::SystemJs => "system",
+    }
+  }
+}
+
+impl From<String> for OutputFormat {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "esm" => OutputFormat::Esm,
+      "cjs" => OutputFormat::Cjs,
+      "iife" => OutputFormat::Iife,
+      "umd" => OutputFormat::Umd,
+      "system" => OutputFormat::SystemJs,
+      _ => unreachable!("unknown output format"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputExports {
+  Auto,
+  Default,
+  Named,
+  None,
+}
+
+impl From<String> for OutputExports {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "auto" => OutputExports::Auto,
+      "default" => OutputExports::Default,
+      "named" => OutputExports::Named,
+      "none" => OutputExports::None,
+      _ => unreachable!("unknown output exports"),
+    }
+  }
+}
+
+/// How default and namespace imports of external modules are resolved in formats loading them with `require` or
+/// globals, like `output.interop` of rollup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteropMode {
+  /// The default export is `module.exports`, unless the module is marked with `__esModule`.
+  Auto,
+  /// Externals are transpiled ES modules, so they are used as namespaces as is.
+  EsModule,
+  /// The default export is always `module.exports`, even if the module is marked with `__esModule`.
+  Default,
+  /// Like `Default`, but only default imports are allowed.
+  DefaultOnly,
+  /// Like `Auto`, but objects with a `default` property are also treated as ES modules.
+  Compat,
+}
+
+impl From<String> for InteropMode {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "auto" => InteropMode::Auto,
+      "esModule" => InteropMode::EsModule,
+      "default" => InteropMode::Default,
+      "defaultOnly" => InteropMode::DefaultOnly,
+      "compat" => InteropMode::Compat,
+      _ => unreachable!("unknown interop mode"),
+    }
+  }
+}
+
+pub type InteropFn = dyn Fn(&str) -> InteropMode + Send + Sync;
+
+/// `output.interop` for all external modules, or decided per module.
+pub enum InteropOption {
+  Mode(InteropMode),
+  /// Called with the id of each external module, returning its interop mode.
+  Fn(Box<InteropFn>),
+}
+
+impl Debug for InteropOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Mode(value) => write!(f, "InteropOption::Mode({value:?})"),
+      Self::Fn(_) => write!(f, "InteropOption::Fn(...)"),
+    }
+  }
+}
+
+impl Default for InteropOption {
+  fn default() -> Self {
+    Self::Mode(InteropMode::Auto)
+  }
+}
+
+impl From<InteropMode> for InteropOption {
+  fn from(value: InteropMode) -> Self {
+    Self::Mode(value)
+  }
+}
+
+impl From<String> for InteropOption {
+  fn from(value: String) -> Self {
+    Self::Mode(value.into())
+  }
+}
+
+impl InteropOption {
+  /// The interop mode of the external module `id`.
+  pub fn call(&self, id: &str) -> InteropMode {
+    match self {
+      Self::Mode(value) => *value,
+      Self::Fn(value) => value(id),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum SourceMapType {
+  /// Write the source map to a separate `.map` file and reference it with a `//# sourceMappingURL` comment.
+  File,
+  /// Append the source map to the chunk as a data url.
+  Inline,
+  /// Write the source map to a separate `.map` file without referencing it from the chunk.
+  Hidden,
+}
+
+impl From<String> for SourceMapType {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "file" => SourceMapType::File,
+      "inline" => SourceMapType::Inline,
+      "hidden" => SourceMapType::Hidden,
+      _ => unreachable!("unknown sourcemap type"),
+    }
+  }
+}
+
+/// What to do with legal comments, which are comments starting with `/*!` or `//!`, or containing `@license` or
+/// `@preserve`. Identical legal comments of a chunk are only kept once.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LegalComments {
+  /// Keep legal comments at the top of the code of the module they come from.
+  #[default]
+  Inline,
+  /// Collect legal comments at the end of the chunk.
+  Eof,
+  /// Drop legal comments.
+  None,
+  /// Write legal comments of each chunk to a `<chunk>.LEGAL.txt` asset, which the chunk points to with a comment.
+  External,
+}
+
+impl From<String> for LegalComments {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "inline" => LegalComments::Inline,
+      "eof" => LegalComments::Eof,
+      "none" => LegalComments::None,
+      "external" => LegalComments::External,
+      _ => unreachable!("unknown legal comments option"),
+    }
+  }
+}
+
+pub type AddonFn = dyn Fn(RenderedChunk) -> Pin<Box<(dyn Future<Output = Result<String, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Code added to each chunk, used by `banner`, `footer`, `intro` and `outro`.
+pub enum AddonOutputOption {
+  String(String),
+  Fn(Box<AddonFn>),
+}
+
+impl Debug for AddonOutputOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::String(value) => write!(f, "AddonOutputOption::String({value:?})"),
+      Self::Fn(_) => write!(f, "AddonOutputOption::Fn(...)"),
+    }
+  }
+}
+
+impl Default for AddonOutputOption {
+  fn default() -> Self {
+    Self::String(String::new())
+  }
+}
+
+impl AddonOutputOption {
+  pub async fn call(&self, chunk: &RenderedChunk) -> Result<String, BuildError> {
+    match self {
+      Self::String(value) => Ok(value.clone()),
+      Self::Fn(value) => value(chunk.clone()).await,
+    }
+  }
+}
+
+pub type ManualChunksFn = dyn Fn(
+    String,
+    ManualChunkMeta,
+  ) -> Pin<Box<(dyn Future<Output = Result<Option<String>, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Puts modules into chunks with the given names, before the automatic chunk splitting.
+pub enum ManualChunksOption {
+  /// Maps chunk names to ids of modules, which are absolute paths or paths relative to the cwd.
+  Map(FxHashMap<String, Vec<String>>),
+  /// Called with the id of each module and a way to inspect the module graph, returning the name of the chunk the
+  /// module should be put into.
+  Fn(Box<ManualChunksFn>),
+}
+
+impl Debug for ManualChunksOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Map(value) => write!(f, "ManualChunksOption::Map({value:?})"),
+      Self::Fn(_) => write!(f, "ManualChunksOption::Fn(...)"),
+    }
+  }
+}
+
+pub type ChunkFileNamesFn = dyn Fn(
+    PreRenderedChunk,
+  ) -> Pin<Box<(dyn Future<Output = Result<String, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// File names of chunks, used by `entry_file_names` and `chunk_file_names`.
+pub enum ChunkFileNamesOption {
+  Template(FileNameTemplate),
+  /// Called with each chunk, returning the pattern of its file name, which supports the same placeholders.
+  Fn(Box<ChunkFileNamesFn>),
+}
+
+impl Debug for ChunkFileNamesOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Template(value) => write!(f, "ChunkFileNamesOption::Template({value:?})"),
+      Self::Fn(_) => write!(f, "ChunkFileNamesOption::Fn(...)"),
+    }
+  }
+}
+
+impl From<String> for ChunkFileNamesOption {
+  fn from(template: String) -> Self {
+    Self::Template(FileNameTemplate::from(template))
+  }
+}
+
+pub type SourcemapPathTransformFn = dyn Fn(&str, &str) -> String + Send + Sync;
+
+/// Called with the path of each source relative to the source map, and the absolute path of the source map,
+/// returning the path written into the source map.
+pub struct SourcemapPathTransform(Box<SourcemapPathTransformFn>);
+
+impl Debug for SourcemapPathTransform {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "SourcemapPathTransform(...)")
+  }
+}
+
+impl SourcemapPathTransform {
+  pub fn new(f: Box<SourcemapPathTransformFn>) -> Self {
+    Self(f)
+  }
+
+  pub fn call(&self, relative_source_path: &str, sourcemap_path: &str) -> String {
+    (self.0)(relative_source_path, sourcemap_path)
+  }
+}
+
+pub type GlobalsFn = dyn Fn(&str) -> String + Send + Sync;
+
+/// Global variable names of external modules.
+pub enum GlobalsOption {
+  /// Maps ids of external modules to global variable names.
+  Map(FxHashMap<String, String>),
+  /// Called with the id of each external module, returning its global variable name.
+  Fn(Box<GlobalsFn>),
+}
+
+impl Debug for GlobalsOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Map(value) => write!(f, "GlobalsOption::Map({value:?})"),
+      Self::Fn(_) => write!(f, "GlobalsOption::Fn(...)"),
+    }
+  }
+}
+
+impl Default for GlobalsOption {
+  fn default() -> Self {
+    Self::Map(FxHashMap::default())
+  }
+}
+
+impl GlobalsOption {
+  /// `None` if the module is missing from the map.
+  pub fn call(&self, id: &str) -> Option<String> {
+    match self {
+      Self::Map(value) => value.get(id).cloned(),
+      Self::Fn(value) => Some(value(id)),
+    }
+  }
+}
+
+pub type PathsFn = dyn Fn(&str) -> String + Send + Sync;
+
+/// Specifiers that external modules are imported with in the output, instead of their ids.
+pub enum PathsOption {
+  /// Maps ids of external modules to specifiers. Modules missing from the map keep their ids.
+  Map(FxHashMap<String, String>),
+  /// Called with the id of each external module, returning its specifier.
+  Fn(Box<PathsFn>),
+}
+
+impl Debug for PathsOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Map(value) => write!(f, "PathsOption::Map({value:?})"),
+      Self::Fn(_) => write!(f, "PathsOption::Fn(...)"),
+    }
+  }
+}
+
+impl PathsOption {
+  pub fn call(&self, id: &str) -> String {
+    match self {
+      Self::Map(value) => value.get(id).cloned().unwrap_or_else(|| id.to_string()),
+      Self::Fn(value) => value(id),
+    }
+  }
+}
+
+#[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct OutputOptions {
+  /// Pattern of the file names of entry chunks, supports `[name]`, `[hash]`, `[hash:<length>]`, `[format]`, `[ext]`
+  /// and `[extname]`.
+  pub entry_file_names: ChunkFileNamesOption,
+  /// Pattern of the file names of other chunks, supports the same placeholders as `entry_file_names`.
+  pub chunk_file_names: ChunkFileNamesOption,
+  /// Pattern of the file names of assets emitted by plugins, supports `[name]`, `[hash]`, `[hash:<length>]`, `[ext]`
+  /// and `[extname]`. Hashes are computed from the sources of assets, and assets with the same source share the same
+  /// file.
+  pub asset_file_names: FileNameTemplate,
+  /// The number of characters of hashes rendered by `[hash]` in `entry_file_names` and `chunk_file_names`.
+  /// `[hash:<length>]` renders hashes of the given length instead.
+  pub hash_length: usize,
+  /// The algorithm used to compute hashes of chunks.
+  pub hash_algorithm: HashAlgorithm,
+  pub dir: String,
+  pub format: OutputFormat,
+  /// How the exports of entry chunks are exposed. Used by the `cjs`, `iife` and `umd` formats.
+  pub exports: OutputExports,
+  /// How default and namespace imports of external modules are resolved in formats without `import` statements.
+  pub interop: InteropOption,
+  /// Whether to generate source maps for chunks. `None` means source maps are disabled.
+  pub sourcemap: Option<SourceMapType>,
+  /// Omit whitespace that isn't needed from the generated code. Unlike minification, names are kept as they are.
+  pub compact: bool,
+  /// Minify the generated code. Besides what `compact` does, names local to chunks are shortened and constant
+  /// expressions like `1 + 2` are folded. Exported names and names imported from external modules are kept.
+  pub minify: bool,
+  pub legal_comments: LegalComments,
+  /// Whether to freeze namespace objects, like the one of `import * as ns from './foo'`, with `Object.freeze`.
+  pub freeze: bool,
+  /// Whether to leave the content of sources out of source maps. `sourcesContent` is omitted if enabled.
+  pub sourcemap_exclude_sources: bool,
+  /// The variable name the exports of the entry get assigned to. Used by the `iife` and `umd` formats.
+  pub name: Option<String>,
+  /// Whether to add the exports to the global variable named `name` if it already exists, instead of replacing it.
+  /// Used by the `iife` format.
+  pub extend: bool,
+  /// Global variable names of external modules, as a map from their ids or a function. Used by the `iife` and
+  /// `umd` formats.
+  pub globals: GlobalsOption,
+  /// Remaps the ids of external modules to the specifiers they are imported with, like a URL of a CDN.
+  pub paths: Option<PathsOption>,
+  /// Code placed at the top of each chunk, outside of the format wrapper.
+  pub banner: AddonOutputOption,
+  /// Code placed at the bottom of each chunk, outside of the format wrapper.
+  pub footer: AddonOutputOption,
+  /// Code placed at the top of each chunk, inside of the format wrapper.
+  pub intro: AddonOutputOption,
+  /// Code placed at the bottom of each chunk, inside of the format wrapper.
+  pub outro: AddonOutputOption,
+  /// Put dynamically imported modules into the chunk of the only entry instead of splitting them into their own
+  /// chunks. `import()` of them resolves to their namespace objects.
+  pub inline_dynamic_imports: bool,
+  /// Emit each module as a separate chunk instead of merging modules into chunks. Chunks are named by the paths
+  /// of their modules relative to the cwd, and all of them use `entry_file_names`.
+  pub preserve_modules: bool,
+  /// Path prefix stripped from the paths of modules when `preserve_modules` is enabled, like `src`.
+  pub preserve_modules_root: Option<String>,
+  /// Groups modules into named chunks. Static dependencies of these modules that are not assigned to a chunk are
+  /// put into the same chunk. Ignored when `preserve_modules` is enabled.
+  pub manual_chunks: Option<ManualChunksOption>,
+  /// Transforms the paths of sources in source maps, like making them absolute or prefixing them with a URL.
+  pub sourcemap_path_transform: Option<SourcemapPathTransform>,
+  /// Generate a [`Metafile`](crate::Metafile) reporting the sizes of modules and chunks along with their imports.
+  pub metafile: bool,
+}
+
+impl Default for OutputOptions {
+  fn default() -> Self {
+    Self {
+      entry_file_names: ChunkFileNamesOption::from("[name].js".to_string()),
+      chunk_file_names: ChunkFileNamesOption::from("[name]-[hash].js".to_string()),
+      asset_file_names: FileNameTemplate::from("assets/[name]-[hash][extname]".to_string()),
+      hash_length: 8,
+      hash_algorithm: HashAlgorithm::default(),
+      dir: "dist".into(),
+      format: OutputFormat::Esm,
+      exports: OutputExports::Auto,
+      interop: InteropOption::default(),
+      sourcemap: None,
+      sourcemap_exclude_sources: false,
+      compact: false,
+      minify: false,
+      legal_comments: LegalComments::default(),
+      freeze: true,
+      name: None,
+      extend: false,
+      globals: GlobalsOption::default(),
+      paths: None,
+      banner: AddonOutputOption::default(),
+      footer: AddonOutputOption::default(),
+      intro: AddonOutputOption::default(),
+      outro: AddonOutputOption::default(),
+      inline_dynamic_imports: false,
+      preserve_modules: false,
+      preserve_modules_root: None,
+      manual_chunks: None,
+      sourcemap_path_transform: None,
+      metafile: false,
+    }
+  }
+}
@@ -0,0 +1,11 @@
+/// Information about a chunk before it's rendered, which is all that is known when its file name is decided.
+#[derive(Debug, Clone)]
+pub struct PreRenderedChunk {
+  /// The name of the chunk, which is used by `[name]`.
+  pub name: String,
+  pub is_entry: bool,
+  pub is_dynamic_entry: bool,
+  pub facade_module_id: Option<String>,
+  pub module_ids: Vec<String>,
+  pub exports: Vec<String>,
+}
@@ -13,4 +13,11 @@ pub struct RenderedChunk {
   // RenderedChunk
   pub file_name: String,
   pub modules: FxHashMap<String, RenderedModule>,
+  /// File names of chunks and ids of external modules that are statically imported by the chunk.
+  pub imports: Vec<String>,
+  /// File names of chunks and ids of external modules that are dynamically imported by the chunk.
+  pub dynamic_imports: Vec<String>,
+  /// File names of the CSS assets of CSS modules in the chunk, which are not referred to by the code of the chunk,
+  /// so they are loaded by `<link>` tags instead.
+  pub imported_css: Vec<String>,
 }
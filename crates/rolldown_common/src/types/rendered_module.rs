@@ -1,4 +1,6 @@
 #[derive(Debug, Clone)]
 pub struct RenderedModule {
   pub code: Option<String>,
+  /// The length of the code of the module in the chunk, which is 0 if it's tree shaken entirely.
+  pub rendered_length: usize,
 }
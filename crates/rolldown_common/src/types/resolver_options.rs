@@ -0,0 +1,97 @@
+use std::{borrow::Cow, path::PathBuf};
+
+use regex::Regex;
+
+/// What an [`AliasItem`] matches.
+#[derive(Debug)]
+pub enum AliasFind {
+  /// Matches the specifier itself, or specifiers starting with it followed by `/`.
+  String(String),
+  /// Matches specifiers the regex matches, the first match is replaced.
+  Regex(Regex),
+}
+
+#[derive(Debug)]
+pub struct AliasItem {
+  pub find: AliasFind,
+  /// Replaces the matched part of the specifier. Regex replacements could refer to capture groups, like `$1`.
+  pub replacement: String,
+}
+
+impl AliasItem {
+  /// The aliased specifier, `None` if the item doesn't match `specifier`.
+  pub fn apply<'a>(&self, specifier: &'a str) -> Option<Cow<'a, str>> {
+    match &self.find {
+      AliasFind::String(find) => {
+        let rest = specifier.strip_prefix(find.as_str())?;
+        (rest.is_empty() || rest.starts_with('/'))
+          .then(|| Cow::Owned(format!("{}{rest}", self.replacement)))
+      }
+      AliasFind::Regex(find) => {
+        find.is_match(specifier).then(|| find.replace(specifier, self.replacement.as_str()))
+      }
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct ResolverOptions {
+  /// Replaces specifiers of imports and entries before they get resolved, like `@/utils` to `./src/utils`. Items
+  /// are tried in order and the first matching one wins. The replaced specifier is resolved relative to the
+  /// importer as usual.
+  pub alias: Option<Vec<AliasItem>>,
+
+  /// A list of alias fields in description files.
+  /// Specify a field, such as `browser`, to be parsed according to [this specification](https://github.com/defunctzombie/package-browser-field-spec).
+  /// Can be a path to json object such as `["path", "to", "exports"]`.
+  ///
+  /// Default `[]`
+  pub alias_fields: Option<Vec<Vec<String>>>,
+
+  /// Condition names for exports field which defines entry points of a package.
+  /// `import` or `require` is added by the kind of the import.
+  /// The key order in the exports field is significant. During condition matching, earlier entries have higher priority and take precedence over later entries.
+  ///
+  /// Default `[]`
+  pub condition_names: Option<Vec<String>>,
+
+  /// A list of exports fields in description files.
+  /// Can be a path to json object such as `["path", "to", "exports"]`.
+  ///
+  /// Default `[["exports"]]`.
+  pub exports_fields: Option<Vec<Vec<String>>>,
+  /// Attempt to resolve these extensions in order.
+  /// If multiple files share the same name but have different extensions,
+  /// will resolve the one with the extension listed first in the array and skip the rest.
+  ///
+  /// Default `[".js", ".json", ".node"]`
+  pub extensions: Option<Vec<String>>,
+
+  /// A list of main fields in description files
+  ///
+  /// Default `["main"]`.
+  pub main_fields: Option<Vec<String>>,
+
+  /// The filename to be used while resolving directories.
+  ///
+  /// Default `["index"]`
+  pub main_files: Option<Vec<String>>,
+
+  /// A list of directories to resolve modules from, can be absolute path or folder name.
+  ///
+  /// Default `["node_modules"]`
+  pub modules: Option<Vec<String>>,
+  /// Whether to resolve symlinks to their symlinked location.
+  /// When enabled, symlinked resources are resolved to their real path, not their symlinked location.
+  /// Note that this may cause module resolution to fail when using tools that symlink packages (like npm link).
+  ///
+  /// Default `true`
+  pub symlinks: Option<bool>,
+
+  /// The tsconfig whose `compilerOptions.paths` and `baseUrl`, including the ones inherited by `extends`, are
+  /// used to resolve bare specifiers. Targets of a pattern are tried in order, and specifiers matching no
+  /// pattern are resolved as usual. Relative paths are resolved against `cwd`.
+  ///
+  /// Default `None`
+  pub tsconfig_filename: Option<PathBuf>,
+}
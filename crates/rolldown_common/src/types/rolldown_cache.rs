@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use rolldown_error::BuildError;
+use rolldown_sourcemap::SourceMapChain;
+use rolldown_utils::hash::HashAlgorithm;
+use serde::{Deserialize, Serialize};
+
+use crate::Loader;
+
+/// The result of `transform` hooks of a module, which is reused by the next build if the loaded code and the watch
+/// files of the module don't change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedModule {
+  /// The hash of the code before `transform` hooks.
+  pub original_code_hash: String,
+  pub code: String,
+  pub sourcemap_chain: SourceMapChain,
+  /// The loader set by `transform` hooks.
+  pub loader: Option<Loader>,
+  /// Files added by `this.addWatchFile` while transforming the module, along with the hash of their content at
+  /// that time, or `None` if they didn't exist.
+  pub watch_files: Vec<(String, Option<String>)>,
+}
+
+impl CachedModule {
+  pub fn hash_code(code: &str) -> String {
+    HashAlgorithm::default().hash_hex(code.as_bytes())
+  }
+}
+
+/// Transformed modules of a build keyed by module ids, which could be passed to `InputOptions::cache` of a later
+/// build to skip `transform` hooks of modules that don't change. Whether a module changes is decided by the hash of
+/// its loaded code, rather than modification times, so the cache still works after being written to disk.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RolldownCache {
+  pub modules: BTreeMap<String, CachedModule>,
+}
+
+impl RolldownCache {
+  pub fn serialize(&self) -> Result<String, BuildError> {
+    serde_json::to_string(self).map_err(|e| BuildError::invalid_option("cache", e.to_string()))
+  }
+
+  pub fn deserialize(json: &str) -> Result<Self, BuildError> {
+    serde_json::from_str(json).map_err(|e| BuildError::invalid_option("cache", e.to_string()))
+  }
+}
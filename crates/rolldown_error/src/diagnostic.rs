@@ -1,11 +1,38 @@
 use std::{fmt::Display, ops::Range};
 
 use ariadne::{sources, Config, Label, Report, ReportBuilder, ReportKind};
+use oxc::span::Span;
 
 use crate::error::Severity;
 
 type Labels = Vec<Label<(String, Range<usize>)>>;
 
+/// Where in a module a diagnostic points at, so it could be presented without parsing the rendered string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticLocation {
+  pub path: String,
+  /// Byte offsets in the code of the module.
+  pub start: u32,
+  pub end: u32,
+  /// 1-based line and 0-based column in characters of `start`, the same as the ones of rollup.
+  pub line: usize,
+  pub column: usize,
+}
+
+impl DiagnosticLocation {
+  pub fn new(path: impl Into<String>, source: &str, span: Span) -> Self {
+    let before = source.get(..span.start as usize).unwrap_or(source);
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    Self {
+      path: path.into(),
+      start: span.start,
+      end: span.end,
+      line: before.matches('\n').count() + 1,
+      column: before[line_start..].chars().count(),
+    }
+  }
+}
+
 #[derive(Debug, Default)]
 pub struct DiagnosticBuilder {
   pub code: Option<&'static str>,
@@ -13,6 +40,11 @@ pub struct DiagnosticBuilder {
   pub files: Option<Vec<(String, String)>>,
   pub labels: Option<Labels>,
   pub severity: Option<Severity>,
+  pub location: Option<DiagnosticLocation>,
+  /// Modules from an entry to the module of `location`, each importing the next one.
+  pub importer_chain: Option<Vec<String>>,
+  /// A suggestion on fixing the problem.
+  pub help: Option<String>,
 }
 
 impl DiagnosticBuilder {
@@ -23,6 +55,9 @@ impl DiagnosticBuilder {
       severity: self.severity.expect("Field `severity` should be set"),
       labels: self.labels.unwrap_or_default(),
       files: self.files.unwrap_or_default(),
+      location: self.location,
+      importer_chain: self.importer_chain.unwrap_or_default(),
+      help: self.help,
     }
   }
 }
@@ -34,9 +69,36 @@ pub struct Diagnostic {
   pub(crate) files: Vec<(String, String)>,
   pub(crate) labels: Labels,
   pub(crate) severity: Severity,
+  pub(crate) location: Option<DiagnosticLocation>,
+  pub(crate) importer_chain: Vec<String>,
+  pub(crate) help: Option<String>,
 }
 
 impl Diagnostic {
+  pub fn code(&self) -> &'static str {
+    self.code
+  }
+
+  pub fn summary(&self) -> &str {
+    &self.summary
+  }
+
+  pub fn severity(&self) -> &Severity {
+    &self.severity
+  }
+
+  pub fn location(&self) -> Option<&DiagnosticLocation> {
+    self.location.as_ref()
+  }
+
+  pub fn importer_chain(&self) -> &[String] {
+    &self.importer_chain
+  }
+
+  pub fn help(&self) -> Option<&str> {
+    self.help.as_deref()
+  }
+
   fn init_report_builder(&mut self) -> ReportBuilder<'static, (String, Range<usize>)> {
     let mut builder = Report::<(String, Range<usize>)>::build(
       match self.severity {
@@ -52,6 +114,13 @@ impl Diagnostic {
     for label in self.labels.clone() {
       builder = builder.with_label(label);
     }
+    // A chain of a single module means the module is an entry
+    if self.importer_chain.len() > 1 {
+      builder = builder.with_note(format!("Import chain: {}", self.importer_chain.join(" -> ")));
+    }
+    if let Some(help) = &self.help {
+      builder = builder.with_help(help);
+    }
 
     builder
   }
@@ -73,3 +142,12 @@ impl Display for Diagnostic {
     self.as_string().fmt(f)
   }
 }
+
+#[test]
+fn test_diagnostic_location() {
+  let source = "const a = 1\nconst é = b";
+  let location = DiagnosticLocation::new("main.js", source, Span::new(23, 24));
+  assert_eq!((location.line, location.column), (2, 10));
+  let location = DiagnosticLocation::new("main.js", source, Span::new(6, 7));
+  assert_eq!((location.line, location.column), (1, 6));
+}
@@ -10,9 +10,41 @@ use oxc::span::Span;
 use crate::{
   diagnostic::Diagnostic,
   error_kind::{
-    external_entry::ExternalEntry, sourcemap_error::SourceMapError,
-    unresolved_entry::UnresolvedEntry, unresolved_import::UnresolvedImport,
-    unsupported_eval::UnsupportedEval, BuildErrorLike, NapiError,
+    circular_chunk::CircularChunk,
+    circular_dependency::CircularDependency,
+    duplicate_entry_name::DuplicateEntryName,
+    dynamic_asset_url::DynamicAssetUrl,
+    entry_signature_extended::EntrySignatureExtended,
+    external_entry::ExternalEntry,
+    file_name_conflict::FileNameConflict,
+    file_not_found::FileNotFound,
+    inconsistent_import_attributes::InconsistentImportAttributes,
+    invalid_data_url::InvalidDataUrl,
+    invalid_export_option::InvalidExportOption,
+    invalid_file_url::InvalidFileUrl,
+    invalid_hash_length::InvalidHashLength,
+    invalid_json::InvalidJson,
+    invalid_option::InvalidOption,
+    invalid_tla_format::InvalidTlaFormat,
+    missing_export::MissingExport,
+    missing_global_name::MissingGlobalName,
+    missing_name_option_for_iife_export::MissingNameOptionForIifeExport,
+    missing_name_option_for_umd_export::MissingNameOptionForUmdExport,
+    mixed_exports::MixedExports,
+    parse_error::ParseError,
+    plugin_log::{LogPosition, PluginLog},
+    require_tla::RequireTla,
+    shimmed_export::ShimmedExport,
+    sourcemap_error::SourceMapError,
+    unexpected_named_import::UnexpectedNamedImport,
+    unloaded_virtual_module::UnloadedVirtualModule,
+    unresolved_entry::UnresolvedEntry,
+    unresolved_import::UnresolvedImport,
+    unresolved_import_treated_as_external::UnresolvedImportTreatedAsExternal,
+    unsupported_code_splitting_format::UnsupportedCodeSplittingFormat,
+    unsupported_eval::UnsupportedEval,
+    unsupported_syntax::UnsupportedSyntax,
+    BuildErrorLike, NapiError,
   },
 };
 
@@ -71,12 +103,38 @@ impl BuildError {
     self
   }
 
+  /// Turn a warning into an error, like the ones upgraded by `on_log`.
+  #[must_use]
+  pub fn with_severity_error(mut self) -> Self {
+    self.severity = Severity::Error;
+    self
+  }
+
   pub fn into_diagnostic(self) -> Diagnostic {
+    self.to_diagnostic()
+  }
+
+  /// The structured data of the error, like where it points at, besides the rendered string of it.
+  pub fn to_diagnostic(&self) -> Diagnostic {
     let mut builder = self.inner.diagnostic_builder();
-    builder.severity = Some(self.severity);
+    builder.severity = Some(self.severity.clone());
     builder.build()
   }
 
+  /// Errors with the same key are the same problem, like an unresolvable specifier imported by many modules.
+  pub fn dedup_key(&self) -> String {
+    format!("{}:{}", self.code(), self.inner.dedup_key())
+  }
+
+  /// The module the error is about and the offset in it, by which errors of a build are ordered. It's empty if the
+  /// error isn't about a module.
+  pub fn position(&self) -> (String, u32) {
+    match self.to_diagnostic().location() {
+      Some(location) => (location.path.clone(), location.start),
+      None => (self.inner.module_path().unwrap_or_default(), 0),
+    }
+  }
+
   // --- private
 
   fn new_inner(inner: impl Into<Box<dyn BuildErrorLike>>) -> Self {
@@ -88,18 +146,245 @@ impl BuildError {
     Self::new_inner(ExternalEntry { id: unresolved_id.as_ref().to_path_buf() })
   }
 
+  pub fn duplicate_entry_name(
+    name: impl Into<String>,
+    first_import: impl Into<String>,
+    second_import: impl Into<String>,
+  ) -> Self {
+    Self::new_inner(DuplicateEntryName {
+      name: name.into(),
+      first_import: first_import.into(),
+      second_import: second_import.into(),
+    })
+  }
+
   pub fn unresolved_entry(unresolved_id: impl AsRef<Path>) -> Self {
     Self::new_inner(UnresolvedEntry { unresolved_id: unresolved_id.as_ref().to_path_buf() })
   }
 
   pub fn unresolved_import(specifier: impl Into<StaticStr>, importer: impl Into<PathBuf>) -> Self {
-    Self::new_inner(UnresolvedImport { specifier: specifier.into(), importer: importer.into() })
+    Self::new_inner(UnresolvedImport {
+      specifier: specifier.into(),
+      importer: importer.into(),
+      tsconfig: None,
+    })
+  }
+
+  /// An unresolved import of a bare specifier while `resolve.tsconfig_filename` is set, with the `paths`
+  /// pattern that was consulted.
+  pub fn unresolved_import_with_tsconfig(
+    specifier: impl Into<StaticStr>,
+    importer: impl Into<PathBuf>,
+    tsconfig: impl Into<PathBuf>,
+    pattern: Option<String>,
+  ) -> Self {
+    Self::new_inner(UnresolvedImport {
+      specifier: specifier.into(),
+      importer: importer.into(),
+      tsconfig: Some((tsconfig.into(), pattern)),
+    })
+  }
+
+  pub fn missing_name_option_for_iife_export() -> Self {
+    Self::new_inner(MissingNameOptionForIifeExport)
+  }
+
+  pub fn missing_name_option_for_umd_export() -> Self {
+    Self::new_inner(MissingNameOptionForUmdExport)
+  }
+
+  pub fn entry_signature_extended(
+    entry_module: impl Into<PathBuf>,
+    extra_exports: Vec<String>,
+  ) -> Self {
+    Self::new_inner(EntrySignatureExtended { entry_module: entry_module.into(), extra_exports })
+  }
+
+  pub fn mixed_exports(entry_module: impl Into<PathBuf>) -> Self {
+    Self::new_inner(MixedExports { entry_module: entry_module.into() })
+  }
+
+  pub fn invalid_export_option(
+    export_mode: &'static str,
+    entry_module: impl Into<PathBuf>,
+    exported_keys: Vec<String>,
+  ) -> Self {
+    Self::new_inner(InvalidExportOption {
+      export_mode,
+      entry_module: entry_module.into(),
+      exported_keys,
+    })
+  }
+
+  pub fn invalid_hash_length(length: usize, max_length: usize) -> Self {
+    Self::new_inner(InvalidHashLength { length, max_length, pattern: None })
+  }
+
+  pub fn invalid_hash_length_in_pattern(
+    length: usize,
+    max_length: usize,
+    pattern: impl Into<String>,
+  ) -> Self {
+    Self::new_inner(InvalidHashLength { length, max_length, pattern: Some(pattern.into()) })
+  }
+
+  pub fn invalid_data_url(url: impl Into<String>, reason: impl Into<String>) -> Self {
+    Self::new_inner(InvalidDataUrl { url: url.into(), reason: reason.into() })
+  }
+
+  /// `line` and `column` are 1-based, the same as the ones of `serde_json::Error`.
+  pub fn invalid_json(
+    path: impl Into<PathBuf>,
+    reason: impl Into<String>,
+    line: usize,
+    column: usize,
+  ) -> Self {
+    Self::new_inner(InvalidJson { path: path.into(), reason: reason.into(), line, column })
+  }
+
+  pub fn invalid_file_url(reference_id: impl Into<String>, url: impl Into<String>) -> Self {
+    Self::new_inner(InvalidFileUrl { reference_id: reference_id.into(), url: url.into() })
+  }
+
+  pub fn invalid_option(option: &'static str, explanation: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption { option, explanation: explanation.into() })
+  }
+
+  /// `span` is the span of the import specifier in `importer_source`. `exported_names` are suggested in place of
+  /// the missing export, and `importer_chain` is the modules from an entry to the importer.
+  pub fn missing_export(
+    importer: impl Into<String>,
+    importee: impl Into<String>,
+    missing_export: impl Into<String>,
+    importer_source: Arc<str>,
+    span: Span,
+    exported_names: Vec<String>,
+    importer_chain: Vec<String>,
+  ) -> Self {
+    Self::new_inner(MissingExport {
+      importer: importer.into(),
+      importee: importee.into(),
+      binding: missing_export.into(),
+      importer_source,
+      span,
+      exported_names,
+      importer_chain,
+    })
+  }
+
+  pub fn unresolved_import_treated_as_external(
+    specifier: impl Into<String>,
+    importer: impl Into<PathBuf>,
+  ) -> Self {
+    Self::new_inner(UnresolvedImportTreatedAsExternal {
+      specifier: specifier.into(),
+      importer: importer.into(),
+    })
+  }
+
+  pub fn shimmed_export(module_id: impl Into<String>, binding: impl Into<String>) -> Self {
+    Self::new_inner(ShimmedExport { module_id: module_id.into(), binding: binding.into() })
+  }
+
+  pub fn missing_global_name(module_id: String, guessed_name: String) -> Self {
+    Self::new_inner(MissingGlobalName { module_id, guessed_name })
+  }
+
+  pub fn file_not_found(reference_id: impl Into<String>) -> Self {
+    Self::new_inner(FileNotFound { reference_id: reference_id.into() })
+  }
+
+  pub fn unloaded_virtual_module(id: impl Into<String>) -> Self {
+    Self::new_inner(UnloadedVirtualModule { id: id.into() })
+  }
+
+  pub fn file_name_conflict(file_name: impl Into<String>) -> Self {
+    Self::new_inner(FileNameConflict { file_name: file_name.into() })
+  }
+
+  pub fn inconsistent_import_attributes(
+    importer: impl Into<String>,
+    importee: impl Into<String>,
+    existing_attributes: Vec<(String, String)>,
+    new_attributes: Vec<(String, String)>,
+  ) -> Self {
+    Self::new_inner(InconsistentImportAttributes {
+      importer: importer.into(),
+      importee: importee.into(),
+      existing_attributes,
+      new_attributes,
+    })
+  }
+
+  pub fn invalid_tla_format(
+    filename: impl Into<String>,
+    source: Arc<str>,
+    span: Span,
+    format: &'static str,
+  ) -> Self {
+    Self::new_inner(InvalidTlaFormat { filename: filename.into(), source, span, format })
+  }
+
+  pub fn require_tla(importer: impl Into<String>, importee: impl Into<String>) -> Self {
+    Self::new_inner(RequireTla { importer: importer.into(), importee: importee.into() })
+  }
+
+  pub fn unsupported_code_splitting_format(format: &'static str) -> Self {
+    Self::new_inner(UnsupportedCodeSplittingFormat { format })
+  }
+
+  pub fn circular_chunk(chunk_names: Vec<String>) -> Self {
+    Self::new_inner(CircularChunk { chunk_names })
+  }
+
+  pub fn circular_dependency(paths: Vec<String>) -> Self {
+    Self::new_inner(CircularDependency { paths })
+  }
+
+  pub fn unexpected_named_import(
+    module_id: impl Into<String>,
+    imported: impl Into<String>,
+  ) -> Self {
+    Self::new_inner(UnexpectedNamedImport {
+      module_id: module_id.into(),
+      imported: imported.into(),
+    })
   }
 
   pub fn sourcemap_error(reason: String) -> Self {
     Self::new_inner(SourceMapError { reason })
   }
 
+  /// A warning emitted by `this.warn` of a plugin.
+  pub fn plugin_warning(
+    plugin: impl Into<String>,
+    message: impl Into<String>,
+    position: Option<LogPosition>,
+  ) -> Self {
+    Self::new_inner(PluginLog {
+      code: "PLUGIN_WARNING",
+      plugin: plugin.into(),
+      message: message.into(),
+      position,
+    })
+    .with_severity_warning()
+  }
+
+  /// A log emitted by `this.info` or `this.debug` of a plugin.
+  pub fn plugin_log(
+    plugin: impl Into<String>,
+    message: impl Into<String>,
+    position: Option<LogPosition>,
+  ) -> Self {
+    Self::new_inner(PluginLog {
+      code: "PLUGIN_LOG",
+      plugin: plugin.into(),
+      message: message.into(),
+      position,
+    })
+    .with_severity_warning()
+  }
+
   // --- rolldown specific
   pub fn napi_error(status: String, reason: String) -> Self {
     Self::new_inner(NapiError { status, reason })
@@ -108,6 +393,23 @@ impl BuildError {
   pub fn unsupported_eval(filename: String, source: Arc<str>, span: Span) -> Self {
     Self::new_inner(UnsupportedEval { filename, eval_span: span, source })
   }
+
+  pub fn dynamic_asset_url(filename: String, source: Arc<str>, span: Span) -> Self {
+    Self::new_inner(DynamicAssetUrl { filename, source, span })
+  }
+
+  pub fn unsupported_syntax(
+    filename: String,
+    source: Arc<str>,
+    span: Span,
+    syntax: &'static str,
+  ) -> Self {
+    Self::new_inner(UnsupportedSyntax { filename, source, span, syntax })
+  }
+
+  pub fn parse_error(filename: String, source: Arc<str>, span: Span, reason: String) -> Self {
+    Self::new_inner(ParseError { filename, source, span, reason })
+  }
 }
 
 impl From<std::io::Error> for BuildError {
@@ -0,0 +1,20 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct CircularChunk {
+  /// Names of the chunks in the cycle, starting and ending with the same chunk.
+  pub(crate) chunk_names: Vec<String>,
+}
+
+impl BuildErrorLike for CircularChunk {
+  fn code(&self) -> &'static str {
+    "CIRCULAR_CHUNK"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Circular chunk: {}. Please adjust the manual chunk logic for these chunks.",
+      self.chunk_names.join(" -> ")
+    )
+  }
+}
@@ -0,0 +1,17 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct CircularDependency {
+  /// Paths of the modules in the cycle, starting and ending with the same module.
+  pub(crate) paths: Vec<String>,
+}
+
+impl BuildErrorLike for CircularDependency {
+  fn code(&self) -> &'static str {
+    "CIRCULAR_DEPENDENCY"
+  }
+
+  fn message(&self) -> String {
+    format!("Circular dependency: {}", self.paths.join(" -> "))
+  }
+}
@@ -0,0 +1,21 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct DuplicateEntryName {
+  pub(crate) name: String,
+  pub(crate) first_import: String,
+  pub(crate) second_import: String,
+}
+
+impl BuildErrorLike for DuplicateEntryName {
+  fn code(&self) -> &'static str {
+    "DUPLICATE_ENTRY_NAME"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Entries \"{}\" and \"{}\" are both named \"{}\". Names of entries in `input` must be unique.",
+      self.first_import, self.second_import, self.name
+    )
+  }
+}
@@ -0,0 +1,50 @@
+use std::{path::Path, sync::Arc};
+
+use ariadne::Label;
+use oxc::span::Span;
+
+use crate::{
+  diagnostic::{DiagnosticBuilder, DiagnosticLocation},
+  PathExt,
+};
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct DynamicAssetUrl {
+  pub filename: String,
+  pub source: Arc<str>,
+  pub span: Span,
+}
+
+impl BuildErrorLike for DynamicAssetUrl {
+  fn code(&self) -> &'static str {
+    "DYNAMIC_ASSET_URL"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "The path of `new URL(..., import.meta.url)` isn't a string literal at {}",
+      self.filename
+    )
+  }
+
+  fn diagnostic_builder(&self) -> crate::diagnostic::DiagnosticBuilder {
+    let filename = Path::new(&self.filename).relative_display();
+    DiagnosticBuilder {
+      code: Some(self.code()),
+      summary: Some(
+        "Only string literals in `new URL(..., import.meta.url)` are emitted as assets."
+          .to_string(),
+      ),
+      files: Some(vec![(filename.clone(), self.source.to_string())]),
+      labels: Some(vec![Label::new((
+        filename,
+        (self.span.start as usize..self.span.end as usize),
+      ))
+      .with_message("The URL is left as is.")]),
+      location: Some(DiagnosticLocation::new(&self.filename, &self.source, self.span)),
+      ..Default::default()
+    }
+  }
+}
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use crate::PathExt;
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct EntrySignatureExtended {
+  pub(crate) entry_module: PathBuf,
+  pub(crate) extra_exports: Vec<String>,
+}
+
+impl BuildErrorLike for EntrySignatureExtended {
+  fn code(&self) -> &'static str {
+    "ENTRY_SIGNATURE_EXTENDED"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "The chunk of entry module \"{}\" has to export {} for other chunks, which the entry module doesn't export. Set \"preserveEntrySignatures\" to \"allow-extension\" to allow it.",
+      self.entry_module.relative_display(),
+      self.extra_exports.iter().map(|name| format!("\"{name}\"")).collect::<Vec<_>>().join(", ")
+    )
+  }
+}
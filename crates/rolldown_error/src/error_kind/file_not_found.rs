@@ -0,0 +1,16 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct FileNotFound {
+  pub(crate) reference_id: String,
+}
+
+impl BuildErrorLike for FileNotFound {
+  fn code(&self) -> &'static str {
+    "FILE_NOT_FOUND"
+  }
+
+  fn message(&self) -> String {
+    format!("Plugin error - Unable to get file name for unknown file \"{}\".", self.reference_id)
+  }
+}
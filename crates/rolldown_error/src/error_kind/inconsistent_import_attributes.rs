@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use crate::PathExt;
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InconsistentImportAttributes {
+  pub(crate) importer: String,
+  pub(crate) importee: String,
+  /// Attributes of the first import of the module, like `type: "json"`.
+  pub(crate) existing_attributes: Vec<(String, String)>,
+  pub(crate) new_attributes: Vec<(String, String)>,
+}
+
+impl InconsistentImportAttributes {
+  fn format_attributes(attributes: &[(String, String)]) -> String {
+    if attributes.is_empty() {
+      return "no attributes".to_string();
+    }
+    let attributes =
+      attributes.iter().map(|(key, value)| format!("{key}: \"{value}\"")).collect::<Vec<_>>();
+    format!("{{ {} }}", attributes.join(", "))
+  }
+}
+
+impl BuildErrorLike for InconsistentImportAttributes {
+  fn code(&self) -> &'static str {
+    "INCONSISTENT_IMPORT_ATTRIBUTES"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Module \"{}\" is imported by \"{}\" with {}, but it's imported with {} elsewhere.",
+      Path::new(&self.importee).relative_display(),
+      Path::new(&self.importer).relative_display(),
+      Self::format_attributes(&self.new_attributes),
+      Self::format_attributes(&self.existing_attributes),
+    )
+  }
+}
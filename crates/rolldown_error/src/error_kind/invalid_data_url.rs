@@ -0,0 +1,24 @@
+use super::BuildErrorLike;
+
+/// Data URLs of long modules are cut at this length in messages.
+const MAX_DISPLAYED_URL_LEN: usize = 50;
+
+#[derive(Debug)]
+pub struct InvalidDataUrl {
+  pub(crate) url: String,
+  pub(crate) reason: String,
+}
+
+impl BuildErrorLike for InvalidDataUrl {
+  fn code(&self) -> &'static str {
+    "INVALID_DATA_URL"
+  }
+
+  fn message(&self) -> String {
+    let url = match self.url.char_indices().nth(MAX_DISPLAYED_URL_LEN) {
+      Some((end, _)) => format!("{}...", &self.url[..end]),
+      None => self.url.clone(),
+    };
+    format!("Could not load \"{url}\": {}.", self.reason)
+  }
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use crate::PathExt;
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InvalidExportOption {
+  pub(crate) export_mode: &'static str,
+  pub(crate) entry_module: PathBuf,
+  pub(crate) exported_keys: Vec<String>,
+}
+
+impl BuildErrorLike for InvalidExportOption {
+  fn code(&self) -> &'static str {
+    "INVALID_EXPORT_OPTION"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "\"{}\" was specified for \"output.exports\", but entry module \"{}\" has the following exports: {}",
+      self.export_mode,
+      self.entry_module.relative_display(),
+      self.exported_keys.iter().map(|key| format!("\"{key}\"")).collect::<Vec<_>>().join(", ")
+    )
+  }
+}
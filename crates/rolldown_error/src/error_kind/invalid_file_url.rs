@@ -0,0 +1,21 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InvalidFileUrl {
+  pub(crate) reference_id: String,
+  /// The code returned by `resolve_file_url` hooks.
+  pub(crate) url: String,
+}
+
+impl BuildErrorLike for InvalidFileUrl {
+  fn code(&self) -> &'static str {
+    "INVALID_FILE_URL"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "The URL `{}` resolved for the file \"{}\" by plugins is not a valid expression.",
+      self.url, self.reference_id
+    )
+  }
+}
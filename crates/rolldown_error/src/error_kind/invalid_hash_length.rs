@@ -0,0 +1,27 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InvalidHashLength {
+  pub(crate) length: usize,
+  /// The length of hashes computed by the hash algorithm.
+  pub(crate) max_length: usize,
+  /// The file name pattern with `[hash:<length>]`, or `None` for `output.hashLength`.
+  pub(crate) pattern: Option<String>,
+}
+
+impl BuildErrorLike for InvalidHashLength {
+  fn code(&self) -> &'static str {
+    "VALIDATION_ERROR"
+  }
+
+  fn message(&self) -> String {
+    let source = match &self.pattern {
+      Some(pattern) => format!("`[hash:{}]` is used in \"{pattern}\"", self.length),
+      None => format!("`output.hashLength` is {}", self.length),
+    };
+    format!(
+      "Hashes in file names must be between 6 and {} characters long with the chosen hash algorithm, but {source}.",
+      self.max_length
+    )
+  }
+}
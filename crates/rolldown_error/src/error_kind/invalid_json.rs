@@ -0,0 +1,27 @@
+use super::BuildErrorLike;
+use crate::PathExt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct InvalidJson {
+  pub(crate) path: PathBuf,
+  pub(crate) reason: String,
+  pub(crate) line: usize,
+  pub(crate) column: usize,
+}
+
+impl BuildErrorLike for InvalidJson {
+  fn code(&self) -> &'static str {
+    "PARSE_ERROR"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Could not parse {}:{}:{} as JSON: {}.",
+      self.path.relative_display(),
+      self.line,
+      self.column,
+      self.reason
+    )
+  }
+}
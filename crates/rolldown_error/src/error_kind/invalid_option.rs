@@ -0,0 +1,18 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InvalidOption {
+  /// The name of the option, like `output.inlineDynamicImports`.
+  pub(crate) option: &'static str,
+  pub(crate) explanation: String,
+}
+
+impl BuildErrorLike for InvalidOption {
+  fn code(&self) -> &'static str {
+    "INVALID_OPTION"
+  }
+
+  fn message(&self) -> String {
+    format!("Invalid value for option \"{}\" - {}.", self.option, self.explanation)
+  }
+}
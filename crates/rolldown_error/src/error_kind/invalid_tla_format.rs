@@ -0,0 +1,49 @@
+use std::{path::Path, sync::Arc};
+
+use ariadne::Label;
+use oxc::span::Span;
+
+use crate::{
+  diagnostic::{DiagnosticBuilder, DiagnosticLocation},
+  PathExt,
+};
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InvalidTlaFormat {
+  pub filename: String,
+  pub source: Arc<str>,
+  pub span: Span,
+  pub format: &'static str,
+}
+
+impl BuildErrorLike for InvalidTlaFormat {
+  fn code(&self) -> &'static str {
+    "INVALID_TLA_FORMAT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Module format \"{}\" does not support top-level await, which is used in {}. Use the \"esm\" output format rather.",
+      self.format,
+      Path::new(&self.filename).relative_display()
+    )
+  }
+
+  fn diagnostic_builder(&self) -> crate::diagnostic::DiagnosticBuilder {
+    let filename = Path::new(&self.filename).relative_display();
+    DiagnosticBuilder {
+      code: Some(self.code()),
+      summary: Some(self.message()),
+      files: Some(vec![(filename.clone(), self.source.to_string())]),
+      labels: Some(vec![Label::new((
+        filename,
+        (self.span.start as usize..self.span.end as usize),
+      ))
+      .with_message("Top-level await is used here.")]),
+      location: Some(DiagnosticLocation::new(&self.filename, &self.source, self.span)),
+      ..Default::default()
+    }
+  }
+}
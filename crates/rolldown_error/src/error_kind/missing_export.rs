@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use ariadne::Label;
+use oxc::span::Span;
+
+use crate::diagnostic::{DiagnosticBuilder, DiagnosticLocation};
+
+use super::BuildErrorLike;
+
+/// Exported names listed in the suggestion at most.
+const MAX_LISTED_EXPORTS: usize = 10;
+
+#[derive(Debug)]
+pub struct MissingExport {
+  pub(crate) importer: String,
+  pub(crate) importee: String,
+  pub(crate) binding: String,
+  /// The code of the importer and the span of the import specifier in it.
+  pub(crate) importer_source: Arc<str>,
+  pub(crate) span: Span,
+  pub(crate) exported_names: Vec<String>,
+  pub(crate) importer_chain: Vec<String>,
+}
+
+impl MissingExport {
+  /// An exported name that differs from the missing one in case or a few characters, like a typo.
+  fn closest_exported_name(&self) -> Option<&str> {
+    let binding = self.binding.to_lowercase();
+    let max_distance = (self.binding.chars().count() / 3).max(1);
+    self
+      .exported_names
+      .iter()
+      .map(|name| (edit_distance(&binding, &name.to_lowercase()), name))
+      .filter(|(distance, _)| *distance <= max_distance)
+      .min_by_key(|(distance, _)| *distance)
+      .map(|(_, name)| name.as_str())
+  }
+
+  fn help(&self) -> String {
+    if self.exported_names.is_empty() {
+      return format!("\"{}\" has no exports.", self.importee);
+    }
+    let mut listed = self
+      .exported_names
+      .iter()
+      .take(MAX_LISTED_EXPORTS)
+      .map(|name| format!("`{name}`"))
+      .collect::<Vec<_>>();
+    if self.exported_names.len() > MAX_LISTED_EXPORTS {
+      listed.push(format!("and {} more", self.exported_names.len() - MAX_LISTED_EXPORTS));
+    }
+    let exported_names = format!("Exported names are: {}.", listed.join(", "));
+    match self.closest_exported_name() {
+      Some(name) => format!("Did you mean `{name}`? {exported_names}"),
+      None => exported_names,
+    }
+  }
+}
+
+impl BuildErrorLike for MissingExport {
+  fn code(&self) -> &'static str {
+    "MISSING_EXPORT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "\"{}\" is not exported by \"{}\", imported by \"{}\".",
+      self.binding, self.importee, self.importer
+    )
+  }
+
+  fn diagnostic_builder(&self) -> DiagnosticBuilder {
+    DiagnosticBuilder {
+      code: Some(self.code()),
+      summary: Some(self.message()),
+      files: Some(vec![(self.importer.clone(), self.importer_source.to_string())]),
+      labels: Some(vec![Label::new((
+        self.importer.clone(),
+        (self.span.start as usize..self.span.end as usize),
+      ))
+      .with_message(format!("Missing export \"{}\".", self.binding))]),
+      location: Some(DiagnosticLocation::new(&self.importer, &self.importer_source, self.span)),
+      importer_chain: Some(self.importer_chain.clone()),
+      help: Some(self.help()),
+      ..Default::default()
+    }
+  }
+}
+
+/// The Levenshtein distance in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b = b.chars().collect::<Vec<_>>();
+  let mut previous = (0..=b.len()).collect::<Vec<_>>();
+  for (i, a_char) in a.chars().enumerate() {
+    let mut current = vec![i + 1; b.len() + 1];
+    for (j, b_char) in b.iter().enumerate() {
+      let substitution = previous[j] + usize::from(a_char != *b_char);
+      current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+    }
+    previous = current;
+  }
+  previous[b.len()]
+}
+
+#[test]
+fn test_edit_distance() {
+  assert_eq!(edit_distance("foo", "foo"), 0);
+  assert_eq!(edit_distance("button", "buton"), 1);
+  assert_eq!(edit_distance("kitten", "sitting"), 3);
+  assert_eq!(edit_distance("", "abc"), 3);
+}
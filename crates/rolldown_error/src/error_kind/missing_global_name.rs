@@ -0,0 +1,20 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct MissingGlobalName {
+  pub(crate) module_id: String,
+  pub(crate) guessed_name: String,
+}
+
+impl BuildErrorLike for MissingGlobalName {
+  fn code(&self) -> &'static str {
+    "MISSING_GLOBAL_NAME"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "No name was provided for external module \"{}\" in \"output.globals\" – guessing \"{}\".",
+      self.module_id, self.guessed_name
+    )
+  }
+}
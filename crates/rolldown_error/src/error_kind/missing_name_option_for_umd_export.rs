@@ -0,0 +1,14 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct MissingNameOptionForUmdExport;
+
+impl BuildErrorLike for MissingNameOptionForUmdExport {
+  fn code(&self) -> &'static str {
+    "INVALID_OPTION"
+  }
+
+  fn message(&self) -> String {
+    "You must supply \"output.name\" for UMD bundles that have exports so that the exports are accessible in environments without a module loader.".to_string()
+  }
+}
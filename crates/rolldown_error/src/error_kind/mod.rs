@@ -1,11 +1,40 @@
 use std::fmt::Debug;
 
 use crate::diagnostic::DiagnosticBuilder;
+pub mod circular_chunk;
+pub mod circular_dependency;
+pub mod duplicate_entry_name;
+pub mod dynamic_asset_url;
+pub mod entry_signature_extended;
 pub mod external_entry;
+pub mod file_name_conflict;
+pub mod file_not_found;
+pub mod inconsistent_import_attributes;
+pub mod invalid_data_url;
+pub mod invalid_export_option;
+pub mod invalid_file_url;
+pub mod invalid_hash_length;
+pub mod invalid_json;
+pub mod invalid_option;
+pub mod invalid_tla_format;
+pub mod missing_export;
+pub mod missing_global_name;
+pub mod missing_name_option_for_iife_export;
+pub mod missing_name_option_for_umd_export;
+pub mod mixed_exports;
+pub mod parse_error;
+pub mod plugin_log;
+pub mod require_tla;
+pub mod shimmed_export;
 pub mod sourcemap_error;
+pub mod unexpected_named_import;
+pub mod unloaded_virtual_module;
 pub mod unresolved_entry;
 pub mod unresolved_import;
+pub mod unresolved_import_treated_as_external;
+pub mod unsupported_code_splitting_format;
 pub mod unsupported_eval;
+pub mod unsupported_syntax;
 
 // TODO(hyf0): Not a good name, probably should rename to `BuildError`
 pub trait BuildErrorLike: Debug + Sync + Send {
@@ -20,6 +49,16 @@ pub trait BuildErrorLike: Debug + Sync + Send {
       ..Default::default()
     }
   }
+
+  /// Errors with the same code and key are reported once. By default, they have to read the same.
+  fn dedup_key(&self) -> String {
+    self.message()
+  }
+
+  /// The module the error is about, if the diagnostic doesn't point at a location in it.
+  fn module_path(&self) -> Option<String> {
+    None
+  }
 }
 
 impl<T: BuildErrorLike + 'static> From<T> for Box<dyn BuildErrorLike>
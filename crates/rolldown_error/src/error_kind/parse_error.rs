@@ -0,0 +1,46 @@
+use std::{path::Path, sync::Arc};
+
+use ariadne::Label;
+use oxc::span::Span;
+
+use crate::{
+  diagnostic::{DiagnosticBuilder, DiagnosticLocation},
+  PathExt,
+};
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct ParseError {
+  pub filename: String,
+  pub source: Arc<str>,
+  pub span: Span,
+  /// The message of the parser, like `Unexpected token`.
+  pub reason: String,
+}
+
+impl BuildErrorLike for ParseError {
+  fn code(&self) -> &'static str {
+    "PARSE_ERROR"
+  }
+
+  fn message(&self) -> String {
+    format!("{} in {}", self.reason, Path::new(&self.filename).relative_display())
+  }
+
+  fn diagnostic_builder(&self) -> crate::diagnostic::DiagnosticBuilder {
+    let filename = Path::new(&self.filename).relative_display();
+    DiagnosticBuilder {
+      code: Some(self.code()),
+      summary: Some(self.message()),
+      files: Some(vec![(filename.clone(), self.source.to_string())]),
+      labels: Some(vec![Label::new((
+        filename,
+        (self.span.start as usize..self.span.end as usize),
+      ))
+      .with_message(&self.reason)]),
+      location: Some(DiagnosticLocation::new(&self.filename, &self.source, self.span)),
+      ..Default::default()
+    }
+  }
+}
@@ -0,0 +1,97 @@
+use std::{path::Path, sync::Arc};
+
+use ariadne::Label;
+use oxc::span::Span;
+
+use crate::{
+  diagnostic::{DiagnosticBuilder, DiagnosticLocation},
+  PathExt,
+};
+
+use super::BuildErrorLike;
+
+/// Where in a module a plugin log is about, which is rendered like the labels of bundler-native diagnostics.
+#[derive(Debug, Clone)]
+pub struct LogPosition {
+  pub module_id: String,
+  /// The code `span` points into.
+  pub source: Arc<str>,
+  pub span: Span,
+}
+
+/// A log emitted by `this.warn`, `this.info` or `this.debug` of a plugin.
+#[derive(Debug)]
+pub struct PluginLog {
+  /// `PLUGIN_WARNING` for warnings and `PLUGIN_LOG` for the others, like rollup.
+  pub(crate) code: &'static str,
+  pub(crate) plugin: String,
+  pub(crate) message: String,
+  pub(crate) position: Option<LogPosition>,
+}
+
+impl BuildErrorLike for PluginLog {
+  fn code(&self) -> &'static str {
+    self.code
+  }
+
+  fn message(&self) -> String {
+    match &self.position {
+      Some(position) => format!(
+        "[plugin {}] {}: {}",
+        self.plugin,
+        Path::new(&position.module_id).relative_display(),
+        self.message
+      ),
+      None => format!("[plugin {}] {}", self.plugin, self.message),
+    }
+  }
+
+  fn diagnostic_builder(&self) -> DiagnosticBuilder {
+    let Some(position) = &self.position else {
+      return DiagnosticBuilder {
+        code: Some(self.code()),
+        summary: Some(self.message()),
+        ..Default::default()
+      };
+    };
+    let filename = Path::new(&position.module_id).relative_display();
+    DiagnosticBuilder {
+      code: Some(self.code()),
+      summary: Some(format!("[plugin {}] {}", self.plugin, self.message)),
+      files: Some(vec![(filename.clone(), position.source.to_string())]),
+      labels: Some(vec![Label::new((
+        filename,
+        (position.span.start as usize..position.span.end as usize),
+      ))]),
+      location: Some(DiagnosticLocation::new(&position.module_id, &position.source, position.span)),
+      ..Default::default()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use oxc::span::Span;
+
+  use super::LogPosition;
+  use crate::BuildError;
+
+  #[test]
+  fn positions_are_rendered_like_native_diagnostics() {
+    let position = LogPosition {
+      module_id: "/project/main.js".to_string(),
+      source: "console.log(1)".into(),
+      span: Span::new(0, 7),
+    };
+    let warning = BuildError::plugin_warning("logger", "avoid console", Some(position));
+    assert_eq!(warning.code(), "PLUGIN_WARNING");
+    assert!(warning.to_string().starts_with("[plugin logger] "), "{warning}");
+    assert!(warning.to_string().ends_with("main.js: avoid console"), "{warning}");
+    let diagnostic = warning.into_diagnostic().to_string();
+    assert!(diagnostic.contains("console.log(1)"), "{diagnostic}");
+
+    let log = BuildError::plugin_log("logger", "transformed", None);
+    assert_eq!(log.code(), "PLUGIN_LOG");
+    assert_eq!(log.to_string(), "[plugin logger] transformed");
+  }
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+
+use crate::PathExt;
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct RequireTla {
+  pub(crate) importer: String,
+  pub(crate) importee: String,
+}
+
+impl BuildErrorLike for RequireTla {
+  fn code(&self) -> &'static str {
+    "REQUIRE_TLA"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "\"{}\" can't be required by \"{}\", since it contains a top-level await or imports a module containing one.",
+      Path::new(&self.importee).relative_display(),
+      Path::new(&self.importer).relative_display(),
+    )
+  }
+}
@@ -0,0 +1,20 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct ShimmedExport {
+  pub(crate) module_id: String,
+  pub(crate) binding: String,
+}
+
+impl BuildErrorLike for ShimmedExport {
+  fn code(&self) -> &'static str {
+    "SHIMMED_EXPORT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Missing export \"{}\" has been shimmed in module \"{}\".",
+      self.binding, self.module_id
+    )
+  }
+}
@@ -0,0 +1,20 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct UnexpectedNamedImport {
+  pub(crate) module_id: String,
+  pub(crate) imported: String,
+}
+
+impl BuildErrorLike for UnexpectedNamedImport {
+  fn code(&self) -> &'static str {
+    "UNEXPECTED_NAMED_IMPORT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "The named export \"{}\" was imported from the external module \"{}\" even though its interop type is \"defaultOnly\". Either remove or change this import or change the value of the \"output.interop\" option.",
+      self.imported, self.module_id
+    )
+  }
+}
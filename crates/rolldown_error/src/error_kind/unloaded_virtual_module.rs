@@ -0,0 +1,19 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct UnloadedVirtualModule {
+  pub(crate) id: String,
+}
+
+impl BuildErrorLike for UnloadedVirtualModule {
+  fn code(&self) -> &'static str {
+    "UNLOADED_VIRTUAL_MODULE"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Could not load virtual module \"{}\": no plugin loaded it, and ids starting with \"\\0\" aren't read from the file system.",
+      self.id.replace('\0', "\\0")
+    )
+  }
+}
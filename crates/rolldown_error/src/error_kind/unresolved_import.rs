@@ -6,6 +6,8 @@ use std::path::PathBuf;
 pub struct UnresolvedImport {
   pub(crate) specifier: StaticStr,
   pub(crate) importer: PathBuf,
+  /// The configured tsconfig and the pattern of its `compilerOptions.paths` that matched the specifier.
+  pub(crate) tsconfig: Option<(PathBuf, Option<String>)>,
 }
 
 impl BuildErrorLike for UnresolvedImport {
@@ -14,6 +16,34 @@ impl BuildErrorLike for UnresolvedImport {
   }
 
   fn message(&self) -> String {
-    format!("Could not resolve {} from {}.", self.specifier, self.importer.relative_display())
+    let mut message =
+      format!("Could not resolve {} from {}.", self.specifier, self.importer.relative_display());
+    match &self.tsconfig {
+      Some((tsconfig, Some(pattern))) => message.push_str(&format!(
+        " The targets of \"{pattern}\" in compilerOptions.paths of {} were tried.",
+        tsconfig.relative_display()
+      )),
+      Some((tsconfig, None)) => message.push_str(&format!(
+        " No pattern in compilerOptions.paths of {} matched it.",
+        tsconfig.relative_display()
+      )),
+      None => {}
+    }
+    message
+  }
+
+  /// A relative specifier is the same one if it's imported from the same directory, while a bare one is the same one
+  /// wherever it's imported from.
+  fn dedup_key(&self) -> String {
+    if self.specifier.starts_with('.') {
+      let dir = self.importer.parent().unwrap_or(&self.importer);
+      dir.join(self.specifier.as_ref()).to_string_lossy().into_owned()
+    } else {
+      self.specifier.to_string()
+    }
+  }
+
+  fn module_path(&self) -> Option<String> {
+    Some(self.importer.to_string_lossy().into_owned())
   }
 }
@@ -0,0 +1,23 @@
+use super::BuildErrorLike;
+use crate::PathExt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct UnresolvedImportTreatedAsExternal {
+  pub(crate) specifier: String,
+  pub(crate) importer: PathBuf,
+}
+
+impl BuildErrorLike for UnresolvedImportTreatedAsExternal {
+  fn code(&self) -> &'static str {
+    "UNRESOLVED_IMPORT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "\"{}\" is imported by \"{}\", but could not be resolved – treating it as an external dependency.",
+      self.specifier,
+      self.importer.relative_display()
+    )
+  }
+}
@@ -0,0 +1,19 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct UnsupportedCodeSplittingFormat {
+  pub(crate) format: &'static str,
+}
+
+impl BuildErrorLike for UnsupportedCodeSplittingFormat {
+  fn code(&self) -> &'static str {
+    "INVALID_OPTION"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Invalid value \"{}\" for option \"output.format\" - UMD and IIFE output formats are not supported for code-splitting builds.",
+      self.format
+    )
+  }
+}
@@ -3,7 +3,10 @@ use std::{path::Path, sync::Arc};
 use ariadne::Label;
 use oxc::span::Span;
 
-use crate::{diagnostic::DiagnosticBuilder, PathExt};
+use crate::{
+  diagnostic::{DiagnosticBuilder, DiagnosticLocation},
+  PathExt,
+};
 
 use super::BuildErrorLike;
 
@@ -35,6 +38,7 @@ impl BuildErrorLike for UnsupportedEval {
         (self.eval_span.start as usize..self.eval_span.end as usize),
       ))
       .with_message("Used `eval` function here.")]),
+      location: Some(DiagnosticLocation::new(&self.filename, &self.source, self.eval_span)),
       ..Default::default()
     }
   }
@@ -0,0 +1,46 @@
+use std::{path::Path, sync::Arc};
+
+use ariadne::Label;
+use oxc::span::Span;
+
+use crate::{
+  diagnostic::{DiagnosticBuilder, DiagnosticLocation},
+  PathExt,
+};
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct UnsupportedSyntax {
+  pub filename: String,
+  pub source: Arc<str>,
+  pub span: Span,
+  /// What's not supported, like `TypeScript namespaces with values`.
+  pub syntax: &'static str,
+}
+
+impl BuildErrorLike for UnsupportedSyntax {
+  fn code(&self) -> &'static str {
+    "UNSUPPORTED_SYNTAX"
+  }
+
+  fn message(&self) -> String {
+    format!("Unsupported {} at {}", self.syntax, self.filename)
+  }
+
+  fn diagnostic_builder(&self) -> crate::diagnostic::DiagnosticBuilder {
+    let filename = Path::new(&self.filename).relative_display();
+    DiagnosticBuilder {
+      code: Some(self.code()),
+      summary: Some(format!("Rolldown does not support {} currently.", self.syntax)),
+      files: Some(vec![(filename.clone(), self.source.to_string())]),
+      labels: Some(vec![Label::new((
+        filename,
+        (self.span.start as usize..self.span.end as usize),
+      ))
+      .with_message(format!("Used {} here.", self.syntax))]),
+      location: Some(DiagnosticLocation::new(&self.filename, &self.source, self.span)),
+      ..Default::default()
+    }
+  }
+}
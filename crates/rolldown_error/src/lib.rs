@@ -9,8 +9,11 @@ use sugar_path::SugarPath;
 
 pub(crate) type StaticStr = Cow<'static, str>;
 
-// pub use crate::{diagnostic::Diagnostic, error::BuildError};
-pub use crate::error::BuildError;
+pub use crate::{
+  diagnostic::{Diagnostic, DiagnosticLocation},
+  error::{BuildError, Severity},
+  error_kind::plugin_log::LogPosition,
+};
 
 trait PathExt {
   fn relative_display(&self) -> String;
@@ -28,6 +28,11 @@ pub trait FileSystem: Send + Sync + OxcResolverFileSystem {
   /// * See [std::fs::write]
   fn write(&self, path: &Path, content: &[u8]) -> io::Result<()>;
 
+  /// # Errors
+  ///
+  /// * See [std::fs::read]
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
   /// # Errors
   ///
   /// * See [std::path::Path::exists]
@@ -56,10 +56,17 @@ impl FileSystem for MemoryFileSystem {
   }
 
   fn create_dir_all(&self, path: &Path) -> io::Result<()> {
-    self
-      .fs
-      .create_dir(&path.to_string_lossy())
-      .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    // Like `std::fs::create_dir_all`, existing directories are fine
+    for path in path.ancestors().collect::<Vec<_>>().iter().rev() {
+      let path = path.to_string_lossy();
+      if !self.fs.exists(path.as_ref()).unwrap_or_default() {
+        self
+          .fs
+          .create_dir(path.as_ref())
+          .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+      }
+    }
+    Ok(())
   }
 
   fn write(&self, path: &Path, content: &[u8]) -> io::Result<()> {
@@ -72,8 +79,18 @@ impl FileSystem for MemoryFileSystem {
     Ok(())
   }
 
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    let mut buf = vec![];
+    self
+      .fs
+      .open_file(&path.to_string_lossy())
+      .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?
+      .read_to_end(&mut buf)?;
+    Ok(buf)
+  }
+
   fn exists(&self, path: &Path) -> bool {
-    self.fs.exists(path.to_string_lossy().as_ref()).is_ok()
+    self.fs.exists(path.to_string_lossy().as_ref()).unwrap_or_default()
   }
 }
 
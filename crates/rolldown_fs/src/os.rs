@@ -28,6 +28,10 @@ impl FileSystem for OsFileSystem {
     std::fs::write(path, content)
   }
 
+  fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+    std::fs::read(path)
+  }
+
   fn exists(&self, path: &Path) -> bool {
     path.exists()
   }
@@ -1,7 +1,7 @@
 use oxc::{
   allocator::{self, Allocator},
   ast::ast::{self, Statement},
-  span::Atom,
+  span::{Atom, Span},
 };
 
 use crate::{Dummy, IntoIn};
@@ -112,6 +112,27 @@ impl<'ast> AstSnippet<'ast> {
     ast::Expression::CallExpression(call_expr.into_in(self.alloc))
   }
 
+  /// `object.property(arg)`
+  pub fn member_call_expr_with_arg_expr(
+    &self,
+    object: Atom<'ast>,
+    property: Atom<'ast>,
+    arg: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    let mut arguments = allocator::Vec::new_in(self.alloc);
+    arguments.push(ast::Argument::Expression(arg));
+    ast::Expression::CallExpression(
+      ast::CallExpression {
+        callee: ast::Expression::MemberExpression(
+          self.literal_prop_access_member_expr(object, property).into_in(self.alloc),
+        ),
+        arguments,
+        ..Dummy::dummy(self.alloc)
+      }
+      .into_in(self.alloc),
+    )
+  }
+
   /// `name(arg1, arg2)`
   pub fn call_expr_with_2arg_expr_expr(
     &self,
@@ -129,12 +150,20 @@ impl<'ast> AstSnippet<'ast> {
 
   /// `name()`
   pub fn call_expr_stmt(&self, name: Atom<'ast>) -> ast::Statement<'_> {
+    self.expr_stmt(self.call_expr_expr(name))
+  }
+
+  /// `[expr];`
+  pub fn expr_stmt(&self, expr: ast::Expression<'ast>) -> ast::Statement<'ast> {
     ast::Statement::ExpressionStatement(
-      ast::ExpressionStatement {
-        expression: self.call_expr_expr(name),
-        ..Dummy::dummy(self.alloc)
-      }
-      .into_in(self.alloc),
+      ast::ExpressionStatement { expression: expr, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+    )
+  }
+
+  /// `await [expr]`
+  pub fn await_expr(&self, expr: ast::Expression<'ast>) -> ast::Expression<'ast> {
+    ast::Expression::AwaitExpression(
+      ast::AwaitExpression { span: Span::default(), argument: expr }.into_in(self.alloc),
     )
   }
 
@@ -229,9 +258,11 @@ impl<'ast> AstSnippet<'ast> {
     binding_name: Atom<'ast>,
     esm_fn_name: Atom<'ast>,
     body: allocator::Vec<'ast, Statement<'ast>>,
+    is_async: bool,
   ) -> ast::Statement<'ast> {
-    // () => { ... }
+    // () => { ... }, or async () => { ... } for bodies with top-level await
     let arrow_expr: ast::ArrowFunctionExpression<'_> = ast::ArrowFunctionExpression {
+      r#async: is_async,
       body: ast::FunctionBody { statements: body, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
       ..Dummy::dummy(self.alloc)
     };
@@ -270,6 +301,15 @@ impl<'ast> AstSnippet<'ast> {
     )
   }
 
+  /// ```js
+  /// "value"
+  /// ```
+  pub fn string_literal_expr(&self, value: Atom<'ast>) -> ast::Expression<'ast> {
+    ast::Expression::StringLiteral(
+      ast::StringLiteral { span: Dummy::dummy(self.alloc), value }.into_in(self.alloc),
+    )
+  }
+
   /// ```js
   /// 42
   /// ```
@@ -285,6 +325,20 @@ impl<'ast> AstSnippet<'ast> {
     )
   }
 
+  /// ```js
+  /// void 0
+  /// ```
+  pub fn void_zero_expr(&self) -> ast::Expression<'ast> {
+    ast::Expression::UnaryExpression(
+      ast::UnaryExpression {
+        span: Dummy::dummy(self.alloc),
+        operator: oxc::syntax::operator::UnaryOperator::Void,
+        argument: self.number_expr(0.0),
+      }
+      .into_in(self.alloc),
+    )
+  }
+
   /// ```js
   ///  id = ...
   /// ￣￣ AssignmentTarget
@@ -311,4 +365,46 @@ impl<'ast> AstSnippet<'ast> {
       .into_in(self.alloc),
     )
   }
+
+  /// `Promise.resolve().then(() => [expr])`
+  pub fn promise_resolve_then_call_expr(
+    &self,
+    expr: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    let resolve_call_expr = ast::CallExpression {
+      callee: ast::Expression::MemberExpression(
+        self
+          .literal_prop_access_member_expr("Promise".into(), "resolve".into())
+          .into_in(self.alloc),
+      ),
+      arguments: allocator::Vec::new_in(self.alloc),
+      ..Dummy::dummy(self.alloc)
+    };
+    self.promise_then_call_expr(
+      ast::Expression::CallExpression(resolve_call_expr.into_in(self.alloc)),
+      expr,
+    )
+  }
+
+  /// `[promise].then(() => [expr])`
+  pub fn promise_then_call_expr(
+    &self,
+    promise: ast::Expression<'ast>,
+    expr: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    let then_callee = ast::Expression::MemberExpression(
+      ast::MemberExpression::StaticMemberExpression(ast::StaticMemberExpression {
+        object: promise,
+        property: self.id_name("then".into()),
+        ..Dummy::dummy(self.alloc)
+      })
+      .into_in(self.alloc),
+    );
+    let mut arguments = allocator::Vec::new_in(self.alloc);
+    arguments.push(ast::Argument::Expression(self.only_return_arrow_expr(expr)));
+    ast::Expression::CallExpression(
+      ast::CallExpression { callee: then_callee, arguments, ..Dummy::dummy(self.alloc) }
+        .into_in(self.alloc),
+    )
+  }
 }
@@ -2,20 +2,39 @@ use std::{fmt::Debug, pin::Pin, sync::Arc};
 
 use oxc::{
   allocator::Allocator,
-  ast::ast,
+  ast::{ast, CommentKind, Visit},
   codegen::{Codegen, CodegenOptions},
   parser::Parser,
   semantic::{Semantic, SemanticBuilder},
-  span::SourceType,
+  span::{GetSpan, SourceType, Span},
 };
 
 use crate::{Dummy, StatementExt, TakeIn};
 pub struct OxcCompiler;
 
+/// How [`OxcCompiler::parse_with_options`] parses the source.
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOptions {
+  pub source_type: SourceType,
+  pub allow_return_outside_function: bool,
+  /// Allow `import` and `export` declarations in scripts and outside of the top level. Otherwise they are reported
+  /// as syntax errors, which the parser of oxc leaves to the semantic analysis.
+  pub allow_import_export_everywhere: bool,
+}
+
+impl ParseOptions {
+  /// The options rolldown parses modules with.
+  pub fn new(source_type: SourceType) -> Self {
+    Self { source_type, allow_return_outside_function: false, allow_import_export_everywhere: true }
+  }
+}
+
 #[allow(clippy::box_collection, clippy::non_send_fields_in_send_ty, unused)]
 pub struct OxcProgram {
   program: ast::Program<'static>,
   source: Pin<Arc<str>>,
+  /// Spans of the content of comments in the source, without `//`, `/*` and `*/`.
+  comments: Vec<(u32, u32, CommentKind)>,
   // Order matters here, we need drop the program first, then drop the allocator. Otherwise, there will be a segmentation fault.
   // The `program` is allocated on the `allocator`. Clippy think it's not used, but it's used.
   allocator: Pin<Box<Allocator>>,
@@ -35,7 +54,7 @@ impl Default for OxcProgram {
       let alloc = std::mem::transmute::<_, &'static Allocator>(allocator.as_ref());
       ast::Program::dummy(alloc)
     };
-    Self { program, source, allocator }
+    Self { program, source, comments: vec![], allocator }
   }
 }
 
@@ -47,6 +66,10 @@ impl OxcProgram {
     &self.source
   }
 
+  pub fn comments(&self) -> &[(u32, u32, CommentKind)] {
+    &self.comments
+  }
+
   pub fn program(&self) -> &ast::Program<'_> {
     // SAFETY: `&'a ast::Program<'a>` can't outlive the `&'a ast::Program<'static>`.
     unsafe { std::mem::transmute(&self.program) }
@@ -88,30 +111,145 @@ impl OxcProgram {
 
 impl OxcCompiler {
   pub fn parse(source: impl Into<Arc<str>>, ty: SourceType) -> OxcProgram {
+    Self::parse_with_errors(source, ty).0
+  }
+
+  /// Parse the source along with the messages and spans of syntax errors, which the parser recovers from.
+  pub fn parse_with_errors(
+    source: impl Into<Arc<str>>,
+    ty: SourceType,
+  ) -> (OxcProgram, Vec<(String, Span)>) {
+    Self::parse_with_options(source, ParseOptions::new(ty))
+  }
+
+  /// Parse the source with `options` along with the messages and spans of syntax errors.
+  pub fn parse_with_options(
+    source: impl Into<Arc<str>>,
+    options: ParseOptions,
+  ) -> (OxcProgram, Vec<(String, Span)>) {
     let source = Pin::new(source.into());
     let allocator = Box::pin(oxc::allocator::Allocator::default());
-    let program = unsafe {
+    let (program, trivias, errors) = unsafe {
       let source = std::mem::transmute::<_, &'static str>(&*source);
       let alloc = std::mem::transmute::<_, &'static Allocator>(allocator.as_ref());
-      Parser::new(alloc, source, ty).parse().program
+      let ret = Parser::new(alloc, source, options.source_type)
+        .allow_return_outside_function(options.allow_return_outside_function)
+        .parse();
+      (ret.program, ret.trivias, ret.errors)
     };
+    let mut errors = errors
+      .into_iter()
+      .map(|error| {
+        let span = error
+          .labels()
+          .and_then(|mut labels| labels.next())
+          .map(|label| {
+            let start = u32::try_from(label.offset()).unwrap_or_default();
+            Span::new(start, start + u32::try_from(label.len()).unwrap_or_default())
+          })
+          .unwrap_or_default();
+        (error.to_string(), span)
+      })
+      .collect::<Vec<_>>();
+    if !options.allow_import_export_everywhere {
+      let mut finder = MisplacedModuleDeclarationFinder {
+        allow_top_level: options.source_type.is_module(),
+        depth: 0,
+        spans: vec![],
+      };
+      finder.visit_program(&program);
+      errors.extend(finder.spans.into_iter().map(|span| {
+        let message = if options.source_type.is_module() {
+          "'import' and 'export' may only appear at the top level"
+        } else {
+          "'import' and 'export' may appear only with 'sourceType: module'"
+        };
+        (message.to_string(), span)
+      }));
+    }
+
+    (OxcProgram { program, source, comments: trivias.comments, allocator }, errors)
+  }
 
-    OxcProgram { program, source, allocator }
+  /// Print the ast. With `compact`, whitespace that isn't needed is omitted.
+  pub fn print(ast: &OxcProgram, compact: bool) -> String {
+    Self::print_with_options(ast, None, compact).0
   }
 
-  pub fn print(ast: &OxcProgram) -> String {
-    let codegen = Codegen::<false>::new(
-      ast.source(),
-      CodegenOptions { enable_typescript: false, enable_source_map: None },
-    );
-    let codegen_ret = codegen.build(&ast.program);
-    codegen_ret.source_text
+  /// Print the ast along with a source map in json, whose only source is `source_name`.
+  pub fn print_with_sourcemap(
+    ast: &OxcProgram,
+    source_name: &str,
+    compact: bool,
+  ) -> (String, Option<String>) {
+    Self::print_with_options(ast, Some(source_name.to_string()), compact)
+  }
+
+  fn print_with_options(
+    ast: &OxcProgram,
+    source_name: Option<String>,
+    compact: bool,
+  ) -> (String, Option<String>) {
+    let options = CodegenOptions { enable_typescript: false, enable_source_map: source_name };
+    // Minifying in the codegen of oxc only means omitting whitespace, names are kept as they are.
+    let codegen_ret = if compact {
+      Codegen::<true>::new(ast.source(), options).build(&ast.program)
+    } else {
+      Codegen::<false>::new(ast.source(), options).build(&ast.program)
+    };
+    let map = codegen_ret.source_map.and_then(|map| {
+      let mut buf = vec![];
+      map.to_writer(&mut buf).ok()?;
+      String::from_utf8(buf).ok()
+    });
+    (codegen_ret.source_text, map)
+  }
+}
+
+/// Collects spans of `import` and `export` declarations that aren't at the top level of a module.
+struct MisplacedModuleDeclarationFinder {
+  allow_top_level: bool,
+  depth: usize,
+  spans: Vec<Span>,
+}
+
+impl<'a> Visit<'a> for MisplacedModuleDeclarationFinder {
+  fn visit_statement(&mut self, stmt: &ast::Statement<'a>) {
+    if let ast::Statement::ModuleDeclaration(decl) = stmt {
+      if self.depth > 0 || !self.allow_top_level {
+        self.spans.push(decl.span());
+      }
+    }
+    self.depth += 1;
+    self.visit_statement_match(stmt);
+    self.depth -= 1;
   }
 }
 
 #[test]
 fn basic_test() {
   let ast = OxcCompiler::parse("const a = 1;".to_string(), SourceType::default());
-  let code = OxcCompiler::print(&ast);
+  let code = OxcCompiler::print(&ast, false);
   assert_eq!(code, "const a = 1;\n");
+  let code = OxcCompiler::print(&ast, true);
+  assert_eq!(code, "const a=1;");
+}
+
+#[test]
+fn misplaced_module_declarations() {
+  let parse = |source: &str, source_type: SourceType, allow_import_export_everywhere: bool| {
+    let options = ParseOptions {
+      source_type,
+      allow_return_outside_function: false,
+      allow_import_export_everywhere,
+    };
+    OxcCompiler::parse_with_options(source.to_string(), options).1.len()
+  };
+  let module = SourceType::default().with_module(true);
+  let script = SourceType::default().with_script(true);
+  assert_eq!(parse("import a from 'a';", module, false), 0);
+  assert_eq!(parse("import a from 'a';", script, false), 1);
+  assert_eq!(parse("import a from 'a';", script, true), 0);
+  assert_eq!(parse("{ import a from 'a'; }", module, false), 1);
+  assert_eq!(parse("function f() { export const a = 1; }", module, true), 0);
 }
@@ -36,6 +36,16 @@ impl<'ast> DummyIn<'ast> for ast::Declaration<'ast> {
     Self::VariableDeclaration(Box(alloc.alloc(DummyIn::dummy(alloc))))
   }
 }
+impl<'ast> DummyIn<'ast> for ast::EmptyStatement {
+  fn dummy(alloc: &'ast Allocator) -> Self {
+    Self { span: DummyIn::dummy(alloc) }
+  }
+}
+impl<'ast> DummyIn<'ast> for ast::Statement<'ast> {
+  fn dummy(alloc: &'ast Allocator) -> Self {
+    Self::EmptyStatement(Box(alloc.alloc(DummyIn::dummy(alloc))))
+  }
+}
 impl<'ast> DummyIn<'ast> for ast::ExpressionStatement<'ast> {
   fn dummy(alloc: &'ast Allocator) -> Self {
     Self { span: DummyIn::dummy(alloc), expression: DummyIn::dummy(alloc) }
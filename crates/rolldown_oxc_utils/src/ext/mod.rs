@@ -204,6 +204,12 @@ pub trait ExpressionExt<'ast> {
 
   fn as_identifier(&self) -> Option<&ast::IdentifierReference<'ast>>;
   fn as_identifier_mut(&mut self) -> Option<&mut ast::IdentifierReference<'ast>>;
+
+  /// `import.meta.url`
+  fn is_import_meta_url(&self) -> bool;
+
+  /// The reference id of `import.meta.ROLLUP_FILE_URL_<referenceId>`.
+  fn as_import_meta_file_url(&self) -> Option<&str>;
 }
 
 impl<'ast> ExpressionExt<'ast> for ast::Expression<'ast> {
@@ -230,6 +236,35 @@ impl<'ast> ExpressionExt<'ast> for ast::Expression<'ast> {
       None
     }
   }
+
+  fn is_import_meta_url(&self) -> bool {
+    let ast::Expression::MemberExpression(member_expr) = self else {
+      return false;
+    };
+    let ast::MemberExpression::StaticMemberExpression(member_expr) = &**member_expr else {
+      return false;
+    };
+    matches!(
+      &member_expr.object,
+      ast::Expression::MetaProperty(meta) if meta.meta.name == "import" && meta.property.name == "meta"
+    ) && member_expr.property.name == "url"
+  }
+
+  fn as_import_meta_file_url(&self) -> Option<&str> {
+    let ast::Expression::MemberExpression(member_expr) = self else {
+      return None;
+    };
+    let ast::MemberExpression::StaticMemberExpression(member_expr) = &**member_expr else {
+      return None;
+    };
+    if !matches!(
+      &member_expr.object,
+      ast::Expression::MetaProperty(meta) if meta.meta.name == "import" && meta.property.name == "meta"
+    ) {
+      return None;
+    }
+    member_expr.property.name.strip_prefix("ROLLUP_FILE_URL_")
+  }
 }
 
 pub trait De<'ast> {
@@ -9,5 +9,5 @@ mod take_in;
 pub use crate::{
   ast_snippet::AstSnippet, dummy::Dummy, from_in::FromIn, into_in::IntoIn, take_in::TakeIn,
 };
-pub use compiler::{OxcCompiler, OxcProgram};
+pub use compiler::{OxcCompiler, OxcProgram, ParseOptions};
 pub use ext::{BindingIdentifierExt, BindingPatternExt, ExpressionExt, StatementExt};
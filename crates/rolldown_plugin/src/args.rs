@@ -1,4 +1,12 @@
-use rolldown_common::{ImportKind, RenderedChunk};
+use rolldown_common::{
+  ImportAttributes, ImportKind, InputOptions, ModuleInfo, Output, OutputOptions, RenderedChunk,
+};
+use rolldown_oxc_utils::OxcProgram;
+
+#[derive(Debug)]
+pub struct HookBuildStartArgs<'a> {
+  pub input_options: &'a InputOptions,
+}
 
 #[derive(Debug)]
 pub struct HookResolveIdArgs<'a> {
@@ -12,6 +20,23 @@ pub struct HookResolveIdArgsOptions {
   pub is_entry: bool,
   // Rollup hasn't this filed, but since Rolldown support cjs as first citizen, so we need to generate `kind` to distinguish it.
   pub kind: ImportKind,
+  /// Attributes of the import, like `type: "json"` of `import data from './data.json' with { type: 'json' }`.
+  pub attributes: ImportAttributes,
+}
+
+/// The source of `import(...)`.
+#[derive(Debug, Clone, Copy)]
+pub enum DynamicImportSpecifier<'a> {
+  String(&'a str),
+  /// The code of a source that isn't a string literal, like `` `./locales/${lang}.js` ``, which plugins could
+  /// parse to decide what it imports.
+  Expression(&'a str),
+}
+
+#[derive(Debug)]
+pub struct HookResolveDynamicImportArgs<'a> {
+  pub importer: &'a str,
+  pub specifier: DynamicImportSpecifier<'a>,
 }
 
 #[derive(Debug)]
@@ -20,14 +45,24 @@ pub struct HookTransformArgs<'a> {
   pub code: &'a String,
 }
 
+#[derive(Debug)]
+pub struct HookShouldTransformCachedModuleArgs<'a> {
+  pub id: &'a str,
+  /// The transformed code of the module cached by the previous build.
+  pub code: &'a str,
+}
+
 #[derive(Debug)]
 pub struct HookLoadArgs<'a> {
   pub id: &'a str,
+  /// Attributes of the first import of the module, which are empty for entries.
+  pub attributes: &'a ImportAttributes,
 }
 
-#[derive(Debug, Default)]
-pub struct HookBuildEndArgs {
-  pub error: String,
+#[derive(Debug)]
+pub struct HookModuleParsedArgs<'a> {
+  pub module_info: &'a ModuleInfo,
+  pub ast: &'a OxcProgram,
 }
 
 #[derive(Debug)]
@@ -35,3 +70,45 @@ pub struct RenderChunkArgs<'a> {
   pub code: String,
   pub chunk: &'a RenderedChunk,
 }
+
+/// An emitted file referenced by `import.meta.ROLLUP_FILE_URL_<referenceId>` in a module.
+#[derive(Debug)]
+pub struct HookResolveFileUrlArgs<'a> {
+  pub reference_id: &'a str,
+  pub file_name: &'a str,
+  /// The file name of the chunk containing the module, which could still contain hash placeholders.
+  pub chunk_id: &'a str,
+  /// `es`, `cjs`, `iife`, `umd` or `system`.
+  pub format: &'static str,
+  pub module_id: &'a str,
+  /// The file name of the emitted file relative to the chunk.
+  pub relative_path: &'a str,
+}
+
+#[derive(Debug)]
+pub struct HookGenerateBundleArgs<'a> {
+  pub output_options: &'a OutputOptions,
+  /// Plugins could remove files from the output by removing them from the bundle.
+  pub bundle: &'a mut Vec<Output>,
+  pub is_write: bool,
+}
+
+#[derive(Debug)]
+pub struct HookWriteBundleArgs<'a> {
+  pub output_options: &'a OutputOptions,
+  pub bundle: &'a Vec<Output>,
+}
+
+/// How a watched file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchChangeKind {
+  Create,
+  Update,
+  Delete,
+}
+
+#[derive(Debug)]
+pub struct HookWatchChangeArgs<'a> {
+  pub id: &'a str,
+  pub event: WatchChangeKind,
+}
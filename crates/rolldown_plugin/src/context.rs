@@ -1,19 +1,353 @@
+use std::{fmt::Debug, sync::Arc};
+
+use oxc::span::SourceType;
+use rolldown_common::{ImportKind, LogLevel, ModuleInfo, SharedModuleInfoTable};
+use rolldown_error::{BuildError, LogPosition};
+use rolldown_oxc_utils::{OxcCompiler, OxcProgram, ParseOptions};
+use rolldown_sourcemap::SourceMap;
+
+use crate::{
+  file_emitter::{EmittedChunk, EmittedFile, SharedFileEmitter},
+  plugin_logs::SharedPluginLogs,
+  transform_dependencies::SharedTransformDependencies,
+  transform_sourcemaps::SharedTransformSourcemaps,
+  watch_files::SharedWatchFiles,
+};
+
+#[derive(Debug, Clone)]
+pub struct PluginContextResolveOptions {
+  /// Skip the `resolve_id` hook of the calling plugin for this specifier and importer.
+  pub skip_self: bool,
+  pub kind: ImportKind,
+}
+
+impl Default for PluginContextResolveOptions {
+  fn default() -> Self {
+    Self { skip_self: false, kind: ImportKind::Import }
+  }
+}
+
+/// Whether [`PluginContext::parse`] parses the code as a script or an ES module.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseSourceType {
+  Script,
+  #[default]
+  Module,
+}
+
+/// Options of [`PluginContext::parse`], like the ones of acorn passed to `this.parse` of rollup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PluginContextParseOptions {
+  pub allow_return_outside_function: bool,
+  pub source_type: ParseSourceType,
+  /// Allow `import` and `export` declarations in scripts and outside of the top level.
+  pub allow_import_export_everywhere: bool,
+}
+
+/// The result of [`PluginContext::resolve`], like the one returned by `this.resolve` of rollup.
+#[derive(Debug, Clone)]
+pub struct ResolvedId {
+  pub id: String,
+  pub external: bool,
+  /// Whether the module has side effects, `None` means it's left to the bundler to decide.
+  pub module_side_effects: Option<bool>,
+}
+
+/// A `resolve_id` hook skipped by `this.resolve` with `skip_self`. Skipped hooks are inherited by `resolve_id` hooks
+/// called within the same `this.resolve`, so plugins calling each other's `resolve_id` don't recurse infinitely.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedResolveCall {
+  pub plugin_index: usize,
+  pub specifier: String,
+  pub importer: Option<String>,
+}
+
+/// Runs the full `resolve_id` pipeline of the bundler, that is `resolve_id` hooks of plugins and the builtin resolver.
+#[async_trait::async_trait]
+pub trait PluginContextResolver: Debug + Send + Sync {
+  async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&str>,
+    kind: ImportKind,
+    skipped_resolve_calls: Vec<SkippedResolveCall>,
+  ) -> Result<ResolvedId, BuildError>;
+}
+
+pub type SharedPluginContextResolver = Arc<dyn PluginContextResolver>;
+
 /// [`PluginContext`] itself will carry some general data for all hooks and a `context` field for
 /// specific data for different hooks.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone)]
 pub struct PluginContext<Ctx = ()> {
   /// The field is used to pass specific context for different hooks.
   pub context: Ctx,
+  file_emitter: SharedFileEmitter,
+  module_infos: SharedModuleInfoTable,
+  watch_files: SharedWatchFiles,
+  resolver: SharedPluginContextResolver,
+  logs: SharedPluginLogs,
+  /// The index of the plugin the context is created for.
+  plugin_index: usize,
+  /// The name of the plugin, which prefixes the messages of its logs.
+  plugin_name: String,
+  skipped_resolve_calls: Vec<SkippedResolveCall>,
+  /// Only set for contexts of `transform` hooks.
+  transform_dependencies: Option<SharedTransformDependencies>,
+  /// Only set for contexts of `transform` hooks.
+  transform_sourcemaps: Option<SharedTransformSourcemaps>,
 }
 
 impl PluginContext {
-  pub fn new() -> Self {
-    Self::with_context(())
+  #[allow(clippy::too_many_arguments)]
+  pub fn new(
+    file_emitter: SharedFileEmitter,
+    module_infos: SharedModuleInfoTable,
+    watch_files: SharedWatchFiles,
+    resolver: SharedPluginContextResolver,
+    logs: SharedPluginLogs,
+    plugin_index: usize,
+    plugin_name: String,
+    skipped_resolve_calls: Vec<SkippedResolveCall>,
+  ) -> Self {
+    Self {
+      context: (),
+      file_emitter,
+      module_infos,
+      watch_files,
+      resolver,
+      logs,
+      plugin_index,
+      plugin_name,
+      skipped_resolve_calls,
+      transform_dependencies: None,
+      transform_sourcemaps: None,
+    }
+  }
+
+  /// Record watch files added and files emitted with this context into `transform_dependencies`.
+  #[must_use]
+  pub fn with_transform_dependencies(
+    mut self,
+    transform_dependencies: SharedTransformDependencies,
+  ) -> Self {
+    self.transform_dependencies = Some(transform_dependencies);
+    self
+  }
+
+  /// Compose maps returned before the hook called with this context from `transform_sourcemaps`.
+  #[must_use]
+  pub fn with_transform_sourcemaps(
+    mut self,
+    transform_sourcemaps: SharedTransformSourcemaps,
+  ) -> Self {
+    self.transform_sourcemaps = Some(transform_sourcemaps);
+    self
   }
 }
 
 impl<T> PluginContext<T> {
-  pub fn with_context(context: T) -> Self {
-    Self { context }
+  pub fn with_context<U>(self, context: U) -> PluginContext<U> {
+    PluginContext {
+      context,
+      file_emitter: self.file_emitter,
+      module_infos: self.module_infos,
+      watch_files: self.watch_files,
+      resolver: self.resolver,
+      logs: self.logs,
+      plugin_index: self.plugin_index,
+      plugin_name: self.plugin_name,
+      skipped_resolve_calls: self.skipped_resolve_calls,
+      transform_dependencies: self.transform_dependencies,
+      transform_sourcemaps: self.transform_sourcemaps,
+    }
+  }
+
+  /// Emit an asset or an extra entry chunk to be included in the output, like `this.emitFile` of rollup. Returns
+  /// the reference id of the file, which could be passed to `get_file_name`.
+  pub fn emit_file(&self, file: impl Into<EmittedFile>) -> String {
+    if let Some(transform_dependencies) = &self.transform_dependencies {
+      transform_dependencies.mark_emits_files();
+    }
+    match file.into() {
+      EmittedFile::Asset(asset) => self.file_emitter.emit_file(asset),
+      EmittedFile::Chunk(chunk) => self.file_emitter.emit_chunk(chunk),
+    }
+  }
+
+  /// Emit an extra entry chunk, like `this.emitFile({ type: "chunk" })` of rollup. Returns the reference id of the
+  /// chunk.
+  pub fn emit_chunk(&self, chunk: EmittedChunk) -> String {
+    self.emit_file(chunk)
+  }
+
+  /// Get the file name of an emitted file by its reference id, like `this.getFileName` of rollup.
+  pub fn get_file_name(&self, reference_id: &str) -> Result<String, BuildError> {
+    self.file_emitter.get_file_name(reference_id)
+  }
+
+  /// Get the info of a loaded module by its id, like `this.getModuleInfo` of rollup. Returns `None` for modules
+  /// that are not loaded yet.
+  pub fn get_module_info(&self, id: &str) -> Option<ModuleInfo> {
+    self.module_infos.get(id)
+  }
+
+  /// Ids of all modules loaded so far, including external ones, like `this.getModuleIds` of rollup. Hooks of the
+  /// scan, like `resolve_id` and `load`, only see part of the graph, while hooks after it see every module.
+  pub fn get_module_ids(&self) -> impl Iterator<Item = String> {
+    self.module_infos.ids().into_iter()
+  }
+
+  /// Watch an extra file in watch mode, like `this.addWatchFile` of rollup. Changes of the file trigger a rebuild.
+  pub fn add_watch_file(&self, id: &str) {
+    if let Some(transform_dependencies) = &self.transform_dependencies {
+      transform_dependencies.add_watch_file(id);
+    }
+    self.watch_files.add(id);
+  }
+
+  /// Files added by `add_watch_file` in the current build, like `this.getWatchFiles` of rollup.
+  pub fn get_watch_files(&self) -> Vec<String> {
+    self.watch_files.ids()
+  }
+
+  /// The map from the code passed to the current `transform` hook to the original sources, composed of the maps
+  /// returned by `load` and the previous `transform` hooks, like `this.getCombinedSourcemap` of rollup. The code is
+  /// mapped to itself if no hook returns a map. Only available in `transform` hooks.
+  pub fn get_combined_sourcemap(&self) -> Result<SourceMap, BuildError> {
+    self.transform_sourcemaps.as_ref().map_or_else(
+      || {
+        Err(BuildError::sourcemap_error(
+          "`get_combined_sourcemap` is only available in `transform` hooks".to_string(),
+        ))
+      },
+      |transform_sourcemaps| transform_sourcemaps.combined(),
+    )
+  }
+
+  /// Parse `code` into an ast with the same parser as the bundler, like `this.parse` of rollup. The first syntax
+  /// error is returned if there is any.
+  pub fn parse(
+    &self,
+    code: &str,
+    options: PluginContextParseOptions,
+  ) -> Result<OxcProgram, BuildError> {
+    let source: Arc<str> = code.into();
+    let source_type = SourceType::default()
+      .with_script(options.source_type == ParseSourceType::Script)
+      .with_module(options.source_type == ParseSourceType::Module);
+    let (program, errors) = OxcCompiler::parse_with_options(
+      Arc::clone(&source),
+      ParseOptions {
+        source_type,
+        allow_return_outside_function: options.allow_return_outside_function,
+        allow_import_export_everywhere: options.allow_import_export_everywhere,
+      },
+    );
+    match errors.into_iter().next() {
+      Some((reason, span)) => {
+        Err(BuildError::parse_error(self.plugin_name.clone(), source, span, reason))
+      }
+      None => Ok(program),
+    }
+  }
+
+  /// Resolve `specifier` imported by `importer` the same way as the bundler, like `this.resolve` of rollup.
+  pub async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&str>,
+    options: PluginContextResolveOptions,
+  ) -> Result<ResolvedId, BuildError> {
+    let mut skipped_resolve_calls = self.skipped_resolve_calls.clone();
+    if options.skip_self {
+      skipped_resolve_calls.push(SkippedResolveCall {
+        plugin_index: self.plugin_index,
+        specifier: specifier.to_string(),
+        importer: importer.map(ToString::to_string),
+      });
+    }
+    self.resolver.resolve(specifier, importer, options.kind, skipped_resolve_calls).await
+  }
+
+  /// Emit a warning, like `this.warn` of rollup. Warnings don't stop the build unless `on_log` turns them into
+  /// errors. `position` points at the code of a module the warning is about.
+  pub fn warn(&self, message: impl Into<String>, position: Option<LogPosition>) {
+    self.logs.add(LogLevel::Warn, BuildError::plugin_warning(&self.plugin_name, message, position));
+  }
+
+  /// Emit an info log, like `this.info` of rollup. It's dropped if `log_level` is `warn` or `silent`.
+  pub fn info(&self, message: impl Into<String>, position: Option<LogPosition>) {
+    self.logs.add(LogLevel::Info, BuildError::plugin_log(&self.plugin_name, message, position));
+  }
+
+  /// Emit a debug log, like `this.debug` of rollup. It's dropped unless `log_level` is `debug`.
+  pub fn debug(&self, message: impl Into<String>, position: Option<LogPosition>) {
+    self.logs.add(LogLevel::Debug, BuildError::plugin_log(&self.plugin_name, message, position));
+  }
+
+  pub fn file_emitter(&self) -> &SharedFileEmitter {
+    &self.file_emitter
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use rolldown_common::ImportKind;
+  use rolldown_error::BuildError;
+
+  use super::{
+    ParseSourceType, PluginContext, PluginContextParseOptions, PluginContextResolver, ResolvedId,
+    SkippedResolveCall,
+  };
+
+  #[derive(Debug)]
+  struct UnreachableResolver;
+
+  #[async_trait::async_trait]
+  impl PluginContextResolver for UnreachableResolver {
+    async fn resolve(
+      &self,
+      _specifier: &str,
+      _importer: Option<&str>,
+      _kind: ImportKind,
+      _skipped_resolve_calls: Vec<SkippedResolveCall>,
+    ) -> Result<ResolvedId, BuildError> {
+      unreachable!("parsing doesn't resolve anything")
+    }
+  }
+
+  fn context() -> PluginContext {
+    PluginContext::new(
+      Arc::default(),
+      Arc::default(),
+      Arc::default(),
+      Arc::new(UnreachableResolver),
+      Arc::default(),
+      0,
+      "parse".to_string(),
+      vec![],
+    )
+  }
+
+  #[test]
+  fn plugins_parse_code_with_the_parser_of_the_bundler() {
+    let ctx = context();
+    let program = ctx
+      .parse("import { a } from './a'\nconsole.log(a)", PluginContextParseOptions::default())
+      .unwrap();
+    assert_eq!(program.program().body.len(), 2);
+
+    let codes = [true, false].map(|allow_return_outside_function| {
+      let options = PluginContextParseOptions {
+        allow_return_outside_function,
+        source_type: ParseSourceType::Script,
+        allow_import_export_everywhere: false,
+      };
+      ctx.parse("return 1", options).err().map_or("", |err| err.code())
+    });
+    assert_eq!(codes, ["", "PARSE_ERROR"]);
   }
 }
@@ -0,0 +1,296 @@
+use std::{
+  path::Path,
+  sync::{Arc, Mutex, MutexGuard},
+};
+
+use rolldown_common::{
+  AssetSource, FileNameRenderOptions, FileNameTemplate, NormalModuleId, OutputAsset,
+};
+use rolldown_error::BuildError;
+use rolldown_utils::hash::{HashAlgorithm, CONTENT_HASH_LENGTH};
+use rustc_hash::FxHashSet;
+
+#[derive(Debug, Clone)]
+pub struct EmittedAsset {
+  /// Used to render `output.assetFileNames` if `file_name` is not given.
+  pub name: Option<String>,
+  /// The exact file name of the asset, relative to the output directory.
+  pub file_name: Option<String>,
+  pub source: AssetSource,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmittedChunk {
+  /// The name of the chunk, used to render `output.entryFileNames`.
+  pub name: Option<String>,
+  /// The id of the entry module, which is resolved like entries in `input`.
+  pub id: String,
+}
+
+/// A file emitted by plugins, like the `type` of files passed to `this.emitFile` of rollup.
+#[derive(Debug, Clone)]
+pub enum EmittedFile {
+  Asset(EmittedAsset),
+  Chunk(EmittedChunk),
+}
+
+impl From<EmittedAsset> for EmittedFile {
+  fn from(asset: EmittedAsset) -> Self {
+    Self::Asset(asset)
+  }
+}
+
+impl From<EmittedChunk> for EmittedFile {
+  fn from(chunk: EmittedChunk) -> Self {
+    Self::Chunk(chunk)
+  }
+}
+
+#[derive(Debug)]
+enum EmittedFileKind {
+  Asset(EmittedAsset),
+  Chunk {
+    chunk: EmittedChunk,
+    /// The entry module of the chunk, which is known once the module loader has resolved `chunk.id`.
+    module_id: Option<NormalModuleId>,
+  },
+}
+
+#[derive(Debug)]
+struct EmittedFileEntry {
+  kind: EmittedFileKind,
+  /// The final file name, which is only known once output options are available.
+  file_name: Option<String>,
+  /// Emitted chunks are consumed by the module loader, and emitted assets by the bundle.
+  is_consumed: bool,
+}
+
+#[derive(Debug, Default)]
+struct FileEmitterState {
+  /// Indexed by reference ids.
+  files: Vec<EmittedFileEntry>,
+  asset_file_names: Option<FileNameTemplate>,
+}
+
+/// Collects files emitted by plugins via `this.emitFile`, so they could be added to the final bundle.
+///
+/// Assets emitted before output options are known, like in `buildStart` or `transform`, get their file names
+/// when the output is generated. Emitted chunks get their file names once chunks are generated. File names
+/// requested before that are placeholders, which are replaced with the final file names in rendered chunks.
+#[derive(Debug, Default)]
+pub struct FileEmitter {
+  state: Mutex<FileEmitterState>,
+}
+
+impl FileEmitter {
+  fn state(&self) -> MutexGuard<'_, FileEmitterState> {
+    self.state.lock().expect("FileEmitter lock should not be poisoned")
+  }
+
+  /// Emit a file and return the reference id of it.
+  pub fn emit_file(&self, asset: EmittedAsset) -> String {
+    let mut state = self.state();
+    let file_name = asset.file_name.clone().or_else(|| {
+      state.asset_file_names.as_ref().map(|template| state.asset_file_name(template, &asset))
+    });
+    state.files.push(EmittedFileEntry {
+      kind: EmittedFileKind::Asset(asset),
+      file_name,
+      is_consumed: false,
+    });
+    (state.files.len() - 1).to_string()
+  }
+
+  /// Emit an extra entry chunk and return the reference id of it. Emitting the same id again returns the same
+  /// reference id.
+  pub fn emit_chunk(&self, chunk: EmittedChunk) -> String {
+    let mut state = self.state();
+    let emitted = state.files.iter().position(|file| {
+      matches!(&file.kind, EmittedFileKind::Chunk { chunk: emitted, .. } if emitted.id == chunk.id)
+    });
+    let index = emitted.unwrap_or_else(|| {
+      state.files.push(EmittedFileEntry {
+        kind: EmittedFileKind::Chunk { chunk, module_id: None },
+        file_name: None,
+        is_consumed: false,
+      });
+      state.files.len() - 1
+    });
+    index.to_string()
+  }
+
+  /// Returns the file name of an emitted file, or a placeholder if the file name isn't assigned yet.
+  pub fn get_file_name(&self, reference_id: &str) -> Result<String, BuildError> {
+    let state = self.state();
+    let file = reference_id
+      .parse::<usize>()
+      .ok()
+      .and_then(|index| state.files.get(index))
+      .ok_or_else(|| BuildError::file_not_found(reference_id))?;
+    Ok(file.file_name.clone().unwrap_or_else(|| file_name_placeholder(reference_id)))
+  }
+
+  /// Assign file names to emitted assets with `output.assetFileNames`. Assets emitted later get their file
+  /// names immediately.
+  pub fn assign_file_names(&self, asset_file_names: &FileNameTemplate) {
+    let mut state = self.state();
+    // Assets are named in the order they are emitted, so earlier ones keep the file names without numbers.
+    for index in 0..state.files.len() {
+      if let (EmittedFileKind::Asset(asset), None) =
+        (&state.files[index].kind, &state.files[index].file_name)
+      {
+        let file_name = state.asset_file_name(asset_file_names, asset);
+        state.files[index].file_name = Some(file_name);
+      }
+    }
+    state.asset_file_names = Some(asset_file_names.clone());
+  }
+
+  /// Emitted chunks that are not passed to the module loader yet, with their reference ids.
+  pub fn take_chunks_for_module_loader(&self) -> Vec<(String, EmittedChunk)> {
+    let mut state = self.state();
+    state
+      .files
+      .iter_mut()
+      .enumerate()
+      .filter(|(_, file)| !file.is_consumed)
+      .filter_map(|(index, file)| match &file.kind {
+        EmittedFileKind::Chunk { chunk, .. } => {
+          file.is_consumed = true;
+          Some((index.to_string(), chunk.clone()))
+        }
+        EmittedFileKind::Asset(_) => None,
+      })
+      .collect()
+  }
+
+  /// Record the entry module that the id of an emitted chunk resolves to.
+  pub fn set_chunk_module_id(&self, reference_id: &str, id: NormalModuleId) {
+    let mut state = self.state();
+    if let Some(EmittedFileEntry { kind: EmittedFileKind::Chunk { module_id, .. }, .. }) =
+      reference_id.parse::<usize>().ok().and_then(|index| state.files.get_mut(index))
+    {
+      *module_id = Some(id);
+    }
+  }
+
+  /// Assign file names to emitted chunks, given the file names of the chunks of entry modules.
+  pub fn assign_chunk_file_names(
+    &self,
+    chunk_file_name: impl Fn(NormalModuleId) -> Option<String>,
+  ) {
+    let mut state = self.state();
+    state.files.iter_mut().for_each(|file| {
+      if let EmittedFileKind::Chunk { module_id: Some(module_id), .. } = &file.kind {
+        file.file_name = chunk_file_name(*module_id);
+      }
+    });
+  }
+
+  /// Replace placeholders returned by `get_file_name` with the final file names.
+  pub fn replace_file_name_placeholders(&self, code: &str) -> String {
+    if !code.contains(PLACEHOLDER_PREFIX) {
+      return code.to_string();
+    }
+    let state = self.state();
+    state.files.iter().enumerate().fold(code.to_string(), |code, (index, file)| {
+      match &file.file_name {
+        Some(file_name) => code.replace(&file_name_placeholder(&index.to_string()), file_name),
+        None => code,
+      }
+    })
+  }
+
+  /// Emitted assets that are not added to the bundle yet. Assets sharing the same file name are added once.
+  pub fn take_assets_for_bundle(&self) -> Vec<OutputAsset> {
+    let mut state = self.state();
+    let mut bundled_file_names = state
+      .files
+      .iter()
+      .filter(|file| file.is_consumed && matches!(file.kind, EmittedFileKind::Asset(_)))
+      .filter_map(|file| file.file_name.clone())
+      .collect::<FxHashSet<_>>();
+    state
+      .files
+      .iter_mut()
+      .filter(|file| !file.is_consumed)
+      .filter_map(|file| match &file.kind {
+        EmittedFileKind::Asset(asset) => {
+          file.is_consumed = true;
+          let file_name =
+            file.file_name.clone().expect("File names should be assigned before bundling");
+          if !bundled_file_names.insert(file_name.clone()) {
+            return None;
+          }
+          Some(OutputAsset { file_name, source: asset.source.clone() })
+        }
+        EmittedFileKind::Chunk { .. } => None,
+      })
+      .collect()
+  }
+
+  /// Forget all emitted files, since every build emits its own files.
+  pub fn clear(&self) {
+    *self.state() = FileEmitterState::default();
+  }
+}
+
+impl FileEmitterState {
+  /// The file name of an asset without an explicit one. Assets with the same source share the same file name, while
+  /// different assets whose file names collide get numbers in their file names, like `logo2.svg`.
+  fn asset_file_name(&self, template: &FileNameTemplate, asset: &EmittedAsset) -> String {
+    let same_asset = self.files.iter().find_map(|file| match (&file.kind, &file.file_name) {
+      (EmittedFileKind::Asset(emitted), Some(file_name))
+        if emitted.file_name.is_none() && emitted.source.as_bytes() == asset.source.as_bytes() =>
+      {
+        Some(file_name.clone())
+      }
+      _ => None,
+    });
+    if let Some(file_name) = same_asset {
+      return file_name;
+    }
+    let file_name = render_asset_file_name(template, asset);
+    let is_used =
+      |file_name: &str| self.files.iter().any(|file| file.file_name.as_deref() == Some(file_name));
+    if !is_used(&file_name) {
+      return file_name;
+    }
+    let path = Path::new(&file_name);
+    let extname =
+      path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+    let stem = &file_name[..file_name.len() - extname.len()];
+    // Only `files.len()` file names are used, so one of these is unused
+    (2..=self.files.len() + 1)
+      .map(|index| format!("{stem}{index}{extname}"))
+      .find(|file_name| !is_used(file_name))
+      .expect("There should be an unused file name")
+  }
+}
+
+const PLACEHOLDER_PREFIX: &str = "!~{";
+
+fn file_name_placeholder(reference_id: &str) -> String {
+  format!("{PLACEHOLDER_PREFIX}{reference_id}}}~")
+}
+
+fn render_asset_file_name(template: &FileNameTemplate, asset: &EmittedAsset) -> String {
+  let name = asset.name.as_deref().unwrap_or("asset");
+  let extname = Path::new(name)
+    .extension()
+    .map(|ext| format!(".{}", ext.to_string_lossy()))
+    .unwrap_or_default();
+  let hash = HashAlgorithm::default().hash_hex(asset.source.as_bytes());
+  // `[hash]` renders the same hash as `content_hash`
+  let render_hash = |length: Option<usize>| {
+    hash[..length.unwrap_or(CONTENT_HASH_LENGTH).min(hash.len())].to_string()
+  };
+  template.render(&FileNameRenderOptions {
+    name: Some(&name[..name.len() - extname.len()]),
+    hash: Some(&render_hash),
+    extname: Some(&extname),
+    ..Default::default()
+  })
+}
+
+pub type SharedFileEmitter = Arc<FileEmitter>;
@@ -1,17 +1,35 @@
 mod args;
 mod context;
+mod file_emitter;
 mod output;
 mod plugin;
+mod plugin_logs;
+mod transform_dependencies;
+mod transform_sourcemaps;
+mod watch_files;
+
+pub use rolldown_common::{ModuleInfoTable, SharedModuleInfoTable};
 
 pub use crate::{
   args::{
-    HookBuildEndArgs, HookLoadArgs, HookResolveIdArgs, HookResolveIdArgsOptions, HookTransformArgs,
-    RenderChunkArgs,
+    DynamicImportSpecifier, HookBuildStartArgs, HookGenerateBundleArgs, HookLoadArgs,
+    HookModuleParsedArgs, HookResolveDynamicImportArgs, HookResolveFileUrlArgs, HookResolveIdArgs,
+    HookResolveIdArgsOptions, HookShouldTransformCachedModuleArgs, HookTransformArgs,
+    HookWatchChangeArgs, HookWriteBundleArgs, RenderChunkArgs, WatchChangeKind,
+  },
+  context::{
+    ParseSourceType, PluginContext, PluginContextParseOptions, PluginContextResolveOptions,
+    PluginContextResolver, ResolvedId, SharedPluginContextResolver, SkippedResolveCall,
   },
-  context::PluginContext,
-  output::{HookLoadOutput, HookRenderChunkOutput, HookResolveIdOutput},
+  file_emitter::{EmittedAsset, EmittedChunk, EmittedFile, FileEmitter, SharedFileEmitter},
+  output::{HookLoadOutput, HookRenderChunkOutput, HookResolveIdOutput, HookSideEffects},
   plugin::{
-    BoxPlugin, HookLoadReturn, HookNoopReturn, HookRenderChunkReturn, HookResolveIdReturn,
+    BoxPlugin, HookAugmentChunkHashReturn, HookLoadReturn, HookNoopReturn, HookRenderChunkReturn,
+    HookResolveFileUrlReturn, HookResolveIdReturn, HookShouldTransformCachedModuleReturn,
     HookTransformReturn, Plugin,
   },
+  plugin_logs::{PluginLogs, SharedPluginLogs},
+  transform_dependencies::{SharedTransformDependencies, TransformDependencies},
+  transform_sourcemaps::{SharedTransformSourcemaps, TransformSourcemaps},
+  watch_files::{SharedWatchFiles, WatchFiles},
 };
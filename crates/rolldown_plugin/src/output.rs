@@ -1,17 +1,42 @@
+use rolldown_common::{Loader, ModuleType};
 use rolldown_sourcemap::SourceMap;
+use rustc_hash::FxHashMap;
+
 #[derive(Debug)]
 pub struct HookResolveIdOutput {
   pub id: String,
   pub external: Option<bool>,
+  /// Whether the module has side effects, `None` means it's left to the bundler to decide.
+  pub module_side_effects: Option<bool>,
+  /// Overrides the module type decided by the extension of the id, like loading the file as text.
+  pub module_type: Option<ModuleType>,
+}
+
+/// `moduleSideEffects` returned by `load` hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookSideEffects {
+  Bool(bool),
+  /// The module has side effects, and none of its statements are tree shaken, even if they're unused.
+  NoTreeshake,
 }
 
 #[derive(Debug)]
 pub struct HookLoadOutput {
   pub code: String,
   pub map: Option<SourceMap>,
+  /// Overrides side effects of the module decided while resolving it.
+  pub module_side_effects: Option<HookSideEffects>,
+  /// The export, usually `default`, whose properties are importable as named exports that the module doesn't
+  /// declare, like modules converted from CommonJS. Only used by `load` hooks.
+  pub synthetic_named_exports: Option<String>,
+  /// Custom data of plugins, exposed by `ModuleInfo::meta`. Only used by `load` hooks.
+  pub meta: Option<FxHashMap<String, serde_json::Value>>,
+  /// Overrides the loader decided by the extension of the module, like parsing a virtual module as TypeScript.
+  pub loader: Option<Loader>,
 }
 
 #[derive(Debug)]
 pub struct HookRenderChunkOutput {
   pub code: String,
+  pub map: Option<SourceMap>,
 }
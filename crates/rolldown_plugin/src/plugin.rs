@@ -1,19 +1,27 @@
 use std::{borrow::Cow, fmt::Debug};
 
-use rolldown_common::Output;
+use rolldown_common::RenderedChunk;
 use rolldown_error::BuildError;
 
 use super::{
-  args::{HookBuildEndArgs, HookLoadArgs, HookResolveIdArgs, HookTransformArgs, RenderChunkArgs},
+  args::{
+    HookBuildStartArgs, HookGenerateBundleArgs, HookLoadArgs, HookModuleParsedArgs,
+    HookResolveDynamicImportArgs, HookResolveFileUrlArgs, HookResolveIdArgs,
+    HookShouldTransformCachedModuleArgs, HookTransformArgs, HookWatchChangeArgs,
+    HookWriteBundleArgs, RenderChunkArgs,
+  },
   context::PluginContext,
   output::{HookLoadOutput, HookRenderChunkOutput, HookResolveIdOutput},
 };
 
 pub type HookResolveIdReturn = Result<Option<HookResolveIdOutput>, BuildError>;
 pub type HookTransformReturn = Result<Option<HookLoadOutput>, BuildError>;
+pub type HookShouldTransformCachedModuleReturn = Result<Option<bool>, BuildError>;
 pub type HookLoadReturn = Result<Option<HookLoadOutput>, BuildError>;
 pub type HookNoopReturn = Result<(), BuildError>;
 pub type HookRenderChunkReturn = Result<Option<HookRenderChunkOutput>, BuildError>;
+pub type HookAugmentChunkHashReturn = Result<Option<String>, BuildError>;
+pub type HookResolveFileUrlReturn = Result<Option<String>, BuildError>;
 
 #[async_trait::async_trait]
 pub trait Plugin: Debug + Send + Sync {
@@ -21,7 +29,11 @@ pub trait Plugin: Debug + Send + Sync {
 
   // The `option` hook consider call at node side.
 
-  async fn build_start(&self, _ctx: &mut PluginContext) -> HookNoopReturn {
+  async fn build_start(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &HookBuildStartArgs,
+  ) -> HookNoopReturn {
     Ok(())
   }
 
@@ -33,6 +45,17 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(None)
   }
 
+  /// Called for each dynamic import before `resolve_id`. String specifiers not resolved by any plugin are passed
+  /// to `resolve_id` as usual, while expressions not resolved by any plugin are left as they are. Following
+  /// plugins are skipped once a plugin returns `Some`.
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &HookResolveDynamicImportArgs,
+  ) -> HookResolveIdReturn {
+    Ok(None)
+  }
+
   async fn load(&self, _ctx: &mut PluginContext, _args: &HookLoadArgs) -> HookLoadReturn {
     Ok(None)
   }
@@ -45,14 +68,39 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(None)
   }
 
+  /// Called for each module whose code is the same as the previous build of the bundler, before reusing the
+  /// cached result of `transform` hooks. Returning `Some(true)` transforms the module again, which is needed if
+  /// the result of `transform` depends on something other than the code and watch files of the module.
+  /// Following plugins are skipped once a plugin returns `Some`.
+  async fn should_transform_cached_module(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookShouldTransformCachedModuleArgs,
+  ) -> HookShouldTransformCachedModuleReturn {
+    Ok(None)
+  }
+
+  // Parallel hook
+  /// Called for each module as soon as it's parsed and its dependencies are resolved.
+  async fn module_parsed(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookModuleParsedArgs,
+  ) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Called after the scan and link stages, with the error if the build failed.
   async fn build_end(
     &self,
     _ctx: &mut PluginContext,
-    _args: Option<&HookBuildEndArgs>,
+    _error: Option<&BuildError>,
   ) -> HookNoopReturn {
     Ok(())
   }
 
+  /// Called for each chunk after its code is generated. Returning `None` keeps the code unchanged, otherwise
+  /// the returned code is passed to the following plugins, and the returned map is added to the chunk's map.
   async fn render_chunk(
     &self,
     _ctx: &PluginContext,
@@ -61,19 +109,55 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(None)
   }
 
-  #[allow(clippy::ptr_arg)]
+  /// Called for each chunk before its hash is computed. The returned string is added to the hash of the chunk,
+  /// which changes its `[hash]` without changing its code, like for state of plugins that the code depends on.
+  async fn augment_chunk_hash(
+    &self,
+    _ctx: &PluginContext,
+    _chunk: &RenderedChunk,
+  ) -> HookAugmentChunkHashReturn {
+    Ok(None)
+  }
+
+  /// Called for each `import.meta.ROLLUP_FILE_URL_<referenceId>` of modules. The returned code replaces the
+  /// expression, like an absolute URL of a CDN, instead of resolving the relative path against `import.meta.url`.
+  /// Following plugins are skipped once a plugin returns `Some`.
+  async fn resolve_file_url(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookResolveFileUrlArgs,
+  ) -> HookResolveFileUrlReturn {
+    Ok(None)
+  }
+
   async fn generate_bundle(
     &self,
     _ctx: &PluginContext,
-    _bundle: &Vec<Output>,
-    _is_write: bool,
+    _args: &mut HookGenerateBundleArgs,
+  ) -> HookNoopReturn {
+    Ok(())
+  }
+
+  // Parallel hook
+  async fn write_bundle(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookWriteBundleArgs,
   ) -> HookNoopReturn {
     Ok(())
   }
 
   // Parallel hook
-  #[allow(clippy::ptr_arg)]
-  async fn write_bundle(&self, _ctx: &PluginContext, _bundle: &Vec<Output>) -> HookNoopReturn {
+  async fn close_bundle(&self, _ctx: &PluginContext) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Called in watch mode for each changed file, before the rebuild triggered by the changes.
+  async fn watch_change(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookWatchChangeArgs,
+  ) -> HookNoopReturn {
     Ok(())
   }
 }
@@ -0,0 +1,28 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rolldown_common::LogLevel;
+use rolldown_error::BuildError;
+
+/// Logs emitted by plugins with `this.warn`, `this.info` and `this.debug`, which are filtered by `log_level` and
+/// passed to `on_log` along with the warnings of the bundler once the output is generated.
+#[derive(Debug, Default)]
+pub struct PluginLogs {
+  /// In the order they are emitted.
+  logs: Mutex<Vec<(LogLevel, BuildError)>>,
+}
+
+impl PluginLogs {
+  fn logs(&self) -> MutexGuard<'_, Vec<(LogLevel, BuildError)>> {
+    self.logs.lock().expect("PluginLogs lock should not be poisoned")
+  }
+
+  pub fn add(&self, level: LogLevel, log: BuildError) {
+    self.logs().push((level, log));
+  }
+
+  pub fn take(&self) -> Vec<(LogLevel, BuildError)> {
+    std::mem::take(&mut *self.logs())
+  }
+}
+
+pub type SharedPluginLogs = Arc<PluginLogs>;
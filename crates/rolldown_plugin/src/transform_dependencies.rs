@@ -0,0 +1,36 @@
+use std::sync::{
+  atomic::{AtomicBool, Ordering},
+  Arc,
+};
+
+use crate::watch_files::WatchFiles;
+
+/// What the `transform` hooks of a module depend on besides its code, like `transformDependencies` of the rollup
+/// cache. The bundler only reuses the transformed code of a module in later builds if these are unchanged.
+#[derive(Debug, Default)]
+pub struct TransformDependencies {
+  /// Files added by `this.addWatchFile` while transforming the module.
+  watch_files: WatchFiles,
+  /// Emitted files are part of a single build, so modules that emit files are always transformed again.
+  emits_files: AtomicBool,
+}
+
+impl TransformDependencies {
+  pub fn watch_files(&self) -> Vec<String> {
+    self.watch_files.ids()
+  }
+
+  pub fn emits_files(&self) -> bool {
+    self.emits_files.load(Ordering::Relaxed)
+  }
+
+  pub(crate) fn add_watch_file(&self, id: &str) {
+    self.watch_files.add(id);
+  }
+
+  pub(crate) fn mark_emits_files(&self) {
+    self.emits_files.store(true, Ordering::Relaxed);
+  }
+}
+
+pub type SharedTransformDependencies = Arc<TransformDependencies>;
@@ -0,0 +1,47 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_error::BuildError;
+use rolldown_sourcemap::{SourceMap, SourceMapChain};
+
+/// The maps of a module returned by `load` and the `transform` hooks called so far, which are composed by
+/// `this.getCombinedSourcemap`.
+#[derive(Debug)]
+pub struct TransformSourcemaps {
+  id: String,
+  /// The code before `transform` hooks, which is mapped to itself if no hook returns a map.
+  original_code: String,
+  chain: Mutex<SourceMapChain>,
+  /// The number of maps returned by `load` hooks at the start of the chain.
+  loaded_len: usize,
+}
+
+impl TransformSourcemaps {
+  pub fn new(id: &str, original_code: &str, loaded_chain: SourceMapChain) -> Self {
+    let loaded_len = loaded_chain.iter().count();
+    Self {
+      id: id.to_string(),
+      original_code: original_code.to_string(),
+      chain: Mutex::new(loaded_chain),
+      loaded_len,
+    }
+  }
+
+  pub fn push(&self, map: SourceMap) {
+    self.chain.lock().expect("Failed to lock the sourcemap chain").push(map);
+  }
+
+  /// The map from the current code to the original sources.
+  pub fn combined(&self) -> Result<SourceMap, BuildError> {
+    let chain = self.chain.lock().expect("Failed to lock the sourcemap chain").clone();
+    Ok(chain.collapse()?.unwrap_or_else(|| SourceMap::identity(&self.id, &self.original_code)))
+  }
+
+  /// Take the maps returned by `transform` hooks, without the ones of `load` hooks.
+  pub fn take_transformed(&self) -> SourceMapChain {
+    let chain =
+      std::mem::take(&mut *self.chain.lock().expect("Failed to lock the sourcemap chain"));
+    chain.into_iter().skip(self.loaded_len).collect()
+  }
+}
+
+pub type SharedTransformSourcemaps = Arc<TransformSourcemaps>;
@@ -0,0 +1,49 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Files added by plugins with `this.addWatchFile`. Besides loaded modules, changes of these files trigger
+/// rebuilds in watch mode.
+#[derive(Debug, Default)]
+pub struct WatchFiles {
+  /// In the order they are added.
+  files: Mutex<Vec<String>>,
+}
+
+impl WatchFiles {
+  fn files(&self) -> MutexGuard<'_, Vec<String>> {
+    self.files.lock().expect("WatchFiles lock should not be poisoned")
+  }
+
+  pub fn add(&self, id: &str) {
+    let mut files = self.files();
+    if !files.iter().any(|file| file == id) {
+      files.push(id.to_string());
+    }
+  }
+
+  pub fn ids(&self) -> Vec<String> {
+    self.files().clone()
+  }
+
+  /// Forget all files, since every build adds its own watch files.
+  pub fn clear(&self) {
+    self.files().clear();
+  }
+}
+
+pub type SharedWatchFiles = Arc<WatchFiles>;
+
+#[cfg(test)]
+mod tests {
+  use super::WatchFiles;
+
+  #[test]
+  fn watch_files_are_deduplicated() {
+    let watch_files = WatchFiles::default();
+    watch_files.add("/project/tailwind.config.js");
+    watch_files.add("/project/styles/_vars.css");
+    watch_files.add("/project/tailwind.config.js");
+    assert_eq!(watch_files.ids(), vec!["/project/tailwind.config.js", "/project/styles/_vars.css"]);
+    watch_files.clear();
+    assert!(watch_files.ids().is_empty());
+  }
+}
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use rolldown_plugin::{HookNoopReturn, Plugin, PluginContext};
+use rolldown_plugin::{HookBuildStartArgs, HookNoopReturn, Plugin, PluginContext};
 
 #[derive(Debug)]
 pub struct HelloPlugin;
@@ -12,7 +12,11 @@ impl Plugin for HelloPlugin {
   }
 
   #[allow(clippy::print_stdout)]
-  async fn build_start(&self, _ctx: &mut PluginContext) -> HookNoopReturn {
+  async fn build_start(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &HookBuildStartArgs,
+  ) -> HookNoopReturn {
     println!("hello");
     Ok(())
   }
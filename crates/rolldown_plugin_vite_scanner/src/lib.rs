@@ -64,7 +64,12 @@ impl<T: FileSystem + 'static + Default> Plugin for ViteScannerPlugin<T> {
 
     // resolve local scripts (`<script>` in Svelte and `<script setup>` in Vue)
     if VIRTUAL_MODULE_REGEX.is_match(source) {
-      return Ok(Some(HookResolveIdOutput { id: (*source).to_string(), external: None }));
+      return Ok(Some(HookResolveIdOutput {
+        id: (*source).to_string(),
+        external: None,
+        module_side_effects: None,
+        module_type: None,
+      }));
     }
 
     // TODO bare imports: record and externalize
@@ -83,19 +88,26 @@ impl<T: FileSystem + 'static + Default> Plugin for ViteScannerPlugin<T> {
       return Ok(Some(HookResolveIdOutput {
         id: (*source).to_string(),
         external: Some(self.entries.contains(&(*source).to_string())),
+        module_side_effects: None,
+        module_type: None,
       }));
     }
 
     // known vite query types: ?worker, ?raw
     if VITE_SPECIAL_QUERY_REGEX.is_match(source) {
-      return Ok(Some(HookResolveIdOutput { id: (*source).to_string(), external: Some(true) }));
+      return Ok(Some(HookResolveIdOutput {
+        id: (*source).to_string(),
+        external: Some(true),
+        module_side_effects: None,
+        module_type: None,
+      }));
     }
 
     Ok(None)
   }
 
   async fn load(&self, _ctx: &mut PluginContext, args: &HookLoadArgs) -> HookLoadReturn {
-    let HookLoadArgs { id } = args;
+    let HookLoadArgs { id, .. } = args;
 
     // extract scripts inside HTML-like files and treat it as a js module
     if HTML_TYPE_REGEX.is_match(id) {
@@ -105,14 +117,28 @@ impl<T: FileSystem + 'static + Default> Plugin for ViteScannerPlugin<T> {
       scripts.into_iter().for_each(|(key, value)| {
         self.scripts.insert(key, value);
       });
-      return Ok(Some(HookLoadOutput { code: content, map: None }));
+      return Ok(Some(HookLoadOutput {
+        code: content,
+        map: None,
+        module_side_effects: None,
+        loader: None,
+        synthetic_named_exports: None,
+        meta: None,
+      }));
     }
 
     // load local scripts (`<script>` in Svelte and `<script setup>` in Vue)
     if VIRTUAL_MODULE_REGEX.is_match(id) {
       let key = id.replace(VIRTUAL_MODULE_PREFIX, "");
       if let Some(content) = self.scripts.get(&key) {
-        return Ok(Some(HookLoadOutput { code: content.to_string(), map: None }));
+        return Ok(Some(HookLoadOutput {
+          code: content.to_string(),
+          map: None,
+          module_side_effects: None,
+          loader: None,
+          synthetic_named_exports: None,
+          meta: None,
+        }));
       }
     }
 
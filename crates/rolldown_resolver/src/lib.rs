@@ -1,5 +1,6 @@
 mod resolver;
 mod resolver_options;
+mod tsconfig_paths;
 
 pub use crate::resolver::{ResolveRet, Resolver};
 pub use crate::resolver_options::ResolverOptions;
@@ -1,35 +1,72 @@
-use rolldown_common::{FilePath, ModuleType, ResolvedPath};
+use rolldown_common::{AliasItem, FilePath, ImportKind, ModuleType, ResolvedPath};
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
-use std::path::PathBuf;
+use std::{borrow::Cow, path::PathBuf};
 use sugar_path::{AsPath, SugarPathBuf};
 
 use oxc_resolver::{Resolution, ResolveError, ResolverGeneric};
+use rolldown_utils::glob::glob_match;
 
-use crate::ResolverOptions;
+use crate::{
+  resolver_options::into_oxc_resolve_options, tsconfig_paths::matched_pattern, ResolverOptions,
+};
 
 #[derive(Debug)]
 pub struct Resolver<T: FileSystem + Default> {
   cwd: PathBuf,
-  inner: ResolverGeneric<T>,
+  alias: Vec<AliasItem>,
+  /// The absolute path of `resolve.tsconfig_filename`.
+  tsconfig: Option<PathBuf>,
+  fs: T,
+  /// Resolves `import` statements and `import()`, with the `import` condition of exports fields.
+  import_resolver: ResolverGeneric<T>,
+  /// Resolves `require()`, with the `require` condition of exports fields. It shares the cache with
+  /// `import_resolver`.
+  require_resolver: ResolverGeneric<T>,
 }
 
 impl<F: FileSystem + Default> Resolver<F> {
-  pub fn with_cwd_and_fs(cwd: PathBuf, resolver_options: Option<ResolverOptions>, fs: F) -> Self {
-    let option = resolver_options.map_or_else(oxc_resolver::ResolveOptions::default, Into::into);
-    let inner_resolver = ResolverGeneric::new_with_file_system(fs, option);
-    Self { cwd, inner: inner_resolver }
+  pub fn with_cwd_and_fs(
+    cwd: PathBuf,
+    mut resolver_options: Option<ResolverOptions>,
+    fs: F,
+  ) -> Self {
+    let alias =
+      resolver_options.as_mut().and_then(|options| options.alias.take()).unwrap_or_default();
+    let tsconfig = resolver_options.as_mut().and_then(|options| {
+      let tsconfig = cwd.join(options.tsconfig_filename.as_ref()?).into_normalize();
+      options.tsconfig_filename = Some(tsconfig.clone());
+      Some(tsconfig)
+    });
+    let options =
+      resolver_options.map_or_else(oxc_resolver::ResolveOptions::default, into_oxc_resolve_options);
+    let with_condition = |condition: &str| {
+      let mut options = options.clone();
+      options.condition_names.push(condition.to_string());
+      options
+    };
+    let import_resolver =
+      ResolverGeneric::new_with_file_system(fs.share(), with_condition("import"));
+    let require_resolver = import_resolver.clone_with_options(with_condition("require"));
+    Self { cwd, alias, tsconfig, fs, import_resolver, require_resolver }
   }
 
   pub fn cwd(&self) -> &PathBuf {
     &self.cwd
   }
+
+  /// Replace `specifier` with the first matching item of `resolve.alias`.
+  pub fn apply_alias<'a>(&self, specifier: &'a str) -> Cow<'a, str> {
+    self.alias.iter().find_map(|item| item.apply(specifier)).unwrap_or(Cow::Borrowed(specifier))
+  }
 }
 
 #[derive(Debug)]
 pub struct ResolveRet {
   pub resolved: ResolvedPath,
   pub module_type: ModuleType,
+  /// The `sideEffects` field of the nearest `package.json` applied to the resolved path.
+  pub package_json_side_effects: Option<bool>,
 }
 
 impl<F: FileSystem + Default> Resolver<F> {
@@ -39,10 +76,15 @@ impl<F: FileSystem + Default> Resolver<F> {
     &self,
     importer: Option<&FilePath>,
     specifier: &str,
+    import_kind: ImportKind,
   ) -> Result<ResolveRet, BuildError> {
+    let inner = match import_kind {
+      ImportKind::Import | ImportKind::DynamicImport => &self.import_resolver,
+      ImportKind::Require => &self.require_resolver,
+    };
     let resolved = if let Some(importer) = importer {
       let context = importer.as_path().parent().expect("Should have a parent dir");
-      self.inner.resolve(context, specifier)
+      inner.resolve(context, specifier)
     } else {
       // If the importer is `None`, it means that the specifier is provided by the user in `input`. In this case, we can't call `resolver.resolve` with
       // `{ context: cwd, specifier: specifier }` due to rollup's default resolve behavior. For specifier `main`, rollup will try to resolve it as
@@ -53,12 +95,12 @@ impl<F: FileSystem + Default> Resolver<F> {
 
       let is_path_like = specifier.starts_with('.') || specifier.starts_with('/');
 
-      let resolved = self.inner.resolve(&self.cwd, joined_specifier.to_str().unwrap());
+      let resolved = inner.resolve(&self.cwd, joined_specifier.to_str().unwrap());
       if resolved.is_ok() {
         resolved
       } else if !is_path_like {
         // If the specifier is not path-like, we should try to resolve it as a bare specifier. This allows us to resolve modules from node_modules.
-        self.inner.resolve(&self.cwd, specifier)
+        inner.resolve(&self.cwd, specifier)
       } else {
         resolved
       }
@@ -71,6 +113,7 @@ impl<F: FileSystem + Default> Resolver<F> {
           ignored: false,
         },
         module_type: calc_module_type(&info),
+        package_json_side_effects: calc_package_json_side_effects(&info),
       }),
       Err(err) => {
         if let ResolveError::Ignored(path) = err {
@@ -80,12 +123,10 @@ impl<F: FileSystem + Default> Resolver<F> {
               ignored: true,
             },
             module_type: ModuleType::CJS,
+            package_json_side_effects: None,
           })
         } else if let Some(importer) = importer {
-          Err(
-            BuildError::unresolved_import(specifier.to_string(), importer.as_path())
-              .with_source(err),
-          )
+          Err(self.unresolved_import(specifier, importer).with_source(err))
         } else {
           Err(BuildError::unresolved_entry(specifier).with_source(err))
         }
@@ -94,12 +135,33 @@ impl<F: FileSystem + Default> Resolver<F> {
   }
 }
 
+impl<F: FileSystem + Default> Resolver<F> {
+  fn unresolved_import(&self, specifier: &str, importer: &FilePath) -> BuildError {
+    match &self.tsconfig {
+      // `paths` only apply to bare specifiers.
+      Some(tsconfig) if !specifier.starts_with(['.', '/']) => {
+        BuildError::unresolved_import_with_tsconfig(
+          specifier.to_string(),
+          importer.as_path(),
+          tsconfig,
+          matched_pattern(&self.fs, tsconfig, specifier),
+        )
+      }
+      _ => BuildError::unresolved_import(specifier.to_string(), importer.as_path()),
+    }
+  }
+}
+
 fn calc_module_type(info: &Resolution) -> ModuleType {
   if let Some(extension) = info.path().extension() {
     if extension == "mjs" {
       return ModuleType::EsmMjs;
     } else if extension == "cjs" {
       return ModuleType::CJS;
+    } else if extension == "json" {
+      return ModuleType::Json;
+    } else if extension == "css" {
+      return ModuleType::Css;
     }
   }
   if let Some(package_json) = info.package_json() {
@@ -112,3 +174,22 @@ fn calc_module_type(info: &Resolution) -> ModuleType {
   }
   ModuleType::Unknown
 }
+
+/// Follow webpack, patterns without `/` match files in any directory of the package.
+fn calc_package_json_side_effects(info: &Resolution) -> Option<bool> {
+  let package_json = info.package_json()?;
+  let side_effects = package_json.raw_json().get("sideEffects")?;
+  if let Some(value) = side_effects.as_bool() {
+    return Some(value);
+  }
+  let relative_path = info.path().strip_prefix(package_json.directory()).ok()?.to_string_lossy();
+  let patterns = side_effects.as_array()?;
+  Some(patterns.iter().filter_map(|pattern| pattern.as_str()).any(|pattern| {
+    let pattern = pattern.trim_start_matches("./");
+    if pattern.contains('/') {
+      glob_match(pattern, &relative_path)
+    } else {
+      glob_match(&format!("**/{pattern}"), &relative_path)
+    }
+  }))
+}
@@ -0,0 +1,53 @@
+use std::path::{Path, PathBuf};
+
+use rolldown_fs::FileSystem;
+use serde_json::Value;
+use sugar_path::SugarPathBuf;
+
+/// The `compilerOptions.paths` pattern of the tsconfig that matches `specifier`, which is only used to explain
+/// failed resolutions. Like TypeScript, an exact key wins, then the wildcard key with the longest prefix.
+///
+/// `paths` inherited by relative `extends` are followed. `None` if there's no match or the tsconfig can't be read.
+pub(crate) fn matched_pattern<F: FileSystem>(
+  fs: &F,
+  tsconfig: &Path,
+  specifier: &str,
+) -> Option<String> {
+  let paths = load_paths(fs, tsconfig.to_path_buf())?;
+  if paths.contains_key(specifier) {
+    return Some(specifier.to_string());
+  }
+  paths
+    .keys()
+    .filter_map(|key| {
+      let (prefix, suffix) = key.split_once('*')?;
+      (specifier.len() >= prefix.len() + suffix.len()
+        && specifier.starts_with(prefix)
+        && specifier.ends_with(suffix))
+      .then_some((prefix.len(), key))
+    })
+    .max_by_key(|(prefix_len, _)| *prefix_len)
+    .map(|(_, key)| key.clone())
+}
+
+fn load_paths<F: FileSystem>(
+  fs: &F,
+  mut tsconfig: PathBuf,
+) -> Option<serde_json::Map<String, Value>> {
+  // Guards against `extends` cycles.
+  for _ in 0..32 {
+    let mut json = fs.read_to_string(&tsconfig).ok()?;
+    json_strip_comments::strip(&mut json).ok()?;
+    let mut value: Value = serde_json::from_str(&json).ok()?;
+    if let Some(Value::Object(paths)) = value.pointer_mut("/compilerOptions/paths").map(Value::take)
+    {
+      return Some(paths);
+    }
+    let extends = value.get("extends")?.as_str()?;
+    if !extends.starts_with('.') {
+      return None;
+    }
+    tsconfig = tsconfig.parent()?.join(extends).into_normalize();
+  }
+  None
+}
@@ -26,7 +26,8 @@ pub fn concat_sourcemaps(
         )
         .map_err(|e| BuildError::sourcemap_error(e.to_string()))?;
     }
-    line_offset += u32::try_from(content.lines().count() + 1)
+    // Each piece is joined with a newline, so the next piece starts right after the last line of the current one.
+    line_offset += u32::try_from(content.matches('\n').count() + 1)
       .map_err(|e| BuildError::sourcemap_error(e.to_string()))?;
   }
 
@@ -76,7 +77,7 @@ mod tests {
         "function sayHello(name: string) {\n  console.log(`Hello, ${name}`);\n}\n"
     ],
     "names": [],
-    "mappings": ";;;AAAA,SAAS,QAAQ,CAAC,IAAY;IAC5B,OAAO,CAAC,GAAG,CAAC,iBAAU,IAAI,CAAE,CAAC,CAAC;AAChC,CAAC"
+    "mappings": ";;AAAA,SAAS,QAAQ,CAAC,IAAY;IAC5B,OAAO,CAAC,GAAG,CAAC,iBAAU,IAAI,CAAE,CAAC,CAAC;AAChC,CAAC"
   }"#;
     assert_eq!(
       map.to_json().as_str().parse::<serde_json::Value>().unwrap(),
@@ -1,8 +1,26 @@
 use parcel_sourcemap::SourceMap as ParcelSourcemap;
 mod concat_sourcemap;
+mod source_map_chain;
 
 pub use concat_sourcemap::concat_sourcemaps;
 use rolldown_error::BuildError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+pub use source_map_chain::SourceMapChain;
+
+/// The JSON form of a source map, which is the same as the one written by parcel, except that `sourcesContent`
+/// is optional.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSourceMap<'a> {
+  version: u8,
+  source_root: Option<&'a str>,
+  mappings: &'a str,
+  sources: &'a Vec<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sources_content: Option<&'a Vec<String>>,
+  names: &'a Vec<String>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct SourceMap {
   pub mappings: String,
@@ -24,23 +42,151 @@ impl SourceMap {
     Self { mappings, names, source_root, sources, sources_content, inner: None }
   }
 
+  /// A map of the code to itself, which maps the start of each line to the same line of `source`.
+  pub fn identity(source: impl Into<String>, code: &str) -> Self {
+    let lines = code.split('\n').count();
+    let mappings =
+      std::iter::once("AAAA").chain(std::iter::repeat("AACA").take(lines - 1)).collect::<Vec<_>>();
+    Self::new(mappings.join(";"), vec![], None, vec![source.into()], vec![code.to_string()])
+  }
+
+  pub fn from_json(json: &str) -> Result<Self, BuildError> {
+    ParcelSourcemap::from_json("", json)
+      .map(Into::into)
+      .map_err(|e| BuildError::sourcemap_error(e.to_string()))
+  }
+
   pub fn to_json(&mut self) -> Option<Result<String, BuildError>> {
-    self
-      .inner
-      .as_mut()
-      .map(|i| i.to_json(None).map_err(|e| BuildError::sourcemap_error(e.to_string())))
+    self.inner.as_mut().map(|inner| {
+      let mut mappings = vec![];
+      inner.write_vlq(&mut mappings).map_err(|e| BuildError::sourcemap_error(e.to_string()))?;
+      let sources_content = inner.get_sources_content();
+      let json = JsonSourceMap {
+        version: 3,
+        source_root: None,
+        mappings: &String::from_utf8_lossy(&mappings),
+        sources: inner.get_sources(),
+        // Omitted if the content of sources is excluded
+        sources_content: (!sources_content.is_empty()).then_some(sources_content),
+        names: inner.get_names(),
+      };
+      serde_json::to_string(&json).map_err(|e| BuildError::sourcemap_error(e.to_string()))
+    })
   }
 
   pub fn to_data_url(&mut self) -> Option<Result<String, BuildError>> {
-    self
-      .inner
-      .as_mut()
-      .map(|i| i.to_data_url(None).map_err(|e| BuildError::sourcemap_error(e.to_string())))
+    self.to_json().map(|json| {
+      let base64 = base64_simd::Base64::STANDARD.encode_to_boxed_str(json?.as_bytes());
+      Ok(format!("data:application/json;charset=utf-8;base64,{base64}"))
+    })
   }
 
   pub fn get_inner(&self) -> Option<&ParcelSourcemap> {
     self.inner.as_ref()
   }
+
+  /// Replace each source path with the one returned by `f`. Sources mapped to the same path are merged.
+  pub fn map_sources(&mut self, f: impl FnMut(&str) -> String) -> Result<(), BuildError> {
+    self.rebuild(f, true)
+  }
+
+  /// Remove the content of sources, so `sourcesContent` is omitted from the serialized map.
+  pub fn exclude_sources_content(&mut self) -> Result<(), BuildError> {
+    self.rebuild(ToString::to_string, false)
+  }
+
+  /// Replace the name of each mapping with the one returned by `f`, which is given the original line, column
+  /// and name. Names are kept if `f` returns `None`.
+  pub fn map_names(
+    &mut self,
+    mut f: impl FnMut(u32, u32, &str) -> Option<String>,
+  ) -> Result<(), BuildError> {
+    let Some(inner) = self.inner.as_ref() else {
+      return Ok(());
+    };
+    let mut map = ParcelSourcemap::new("");
+    map.add_sources(inner.get_sources().clone());
+    for (index, content) in inner.get_sources_content().iter().enumerate() {
+      map
+        .set_source_content(index, content)
+        .map_err(|e| BuildError::sourcemap_error(e.to_string()))?;
+    }
+    for mut mapping in inner.get_mappings() {
+      if let Some(original) = mapping.original.as_mut() {
+        if let Some(name) = original.name {
+          let name =
+            inner.get_name(name).map_err(|e| BuildError::sourcemap_error(e.to_string()))?;
+          let name = f(original.original_line, original.original_column, name)
+            .unwrap_or_else(|| name.to_string());
+          original.name = Some(map.add_name(&name));
+        }
+      }
+      map.add_mapping(mapping.generated_line, mapping.generated_column, mapping.original);
+    }
+    self.inner = Some(map);
+    Ok(())
+  }
+
+  /// Parcel doesn't allow changing sources in place, so the map is rebuilt with the new sources.
+  fn rebuild(
+    &mut self,
+    mut map_source: impl FnMut(&str) -> String,
+    keep_sources_content: bool,
+  ) -> Result<(), BuildError> {
+    let Some(inner) = self.inner.as_ref() else {
+      self.sources = self.sources.iter().map(|source| map_source(source)).collect();
+      if !keep_sources_content {
+        self.sources_content.clear();
+      }
+      return Ok(());
+    };
+    let mut map = ParcelSourcemap::new("");
+    let source_indexes =
+      map.add_sources(inner.get_sources().iter().map(|source| map_source(source)).collect());
+    if keep_sources_content {
+      for (index, content) in inner.get_sources_content().iter().enumerate() {
+        map
+          .set_source_content(source_indexes[index] as usize, content)
+          .map_err(|e| BuildError::sourcemap_error(e.to_string()))?;
+      }
+    }
+    map.add_names(inner.get_names().clone());
+    for mapping in inner.get_mappings() {
+      let original = mapping.original.map(|mut original| {
+        original.source = source_indexes[original.source as usize];
+        original
+      });
+      map.add_mapping(mapping.generated_line, mapping.generated_column, original);
+    }
+    self.inner = Some(map);
+    Ok(())
+  }
+}
+
+/// Serialized as the JSON of the map, so it could be written to disk, like by caches of builds.
+impl Serialize for SourceMap {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let json = match self.clone().to_json() {
+      Some(json) => json.map_err(serde::ser::Error::custom)?,
+      None => serde_json::to_string(&JsonSourceMap {
+        version: 3,
+        source_root: self.source_root.as_deref(),
+        mappings: &self.mappings,
+        sources: &self.sources,
+        sources_content: Some(&self.sources_content),
+        names: &self.names,
+      })
+      .map_err(serde::ser::Error::custom)?,
+    };
+    serializer.serialize_str(&json)
+  }
+}
+
+impl<'de> Deserialize<'de> for SourceMap {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let json = String::deserialize(deserializer)?;
+    Self::from_json(&json).map_err(serde::de::Error::custom)
+  }
 }
 
 impl From<ParcelSourcemap> for SourceMap {
@@ -55,6 +201,10 @@ pub fn collapse_sourcemaps(
   let mut parcel_sourcemap_chain = sourcemap_chain
     .into_iter()
     .map(|sourcemap| {
+      // Maps produced by rolldown itself (e.g. chunk maps) only carry the parcel inner map.
+      if let Some(inner) = sourcemap.inner {
+        return Ok(inner);
+      }
       let mut map = ParcelSourcemap::new(sourcemap.source_root.as_deref().unwrap_or(""));
       map
         .add_vlq_map(
@@ -139,4 +289,18 @@ mod tests {
       expected.parse::<serde_json::Value>().unwrap()
     );
   }
+
+  #[test]
+  fn identity() {
+    let map =
+      super::collapse_sourcemaps(vec![SourceMap::identity("main.js", "a\nb\nc")]).unwrap().unwrap();
+    let lines = map
+      .get_inner()
+      .unwrap()
+      .get_mappings()
+      .iter()
+      .map(|mapping| (mapping.generated_line, mapping.original.unwrap().original_line))
+      .collect::<Vec<_>>();
+    assert_eq!(lines, vec![(0, 0), (1, 1), (2, 2)]);
+  }
 }
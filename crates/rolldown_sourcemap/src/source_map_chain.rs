@@ -0,0 +1,61 @@
+use rolldown_error::BuildError;
+use serde::{Deserialize, Serialize};
+
+use crate::{collapse_sourcemaps, SourceMap};
+
+/// Maps of the transformations applied to a piece of code in order, like the ones returned by `load` and
+/// `transform` hooks of a module, followed by the map of generating the code. Each map points to the code
+/// produced by the previous one, so they compose into a single map from the final code to the original sources.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SourceMapChain {
+  maps: Vec<SourceMap>,
+}
+
+impl SourceMapChain {
+  /// Add the map of a transformation applied after the ones already in the chain. A transformation without a map
+  /// is treated as an identity mapping, which is the same as leaving it out of the chain.
+  pub fn push(&mut self, map: SourceMap) {
+    self.maps.push(map);
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.maps.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &SourceMap> {
+    self.maps.iter()
+  }
+
+  /// Compose the maps into one. Returns `None` if the chain is empty.
+  pub fn collapse(self) -> Result<Option<SourceMap>, BuildError> {
+    collapse_sourcemaps(self.maps)
+  }
+}
+
+impl From<Vec<SourceMap>> for SourceMapChain {
+  fn from(maps: Vec<SourceMap>) -> Self {
+    Self { maps }
+  }
+}
+
+impl FromIterator<SourceMap> for SourceMapChain {
+  fn from_iter<T: IntoIterator<Item = SourceMap>>(iter: T) -> Self {
+    Self { maps: iter.into_iter().collect() }
+  }
+}
+
+impl Extend<SourceMap> for SourceMapChain {
+  fn extend<T: IntoIterator<Item = SourceMap>>(&mut self, iter: T) {
+    self.maps.extend(iter);
+  }
+}
+
+impl IntoIterator for SourceMapChain {
+  type Item = SourceMap;
+  type IntoIter = std::vec::IntoIter<SourceMap>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.maps.into_iter()
+  }
+}
@@ -1,3 +1,11 @@
 mod test_config;
 
-pub use test_config::{input_options::InputItem, TestConfig};
+pub use test_config::{
+  input_options::{
+    ChecksOptions, InputItem, JsxOptions, ModuleSideEffects, ModuleSideEffectsKeyword,
+    PreserveEntrySignatures, PropertyReadSideEffects, PropertyReadSideEffectsKeyword,
+    ResolveOptions, Treeshake, TreeshakeOptions,
+  },
+  output_options::{Globals, Interop},
+  TestConfig,
+};
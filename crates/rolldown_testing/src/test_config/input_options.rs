@@ -7,8 +7,109 @@ use std::collections::HashMap;
 pub struct InputOptions {
   pub input: Option<Vec<InputItem>>,
   pub external: Option<Vec<String>>,
-  pub treeshake: Option<bool>,
+  /// `false` disables tree shaking.
+  pub treeshake: Option<Treeshake>,
+  pub shim_missing_exports: Option<bool>,
   pub resolve: Option<ResolveOptions>,
+  pub json: Option<JsonOptions>,
+  pub jsx: Option<JsxOptions>,
+  pub asset: Option<AssetOptions>,
+  /// Replaces global identifiers or member expressions with expressions.
+  pub define: Option<HashMap<String, String>>,
+  /// Imports `[module_specifier, exported_name]` into modules referencing the keys as globals.
+  pub inject: Option<HashMap<String, (String, String)>>,
+  /// Globs of modules whose circular dependencies are not warned about, relative to the cwd.
+  pub ignored_circular_dependencies: Option<Vec<String>>,
+  pub checks: Option<ChecksOptions>,
+  pub preserve_entry_signatures: Option<PreserveEntrySignatures>,
+  /// Errors of modules are collected until there are this many distinct ones, 100 by default.
+  pub max_errors: Option<usize>,
+}
+
+/// `"strict"`, `"allow-extension"`, `"exports-only"` or `false`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PreserveEntrySignatures {
+  Boolean(bool),
+  Keyword(String),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Treeshake {
+  Boolean(bool),
+  Options(TreeshakeOptions),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TreeshakeOptions {
+  pub module_side_effects: Option<ModuleSideEffects>,
+  /// `false` ignores `/*#__PURE__*/` annotations.
+  pub annotations: Option<bool>,
+  pub property_read_side_effects: Option<PropertyReadSideEffects>,
+  /// `false` analyzes `try` statements like normal blocks.
+  pub try_catch_deoptimization: Option<bool>,
+}
+
+/// `true`, `false` or `"always"`.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum PropertyReadSideEffects {
+  Boolean(bool),
+  Keyword(PropertyReadSideEffectsKeyword),
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub enum PropertyReadSideEffectsKeyword {
+  #[serde(rename = "always")]
+  Always,
+}
+
+/// `true`, `false`, `"no-external"` or globs of modules that have side effects, relative to the cwd.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ModuleSideEffects {
+  Boolean(bool),
+  Keyword(ModuleSideEffectsKeyword),
+  Patterns(Vec<String>),
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub enum ModuleSideEffectsKeyword {
+  #[serde(rename = "no-external")]
+  NoExternal,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct JsonOptions {
+  pub named_exports: Option<bool>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct JsxOptions {
+  /// `automatic` or `classic`
+  pub runtime: Option<String>,
+  pub import_source: Option<String>,
+  pub pragma: Option<String>,
+  pub pragma_frag: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AssetOptions {
+  /// Extensions of files imported as assets without the leading `.`, like `png`.
+  pub extensions: Option<Vec<String>>,
+  pub public_path: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChecksOptions {
+  /// `error` or `warn-external`, which treats bare specifiers that can't be resolved as externals.
+  pub unresolved_import: Option<String>,
 }
 
 #[derive(Deserialize, JsonSchema)]
@@ -25,10 +126,19 @@ pub struct TsConfig {
   pub use_define_for_class_fields: bool,
 }
 
+/// Only one of `find` and `findRegex` should be set.
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AliasItem {
+  pub find: Option<String>,
+  pub find_regex: Option<String>,
+  pub replacement: String,
+}
+
 #[derive(Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ResolveOptions {
-  pub alias: Option<HashMap<String, Vec<String>>>,
+  pub alias: Option<Vec<AliasItem>>,
   pub alias_fields: Option<Vec<Vec<String>>>,
   pub condition_names: Option<Vec<String>>,
   pub exports_fields: Option<Vec<Vec<String>>>,
@@ -37,4 +147,5 @@ pub struct ResolveOptions {
   pub main_files: Option<Vec<String>>,
   pub modules: Option<Vec<String>>,
   pub symlinks: Option<bool>,
+  pub tsconfig_filename: Option<String>,
 }
@@ -1,5 +1,6 @@
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 use crate::impl_serde_default;
 
@@ -11,13 +12,89 @@ fn auto_by_default() -> String {
   "auto".to_string()
 }
 
+fn auto_interop_by_default() -> Interop {
+  Interop::Mode(auto_by_default())
+}
+
 #[derive(Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct OutputOptions {
   #[serde(default = "esm_by_default")]
   pub format: String,
   #[serde(default = "auto_by_default")]
   pub export_mode: String,
+  #[serde(default = "auto_interop_by_default")]
+  pub interop: Interop,
+  pub name: Option<String>,
+  #[serde(default)]
+  pub extend: bool,
+  pub globals: Option<Globals>,
+  /// Maps ids of external modules to the specifiers they are imported with.
+  pub paths: Option<HashMap<String, String>>,
+  /// `file`, `inline` or `hidden`
+  pub sourcemap: Option<String>,
+  /// Prepended to the sources of source maps in place of their leading `../`, standing for the function of
+  /// `sourcemapPathTransform`.
+  pub sourcemap_path_transform: Option<String>,
+  /// Leave the content of sources out of source maps.
+  #[serde(default)]
+  pub sourcemap_exclude_sources: bool,
+  /// Omit whitespace that isn't needed from the generated code.
+  #[serde(default)]
+  pub compact: bool,
+  /// Shorten local names and fold constant expressions besides what `compact` does.
+  #[serde(default)]
+  pub minify: bool,
+  /// `inline`, `eof`, `none` or `external`
+  pub legal_comments: Option<String>,
+  /// Include dynamically imported modules in the chunk of the entry instead of splitting them.
+  #[serde(default)]
+  pub inline_dynamic_imports: bool,
+  /// Freeze namespace objects with `Object.freeze`.
+  #[serde(default = "super::true_by_default")]
+  pub freeze: bool,
+  /// Code placed at the top of each chunk, outside of the format wrapper.
+  pub banner: Option<String>,
+  /// Code placed at the bottom of each chunk, outside of the format wrapper.
+  pub footer: Option<String>,
+  /// Code placed at the top of each chunk, inside of the format wrapper.
+  pub intro: Option<String>,
+  /// Code placed at the bottom of each chunk, inside of the format wrapper.
+  pub outro: Option<String>,
+  /// Emit each module as a separate chunk.
+  #[serde(default)]
+  pub preserve_modules: bool,
+  /// Path prefix stripped from the paths of modules when `preserveModules` is enabled.
+  pub preserve_modules_root: Option<String>,
+  /// Maps chunk names to the ids of modules put into them, relative to the cwd.
+  pub manual_chunks: Option<HashMap<String, Vec<String>>>,
+  /// Defaults to `[name]` with the extension node needs to execute the format. Artifacts with other names can't be
+  /// executed, so `expectExecuted` should be `false`.
+  pub entry_file_names: Option<String>,
+  /// Defaults to `[name]` with the extension node needs to execute the format.
+  pub chunk_file_names: Option<String>,
+  /// The number of characters of hashes rendered by `[hash]`.
+  pub hash_length: Option<usize>,
+  /// `xxhash` or `blake3`
+  pub hash_algorithm: Option<String>,
 }
 
 impl_serde_default!(OutputOptions);
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Globals {
+  Map(HashMap<String, String>),
+  /// `[id]` is replaced with the id of each external module, standing for the function form.
+  Template(String),
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Interop {
+  /// `auto`, `esModule`, `default`, `defaultOnly` or `compat`
+  Mode(String),
+  /// Maps ids of external modules to their modes, standing for the function form. Other modules use `auto`.
+  PerModule(HashMap<String, String>),
+}
@@ -18,6 +18,10 @@ impl BitSet {
     self.entries[bit as usize / 8] |= 1 << (bit & 7);
   }
 
+  pub fn union(&mut self, other: &Self) {
+    self.entries.iter_mut().zip(&other.entries).for_each(|(a, b)| *a |= b);
+  }
+
   pub fn is_empty(&self) -> bool {
     self.entries.iter().all(|&e| e == 0)
   }
@@ -63,4 +67,14 @@ mod tests {
     bs.set_bit(15);
     assert_eq!(bs.to_string(), "10000011_10000001");
   }
+
+  #[test]
+  fn union() {
+    let mut a = BitSet::new(9);
+    a.set_bit(0);
+    let mut b = BitSet::new(9);
+    b.set_bit(8);
+    a.union(&b);
+    assert_eq!(a.to_string(), "00000001_00000001");
+  }
 }
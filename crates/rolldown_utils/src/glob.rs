@@ -0,0 +1,28 @@
+use glob::{MatchOptions, Pattern};
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+  case_sensitive: true,
+  require_literal_separator: true,
+  require_literal_leading_dot: false,
+};
+
+/// Check whether `path` matches the glob `pattern`, where `*` doesn't match `/` while `**` matches any number of
+/// directories. Paths are compared with `/` as the separator. Invalid patterns match nothing.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+  let path = path.replace('\\', "/");
+  Pattern::new(pattern).is_ok_and(|pattern| pattern.matches_with(&path, MATCH_OPTIONS))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::glob_match;
+
+  #[test]
+  fn star_does_not_match_separators() {
+    assert!(glob_match("/src/*.js", "/src/foo.js"));
+    assert!(!glob_match("/src/*.js", "/src/nested/foo.js"));
+    assert!(glob_match("/src/**/*.js", "/src/nested/foo.js"));
+    assert!(glob_match("**/*.css", "C:\\project\\src\\style.css"));
+    assert!(!glob_match("[", "["));
+  }
+}
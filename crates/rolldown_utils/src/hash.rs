@@ -0,0 +1,63 @@
+use xxhash_rust::xxh3::xxh3_128;
+
+/// Algorithms used to compute hashes in file names. All of them give the same hash for the same content, across
+/// builds, machines and platforms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HashAlgorithm {
+  /// XXH3 with 128-bit output.
+  #[default]
+  Xxhash,
+  /// BLAKE3 with 256-bit output, which is slower but cryptographic.
+  Blake3,
+}
+
+impl HashAlgorithm {
+  /// The number of hex characters of the hashes computed by the algorithm.
+  pub fn max_len(self) -> usize {
+    match self {
+      Self::Xxhash => 32,
+      Self::Blake3 => 64,
+    }
+  }
+
+  /// The hash of `content` in hex, which has `max_len` characters.
+  pub fn hash_hex(self, content: &[u8]) -> String {
+    match self {
+      Self::Xxhash => format!("{:032x}", xxh3_128(content)),
+      Self::Blake3 => blake3::hash(content).to_hex().to_string(),
+    }
+  }
+}
+
+impl From<String> for HashAlgorithm {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "xxhash" => Self::Xxhash,
+      "blake3" => Self::Blake3,
+      _ => unreachable!("unknown hash algorithm"),
+    }
+  }
+}
+
+/// The number of characters of hashes returned by `content_hash`.
+pub const CONTENT_HASH_LENGTH: usize = 8;
+
+/// A short hash of `content` to be used in file names. The same content always gets the same hash.
+pub fn content_hash(content: &[u8]) -> String {
+  HashAlgorithm::default().hash_hex(content)[..CONTENT_HASH_LENGTH].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::HashAlgorithm;
+
+  #[test]
+  fn hashes_are_stable() {
+    for algorithm in [HashAlgorithm::Xxhash, HashAlgorithm::Blake3] {
+      let hash = algorithm.hash_hex(b"export default 1");
+      assert_eq!(hash.len(), algorithm.max_len());
+      assert_eq!(hash, algorithm.hash_hex(b"export default 1"));
+      assert_ne!(hash, algorithm.hash_hex(b"export default 2"));
+    }
+  }
+}
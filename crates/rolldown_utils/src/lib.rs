@@ -1,6 +1,8 @@
 // We keep some standalone utilities here
 
 mod bitset;
+pub mod glob;
+pub mod hash;
 mod magic_string_ext;
 
 use std::future::Future;
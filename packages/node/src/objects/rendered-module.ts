@@ -1 +1,3 @@
-export interface RenderedModule {}
+export interface RenderedModule {
+  renderedLength: number
+}
@@ -29,11 +29,18 @@ export interface RolldownOutputChunk {
   facadeModuleId: string | null
   isDynamicEntry: boolean
   moduleIds: string[]
+  imports: string[]
+  dynamicImports: string[]
+  /** File names of the CSS assets of the chunk, which are not imported by its code. */
+  importedCss: string[]
 }
 
 function _assertRolldownOutputChunk() {
   type _ = TypeAssert<
-    IsPropertiesEqual<Omit<RolldownOutputChunk, 'modules'>, OutputChunk>
+    IsPropertiesEqual<
+      Omit<RolldownOutputChunk, 'modules' | 'importedCss'>,
+      OutputChunk
+    >
   >
 }
 
@@ -42,6 +49,36 @@ export interface RolldownOutput {
     RolldownOutputChunk,
     ...(RolldownOutputChunk | RolldownOutputAsset)[],
   ]
+  /** Only generated with `metafile` of output options. */
+  metafile?: Metafile
+}
+
+/** The metafile of esbuild, paths of modules are relative to the cwd. */
+export interface Metafile {
+  inputs: Record<
+    string,
+    {
+      bytes: number
+      imports: MetafileImport[]
+    }
+  >
+  outputs: Record<
+    string,
+    {
+      bytes: number
+      inputs: Record<string, { bytesInOutput: number }>
+      imports: MetafileImport[]
+      exports: string[]
+      entryPoint?: string
+    }
+  >
+}
+
+export interface MetafileImport {
+  path: string
+  kind: 'import-statement' | 'dynamic-import' | 'require-call'
+  external?: boolean
+  original?: string
 }
 
 function _assertRolldownOutput() {
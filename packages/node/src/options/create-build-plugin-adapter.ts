@@ -1,4 +1,8 @@
-import type { Plugin, NormalizedInputOptions } from '../rollup-types'
+import type {
+  Plugin,
+  NormalizedInputOptions,
+  EmittedFile,
+} from '../rollup-types'
 import type {
   PluginOptions,
   SourceResult,
@@ -6,11 +10,18 @@ import type {
   RenderedChunk,
   HookRenderChunkOutput,
   BindingOutputs as Outputs,
+  BindingOutputAsset,
+  HookGenerateBundleOutput,
+  BindingModuleInfo,
+  BindingPluginContext,
+  ResolveFileUrlOptions,
 } from '@rolldown/node-binding'
 import {
   transformToOutputBundle,
   unimplemented,
   transformSourcemap,
+  createPluginContext,
+  transformModuleInfo,
 } from '../utils'
 
 // Note: because napi not catch error, so we need to catch error and print error to debugger in adapter.
@@ -22,12 +33,36 @@ export function createBuildPluginAdapter(
     name: plugin.name ?? 'unknown',
     buildStart: buildStart(plugin.buildStart, options),
     resolveId: resolveId(plugin.resolveId),
+    resolveDynamicImport: resolveDynamicImport(plugin.resolveDynamicImport),
     load: load(plugin.load),
     transform: transform(plugin.transform),
+    shouldTransformCachedModule: shouldTransformCachedModule(
+      plugin.shouldTransformCachedModule,
+    ),
+    moduleParsed: moduleParsed(plugin.moduleParsed),
     buildEnd: buildEnd(plugin.buildEnd),
     renderChunk: renderChunk(plugin.renderChunk),
+    augmentChunkHash: augmentChunkHash(plugin.augmentChunkHash),
+    resolveFileUrl: resolveFileUrl(plugin.resolveFileUrl),
     generateBundle: generateBundle(plugin.generateBundle),
     writeBundle: writeBundle(plugin.writeBundle),
+    closeBundle: closeBundle(plugin.closeBundle),
+  }
+}
+
+function closeBundle(hook: Plugin['closeBundle']) {
+  if (hook) {
+    if (typeof hook !== 'function') {
+      return unimplemented()
+    }
+    return async () => {
+      try {
+        await hook.call({} as any)
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
   }
 }
 
@@ -53,15 +88,48 @@ function generateBundle(hook: Plugin['generateBundle']) {
     if (typeof hook !== 'function') {
       return unimplemented()
     }
-    return async (outputs: Outputs, isWrite: boolean) => {
+    return async (
+      ctx: BindingPluginContext,
+      outputs: Outputs,
+      isWrite: boolean,
+    ): Promise<undefined | HookGenerateBundleOutput> => {
       try {
+        const bundle = transformToOutputBundle(outputs)
+        const originalFileNames = Object.keys(bundle)
+        const emittedAssets: BindingOutputAsset[] = []
+        const context = {
+          ...createPluginContext(ctx),
+          emitFile(file: EmittedFile) {
+            if (file.type !== 'asset' || file.fileName === undefined) {
+              return unimplemented('emitFile without fileName')
+            }
+            emittedAssets.push({
+              fileName: file.fileName,
+              source: normalizeAssetSource(file.source),
+            })
+            return file.fileName
+          },
+        }
         await hook.call(
-          {} as any,
+          context as any,
           {} as any,
           // @ts-expect-error: FIXME: hyf0
-          transformToOutputBundle(outputs),
+          bundle,
           isWrite,
         )
+        // Plugins could also mutate the bundle directly
+        const deletedFileNames = originalFileNames.filter(
+          (fileName) => !(fileName in bundle),
+        )
+        for (const [fileName, item] of Object.entries(bundle)) {
+          if (!originalFileNames.includes(fileName) && item.type === 'asset') {
+            emittedAssets.push({
+              fileName,
+              source: normalizeAssetSource(item.source),
+            })
+          }
+        }
+        return { emittedAssets, deletedFileNames }
       } catch (error) {
         console.error(error)
         throw error
@@ -70,66 +138,73 @@ function generateBundle(hook: Plugin['generateBundle']) {
   }
 }
 
+function normalizeAssetSource(source: string | Uint8Array | undefined) {
+  if (source === undefined) {
+    return ''
+  }
+  return typeof source === 'string' ? source : Buffer.from(source)
+}
+
+// Fields of rollup's `RenderedChunk` that aren't supported yet throw when accessed.
+function transformRenderedChunk(chunk: RenderedChunk) {
+  return Object.assign(
+    {
+      get name() {
+        return unimplemented()
+      },
+      get implicitlyLoadedBefore() {
+        return unimplemented()
+      },
+      get importedBindings() {
+        return unimplemented()
+      },
+      get isImplicitEntry() {
+        return unimplemented()
+      },
+      get referencedFiles() {
+        return unimplemented()
+      },
+      type: 'chunk' as const,
+    },
+    chunk,
+    {
+      get modules() {
+        return Object.fromEntries(
+          Object.entries(chunk.modules).map(([key, value]) => [
+            key,
+            Object.assign(
+              {
+                get code() {
+                  return unimplemented()
+                },
+              },
+              value,
+            ),
+          ]),
+        )
+      },
+      get facadeModuleId() {
+        return chunk.facadeModuleId || null
+      },
+    },
+  )
+}
+
 function renderChunk(hook: Plugin['renderChunk']) {
   if (hook) {
     if (typeof hook !== 'function') {
       return unimplemented()
     }
     return async (
+      ctx: BindingPluginContext,
       code: string,
       chunk: RenderedChunk,
     ): Promise<undefined | HookRenderChunkOutput> => {
       try {
-        let renderedChunk = Object.assign(
-          {
-            get name() {
-              return unimplemented()
-            },
-            get dynamicImports() {
-              return unimplemented()
-            },
-            get imports() {
-              return unimplemented()
-            },
-            get implicitlyLoadedBefore() {
-              return unimplemented()
-            },
-            get importedBindings() {
-              return unimplemented()
-            },
-            get isImplicitEntry() {
-              return unimplemented()
-            },
-            get referencedFiles() {
-              return unimplemented()
-            },
-            type: 'chunk' as const,
-          },
-          chunk,
-          {
-            get modules() {
-              return Object.fromEntries(
-                Object.entries(chunk.modules).map(([key, value]) => [
-                  key,
-                  Object.assign(
-                    {
-                      get code() {
-                        return unimplemented()
-                      },
-                    },
-                    value,
-                  ),
-                ]),
-              )
-            },
-            get facadeModuleId() {
-              return chunk.facadeModuleId || null
-            },
-          },
-        )
+        const renderedChunk = transformRenderedChunk(chunk)
         // TODO options and meta
         const value = await hook.call(
-          {} as any,
+          createPluginContext(ctx) as any,
           code,
           // @ts-expect-error: FIXME: hyf0
           renderedChunk,
@@ -143,8 +218,7 @@ function renderChunk(hook: Plugin['renderChunk']) {
           return { code: value }
         }
         if (typeof value === 'object') {
-          // TODO other filed
-          return { code: value.code }
+          return { code: value.code, map: transformSourcemap(value.map) }
         }
       } catch (error) {
         console.error(error)
@@ -154,6 +228,54 @@ function renderChunk(hook: Plugin['renderChunk']) {
   }
 }
 
+function augmentChunkHash(hook: Plugin['augmentChunkHash']) {
+  if (hook) {
+    if (typeof hook !== 'function') {
+      return unimplemented()
+    }
+    return async (
+      ctx: BindingPluginContext,
+      chunk: RenderedChunk,
+    ): Promise<undefined | string> => {
+      try {
+        const value = await hook.call(
+          createPluginContext(ctx) as any,
+          // @ts-expect-error: FIXME: hyf0
+          transformRenderedChunk(chunk),
+        )
+        return typeof value === 'string' ? value : undefined
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function resolveFileUrl(hook: Plugin['resolveFileUrl']) {
+  if (hook) {
+    if (typeof hook !== 'function') {
+      return unimplemented()
+    }
+    return async (
+      ctx: BindingPluginContext,
+      options: ResolveFileUrlOptions,
+    ): Promise<undefined | string> => {
+      try {
+        const value = await hook.call(
+          createPluginContext(ctx) as any,
+          // @ts-expect-error: `format` is one of the internal module formats
+          options,
+        )
+        return typeof value === 'string' ? value : undefined
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
 function buildStart(
   hook: Plugin['buildStart'],
   options: NormalizedInputOptions,
@@ -162,10 +284,26 @@ function buildStart(
     if (typeof hook !== 'function') {
       return unimplemented()
     }
-    return async () => {
+    return async (ctx: BindingPluginContext) => {
       try {
         // Here use `Object.freeze` to prevent plugin from modifying the options.
-        await hook.call({} as any, Object.freeze(options))
+        await hook.call(createPluginContext(ctx) as any, Object.freeze(options))
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function moduleParsed(hook: Plugin['moduleParsed']) {
+  if (hook) {
+    if (typeof hook !== 'function') {
+      return unimplemented()
+    }
+    return async (info: BindingModuleInfo) => {
+      try {
+        await hook.call({} as any, transformModuleInfo(info))
       } catch (error) {
         console.error(error)
         throw error
@@ -196,12 +334,12 @@ function transform(hook: Plugin['transform']) {
       return unimplemented()
     }
     return async (
+      ctx: BindingPluginContext,
       code: string,
       id: string,
     ): Promise<undefined | SourceResult> => {
       try {
-        // TODO: Need to investigate how to pass context to plugin.
-        const value = await hook.call({} as any, code, id)
+        const value = await hook.call(createPluginContext(ctx) as any, code, id)
         if (value === undefined || value === null) {
           return
         }
@@ -212,7 +350,38 @@ function transform(hook: Plugin['transform']) {
           return
         }
         // TODO other filed
-        return { code: value.code, map: transformSourcemap(value.map) }
+        return {
+          code: value.code,
+          map: transformSourcemap(value.map),
+          loader: getLoader(value),
+        }
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function shouldTransformCachedModule(
+  hook: Plugin['shouldTransformCachedModule'],
+) {
+  if (hook) {
+    if (typeof hook !== 'function') {
+      return unimplemented()
+    }
+    return async (
+      ctx: BindingPluginContext,
+      id: string,
+      code: string,
+    ): Promise<undefined | boolean> => {
+      try {
+        // TODO ast, meta and other fields of the cached module
+        const value = await hook.call(createPluginContext(ctx) as any, {
+          id,
+          code,
+        } as any)
+        return value ?? undefined
       } catch (error) {
         console.error(error)
         throw error
@@ -227,13 +396,14 @@ function resolveId(hook: Plugin['resolveId']) {
       return unimplemented()
     }
     return async (
+      ctx: BindingPluginContext,
       source: string,
       importer?: string,
       options?: any,
     ): Promise<undefined | ResolveIdResult> => {
       try {
         const value = await hook.call(
-          {} as any,
+          createPluginContext(ctx) as any,
           source,
           importer ? importer : undefined,
           options,
@@ -262,14 +432,57 @@ function resolveId(hook: Plugin['resolveId']) {
   }
 }
 
+function resolveDynamicImport(hook: Plugin['resolveDynamicImport']) {
+  if (hook) {
+    if (typeof hook !== 'function') {
+      return unimplemented()
+    }
+    return async (
+      ctx: BindingPluginContext,
+      specifier: string,
+      importer: string,
+    ): Promise<undefined | ResolveIdResult> => {
+      try {
+        const value = await hook.call(
+          createPluginContext(ctx) as any,
+          specifier,
+          importer,
+          { assertions: {} },
+        )
+        if (value === undefined || value === null) {
+          return
+        }
+        if (typeof value === 'string') {
+          return { id: value }
+        }
+        if (value === false) {
+          return { id: specifier, external: true }
+        }
+        if (value.external === 'absolute' || value.external === 'relative') {
+          throw new Error(
+            `External module type {${value.external}} is not supported yet.`,
+          )
+        }
+        return value as ResolveIdResult
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
 function load(hook: Plugin['load']) {
   if (hook) {
     if (typeof hook !== 'function') {
       return unimplemented()
     }
-    return async (id: string): Promise<undefined | SourceResult> => {
+    return async (
+      ctx: BindingPluginContext,
+      id: string,
+    ): Promise<undefined | SourceResult> => {
       try {
-        const value = await hook.call({} as any, id)
+        const value = await hook.call(createPluginContext(ctx) as any, id)
         if (value === undefined || value === null) {
           return
         }
@@ -280,7 +493,22 @@ function load(hook: Plugin['load']) {
           return
         }
         // TODO other filed
-        return { code: value.code, map: transformSourcemap(value.map) }
+        return {
+          code: value.code,
+          map: transformSourcemap(value.map),
+          moduleSideEffects:
+            typeof value.moduleSideEffects === 'boolean'
+              ? value.moduleSideEffects
+              : undefined,
+          moduleSideEffectsNoTreeshake:
+            value.moduleSideEffects === 'no-treeshake' || undefined,
+          syntheticNamedExports:
+            value.syntheticNamedExports === true
+              ? 'default'
+              : value.syntheticNamedExports || undefined,
+          meta: value.meta ? JSON.stringify(value.meta) : undefined,
+          loader: getLoader(value),
+        }
       } catch (error) {
         console.error(error)
         throw error
@@ -288,3 +516,9 @@ function load(hook: Plugin['load']) {
     }
   }
 }
+
+// Not a field of rollup, it tells how the returned code is parsed, like `{ code, loader: 'ts' }`.
+function getLoader(value: object): string | undefined {
+  const loader = (value as { loader?: unknown }).loader
+  return typeof loader === 'string' ? loader : undefined
+}
@@ -16,9 +16,51 @@ export function createInputOptionsAdapter(
     cwd: process.cwd(),
     external: inputOptions.external ? options.external : undefined,
     resolve: options.resolve,
+    json: options.json,
+    jsx: options.jsx,
+    asset: options.asset,
+    preserveEntrySignatures:
+      options.preserveEntrySignatures === false
+        ? 'false'
+        : options.preserveEntrySignatures,
+    shimMissingExports: options.shimMissingExports,
+    treeshake: normalizeTreeshake(options.rolldownTreeshake),
+    define: options.define,
+    inject: options.inject,
+    ignoredCircularDependencies: options.ignoredCircularDependencies,
+    checks: options.checks,
+    logLevel: options.logLevel,
+    onLog: options.rolldownOnLog
+      ? async (level, log) => options.rolldownOnLog!(level, log)
+      : undefined,
+    maxErrors: options.maxErrors,
   }
 }
 
+function normalizeTreeshake(
+  treeshake: RolldownNormalizedInputOptions['rolldownTreeshake'],
+): BindingInputOptions['treeshake'] {
+  const moduleSideEffects = treeshake?.moduleSideEffects
+  const propertyReadSideEffects = treeshake?.propertyReadSideEffects
+  const rest = {
+    annotations: treeshake?.annotations,
+    tryCatchDeoptimization: treeshake?.tryCatchDeoptimization,
+    ...(propertyReadSideEffects === 'always'
+      ? { propertyReadSideEffectsAlways: true }
+      : { propertyReadSideEffects }),
+  }
+  if (typeof moduleSideEffects === 'function') {
+    return { moduleSideEffectsFn: moduleSideEffects, ...rest }
+  }
+  if (Array.isArray(moduleSideEffects)) {
+    return { moduleSideEffectsPatterns: moduleSideEffects, ...rest }
+  }
+  if (moduleSideEffects === 'no-external') {
+    return { moduleSideEffectsNoExternal: true, ...rest }
+  }
+  return { moduleSideEffects, ...rest }
+}
+
 function normalizeInput(
   input: NormalizedInputOptions['input'],
 ): BindingInputOptions['input'] {
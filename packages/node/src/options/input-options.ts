@@ -4,7 +4,15 @@ import {
   Plugin,
 } from '../rollup-types'
 import { ensureArray, normalizePluginOption } from '../utils'
-import { ResolveOptions } from '@rolldown/node-binding'
+import {
+  AssetOptions,
+  BindingLog,
+  ChecksOptions,
+  InjectImport,
+  JsonOptions,
+  JsxOptions,
+  ResolveOptions,
+} from '@rolldown/node-binding'
 
 // TODO export compat plugin type
 export type RolldownPlugin = Plugin
@@ -13,14 +21,80 @@ export interface InputOptions {
   plugins?: RolldownPlugin[]
   external?: RollupInputOptions['external']
   resolve?: RolldownResolveOptions
+  json?: JsonOptions
+  /** How JSX is transformed, the automatic runtime imports `jsx` from `${importSource}/jsx-runtime`. */
+  jsx?: JsxOptions
+  /** Files with these extensions are copied into the output, and imported as their URLs. */
+  asset?: AssetOptions
+  /** `'allow-extension'` lets entry chunks export more than their entry modules, so shared entries aren't split into facades. */
+  preserveEntrySignatures?: RollupInputOptions['preserveEntrySignatures']
+  shimMissingExports?: RollupInputOptions['shimMissingExports']
+  treeshake?: RolldownTreeshakeOptions
+  /** Replaces global identifiers or member expressions, like `process.env.NODE_ENV`, with expressions. */
+  define?: Record<string, string>
+  /** Imports `[module, exportedName]`, or the default export of a module, into modules referencing the keys as globals. */
+  inject?: Record<string, string | [string, string]>
+  /** Globs of modules, like vendor packages that are intentionally circular, whose cycles are not warned about. */
+  ignoredCircularDependencies?: string[]
+  /** `unresolvedImport: 'warn-external'` treats bare imports that can't be resolved as externals with a warning. */
+  checks?: ChecksOptions
+  /** Logs more verbose than the level are dropped, `'silent'` drops warnings too. Defaults to `'info'`. */
+  logLevel?: LogLevelOption
+  /** Called with each warning and plugin log, returning `'suppress'` drops it and `'error'` fails the build. */
+  onLog?: OnLog
+  /** Errors of modules are collected until there are this many distinct ones, and reported together. Defaults to 100. */
+  maxErrors?: number
+}
+
+export type LogLevelOption = 'silent' | 'warn' | 'info' | 'debug'
+
+export type OnLog = (
+  level: 'warn' | 'info' | 'debug',
+  log: BindingLog,
+) =>
+  | undefined
+  | 'keep'
+  | 'suppress'
+  | 'error'
+  | Promise<undefined | 'keep' | 'suppress' | 'error'>
+
+  /** `false`, `'no-external'`, globs of modules that have side effects, or a function deciding it for each module. */
+  moduleSideEffects?:
+    | boolean
+    | 'no-external'
+    | string[]
+    | ((id: string, external: boolean) => boolean)
+  /** `false` ignores `#__PURE__` annotations. */
+  annotations?: boolean
+  /** `false` assumes reading properties has no side effects, `'always'` assumes it has for known builtins too. */
+  propertyReadSideEffects?: boolean | 'always'
+  /**
+   * `false` analyzes `try` statements like normal blocks, which removes more code but could remove the code
+   * that's expected to throw, like feature detection.
+   */
+  tryCatchDeoptimization?: boolean
 }
 
 export type RolldownResolveOptions = Omit<ResolveOptions, 'alias'> & {
-  alias?: Record<string, string>
+  /** Tried in order, the object form matches its keys as strings. */
+  alias?:
+    | Record<string, string>
+    | { find: string | RegExp; replacement: string }[]
 }
 
 export type RolldownNormalizedInputOptions = NormalizedInputOptions & {
   resolve?: ResolveOptions
+  json?: JsonOptions
+  jsx?: JsxOptions
+  asset?: AssetOptions
+  rolldownTreeshake?: RolldownTreeshakeOptions
+  define?: Record<string, string>
+  inject?: Record<string, InjectImport>
+  ignoredCircularDependencies?: string[]
+  checks?: ChecksOptions
+  logLevel?: LogLevelOption
+  rolldownOnLog?: OnLog
+  maxErrors?: number
 }
 
 export async function normalizeInputOptions(
@@ -32,6 +106,19 @@ export async function normalizeInputOptions(
     plugins: await normalizePluginOption(config.plugins),
     external: getIdMatcher(config.external),
     resolve: getResolve(config.resolve),
+    json: config.json,
+    jsx: config.jsx,
+    asset: config.asset,
+    preserveEntrySignatures: config.preserveEntrySignatures ?? 'strict',
+    shimMissingExports: config.shimMissingExports,
+    rolldownTreeshake: config.treeshake,
+    define: config.define,
+    inject: config.inject ? normalizeInject(config.inject) : undefined,
+    ignoredCircularDependencies: config.ignoredCircularDependencies,
+    checks: config.checks,
+    logLevel: config.logLevel,
+    rolldownOnLog: config.onLog,
+    maxErrors: config.maxErrors,
   }
 }
 
@@ -83,11 +170,39 @@ function getResolve(
   if (resolve) {
     return {
       ...resolve,
-      alias: resolve.alias
-        ? Object.fromEntries(
-            Object.entries(resolve.alias).map(([key, value]) => [key, [value]]),
-          )
-        : undefined,
+      alias: resolve.alias ? normalizeAlias(resolve.alias) : undefined,
     }
   }
 }
+
+function normalizeAlias(
+  alias: NonNullable<RolldownResolveOptions['alias']>,
+): NonNullable<ResolveOptions['alias']> {
+  const items = Array.isArray(alias)
+    ? alias
+    : Object.entries(alias).map(([find, replacement]) => ({
+        find,
+        replacement,
+      }))
+  return items.map(({ find, replacement }) =>
+    find instanceof RegExp
+      ? {
+          findRegex: (find.ignoreCase ? '(?i)' : '') + find.source,
+          replacement,
+        }
+      : { find, replacement },
+  )
+}
+
+function normalizeInject(
+  inject: NonNullable<InputOptions['inject']>,
+): NonNullable<RolldownNormalizedInputOptions['inject']> {
+  return Object.fromEntries(
+    Object.entries(inject).map(([key, value]) => [
+      key,
+      typeof value === 'string'
+        ? { from: value, imported: 'default' }
+        : { from: value[0], imported: value[1] },
+    ]),
+  )
+}
@@ -1,24 +1,84 @@
-import { OutputOptions as RollupOutputOptions } from '../rollup-types'
-import { OutputOptions as BindingOutputOptions } from '@rolldown/node-binding'
+import {
+  GetManualChunk,
+  OutputOptions as RollupOutputOptions,
+} from '../rollup-types'
+import {
+  OutputOptions as BindingOutputOptions,
+  PreRenderedChunk,
+} from '@rolldown/node-binding'
 import { unimplemented } from '../utils'
+import { transformModuleInfo } from '../utils/create-plugin-context'
+import path from 'node:path'
 
 export interface OutputOptions {
   dir?: RollupOutputOptions['dir']
-  format?: 'esm'
+  format?: 'esm' | 'cjs' | 'iife' | 'umd' | 'system' | 'systemjs'
   exports?: RollupOutputOptions['exports']
+  interop?: 'auto' | 'esModule' | 'default' | 'defaultOnly' | 'compat'
+  name?: RollupOutputOptions['name']
+  extend?: RollupOutputOptions['extend']
+  globals?: Record<string, string>
+  paths?: Record<string, string>
   sourcemap?: RollupOutputOptions['sourcemap']
+  sourcemapExcludeSources?: RollupOutputOptions['sourcemapExcludeSources']
+  compact?: RollupOutputOptions['compact']
+  freeze?: RollupOutputOptions['freeze']
+  inlineDynamicImports?: RollupOutputOptions['inlineDynamicImports']
+  banner?: RollupOutputOptions['banner']
+  footer?: RollupOutputOptions['footer']
+  intro?: RollupOutputOptions['intro']
+  outro?: RollupOutputOptions['outro']
+  entryFileNames?: string | ((chunkInfo: PreRenderedChunk) => string)
+  chunkFileNames?: string | ((chunkInfo: PreRenderedChunk) => string)
+  assetFileNames?: string
+  preserveModules?: RollupOutputOptions['preserveModules']
+  preserveModulesRoot?: RollupOutputOptions['preserveModulesRoot']
+  manualChunks?: Record<string, string[]> | GetManualChunk
+  // --- Rolldown specific
+  /** Shortens local names and folds constant expressions besides what `compact` does. */
+  minify?: boolean
+  /** Where legal comments like `/*! ... */` go, `inline` by default. */
+  legalComments?: 'inline' | 'eof' | 'none' | 'external'
+  /** The algorithm used to compute `[hash]` in file names, `xxhash` by default. */
+  hashAlgorithm?: 'xxhash' | 'blake3'
+  /** The number of characters of `[hash]` in file names, 8 by default. */
+  hashLength?: number
+  /** Generate the metafile of esbuild in `metafile` of the output, for analyzers like `esbuild-visualizer`. */
+  metafile?: boolean
 }
 
 function normalizeFormat(
   format: OutputOptions['format'],
 ): BindingOutputOptions['format'] {
-  if (format == null || format === 'esm' || format === 'cjs') {
+  if (
+    format == null ||
+    format === 'esm' ||
+    format === 'cjs' ||
+    format === 'iife' ||
+    format === 'umd' ||
+    format === 'system'
+  ) {
     return format
+  } else if (format === 'systemjs') {
+    return 'system'
   } else {
     return unimplemented(`output.format: ${format}`)
   }
 }
 
+function normalizeHashAlgorithm(
+  hashAlgorithm: OutputOptions['hashAlgorithm'],
+): BindingOutputOptions['hashAlgorithm'] {
+  if (
+    hashAlgorithm == null ||
+    hashAlgorithm === 'xxhash' ||
+    hashAlgorithm === 'blake3'
+  ) {
+    return hashAlgorithm
+  }
+  throw new Error(`unknown output.hashAlgorithm: ${hashAlgorithm}`)
+}
+
 function normalizeSourcemap(
   sourcemap: OutputOptions['sourcemap'],
 ): BindingOutputOptions['sourcemap'] {
@@ -29,24 +89,136 @@ function normalizeSourcemap(
     case 'inline':
       return 'inline'
 
-    case false:
-    case undefined:
     case 'hidden':
       return 'hidden'
 
+    case false:
+    case undefined:
+      return undefined
+
     default:
       throw new Error(`unknown sourcemap: ${sourcemap}`)
   }
 }
 
+function normalizeAddon(
+  addon: OutputOptions['banner'],
+): BindingOutputOptions['banner'] {
+  if (addon == null) {
+    return undefined
+  }
+  if (typeof addon === 'function') {
+    return async (chunk) => addon(chunk as any)
+  }
+  return async () => addon
+}
+
+function normalizeManualChunks(
+  manualChunks: OutputOptions['manualChunks'],
+): BindingOutputOptions['manualChunks'] {
+  if (manualChunks == null) {
+    return undefined
+  }
+  if (typeof manualChunks === 'function') {
+    return async (id, meta) =>
+      manualChunks(id, {
+        getModuleIds: () => meta.getModuleIds()[Symbol.iterator](),
+        getModuleInfo: (id) => {
+          const info = meta.getModuleInfo(id)
+          return info ? transformModuleInfo(info) : null
+        },
+      }) ?? undefined
+  }
+  // Ids in the object form are resolved against the cwd, like entries in `input`.
+  const idToName = new Map<string, string>()
+  // Names are visited in sorted order, so a module listed in multiple chunks goes to the same chunk as on the rust side.
+  for (const name of Object.keys(manualChunks).sort()) {
+    for (const id of manualChunks[name]) {
+      const resolved = path.resolve(id)
+      if (!idToName.has(resolved)) {
+        idToName.set(resolved, name)
+      }
+    }
+  }
+  return async (id) => idToName.get(id)
+}
+
+function normalizeChunkFileNames(
+  fileNames: OutputOptions['entryFileNames'],
+): [string | undefined, BindingOutputOptions['entryFileNamesFn']] {
+  if (typeof fileNames === 'function') {
+    return [undefined, async (chunk) => fileNames(chunk)]
+  }
+  return [fileNames, undefined]
+}
+
 export function normalizeOutputOptions(
   opts: OutputOptions,
 ): BindingOutputOptions {
-  const { dir, format, exports, sourcemap } = opts
+  const {
+    dir,
+    format,
+    exports,
+    interop,
+    sourcemap,
+    sourcemapExcludeSources,
+    compact,
+    minify,
+    legalComments,
+    freeze,
+    inlineDynamicImports,
+    name,
+    extend,
+    globals,
+    paths,
+    banner,
+    footer,
+    intro,
+    outro,
+    entryFileNames,
+    chunkFileNames,
+    assetFileNames,
+    preserveModules,
+    preserveModulesRoot,
+    manualChunks,
+    hashAlgorithm,
+    hashLength,
+    metafile,
+  } = opts
+  const [entryFileNamesTemplate, entryFileNamesFn] =
+    normalizeChunkFileNames(entryFileNames)
+  const [chunkFileNamesTemplate, chunkFileNamesFn] =
+    normalizeChunkFileNames(chunkFileNames)
   return {
     dir: dir,
     format: normalizeFormat(format),
     exports,
+    interop,
     sourcemap: normalizeSourcemap(sourcemap),
+    sourcemapExcludeSources,
+    compact,
+    minify,
+    legalComments,
+    freeze,
+    inlineDynamicImports,
+    name,
+    extend,
+    globals,
+    paths,
+    banner: normalizeAddon(banner),
+    footer: normalizeAddon(footer),
+    intro: normalizeAddon(intro),
+    outro: normalizeAddon(outro),
+    entryFileNames: entryFileNamesTemplate,
+    entryFileNamesFn,
+    chunkFileNames: chunkFileNamesTemplate,
+    chunkFileNamesFn,
+    assetFileNames,
+    preserveModules,
+    preserveModulesRoot,
+    manualChunks: normalizeManualChunks(manualChunks),
+    hashAlgorithm: normalizeHashAlgorithm(hashAlgorithm),
+    hashLength,
+    metafile,
   }
 }
@@ -21,8 +21,12 @@ export class RolldownBuild {
     const output = await this.#bundler.write(bindingOptions)
     return transformToRollupOutput(output)
   }
+
+  async close(): Promise<void> {
+    await this.#bundler.close()
+  }
 }
 
 function _assert() {
-  type _ = TypeAssert<HasProperty<RolldownBuild, 'generate' | 'write'>>
+  type _ = TypeAssert<HasProperty<RolldownBuild, 'generate' | 'write' | 'close'>>
 }
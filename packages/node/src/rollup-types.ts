@@ -8,4 +8,8 @@ export type {
   OutputAsset,
   OutputBundle,
   SourceMapInput,
+  EmittedFile,
+  ResolvedId,
+  ModuleInfo,
+  GetManualChunk,
 } from './rollup'
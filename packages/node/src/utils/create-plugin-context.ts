@@ -0,0 +1,105 @@
+import type {
+  BindingModuleInfo,
+  BindingPluginContext,
+} from '@rolldown/node-binding'
+import type { EmittedFile, ModuleInfo, ResolvedId } from '../rollup-types'
+import { unimplemented } from './index'
+
+// Only the methods implemented on the rust side are available for now.
+export function createPluginContext(ctx: BindingPluginContext) {
+  return {
+    emitFile(file: EmittedFile): string {
+      if (file.type === 'chunk') {
+        return ctx.emitChunk({ name: file.name, id: file.id })
+      }
+      if (file.type !== 'asset') {
+        return unimplemented(`emitFile with type ${file.type}`)
+      }
+      return ctx.emitFile({
+        name: file.name,
+        fileName: file.fileName,
+        source:
+          typeof file.source === 'string' || file.source === undefined
+            ? file.source ?? ''
+            : Buffer.from(file.source),
+      })
+    },
+    getFileName(referenceId: string): string {
+      return ctx.getFileName(referenceId)
+    },
+    getModuleInfo(id: string): ModuleInfo | null {
+      const info = ctx.getModuleInfo(id)
+      return info ? transformModuleInfo(info) : null
+    },
+    getModuleIds(): IterableIterator<string> {
+      return ctx.getModuleIds()[Symbol.iterator]()
+    },
+    // Positions aren't passed to the rust side yet.
+    warn(log: PluginLog): void {
+      ctx.warn(getLogMessage(log))
+    },
+    info(log: PluginLog): void {
+      ctx.info(getLogMessage(log))
+    },
+    debug(log: PluginLog): void {
+      ctx.debug(getLogMessage(log))
+    },
+    /** Throws outside of `transform` hooks. */
+    getCombinedSourcemap() {
+      return { version: 3, ...ctx.getCombinedSourcemap() }
+    },
+    addWatchFile(id: string): void {
+      ctx.addWatchFile(id)
+    },
+    getWatchFiles(): string[] {
+      return ctx.getWatchFiles()
+    },
+    async resolve(
+      source: string,
+      importer?: string,
+      options?: {
+        skipSelf?: boolean
+        kind?: 'import-statement' | 'dynamic-import' | 'require-call'
+      },
+    ): Promise<ResolvedId> {
+      const resolved = await ctx.resolve(source, importer, {
+        // Same as rollup, the calling plugin is skipped by default.
+        skipSelf: options?.skipSelf ?? true,
+        kind: options?.kind,
+      })
+      return {
+        id: resolved.id,
+        external: resolved.external,
+        moduleSideEffects: resolved.moduleSideEffects ?? true,
+        resolvedBy: 'rolldown',
+      } as ResolvedId
+    },
+  }
+}
+
+type PluginLog = string | { message: string } | (() => string | { message: string })
+
+function getLogMessage(log: PluginLog): string {
+  const value = typeof log === 'function' ? log() : log
+  return typeof value === 'string' ? value : value.message
+}
+
+export function transformModuleInfo(info: BindingModuleInfo): ModuleInfo {
+  return {
+    id: info.id,
+    code: info.code ?? null,
+    // The ast isn't passed from the rust side yet.
+    ast: null,
+    isEntry: info.isEntry,
+    isExternal: info.isExternal,
+    importedIds: info.importedIds,
+    dynamicallyImportedIds: info.dynamicallyImportedIds,
+    importers: info.importers,
+    dynamicImporters: info.dynamicImporters,
+    exports: info.exportedBindings,
+    exportedBindings: { '.': info.exportedBindings },
+    hasDefaultExport: info.exportedBindings.includes('default'),
+    hasModuleSideEffects: info.hasModuleSideEffects,
+    meta: JSON.parse(info.meta),
+  } as any
+}
@@ -4,6 +4,7 @@ export * from './normalize-plugin-option'
 export * from './ensure-array'
 export * from './create-bundler'
 export * from './transform-sourcemap'
+export * from './create-plugin-context'
 
 export function arraify<T>(value: T | T[]): T[] {
   return Array.isArray(value) ? value : [value]
@@ -18,13 +18,19 @@ function transformToRollupOutputChunk(
     code: chunk.code,
     fileName: chunk.fileName,
     modules: Object.fromEntries(
-      Object.entries(chunk.modules).map(([key, _]) => [key, {}]),
+      Object.entries(chunk.modules).map(([key, module]) => [
+        key,
+        { renderedLength: module.renderedLength },
+      ]),
     ),
     exports: chunk.exports,
     isEntry: chunk.isEntry,
     facadeModuleId: chunk.facadeModuleId || null,
     isDynamicEntry: chunk.isDynamicEntry,
     moduleIds: chunk.moduleIds,
+    imports: chunk.imports,
+    dynamicImports: chunk.dynamicImports,
+    importedCss: chunk.importedCss,
   }
 }
 
@@ -41,7 +47,7 @@ function transformToRollupOutputAsset(
 export function transformToRollupOutput(
   output: BindingOutputs,
 ): RolldownOutput {
-  const { chunks, assets } = output
+  const { chunks, assets, metafile } = output
   const [firstChunk, ...restChunks] = chunks
   return {
     output: [
@@ -49,6 +55,7 @@ export function transformToRollupOutput(
       ...restChunks.map(transformToRollupOutputChunk),
       ...assets.map(transformToRollupOutputAsset),
     ],
+    metafile: metafile ? JSON.parse(metafile) : undefined,
   }
 }
 
@@ -2,19 +2,55 @@ import type { RollupOptions, RollupOutput } from '@rolldown/node'
 import { expect } from 'vitest'
 import path from 'node:path'
 
+const entry = path.join(__dirname, 'main.js')
+const foo = path.join(__dirname, 'foo.js')
+const lodash = path.join(__dirname, 'node_modules/lodash/index.js')
+
+const calls: [string, string | undefined, boolean][] = []
+
 const config: RollupOptions = {
   external: (
     source: string,
     importer: string | undefined,
     isResolved: boolean,
   ) => {
-    expect(importer).toStrictEqual(path.join(__dirname, 'main.js'))
-    if (source.startsWith('external')) {
-      return true
-    }
+    calls.push([source, importer ?? undefined, isResolved])
+    // Ids could be external before or after they are resolved
+    return (
+      (!isResolved && source.startsWith('external')) ||
+      // Builtins can't be resolved, so they must be external before that
+      source.startsWith('node:') ||
+      (isResolved && source.includes('node_modules')) ||
+      source === './lazy.js'
+    )
   },
 }
 
 export default {
   config,
+  afterTest: (output: RollupOutput) => {
+    // Entries have no importer
+    expect(calls).toContainEqual([entry, undefined, false])
+    expect(calls).toContainEqual([entry, undefined, true])
+    expect(calls).toContainEqual(['./foo', entry, false])
+    expect(calls).toContainEqual([foo, entry, true])
+    expect(calls).toContainEqual(['lodash', entry, false])
+    expect(calls).toContainEqual([lodash, entry, true])
+    // Dynamic imports are checked as well
+    expect(calls).toContainEqual(['./lazy.js', entry, false])
+    // Ids that are external before they are resolved, like builtins, aren't
+    // resolved
+    expect(calls).toContainEqual(['node:fs', entry, false])
+    expect(
+      calls.filter(
+        ([source, , isResolved]) => isResolved && !path.isAbsolute(source),
+      ),
+    ).toStrictEqual([])
+
+    const chunk = output.output[0]
+    expect([...chunk.imports].sort()).toStrictEqual(
+      ['external', 'external-a', lodash, 'node:fs', 'node:path'].sort(),
+    )
+    expect(chunk.dynamicImports).toStrictEqual(['./lazy.js'])
+  },
 }
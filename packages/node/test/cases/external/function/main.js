@@ -1,4 +1,8 @@
 import external from 'external'
 import externalA from 'external-a'
+import _ from 'lodash'
+import fs from 'node:fs'
+import path from 'node:path'
 import './foo'
-console.log(external, externalA)
+import('./lazy.js')
+console.log(external, externalA, _, fs, path)
@@ -0,0 +1,49 @@
+import type {
+  InputOptions,
+  OutputOptions,
+  RollupOptions,
+  RollupOutput,
+} from '@rolldown/node'
+import { rolldown } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const renderChunkFn = vi.fn()
+
+const outputOptions = (banner: string): OutputOptions => ({
+  dir: path.join(__dirname, 'dist'),
+  entryFileNames: '[name]-[hash].js',
+  banner,
+})
+
+const config: RollupOptions = {
+  input: path.join(__dirname, 'main.js'),
+  plugins: [
+    {
+      name: 'test-plugin',
+      renderChunk(code) {
+        renderChunkFn()
+        // Banners are added after `renderChunk` hooks
+        expect(code).not.toContain('/* banner')
+      },
+    },
+  ],
+  output: outputOptions('/* banner a */'),
+}
+
+export default {
+  config,
+  afterTest: async (output: RollupOutput) => {
+    expect(renderChunkFn).toHaveBeenCalledTimes(1)
+    expect(output.output[0].code.startsWith('/* banner a */\n')).toBe(true)
+
+    // The banner is part of the content hashed for file names
+    const build = await rolldown(config as InputOptions)
+    try {
+      const changed = await build.generate(outputOptions('/* banner b */'))
+      expect(changed.output[0].fileName).not.toBe(output.output[0].fileName)
+    } finally {
+      await build.close()
+    }
+  },
+}
@@ -0,0 +1,29 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const bannerFn = vi.fn()
+
+const config: RollupOptions = {
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    banner: (chunk) => {
+      bannerFn()
+      expect(chunk.fileName).toBe('main.js')
+      return '#!/usr/bin/env node'
+    },
+    footer: async () => '/* footer */',
+    intro: '/* intro */',
+    outro: '',
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(bannerFn).toHaveBeenCalledTimes(1)
+    const code = output.output[0].code
+    expect(code.startsWith('#!/usr/bin/env node\n/* intro */\n')).toBe(true)
+    expect(code.endsWith('/* footer */')).toBe(true)
+  },
+}
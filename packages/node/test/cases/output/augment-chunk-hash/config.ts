@@ -0,0 +1,53 @@
+import type {
+  InputOptions,
+  OutputOptions,
+  RollupOptions,
+  RollupOutput,
+} from '@rolldown/node'
+import { rolldown } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+// Returned by `augmentChunkHash`, which is changed by later builds
+let version: string | undefined
+
+const config: RollupOptions = {
+  input: path.join(__dirname, 'main.js'),
+  plugins: [
+    {
+      name: 'augment-chunk-hash',
+      augmentChunkHash() {
+        return version
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    entryFileNames: '[name]-[hash].js',
+  },
+}
+
+async function generate(newVersion: string | undefined) {
+  version = newVersion
+  const build = await rolldown(config as InputOptions)
+  try {
+    return await build.generate(config.output as OutputOptions)
+  } finally {
+    await build.close()
+  }
+}
+
+export default {
+  config,
+  afterTest: async (output: RollupOutput) => {
+    const v1 = await generate('v1')
+    const v2 = await generate('v2')
+    const v1Again = await generate('v1')
+
+    // Only file names change, the code is the same
+    expect(v1.output[0].code).toBe(output.output[0].code)
+    expect(v1.output[0].fileName).not.toBe(output.output[0].fileName)
+    expect(v2.output[0].fileName).not.toBe(v1.output[0].fileName)
+    expect(v1Again.output[0].fileName).toBe(v1.output[0].fileName)
+  },
+}
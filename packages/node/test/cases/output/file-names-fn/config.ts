@@ -0,0 +1,55 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const chunkFileNamesFn = vi.fn()
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'emit-asset',
+      buildStart() {
+        this.emitFile({
+          type: 'asset',
+          name: 'logo.svg',
+          source: '<svg></svg>',
+        })
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    entryFileNames: '[name]-[hash:6]-[hash].js',
+    // File names could be decided per chunk
+    chunkFileNames: (chunk) => {
+      chunkFileNamesFn()
+      expect(chunk.isDynamicEntry).toBe(true)
+      expect(chunk.exports).toStrictEqual(['lazy'])
+      return `${chunk.name}/[name]-[hash:12].js`
+    },
+    assetFileNames: '[hash:4]-[hash:12]-[hash][extname]',
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(chunkFileNamesFn).toHaveBeenCalledTimes(1)
+    const [main, lazy] = output.output
+    const [, name, shortHash, hash] = main.fileName.match(
+      /^(\w+)-(\w+)-(\w+)\.js$/,
+    )!
+    expect([name, shortHash.length, hash.length]).toStrictEqual(['main', 6, 8])
+    expect(hash.startsWith(shortHash)).toBe(true)
+    expect(lazy.fileName).toMatch(/^lazy_js\/lazy_js-\w{12}\.js$/)
+    expect(main.code).toContain(lazy.fileName)
+    // Assets support hashes of custom lengths too
+    const logo = output.output.find((item) => item.type === 'asset')!
+    const [short, long, assetHash] = logo.fileName.split(/[-.]/)
+    expect([short.length, long.length, assetHash.length]).toStrictEqual([
+      4, 12, 8,
+    ])
+    expect(long.startsWith(assetHash)).toBe(true)
+    expect(assetHash.startsWith(short)).toBe(true)
+  },
+}
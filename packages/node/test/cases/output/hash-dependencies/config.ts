@@ -0,0 +1,79 @@
+import type {
+  InputOptions,
+  OutputOptions,
+  RollupOptions,
+  RollupOutput,
+} from '@rolldown/node'
+import { rolldown } from '@rolldown/node'
+import type { OutputChunk } from 'rollup'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+// Appended to the chunk of `dep.js`, which is changed by later builds
+let comment = 'a'
+
+const config: RollupOptions = {
+  input: path.join(__dirname, 'main.js'),
+  plugins: [
+    {
+      name: 'comment-dep',
+      renderChunk(code, chunk) {
+        if (chunk.facadeModuleId === path.join(__dirname, 'dep.js')) {
+          return `${code}\n// ${comment}`
+        }
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    chunkFileNames: '[name]-[hash].js',
+  },
+}
+
+async function generate(newComment: string) {
+  comment = newComment
+  const build = await rolldown(config as InputOptions)
+  try {
+    return await build.generate(config.output as OutputOptions)
+  } finally {
+    await build.close()
+  }
+}
+
+function findFileName(output: RollupOutput, prefix: string) {
+  const fileName = output.output
+    .map((item) => item.fileName)
+    .find((fileName) => fileName.startsWith(prefix))
+  expect(fileName).toBeDefined()
+  return fileName!
+}
+
+export default {
+  config,
+  afterTest: async (output: RollupOutput) => {
+    const changed = await generate('b')
+    const again = await generate('a')
+
+    // `lazy.js` doesn't change, but it imports `dep.js` whose content is changed by `renderChunk`
+    expect(findFileName(changed, 'dep_js-')).not.toBe(
+      findFileName(output, 'dep_js-'),
+    )
+    expect(findFileName(changed, 'lazy_js-')).not.toBe(
+      findFileName(output, 'lazy_js-'),
+    )
+    expect(findFileName(again, 'lazy_js-')).toBe(
+      findFileName(output, 'lazy_js-'),
+    )
+
+    const main = changed.output.find(
+      (item) => item.fileName === 'main.js',
+    ) as OutputChunk
+    expect(main.code).toContain(findFileName(changed, 'lazy_js-'))
+    const lazy = changed.output.find(
+      (item) => item.fileName === findFileName(changed, 'lazy_js-'),
+    ) as OutputChunk
+    expect(lazy.dynamicImports).toStrictEqual([
+      findFileName(changed, 'dep_js-'),
+    ])
+  },
+}
@@ -0,0 +1,22 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const config: RollupOptions = {
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    chunkFileNames: '[name]-[hash].js',
+    hashAlgorithm: 'blake3',
+    hashLength: 12,
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const lazy = output.output.find((item) => item.fileName.startsWith('lazy'))
+    expect(lazy?.fileName).toMatch(/^lazy_js-[0-9a-f]{12}\.js$/)
+    const main = output.output.find((item) => item.fileName === 'main.js')
+    expect(main?.type === 'chunk' && main.code).toContain(lazy?.fileName)
+  },
+}
@@ -0,0 +1,27 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const config: RollupOptions = {
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    // Modules imported by the entry go to their own chunk
+    manualChunks: (id, { getModuleIds, getModuleInfo }) => {
+      expect([...getModuleIds()]).toContain(id)
+      const importers = getModuleInfo(id)?.importers ?? []
+      if (importers.some((importer) => getModuleInfo(importer)?.isEntry)) {
+        return 'lib'
+      }
+    },
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const lib = output.output.find((item) => item.fileName.startsWith('lib'))
+    expect(lib?.type === 'chunk' && lib.moduleIds.length).toBe(2)
+    const main = output.output.find((item) => item.fileName === 'main.js')
+    expect(main?.type === 'chunk' && main.code).toContain(lib?.fileName)
+  },
+}
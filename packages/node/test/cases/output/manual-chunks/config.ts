@@ -0,0 +1,26 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const config: RollupOptions = {
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    manualChunks: (id) => {
+      if (id.includes('vendor')) {
+        return 'vendor'
+      }
+    },
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const vendor = output.output.find((item) =>
+      item.fileName.startsWith('vendor'),
+    )
+    expect(vendor?.type === 'chunk' && vendor.code).toContain("'lib'")
+    const main = output.output.find((item) => item.fileName === 'main.js')
+    expect(main?.type === 'chunk' && main.code).toContain(vendor?.fileName)
+  },
+}
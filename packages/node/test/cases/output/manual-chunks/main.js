@@ -0,0 +1,3 @@
+import { lib } from './vendor/lib.js'
+
+console.log(lib)
@@ -0,0 +1,50 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import fs from 'node:fs'
+import path from 'node:path'
+
+// Paths of modules are relative to the cwd
+const relative = (file: string) =>
+  path.relative(process.cwd(), path.join(__dirname, file))
+
+const config: RollupOptions = {
+  input: path.join(__dirname, 'main.js'),
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    metafile: true,
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const metafile = output.metafile!
+    const [a, lazy, main] = ['a.js', 'lazy.js', 'main.js'].map(relative)
+    expect(Object.keys(metafile.inputs).sort()).toStrictEqual(
+      [a, lazy, main].sort(),
+    )
+    expect(
+      metafile.inputs[main].imports.map(({ path, kind }) => [path, kind]),
+    ).toStrictEqual([
+      [a, 'import-statement'],
+      [lazy, 'dynamic-import'],
+    ])
+    expect(metafile.inputs[a].bytes).toBe(
+      fs.readFileSync(path.join(__dirname, 'a.js')).length,
+    )
+
+    const mainOutput = metafile.outputs['main.js']
+    expect(mainOutput.entryPoint).toBe(main)
+    expect(mainOutput.bytes).toBe(Buffer.byteLength(output.output[0].code))
+    // `unused` is tree shaken, so `a.js` contributes less than its size
+    const aBytes = mainOutput.inputs[a].bytesInOutput
+    expect(aBytes).toBeGreaterThan(0)
+    expect(aBytes).toBeLessThan(metafile.inputs[a].bytes)
+    expect(mainOutput.inputs[lazy]).toBeUndefined()
+    expect(
+      mainOutput.imports.some(
+        (item) => item.kind === 'dynamic-import' && !item.external,
+      ),
+    ).toBe(true)
+  },
+}
@@ -0,0 +1,4 @@
+import { used } from './a.js'
+
+console.log(used)
+import('./lazy.js')
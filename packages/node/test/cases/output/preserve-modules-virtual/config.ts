@@ -0,0 +1,38 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'virtual-module',
+      resolveId(id) {
+        if (id === 'virtual:config') {
+          return '\0virtual:config'
+        }
+      },
+      load(id) {
+        if (id === '\0virtual:config') {
+          return 'export const debug = false'
+        }
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    preserveModules: true,
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const fileNames = output.output.map((item) => item.fileName).sort()
+    // `unused.js` is tree shaken, since it has no side effects
+    expect(fileNames).toStrictEqual(['_virtual/virtual_config.js', 'main.js'])
+    const main = output.output.find((item) => item.fileName === 'main.js')
+    expect(main?.type === 'chunk' && main.code).toContain(
+      'from "./_virtual/virtual_config.js"',
+    )
+  },
+}
@@ -0,0 +1,4 @@
+import { debug } from 'virtual:config'
+import './unused.js'
+
+console.log(debug)
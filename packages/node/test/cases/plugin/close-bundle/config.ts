@@ -0,0 +1,28 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+
+const writeBundleFn = vi.fn()
+const closeBundleFn = vi.fn()
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'test-plugin',
+      writeBundle: () => {
+        writeBundleFn()
+      },
+      closeBundle: () => {
+        closeBundleFn()
+        // `closeBundle` is the last hook
+        expect(writeBundleFn).toHaveBeenCalledTimes(1)
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(closeBundleFn).toHaveBeenCalledTimes(1)
+  },
+}
@@ -0,0 +1,51 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+const plain = path.join(__dirname, './plain.js')
+
+const mappings: Record<string, string> = {}
+const sources: Record<string, string[]> = {}
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'header',
+      transform(code, id) {
+        if (id === entry) {
+          // The moved lines are mapped back to the original ones
+          return {
+            code: `// header\n${code}`,
+            map: {
+              mappings: ';AAAA;AACA',
+              sources: ['main.js'],
+              sourcesContent: [code],
+              names: [],
+            },
+          }
+        }
+      },
+    },
+    {
+      name: 'combined-sourcemap',
+      transform(_code, id) {
+        const map = this.getCombinedSourcemap()
+        mappings[id] = map.mappings
+        sources[id] = map.sources
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (_output: RollupOutput) => {
+    // Composed with the map of the previous transform
+    expect(mappings[entry]).toBe(';AAAA;AACA')
+    // Without maps of previous transforms, the code is mapped to itself
+    expect(sources[plain]).toStrictEqual([plain])
+    expect(mappings[plain]).toBe('AAAA;AACA')
+  },
+}
@@ -0,0 +1,2 @@
+import './plain.js'
+console.log(1)
\ No newline at end of file
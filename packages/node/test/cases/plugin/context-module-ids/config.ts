@@ -0,0 +1,42 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const idsInLoad: string[][] = []
+let idsInRenderChunk: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'module-ids',
+      resolveId(id) {
+        if (id === 'node:fs') {
+          return { id, external: true }
+        }
+      },
+      load() {
+        idsInLoad.push([...this.getModuleIds()].sort())
+      },
+      renderChunk() {
+        idsInRenderChunk = [...this.getModuleIds()].sort()
+        return null
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    // Imports of `main.js` are loaded once it's parsed, before the graph is complete
+    expect(idsInLoad).toStrictEqual([[], [entry, 'node:fs']])
+    expect(idsInRenderChunk).toStrictEqual([
+      path.join(__dirname, './a.js'),
+      entry,
+      'node:fs',
+    ])
+  },
+}
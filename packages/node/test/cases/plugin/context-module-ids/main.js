@@ -0,0 +1,4 @@
+import { a } from './a.js'
+import fs from 'node:fs'
+
+fs.write(a)
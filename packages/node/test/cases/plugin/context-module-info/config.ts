@@ -0,0 +1,61 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+const foo = path.join(__dirname, './foo.js')
+const lazy = path.join(__dirname, './lazy.js')
+
+const renderChunkFn = vi.fn()
+
+const config: RollupOptions = {
+  input: entry,
+  external: ['node:fs'],
+  plugins: [
+    {
+      name: 'test-plugin',
+      renderChunk(_code, chunk) {
+        if (chunk.facadeModuleId !== entry) {
+          return null
+        }
+        renderChunkFn()
+        expect([...this.getModuleIds()].sort()).toStrictEqual(
+          [entry, foo, lazy, 'node:fs'].sort(),
+        )
+        expect(this.getModuleInfo(entry)).toMatchObject({
+          id: entry,
+          isEntry: true,
+          isExternal: false,
+          importedIds: ['node:fs', foo],
+          dynamicallyImportedIds: [lazy],
+          importers: [],
+          hasModuleSideEffects: true,
+        })
+        expect(this.getModuleInfo(foo)).toMatchObject({
+          isEntry: false,
+          importers: [entry],
+          dynamicImporters: [],
+        })
+        expect(this.getModuleInfo(lazy)).toMatchObject({
+          importers: [],
+          dynamicImporters: [entry],
+        })
+        expect(this.getModuleInfo('node:fs')).toMatchObject({
+          isExternal: true,
+          code: null,
+          importers: [entry],
+          hasModuleSideEffects: true,
+        })
+        expect(this.getModuleInfo(path.join(__dirname, './none.js'))).toBe(null)
+        return null
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(renderChunkFn).toHaveBeenCalledTimes(1)
+  },
+}
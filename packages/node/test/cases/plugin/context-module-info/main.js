@@ -0,0 +1,6 @@
+import fs from 'node:fs'
+import { foo } from './foo'
+
+console.log(fs, foo)
+
+import('./lazy')
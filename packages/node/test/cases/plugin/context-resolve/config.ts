@@ -0,0 +1,65 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const resolveIdFn = vi.fn()
+const transformFn = vi.fn()
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'external-plugin',
+      resolveId(id) {
+        if (id.startsWith('node:')) {
+          return { id, external: true, moduleSideEffects: false }
+        }
+      },
+    },
+    {
+      name: 'test-plugin',
+      async resolveId(id, importer) {
+        if (id !== './foo') {
+          return
+        }
+        resolveIdFn()
+        // The hook of this plugin is skipped, so it isn't called recursively.
+        const resolved = await this.resolve(id, importer, {
+          skipSelf: true,
+        })
+        expect(resolved).toMatchObject({
+          id: path.join(__dirname, './foo.js'),
+          external: false,
+        })
+        return resolved
+      },
+      async transform(code, id) {
+        if (id !== entry) {
+          return
+        }
+        transformFn()
+        // Ids are resolved by hooks of other plugins too
+        expect(await this.resolve('node:fs', id)).toMatchObject({
+          id: 'node:fs',
+          external: true,
+          moduleSideEffects: false,
+        })
+        expect(await this.resolve('./foo', id)).toMatchObject({
+          id: path.join(__dirname, './foo.js'),
+          external: false,
+        })
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(resolveIdFn).toHaveBeenCalledTimes(1)
+    expect(transformFn).toHaveBeenCalledTimes(1)
+    expect(output.output[0].code).toContain("'foo'")
+  },
+}
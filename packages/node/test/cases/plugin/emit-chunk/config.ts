@@ -0,0 +1,63 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import type { OutputChunk } from 'rollup'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const generateBundleFn = vi.fn()
+let referenceId: string
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'test-plugin',
+      transform(code, id) {
+        if (id === entry) {
+          referenceId = this.emitFile({
+            type: 'chunk',
+            id: path.join(__dirname, './worker.js'),
+          })
+          // Emitting the same id again reuses the chunk
+          expect(
+            this.emitFile({
+              type: 'chunk',
+              id: path.join(__dirname, './worker.js'),
+            }),
+          ).toBe(referenceId)
+          return `${code}\nexport const worker = ${JSON.stringify(
+            this.getFileName(referenceId),
+          )}`
+        }
+      },
+      generateBundle() {
+        generateBundleFn()
+        expect(this.getFileName(referenceId)).toBe('worker.js')
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(generateBundleFn).toHaveBeenCalledTimes(1)
+    // Emitted chunks are bundled once, and never as assets
+    const workers = output.output.filter(
+      (item) => item.fileName === 'worker.js',
+    )
+    expect(workers).toHaveLength(1)
+    const worker = output.output.find(
+      (item) => item.fileName === 'worker.js',
+    ) as OutputChunk
+    expect(worker.type).toBe('chunk')
+    expect(worker.isEntry).toBe(true)
+    expect(worker.isDynamicEntry).toBe(false)
+    expect(worker.facadeModuleId).toBe(path.join(__dirname, './worker.js'))
+    // Modules shared with other entries are split into a common chunk
+    expect(worker.moduleIds).not.toContain(path.join(__dirname, './shared.js'))
+    const main = output.output.find((item) => item.fileName === 'main.js')
+    expect(main?.type === 'chunk' && main.code).toContain('worker.js')
+  },
+}
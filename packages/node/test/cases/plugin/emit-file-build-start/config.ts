@@ -0,0 +1,60 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import type { OutputChunk } from 'rollup'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+let referenceIds: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'emit-files',
+      buildStart() {
+        referenceIds = [
+          this.emitFile({
+            type: 'chunk',
+            name: 'worker',
+            id: path.join(__dirname, './worker-entry.js'),
+          }),
+          this.emitFile({
+            type: 'asset',
+            name: 'manifest.json',
+            source: '{}',
+          }),
+        ]
+      },
+      transform(code, id) {
+        if (id === entry) {
+          // File names of files emitted in `buildStart` are known while transforming
+          const fileNames = referenceIds.map((referenceId) =>
+            this.getFileName(referenceId),
+          )
+          return `${code}\nexport const files = ${JSON.stringify(fileNames)}`
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const worker = output.output.find(
+      (item) => item.fileName === 'worker.js',
+    ) as OutputChunk
+    expect(worker.isEntry).toBe(true)
+    expect(worker.facadeModuleId).toBe(
+      path.join(__dirname, './worker-entry.js'),
+    )
+    const manifest = output.output.find((item) =>
+      item.fileName.startsWith('assets/manifest-'),
+    )
+    expect(manifest?.type).toBe('asset')
+    expect(output.output[0].code).toContain(
+      `const files = ['worker.js', '${manifest!.fileName}']`,
+    )
+  },
+}
@@ -0,0 +1,53 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+let referenceIds: string[] = []
+let fileNames: string[] = []
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'emit-assets',
+      buildStart() {
+        referenceIds = [
+          this.emitFile({ type: 'asset', name: 'a.txt', source: 'same' }),
+          this.emitFile({ type: 'asset', name: 'b.txt', source: 'same' }),
+          this.emitFile({ type: 'asset', name: 'a.txt', source: 'other' }),
+        ]
+      },
+      generateBundle() {
+        referenceIds.push(
+          this.emitFile({ type: 'asset', name: 'a.txt', source: 'another' }),
+        )
+        fileNames = referenceIds.map((referenceId) =>
+          this.getFileName(referenceId),
+        )
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    assetFileNames: 'assets/[name][extname]',
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    // Assets with the same source share a file name, and colliding names are
+    // numbered in the order they are emitted
+    expect(fileNames).toStrictEqual([
+      'assets/a.txt',
+      'assets/a.txt',
+      'assets/a2.txt',
+      'assets/a3.txt',
+    ])
+    const assets = output.output.filter((item) => item.type === 'asset')
+    expect(assets.map((asset) => asset.fileName)).toStrictEqual([
+      'assets/a.txt',
+      'assets/a2.txt',
+      'assets/a3.txt',
+    ])
+  },
+}
@@ -0,0 +1,78 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+// There is no such file on disk, it's loaded by the plugin.
+const logoId = path.join(__dirname, './logo.svg')
+
+let lateFileNames: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'test-plugin',
+      buildStart() {
+        const referenceId = this.emitFile({
+          type: 'asset',
+          // `fileName` takes precedence over `name`
+          name: 'ignored.txt',
+          fileName: 'robots.txt',
+          source: 'User-agent: *',
+        })
+        expect(this.getFileName(referenceId)).toBe('robots.txt')
+        expect(() => this.getFileName('unknown')).toThrow(
+          'Unable to get file name for unknown file "unknown"',
+        )
+      },
+      resolveId(id) {
+        if (id === './logo.svg') {
+          return logoId
+        }
+      },
+      load(id) {
+        if (id === logoId) {
+          const referenceId = this.emitFile({
+            type: 'asset',
+            name: 'logo.svg',
+            source: new TextEncoder().encode('<svg></svg>'),
+          })
+          return `export default ${JSON.stringify(this.getFileName(referenceId))}`
+        }
+      },
+      generateBundle() {
+        // Assets emitted here get their file names immediately
+        lateFileNames = ['a', 'b'].map((source) =>
+          this.getFileName(this.emitFile({ type: 'asset', source })),
+        )
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    assetFileNames: 'static/[name]-[hash][extname]',
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const fileNames = output.output.map((item) => item.fileName)
+    // Every emitted asset is added to the bundle once
+    expect(new Set(fileNames).size).toBe(fileNames.length)
+    expect(fileNames).toContain('robots.txt')
+    const logo = fileNames.find((fileName) =>
+      /^static\/logo-[0-9a-f]{8}\.svg$/.test(fileName),
+    )
+    expect(logo).toBeDefined()
+    const chunk = output.output.find((item) => item.fileName === 'main.js')
+    expect(chunk?.type === 'chunk' && chunk.code).toContain(logo)
+    // Hashes depend on the sources of assets
+    const [a, b] = lateFileNames
+    expect(a).toMatch(/^static\/asset-[0-9a-f]{8}$/)
+    expect(b).toMatch(/^static\/asset-[0-9a-f]{8}$/)
+    expect(a).not.toBe(b)
+    expect(fileNames).toEqual(expect.arrayContaining(lateFileNames))
+  },
+}
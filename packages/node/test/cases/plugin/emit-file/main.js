@@ -0,0 +1 @@
+export { default as logo } from './logo.svg'
@@ -0,0 +1,49 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import type { OutputAsset, OutputChunk } from 'rollup'
+import { expect } from 'vitest'
+
+type ChunkWithCss = OutputChunk & { importedCss: string[] }
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'link-css',
+      generateBundle(_options, bundle) {
+        const links = Object.values(bundle)
+          .flatMap((output) =>
+            output.type === 'chunk' && output.isEntry
+              ? (output as ChunkWithCss).importedCss
+              : [],
+          )
+          .map((fileName) => `<link rel="stylesheet" href="${fileName}">`)
+        this.emitFile({
+          type: 'asset',
+          fileName: 'index.html',
+          source: links.join('\n'),
+        })
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const main = output.output[0] as ChunkWithCss
+    // CSS of modules in the same chunk are concatenated into one asset
+    expect(main.importedCss).toHaveLength(1)
+    const [cssFileName] = main.importedCss
+    expect(cssFileName).toMatch(/^assets\/main-/)
+    const css = output.output.find(
+      (item) => item.fileName === cssFileName,
+    ) as OutputAsset
+    expect(String(css.source)).toContain('color: red')
+    expect(String(css.source)).toContain('color: blue')
+    const html = output.output.find(
+      (item) => item.fileName === 'index.html',
+    ) as OutputAsset
+    expect(String(html.source)).toBe(
+      `<link rel="stylesheet" href="${cssFileName}">`,
+    )
+  },
+}
@@ -0,0 +1,54 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import type { OutputAsset, OutputChunk } from 'rollup'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'manifest-plugin',
+      generateBundle(_options, bundle) {
+        const manifest = Object.fromEntries(
+          Object.values(bundle)
+            .filter((item): item is OutputChunk => item.type === 'chunk')
+            .map((chunk) => [
+              chunk.fileName,
+              {
+                facadeModuleId: chunk.facadeModuleId,
+                imports: chunk.imports,
+                dynamicImports: chunk.dynamicImports,
+                exports: chunk.exports,
+                modules: Object.keys(chunk.modules),
+              },
+            ]),
+        )
+        this.emitFile({
+          type: 'asset',
+          fileName: 'manifest.json',
+          source: JSON.stringify(manifest),
+        })
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const asset = output.output.find(
+      (item) => item.fileName === 'manifest.json',
+    ) as OutputAsset
+    const manifest = JSON.parse(String(asset.source))
+    const main = manifest['main.js']
+    expect(main.facadeModuleId).toBe(entry)
+    expect(main.exports).toStrictEqual(['value'])
+    expect(main.dynamicImports).toHaveLength(1)
+    expect(manifest[main.dynamicImports[0]].facadeModuleId).toBe(
+      path.join(__dirname, './lazy.js'),
+    )
+    expect(main.modules).toContain(path.join(__dirname, './foo.js'))
+  },
+}
@@ -0,0 +1,5 @@
+import { foo } from './foo.js'
+
+export const value = foo
+
+import('./lazy.js')
@@ -0,0 +1,54 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+
+// Not valid UTF-8
+const image = new Uint8Array([0x89, 0x50, 0x4e, 0x47, 0xff, 0x00])
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'emit-plugin',
+      generateBundle(_options, bundle) {
+        this.emitFile({ type: 'asset', fileName: 'emitted.txt', source: 'a' })
+        this.emitFile({ type: 'asset', fileName: 'deleted.txt', source: 'b' })
+        this.emitFile({ type: 'asset', fileName: 'image.png', source: image })
+        bundle['added.txt'] = {
+          type: 'asset',
+          fileName: 'added.txt',
+          source: 'c',
+        } as any
+      },
+    },
+    {
+      name: 'delete-plugin',
+      generateBundle(_options, bundle) {
+        // Files emitted by previous plugins are visible here
+        expect(Object.keys(bundle).sort()).toStrictEqual([
+          'added.txt',
+          'deleted.txt',
+          'emitted.txt',
+          'image.png',
+          'main.js',
+        ])
+        delete bundle['deleted.txt']
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const fileNames = output.output.map((item) => item.fileName).sort()
+    expect(fileNames).toStrictEqual([
+      'added.txt',
+      'emitted.txt',
+      'image.png',
+      'main.js',
+    ])
+    const asset = output.output.find((item) => item.fileName === 'image.png')
+    expect(asset?.type === 'asset' && [...asset.source]).toStrictEqual([
+      ...image,
+    ])
+  },
+}
@@ -0,0 +1,40 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const textId = path.join(__dirname, './a.txt')
+
+const calls: string[] = []
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'import-attributes',
+      resolveId(source, importer, options) {
+        if (!importer) {
+          return
+        }
+        const { attributes } = options as unknown as {
+          attributes: Record<string, string>
+        }
+        calls.push(`${source} ${JSON.stringify(attributes)}`)
+      },
+      load(id) {
+        if (id === textId) {
+          return "export default 'text'"
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(calls.sort()).toStrictEqual([
+      './a.txt {"type":"text"}',
+      './b.js {"type":"js"}',
+    ])
+    expect(output.output[0].code).toContain("'text'")
+  },
+}
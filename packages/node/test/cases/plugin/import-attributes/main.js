@@ -0,0 +1,4 @@
+import text from './a.txt' with { type: 'text' }
+
+import('./b.js', { with: { type: 'js' } })
+console.log(text)
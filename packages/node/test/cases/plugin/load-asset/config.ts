@@ -0,0 +1,32 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const pixelId = path.join(__dirname, './pixel.gif')
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'asset-loader',
+      // `gif` isn't in `asset.extensions`
+      load(id) {
+        if (id === pixelId) {
+          return { code: 'GIF89a', loader: 'asset' } as any
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const pixel = output.output.find((item) =>
+      item.fileName.startsWith('assets/pixel-'),
+    )
+    expect(pixel?.type === 'asset' && String(pixel.source)).toBe('GIF89a')
+    expect(output.output[0].code).toContain(
+      `var pixel_default = '${pixel!.fileName}'`,
+    )
+  },
+}
@@ -0,0 +1,3 @@
+import pixel from './pixel.gif'
+
+export { pixel }
@@ -0,0 +1,49 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const libId = path.join(__dirname, './lib.js')
+const keptId = path.join(__dirname, './kept.js')
+
+const metas: string[] = []
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'load-options',
+      load(id) {
+        if (id === libId) {
+          return {
+            code: 'const lib = { a: 1, b: 2 }\nexport default lib\nexport const c = 3',
+            syntheticNamedExports: true,
+          }
+        }
+        if (id === keptId) {
+          return {
+            code: 'function unused() {}\nexport const value = 1',
+            moduleSideEffects: 'no-treeshake',
+            meta: { commonjs: { isCommonJS: true } },
+          }
+        }
+      },
+      moduleParsed(moduleInfo) {
+        if (Object.keys(moduleInfo.meta).length > 0) {
+          metas.push(`${moduleInfo.id} ${JSON.stringify(moduleInfo.meta)}`)
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const code = output.output[0].code
+    // `a` is read from the default export of `lib.js`
+    expect(code).toContain('console.log(lib_default.a, c)')
+    expect(code).toContain('function unused()')
+    expect(metas).toStrictEqual([
+      `${keptId} {"commonjs":{"isCommonJS":true}}`,
+    ])
+  },
+}
@@ -0,0 +1,4 @@
+import { a, c } from './lib.js'
+import './kept.js'
+
+console.log(a, c)
@@ -0,0 +1,61 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'reexport-all',
+      resolveId(id) {
+        if (id === 'virtual:all') {
+          return '\0virtual:all'
+        }
+        // Resolved ids of virtual modules are imported by `virtual:all` as they are
+        if (id.startsWith('\0')) {
+          return id
+        }
+      },
+      async load(id) {
+        if (id !== '\0virtual:all') {
+          return
+        }
+        let code = ''
+        for (const specifier of ['virtual:config', './dep.js']) {
+          const resolved = await this.resolve(specifier, entry)
+          code += `export * from ${JSON.stringify(resolved!.id)};\n`
+        }
+        return code
+      },
+    },
+    {
+      name: 'virtual-module',
+      resolveId(id) {
+        if (id === 'virtual:config') {
+          return '\0virtual:config'
+        }
+      },
+      load(id) {
+        if (id === '\0virtual:config') {
+          return 'export const debug = false'
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const main = output.output[0]
+    expect(main.moduleIds).toStrictEqual([
+      '\0virtual:config',
+      path.join(__dirname, './dep.js'),
+      '\0virtual:all',
+      entry,
+    ])
+    expect(main.code).toContain('console.log(debug, value)')
+  },
+}
@@ -0,0 +1,3 @@
+import { debug, value } from 'virtual:all'
+
+console.log(debug, value)
@@ -0,0 +1,43 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const pureId = path.join(__dirname, './pure.js')
+
+const checkedIds: string[] = []
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'test-plugin',
+      load(id) {
+        if (id === pureId) {
+          return { code: "console.log('pure')", moduleSideEffects: false }
+        }
+      },
+    },
+  ],
+  treeshake: {
+    moduleSideEffects: (id, external) => {
+      expect(external).toBe(false)
+      checkedIds.push(id)
+      return !id.endsWith('checked.js')
+    },
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const code = output.output[0].code
+    // `load` overrides the function for `pure.js`
+    expect(code).not.toContain("console.log('pure')")
+    expect(code).toContain("console.log('impure')")
+    expect(code).not.toContain("console.log('checked')")
+    expect(checkedIds.sort()).toStrictEqual(
+      ['checked.js', 'impure.js', 'main.js', 'pure.js'].map((file) =>
+        path.join(__dirname, file),
+      ),
+    )
+  },
+}
@@ -0,0 +1,3 @@
+import './pure.js'
+import './impure.js'
+import './checked.js'
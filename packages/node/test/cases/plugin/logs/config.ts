@@ -0,0 +1,64 @@
+import type {
+  InputOptions,
+  RollupOptions,
+  RollupOutput,
+} from '@rolldown/node'
+import { rolldown } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const received: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'logger',
+      transform(code, id) {
+        if (code.includes('console')) {
+          this.warn('avoid console')
+        }
+        this.info(`transformed ${id}`)
+        this.debug('transform took 1ms')
+      },
+    },
+  ],
+  onLog(level, log) {
+    received.push(`${level} ${log.code} ${log.message}`)
+    return 'keep'
+  },
+}
+
+export default {
+  config,
+  afterTest: async (_output: RollupOutput) => {
+    // Debug logs are dropped by the default log level
+    expect(received.sort()).toStrictEqual([
+      `info PLUGIN_LOG [plugin logger] transformed ${entry}`,
+      'warn PLUGIN_WARNING [plugin logger] avoid console',
+    ])
+
+    // `onLog` decides what is done with logs
+    received.length = 0
+    const build = await rolldown({
+      ...config,
+      logLevel: 'debug',
+      onLog(level, log) {
+        received.push(`${level} ${log.code}`)
+        return level === 'warn' ? 'error' : 'suppress'
+      },
+    } as InputOptions)
+    try {
+      await expect(build.generate()).rejects.toThrow('Build failed')
+    } finally {
+      await build.close()
+    }
+    expect(received.sort()).toStrictEqual([
+      'debug PLUGIN_LOG',
+      'info PLUGIN_LOG',
+      'warn PLUGIN_WARNING',
+    ])
+  },
+}
@@ -0,0 +1,49 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect, vi } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+const foo = path.join(__dirname, './foo.js')
+const lazy = path.join(__dirname, './lazy.js')
+const pkg = path.join(__dirname, './node_modules/pkg/index.js')
+
+const moduleParsedFn = vi.fn()
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'test-plugin',
+      moduleParsed: (moduleInfo) => {
+        moduleParsedFn(moduleInfo.id)
+        if (moduleInfo.id === entry) {
+          expect(moduleInfo.isEntry).toBe(true)
+          expect(moduleInfo.code).toContain('export const value = foo')
+          expect(moduleInfo.importedIds).toStrictEqual([foo, pkg])
+          expect(moduleInfo.dynamicallyImportedIds).toStrictEqual([lazy])
+          expect(moduleInfo.exportedBindings).toStrictEqual({ '.': ['value'] })
+        } else {
+          expect(moduleInfo.isEntry).toBe(false)
+        }
+        if (moduleInfo.id === foo) {
+          expect(moduleInfo.exportedBindings).toStrictEqual({ '.': ['foo'] })
+          expect(moduleInfo.hasModuleSideEffects).toBe(true)
+        }
+        if (moduleInfo.id === pkg) {
+          // Decided by `sideEffects` of the package.json
+          expect(moduleInfo.hasModuleSideEffects).toBe(false)
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    // The runtime module isn't passed to plugins
+    expect(moduleParsedFn.mock.calls.map(([id]) => id).sort()).toStrictEqual(
+      [foo, lazy, entry, pkg].sort(),
+    )
+  },
+}
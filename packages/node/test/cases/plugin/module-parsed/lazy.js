@@ -0,0 +1 @@
+export const lazy = 1
@@ -0,0 +1,8 @@
+import { foo } from './foo.js'
+import pkg from 'pkg'
+
+export const value = foo
+
+console.log(pkg)
+
+import('./lazy.js')
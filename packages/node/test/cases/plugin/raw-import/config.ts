@@ -0,0 +1,34 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'raw-import',
+      // Resolves `?raw` and `?base64` imports like Vite
+      resolveId(source, importer) {
+        const [file, query] = source.split('?')
+        if (query === 'raw' || query === 'base64') {
+          return {
+            id: path.resolve(path.dirname(importer!), file),
+            moduleType: query === 'raw' ? 'text' : 'base64',
+          } as any
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const code = output.output[0].code
+    expect(code).toContain(String.raw`var hello_default = 'Hello "world"\n';`)
+    expect(code).toContain(`var data_default = 'cm9sbGRvd24=';`)
+    // The extension of CommonJS modules doesn't matter
+    expect(code).toContain(
+      String.raw`var lib_default = 'module.exports = 1\n';`,
+    )
+  },
+}
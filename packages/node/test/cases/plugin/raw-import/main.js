@@ -0,0 +1,5 @@
+import text from './hello.txt?raw'
+import data from './data.bin?base64'
+import code from './lib.cjs?raw'
+
+export { text, data, code }
@@ -1,18 +1,43 @@
 import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { OutputAsset, OutputChunk } from 'rollup'
 import { expect, vi } from 'vitest'
 import path from 'node:path'
+import { decodeMappings } from '../../../util'
 
 const entry = path.join(__dirname, './main.js')
 
 const renderChunkFn = vi.fn()
+const received: string[] = []
+
+// Prepend a line to the chunk, along with a map that shifts every line down by one.
+function prependLine(code: string, line: string) {
+  const mappings = code
+    .split('\n')
+    .map((_, index) => (index === 0 ? 'AAAA' : 'AACA'))
+    .join(';')
+  return {
+    code: `${line}\n${code}`,
+    map: {
+      mappings: `;${mappings}`,
+      sources: ['main.js'],
+      sourcesContent: [code],
+      names: [],
+    },
+  }
+}
 
 const config: RollupOptions = {
   input: entry,
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    sourcemap: true,
+  },
   plugins: [
     {
-      name: 'test-plugin',
+      name: 'prepend-first',
       renderChunk: (code, chunk) => {
         renderChunkFn()
+        received.push(code)
         expect(code.indexOf('console.log') > -1).toBe(true)
         expect(chunk.type).toBe('chunk')
         expect(chunk.fileName).toBe('main.js')
@@ -22,7 +47,21 @@ const config: RollupOptions = {
         expect(chunk.exports.length).toBe(0)
         expect(chunk.moduleIds).toStrictEqual([entry])
         expect(Object.keys(chunk.modules).length).toBe(1)
-        return 'render-chunk-code'
+        return prependLine(code, '// first')
+      },
+    },
+    {
+      name: 'keep',
+      renderChunk: (code) => {
+        received.push(code)
+        return null
+      },
+    },
+    {
+      name: 'prepend-second',
+      renderChunk: (code) => {
+        received.push(code)
+        return prependLine(code, '// second')
       },
     },
   ],
@@ -32,6 +71,30 @@ export default {
   config,
   afterTest: (output: RollupOutput) => {
     expect(renderChunkFn).toHaveBeenCalledTimes(1)
-    expect(output.output[0].code).toBe('render-chunk-code')
+    // Each hook receives the code returned by the previous one, which `null`
+    // keeps
+    expect(received[1]).toBe(`// first\n${received[0]}`)
+    expect(received[2]).toBe(received[1])
+
+    const chunk = output.output.find(
+      (item) => item.fileName === 'main.js',
+    ) as OutputChunk
+    expect(chunk.code.startsWith(`// second\n${received[1]}`)).toBe(true)
+
+    // Maps returned by the hooks are composed with the map of the chunk
+    const asset = output.output.find(
+      (item) => item.fileName === 'main.js.map',
+    ) as OutputAsset
+    const map = JSON.parse(asset.source as string)
+    expect(map.sources).toStrictEqual(['../main.js'])
+    const lines = decodeMappings(map.mappings)
+    const outputLines = chunk.code.split('\n')
+    // Lines are moved down by the hooks, but still mapped to the original ones
+    const declaration = outputLines.findIndex((l) => l.includes('const name'))
+    expect(lines[declaration][0][2]).toBe(0)
+    const line = outputLines.findIndex((l) => l.includes('console.log(name)'))
+    const [, sourceIndex, originalLine] = lines[line][0]
+    expect(map.sources[sourceIndex]).toBe('../main.js')
+    expect(originalLine).toBe(1)
   },
 }
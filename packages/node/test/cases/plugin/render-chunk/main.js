@@ -1 +1,2 @@
-console.log()
+const name = 'render-chunk'
+console.log(name)
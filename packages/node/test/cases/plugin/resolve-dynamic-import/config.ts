@@ -0,0 +1,36 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+const calls: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'dynamic-import',
+      resolveDynamicImport(specifier, importer) {
+        calls.push(`${importer} ${specifier}`)
+        if (specifier === './locales/current.js') {
+          return path.join(__dirname, './locales/en.js')
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    // Static imports aren't passed to the hook
+    expect(calls.sort()).toStrictEqual([
+      `${entry} ./c.js`,
+      `${entry} ./locales/current.js`,
+    ])
+    const code = output.output[0].code
+    expect(code).toContain("import('./locales_en_js-")
+    expect(code).toContain("import('./c_js-")
+  },
+}
@@ -0,0 +1,5 @@
+import { value } from './static.js'
+
+export const a = import('./locales/current.js')
+export const c = import('./c.js')
+console.log(value)
@@ -0,0 +1,76 @@
+import type {
+  InputOptions,
+  OutputOptions,
+  RollupOptions,
+  RollupOutput,
+} from '@rolldown/node'
+import { rolldown } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+
+// URLs of files are resolved against `cdn` if it's given
+let cdn: string | undefined
+const calls: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'file-url',
+      transform(code, id) {
+        if (id === entry) {
+          const referenceId = this.emitFile({
+            type: 'asset',
+            name: 'logo.svg',
+            source: '<svg></svg>',
+          })
+          return `${code}\nexport const logo = import.meta.ROLLUP_FILE_URL_${referenceId}`
+        }
+      },
+      resolveFileUrl({ moduleId, chunkId, format, relativePath, fileName }) {
+        calls.push(
+          [moduleId, chunkId, format, relativePath, fileName].join(' '),
+        )
+        if (cdn) {
+          return JSON.stringify(`${cdn}${fileName}`)
+        }
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    entryFileNames: 'js/[name].js',
+  },
+}
+
+export default {
+  config,
+  afterTest: async (output: RollupOutput) => {
+    const fileName = output.output
+      .map((item) => item.fileName)
+      .find((fileName) => fileName.startsWith('assets/logo-'))
+    expect(fileName).toBeDefined()
+    expect(calls).toStrictEqual([
+      `${entry} js/main.js es ../${fileName} ${fileName}`,
+    ])
+    expect(output.output[0].code).toContain(
+      `const logo = new URL('../${fileName}', import.meta.url).href`,
+    )
+
+    cdn = 'https://cdn.example.com/'
+    const build = await rolldown(config as InputOptions)
+    try {
+      const cjs = await build.generate({
+        dir: path.join(__dirname, 'dist'),
+        format: 'cjs',
+      } as OutputOptions)
+      expect(cjs.output[0].code).toContain(
+        `logo = 'https://cdn.example.com/${fileName}'`,
+      )
+    } finally {
+      await build.close()
+    }
+  },
+}
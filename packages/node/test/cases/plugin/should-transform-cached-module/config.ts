@@ -0,0 +1,78 @@
+import type { InputOptions, RollupOptions } from '@rolldown/node'
+import { rolldown } from '@rolldown/node'
+import { expect } from 'vitest'
+import fs from 'node:fs'
+import os from 'node:os'
+import path from 'node:path'
+
+const entry = path.join(__dirname, './main.js')
+const depId = path.join(__dirname, './dep.js')
+const timeId = path.join(__dirname, './time.js')
+// Exported by `main.js`, which is added as a watch file
+const configPath = path.join(os.tmpdir(), 'rolldown-cached-module-config.txt')
+fs.writeFileSync(configPath, 'first')
+
+// The code of `dep.js` is changed between builds by the plugin
+let depCode = "export const a = 'a'"
+let transformed: string[] = []
+
+const config: RollupOptions = {
+  input: entry,
+  plugins: [
+    {
+      name: 'test-plugin',
+      load(id) {
+        if (id === depId) {
+          return depCode
+        }
+      },
+      transform(code, id) {
+        transformed.push(id)
+        if (id === entry) {
+          this.addWatchFile(configPath)
+          const value = fs.readFileSync(configPath, 'utf-8')
+          return `${code}\nexport const config = ${JSON.stringify(value)}`
+        }
+      },
+      // `time.js` is always transformed since it would depend on the time of the build
+      shouldTransformCachedModule({ id }) {
+        if (id === timeId) {
+          return true
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: async () => {
+    // Modules transformed by previous builds of the same bundler are reused
+    const build = await rolldown(config as InputOptions)
+    const generate = async () => {
+      transformed = []
+      const output = await build.generate({ dir: path.join(__dirname, 'dist') })
+      return { code: output.output[0].code, transformed: transformed.sort() }
+    }
+    try {
+      const first = await generate()
+      expect(first.transformed).toStrictEqual([depId, entry, timeId])
+      const second = await generate()
+      expect(second.code).toBe(first.code)
+      expect(second.transformed).toStrictEqual([timeId])
+
+      // New exports of `dep.js` are linked into the namespace imported by the cached `main.js`
+      depCode = "export const a = 'a'; export const b = 'b'"
+      const third = await generate()
+      expect(third.transformed).toStrictEqual([depId, timeId])
+      expect(third.code).toContain('b:() => b')
+
+      fs.writeFileSync(configPath, 'second')
+      const fourth = await generate()
+      expect(fourth.transformed).toStrictEqual([entry, timeId])
+      expect(fourth.code).toContain("const config = 'second'")
+    } finally {
+      await build.close()
+    }
+  },
+}
@@ -0,0 +1,4 @@
+import * as dep from './dep.js'
+import { time } from './time.js'
+
+console.log(dep, time)
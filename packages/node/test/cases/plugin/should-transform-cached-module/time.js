@@ -0,0 +1 @@
+export const time = 0
@@ -0,0 +1,26 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const typedId = path.join(__dirname, './typed.js')
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'typescript-loader',
+      // `typed.js` is written in TypeScript
+      transform(code, id) {
+        if (id === typedId) {
+          return { code, loader: 'ts' } as any
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(output.output[0].code).toContain('const double = n => n * 2')
+  },
+}
@@ -0,0 +1,3 @@
+import { double } from './typed.js'
+
+console.log(double(1))
@@ -0,0 +1,81 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import { OutputAsset, OutputChunk } from 'rollup'
+import { expect } from 'vitest'
+import path from 'node:path'
+import { decodeMappings } from '../../../util'
+
+const entry = path.join(__dirname, './main.js')
+
+// Prepend a line to the code, along with a map that shifts every line down by one.
+function prependLine(code: string, line: string) {
+  const mappings = code
+    .split('\n')
+    .map((_, index) => (index === 0 ? 'AAAA' : 'AACA'))
+    .join(';')
+  return {
+    code: `${line}\n${code}`,
+    map: {
+      mappings: `;${mappings}`,
+      sources: ['main.js'],
+      sourcesContent: [code],
+      names: [],
+    },
+  }
+}
+
+const config: RollupOptions = {
+  input: entry,
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    sourcemap: true,
+  },
+  plugins: [
+    {
+      name: 'prepend-first',
+      transform(code, id) {
+        if (id === entry) {
+          return prependLine(code, `console.log('first')`)
+        }
+      },
+    },
+    {
+      name: 'replace-without-moving',
+      transform(code, id) {
+        if (id === entry) {
+          // Positions are preserved, so no map is needed
+          return { code: code.replace(`'foo'`, `'bar'`), map: null }
+        }
+      },
+    },
+    {
+      name: 'prepend-second',
+      transform(code, id) {
+        if (id === entry) {
+          return prependLine(code, `console.log('second')`)
+        }
+      },
+    },
+  ],
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    const chunk = output.output.find(
+      (item) => item.fileName === 'main.js',
+    ) as OutputChunk
+    const asset = output.output.find(
+      (item) => item.fileName === 'main.js.map',
+    ) as OutputAsset
+    const map = JSON.parse(asset.source as string)
+    expect(map.sources).toStrictEqual(['../main.js'])
+
+    const lines = decodeMappings(map.mappings)
+    const outputLines = chunk.code.split('\n')
+
+    const line = outputLines.findIndex((l) => l.includes('console.log(name)'))
+    const [, sourceIndex, originalLine] = lines[line][0]
+    expect(map.sources[sourceIndex]).toBe('../main.js')
+    expect(originalLine).toBe(2)
+  },
+}
@@ -0,0 +1,3 @@
+const name = 'foo'
+
+console.log(name)
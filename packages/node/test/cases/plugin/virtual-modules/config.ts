@@ -0,0 +1,46 @@
+import type { RollupOptions, RollupOutput } from '@rolldown/node'
+import type { OutputAsset } from 'rollup'
+import { expect } from 'vitest'
+import path from 'node:path'
+
+const config: RollupOptions = {
+  plugins: [
+    {
+      name: 'virtual-entry',
+      resolveId(id) {
+        if (id === 'virtual:entry') {
+          return '\0virtual:entry'
+        }
+      },
+      load(id) {
+        // `\0virtual:config` is imported by its id without being resolved
+        if (id === '\0virtual:entry') {
+          return "export { debug } from '\\0virtual:config'"
+        }
+        if (id === '\0virtual:config') {
+          return 'export const debug = false'
+        }
+      },
+    },
+  ],
+  output: {
+    dir: path.join(__dirname, 'dist'),
+    sourcemap: true,
+  },
+}
+
+export default {
+  config,
+  afterTest: (output: RollupOutput) => {
+    expect(output.output[0].moduleIds).toContain('\0virtual:config')
+    const asset = output.output.find(
+      (item) => item.fileName === 'main.js.map',
+    ) as OutputAsset
+    const map = JSON.parse(asset.source as string)
+    // Virtual modules are named without the `\0` in sourcemaps
+    expect([...map.sources].sort()).toStrictEqual([
+      '../main.js',
+      'virtual:config',
+    ])
+  },
+}
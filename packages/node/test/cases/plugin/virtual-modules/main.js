@@ -0,0 +1,3 @@
+import { debug } from 'virtual:entry'
+
+console.log(debug)
@@ -1,6 +1,7 @@
 import type { RollupOptions, RollupOutput } from '@rolldown/node'
 import { expect, vi } from 'vitest'
 import path from 'node:path'
+import fs from 'node:fs'
 import { OutputChunk } from 'rollup'
 
 const entry = path.join(__dirname, './main.js')
@@ -24,6 +25,8 @@ const config: RollupOptions = {
         expect(chunk.exports.length).toBe(0)
         expect(chunk.moduleIds).toStrictEqual([entry])
         expect(Object.keys(chunk.modules).length).toBe(1)
+        // Files should already be written to disk
+        expect(fs.existsSync(path.join(__dirname, 'dist/main.js'))).toBe(true)
       },
     },
   ],
@@ -26,7 +26,7 @@ function runCases() {
         test(subCaseName, async () => {
           const output = await runCaseBundle(caseRoot, config)
           if (afterTest) {
-            afterTest(output)
+            await afterTest(output)
           }
         })
       }
@@ -37,7 +37,11 @@ function runCases() {
 async function runCaseBundle(caseRoot: string, config?: RollupOptions) {
   config = normalizedOptions(caseRoot, config)
   const build = await rolldown(config as InputOptions)
-  return await build.write(config.output as OutputOptions)
+  try {
+    return await build.write(config.output as OutputOptions)
+  } finally {
+    await build.close()
+  }
 }
 
 function normalizedOptions(caseRoot: string, config?: RollupOptions) {
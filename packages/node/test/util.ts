@@ -6,3 +6,34 @@ export function getOutputChunkNames(output: RollupOutput) {
     .map((chunk) => chunk.fileName)
     .sort()
 }
+
+const BASE64 =
+  'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/'
+
+// Decode mappings into `[generatedColumn, sourceIndex, originalLine, originalColumn]` per line
+export function decodeMappings(mappings: string) {
+  const state = [0, 0, 0, 0]
+  return mappings.split(';').map((line) => {
+    state[0] = 0
+    return line
+      .split(',')
+      .filter(Boolean)
+      .map((segment) => {
+        let value = 0
+        let shift = 0
+        let field = 0
+        for (const char of segment) {
+          const digit = BASE64.indexOf(char)
+          value += (digit & 31) << shift
+          if (digit & 32) {
+            shift += 5
+          } else {
+            state[field++] += value & 1 ? -(value >>> 1) : value >>> 1
+            value = 0
+            shift = 0
+          }
+        }
+        return [...state]
+      })
+  })
+}